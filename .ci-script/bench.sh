@@ -0,0 +1,25 @@
+#!/bin/sh
+
+# Benchmarks of cita-tool, the baseline `master` is saved in the cached target
+# directory by the builds of master, other builds compare against it.
+#
+# The timings depend on the load of the shared CI machine, so a regression
+# reported by criterion is only printed for review, it doesn't fail the build.
+
+set -e
+
+if [ "$TRAVIS_BRANCH" = "master" ] && [ "$TRAVIS_PULL_REQUEST" = "false" ]; then
+    cargo bench -p cita-tool --bench core -- --save-baseline master
+else
+    # No pipe into tee, `sh` has no pipefail and it would hide a failing cargo
+    status=0
+    cargo bench -p cita-tool --bench core -- --baseline master > bench_output.txt || status=$?
+    cat bench_output.txt
+    if [ "$status" -ne 0 ]; then
+        echo "Benchmarks failed with status $status"
+        exit "$status"
+    fi
+    if grep -q "Performance has regressed" bench_output.txt; then
+        echo "Benchmarks regressed against master, check the timings above"
+    fi
+fi
@@ -0,0 +1,10 @@
+#!/bin/sh
+
+set -e
+
+COMPOSE_FILE=cita-tool/tests/integration/docker-compose.yml
+
+docker-compose -f "$COMPOSE_FILE" up -d
+trap 'docker-compose -f "$COMPOSE_FILE" down' EXIT
+
+cargo test -p cita-tool --features integration-tests --test integration
@@ -1,29 +1,48 @@
 mod abi_command;
+mod account_command;
 mod amend_command;
+mod audit_command;
+mod chain_command;
 mod contract_command;
 mod key_command;
+mod net_command;
+mod node_command;
 mod other_command;
 mod rpc_command;
+#[cfg(feature = "server")]
+mod server_command;
 mod store_command;
 mod tx_command;
+mod user_contract_command;
 mod util;
+mod watch_command;
 
 pub(crate) use self::util::{
-    encryption, get_url, h256_validator, is_hex, key_validator, parse_address, parse_height,
-    parse_privkey, parse_u256, parse_u32, parse_u64, search_app,
+    encryption, get_private_key, get_url, h256_validator, is_hex, key_validator, parse_address,
+    parse_height, parse_privkey, parse_u256, parse_u32, parse_u64, search_app,
 };
 
 pub use self::abi_command::{abi_command, abi_processor};
+pub use self::account_command::{account_command, account_processor};
 pub use self::amend_command::{amend_command, amend_processor};
+pub use self::audit_command::{audit_command, audit_processor};
+pub use self::chain_command::{chain_command, chain_processor};
 pub use self::contract_command::{contract_command, contract_processor};
 pub use self::key_command::{key_command, key_processor};
+pub use self::net_command::{net_command, net_processor};
+pub use self::node_command::{node_command, node_processor};
 pub use self::other_command::{
     benchmark_command, benchmark_processor, completion_command, completion_processor,
     search_command, search_processor, string_include, transfer_command, transfer_processor,
 };
+pub(crate) use self::rpc_command::wait_for_receipt;
 pub use self::rpc_command::{rpc_command, rpc_processor};
+#[cfg(feature = "server")]
+pub use self::server_command::{server_command, server_processor};
 pub use self::store_command::{store_command, store_processor};
 pub use self::tx_command::{tx_command, tx_processor};
+pub use self::user_contract_command::{user_contract_command, user_contract_processor};
+pub use self::watch_command::{watch_command, watch_processor};
 
 use cita_tool::parse_url;
 use clap::{crate_version, App, AppSettings, Arg, SubCommand};
@@ -42,7 +61,9 @@ pub fn build_cli(version: &str) -> App {
         .global_setting(AppSettings::DeriveDisplayOrder)
         .subcommand(rpc_command().arg(arg_url.clone()))
         .subcommand(contract_command().arg(arg_url.clone()))
+        .subcommand(user_contract_command().arg(arg_url.clone()))
         .subcommand(key_command())
+        .subcommand(account_command())
         .subcommand(abi_command())
         .subcommand(transfer_command().arg(arg_url.clone()))
         .subcommand(store_command().arg(arg_url.clone()))
@@ -50,7 +71,17 @@ pub fn build_cli(version: &str) -> App {
         .subcommand(search_command())
         .subcommand(tx_command().arg(arg_url.clone()))
         .subcommand(benchmark_command().arg(arg_url.clone()))
+        .subcommand(net_command().arg(arg_url.clone()))
+        .subcommand(node_command().arg(arg_url.clone()))
+        .subcommand(chain_command().arg(arg_url.clone()))
+        .subcommand(audit_command().arg(arg_url.clone()))
+        .subcommand(watch_command().arg(arg_url.clone()))
+        .subcommands(server_commands())
         .subcommand(completion_command())
+        .subcommand(
+            SubCommand::with_name("repl")
+                .about("Start the interactive mode, the same as running without subcommand"),
+        )
         .arg(
             Arg::with_name("algorithm")
                 .long("algorithm")
@@ -71,6 +102,61 @@ pub fn build_cli(version: &str) -> App {
                 .global(true)
                 .help("Display request parameters"),
         )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Print the signed transactions instead of sending them, the quota is estimated if not set"),
+        )
+        .arg(
+            Arg::with_name("json-rpc-version")
+                .long("json-rpc-version")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["1.0", "2.0"])
+                .default_value("2.0")
+                .help("JSON-RPC version of the requests, 1.0 has no jsonrpc key for the old nodes"),
+        )
+        .arg(
+            Arg::with_name("no-progress")
+                .long("no-progress")
+                .global(true)
+                .help("Don't display the spinner of the commands waiting for the chain"),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .global(true)
+                .takes_value(true)
+                .help("TOML file of flag defaults (env: CITA_CLI_CONFIG), the default is ~/.cita-cli.toml"),
+        )
+        .arg(
+            Arg::with_name("output-format")
+                .long("output-format")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "table", "csv"])
+                .help("Output format, the default is json"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .short("v")
+                .global(true)
+                .help("Log JSON-RPC requests and responses, same as RUST_LOG=cita_tool=debug"),
+        )
+}
+
+/// The JSON-RPC proxy command, with the `server` feature
+#[cfg(feature = "server")]
+fn server_commands() -> Vec<App<'static, 'static>> {
+    vec![server_command()]
+}
+
+/// The JSON-RPC proxy command, with the `server` feature
+#[cfg(not(feature = "server"))]
+fn server_commands() -> Vec<App<'static, 'static>> {
+    Vec::new()
 }
 
 /// Interactive parser
@@ -133,13 +219,19 @@ pub fn build_interactive() -> App<'static, 'static> {
         .subcommand(SubCommand::with_name("info").about("Display global variables"))
         .subcommand(rpc_command())
         .subcommand(key_command())
+        .subcommand(account_command())
         .subcommand(abi_command())
         .subcommand(contract_command())
+        .subcommand(user_contract_command())
         .subcommand(transfer_command())
         .subcommand(store_command())
         .subcommand(amend_command())
         .subcommand(tx_command())
         .subcommand(benchmark_command())
+        .subcommand(net_command())
+        .subcommand(node_command())
+        .subcommand(chain_command())
+        .subcommand(audit_command())
         .subcommand(
             SubCommand::with_name("exit")
                 .visible_alias("quit")
@@ -29,7 +29,11 @@ pub fn abi_command() -> App<'static, 'static> {
         .help("ABI json file path");
 
     App::new("ethabi")
-        .about("ABI operation, encode parameter, generate code based on abi and parameters")
+        .alias("abi")
+        .about(
+            "ABI operation, encode parameter, generate code based on abi and parameters, \
+             no connection to the chain",
+        )
         .subcommand(
             SubCommand::with_name("encode")
                 .subcommand(
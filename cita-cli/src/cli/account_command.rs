@@ -0,0 +1,237 @@
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use serde_json::json;
+
+use cita_tool::{
+    address_from_privkey, decode, load_keystore, remove_0x, save_keystore, sign_message,
+    validate_checksum_address, verify_signature, Encryption, HashAlgorithm, KeyPair, Signature,
+};
+
+use crate::cli::{encryption, is_hex, key_validator, parse_address, parse_privkey};
+use crate::interactive::GlobalConfig;
+use crate::printer::Printer;
+
+/// The password of the keystore when `--password` is absent
+const ENV_KEYSTORE_PASSWORD: &str = "CITA_CLI_KEYSTORE_PASSWORD";
+
+/// Account related commands
+pub fn account_command() -> App<'static, 'static> {
+    let arg_key = Arg::with_name("key")
+        .long("key")
+        .takes_value(true)
+        .required(true)
+        .validator(|privkey| key_validator(privkey.as_ref()).map(|_| ()))
+        .help("The private key");
+    let arg_password = Arg::with_name("password")
+        .long("password")
+        .takes_value(true)
+        .help(
+            "The password of the keystore, it's visible to the other processes, \
+             $CITA_CLI_KEYSTORE_PASSWORD or a prompt is used if absent",
+        );
+    let arg_message = Arg::with_name("message")
+        .long("message")
+        .takes_value(true)
+        .required(true)
+        .validator(|message| is_hex(message.as_str()))
+        .help(
+            "The message to sign, it's hashed after the prefix \
+             \"\\x19CITA Signed Message:\\n\" and its length, \
+             Secp256k1 means keccak256/Ed25519 means blake2b/Sm2 means Sm3",
+        );
+
+    App::new("account")
+        .about("Account operations: generating, importing keys, signing and verifying messages")
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Generate a random private key")
+                .arg(
+                    Arg::with_name("keystore")
+                        .long("keystore")
+                        .takes_value(true)
+                        .help("Save the private key to the keystore file instead of printing it"),
+                )
+                .arg(arg_password.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("address")
+                .about("Get the address of a private key")
+                .arg(arg_key.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Decrypt the keystore file and get the address")
+                .arg(
+                    Arg::with_name("keystore")
+                        .long("keystore")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The keystore file"),
+                )
+                .arg(arg_password),
+        )
+        .subcommand(
+            SubCommand::with_name("sign")
+                .about("Sign a message")
+                .arg(arg_key)
+                .arg(arg_message.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Verify the message is signed by the account")
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|address| parse_address(address.as_str()))
+                        .help("The address of the signer"),
+                )
+                .arg(arg_message)
+                .arg(
+                    Arg::with_name("signature")
+                        .long("signature")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|signature| is_hex(signature.as_str()))
+                        .help("The signature"),
+                ),
+        )
+}
+
+/// Account processor
+pub fn account_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &GlobalConfig,
+) -> Result<(), String> {
+    let is_color = !sub_matches.is_present("no-color") && config.color();
+    let result = match sub_matches.subcommand() {
+        ("new", Some(m)) => {
+            let key_pair = KeyPair::new(encryption(m, config));
+            match m.value_of("keystore") {
+                Some(keystore) => {
+                    save_keystore(&key_pair.privkey(), &password(m)?, Path::new(keystore))
+                        .map_err(|err| err.to_string())?;
+                    json!({
+                        "address": format!("0x{:x}", key_pair.address()),
+                        "public": format!("0x{}", key_pair.pubkey()),
+                        "keystore": keystore,
+                    })
+                }
+                None => {
+                    printer.println(&key_pair, is_color);
+                    return Ok(());
+                }
+            }
+        }
+        ("address", Some(m)) => {
+            let privkey = parse_privkey(m.value_of("key").unwrap(), encryption(m, config))?;
+            json!({ "address": format!("0x{:x}", address_from_privkey(&privkey)) })
+        }
+        ("import", Some(m)) => {
+            let keystore = m.value_of("keystore").unwrap();
+            let privkey = load_keystore(Path::new(keystore), &password(m)?, encryption(m, config))
+                .map_err(|err| err.to_string())?;
+            json!({
+                "address": format!("0x{:x}", address_from_privkey(&privkey)),
+                "keystore": keystore,
+            })
+        }
+        ("sign", Some(m)) => {
+            let encryption = encryption(m, config);
+            let privkey = parse_privkey(m.value_of("key").unwrap(), encryption)?;
+            let message =
+                decode(remove_0x(m.value_of("message").unwrap())).map_err(|err| err.to_string())?;
+            let signature = sign_message(&message, &privkey, hash_algorithm(encryption))
+                .map_err(|err| err.to_string())?;
+            json!({ "signature": format!("0x{}", signature) })
+        }
+        ("verify", Some(m)) => {
+            let address = validate_checksum_address(m.value_of("address").unwrap())
+                .map_err(|err| err.to_string())?;
+            let message =
+                decode(remove_0x(m.value_of("message").unwrap())).map_err(|err| err.to_string())?;
+            let signature = Signature::from(
+                &decode(remove_0x(m.value_of("signature").unwrap()))
+                    .map_err(|err| err.to_string())?,
+            );
+            let valid = verify_signature(
+                &message,
+                &signature,
+                address,
+                hash_algorithm(encryption(m, config)),
+            )
+            .map_err(|err| err.to_string())?;
+            json!({ "valid": valid })
+        }
+        _ => return Err(sub_matches.usage().to_owned()),
+    };
+    printer.println(&result, is_color);
+    Ok(())
+}
+
+/// Hash algorithm of the messages, the one of the transactions of the encryption
+fn hash_algorithm(encryption: Encryption) -> HashAlgorithm {
+    match encryption {
+        Encryption::Secp256k1 => HashAlgorithm::Keccak256,
+        Encryption::Ed25519 => HashAlgorithm::Blake2b,
+        Encryption::Sm2 => HashAlgorithm::Sm3,
+    }
+}
+
+/// The password of the keystore: the `--password` flag, the environment or a prompt
+/// without echo, the latter two keeping it out of argv and the shell history
+fn password(m: &ArgMatches) -> Result<String, String> {
+    if let Some(password) = m.value_of("password") {
+        return Ok(password.to_owned());
+    }
+    if let Ok(password) = env::var(ENV_KEYSTORE_PASSWORD) {
+        return Ok(password);
+    }
+    eprint!("Keystore password: ");
+    io::stderr().flush().map_err(|err| err.to_string())?;
+    enable_raw_mode().map_err(|err| {
+        format!(
+            "Can't prompt for the password ({}), pass --password or ${}",
+            err, ENV_KEYSTORE_PASSWORD
+        )
+    })?;
+    let password = read_password();
+    let _ = disable_raw_mode();
+    eprintln!();
+    password
+}
+
+/// Read the keys until Enter in raw mode, none of them being echoed
+fn read_password() -> Result<String, String> {
+    let mut password = String::new();
+    loop {
+        match read().map_err(|err| err.to_string())? {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => return Err("Interrupted".to_owned()),
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            }) => return Ok(password),
+            Event::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            }) => {
+                password.pop();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            }) => password.push(c),
+            _ => {}
+        }
+    }
+}
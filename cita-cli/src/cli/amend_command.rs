@@ -7,6 +7,7 @@ use crate::cli::{
     encryption, get_url, h256_validator, key_validator, parse_address, parse_privkey, parse_u256,
     parse_u64,
 };
+use crate::config_file::{ENV_CHAIN_ID, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
 use crate::printer::Printer;
 
@@ -18,10 +19,11 @@ pub fn amend_command() -> App<'static, 'static> {
     let common_args = [
         Arg::with_name("chain-id")
             .long("chain-id")
+            .env(ENV_CHAIN_ID)
             .takes_value(true)
             .validator(|chain_id| match chain_id.parse::<u32>() {
                 Ok(_) => Ok(()),
-                Err(err) => Err(format!("{:?}", err)),
+                Err(err) => Err(format!("{}", err)),
             })
             .help("The chain_id of transaction"),
         Arg::with_name("admin-private-key")
@@ -32,6 +34,7 @@ pub fn amend_command() -> App<'static, 'static> {
             .help("The private key of super admin"),
         Arg::with_name("quota")
             .long("quota")
+            .env(ENV_QUOTA)
             .takes_value(true)
             .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
             .help("Transaction quota costs, default is 1_000_000"),
@@ -0,0 +1,114 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::json;
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+
+use crate::cli::{get_url, parse_address, parse_height, parse_u64};
+use crate::interactive::GlobalConfig;
+use crate::printer::Printer;
+
+/// Audit command
+pub fn audit_command() -> App<'static, 'static> {
+    let permission_arg = Arg::with_name("permission")
+        .long("permission")
+        .takes_value(true)
+        .required(true)
+        .validator(|permission| parse_address(permission.as_str()))
+        .help("The address of the permission");
+    let height_arg = Arg::with_name("height")
+        .long("height")
+        .default_value("latest")
+        .validator(|s| parse_height(s.as_str()))
+        .takes_value(true)
+        .help("The number of the block");
+
+    App::new("audit")
+        .about("Audit the chain, such as the governance state of system contracts")
+        .subcommand(
+            SubCommand::with_name("governance")
+                .about("Display groups, roles, permissions, admin, quota limits and nodes as JSON")
+                .arg(
+                    Arg::with_name("height")
+                        .long("height")
+                        .takes_value(true)
+                        .validator(|height| parse_u64(height.as_ref()).map(|_| ()))
+                        .help("The number of the block, default is the current height"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("permission-holders")
+                .about("List the accounts having the permission")
+                .arg(permission_arg.clone())
+                .arg(height_arg.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("permission-violations")
+                .about("List the accounts having the permission but not in the allowlist")
+                .arg(permission_arg)
+                .arg(
+                    Arg::with_name("allowed")
+                        .long("allowed")
+                        .takes_value(true)
+                        .multiple(true)
+                        .validator(|account| parse_address(account.as_str()))
+                        .help("The accounts allowed to have the permission"),
+                )
+                .arg(height_arg),
+        )
+}
+
+/// Audit processor
+pub fn audit_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let client = client
+        .set_debug(debug)
+        .set_uri(get_url(sub_matches, config));
+
+    match sub_matches.subcommand() {
+        ("governance", Some(m)) => {
+            let height = match m.value_of("height") {
+                Some(height) => Some(parse_u64(height)?),
+                None => None,
+            };
+            let audit = client
+                .governance_audit(height)
+                .map_err(|err| format!("{}", err))?;
+            printer.println(&json!(audit), true);
+            Ok(())
+        }
+        ("permission-holders", Some(m)) => {
+            let client = AuthorizationClient::create(client);
+            let accounts = client
+                .check_all_accounts_have_permission(
+                    m.value_of("permission").unwrap(),
+                    m.value_of("height"),
+                )
+                .map_err(|err| format!("{}", err))?;
+            printer.println(&json!(accounts), true);
+            Ok(())
+        }
+        ("permission-violations", Some(m)) => {
+            let allowed = m
+                .values_of("allowed")
+                .map(|allowed| allowed.collect::<Vec<&str>>())
+                .unwrap_or_default();
+            let client = AuthorizationClient::create(client);
+            let accounts = client
+                .check_no_account_has_permission(
+                    m.value_of("permission").unwrap(),
+                    &allowed,
+                    m.value_of("height"),
+                )
+                .map_err(|err| format!("{}", err))?;
+            printer.println(&json!(accounts), true);
+            Ok(())
+        }
+        _ => Err(sub_matches.usage().to_owned()),
+    }
+}
@@ -0,0 +1,148 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+use cita_tool::{LowerHex, ToolError};
+
+use crate::cli::{get_url, parse_height, parse_u64};
+use crate::interactive::GlobalConfig;
+use crate::printer::Printer;
+
+/// Blocks requested in one batch by `chain stats`, the concurrency limit applies in a batch
+const STATS_BATCH_SIZE: u64 = 100;
+
+/// Chain command
+pub fn chain_command() -> App<'static, 'static> {
+    App::new("chain")
+        .about("Chain information, such as the chain status")
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Display the height, metadata, nodes, quota and emergency brake"),
+        )
+        .subcommand(
+            SubCommand::with_name("token-info")
+                .about("Display the name, symbol and avatar of the chain token")
+                .arg(
+                    Arg::with_name("height")
+                        .long("height")
+                        .takes_value(true)
+                        .default_value("latest")
+                        .validator(|s| parse_height(s.as_str()))
+                        .help("The block number"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("stats")
+                .about(
+                    "Display the transactions, quota used, block time and proposers \
+                     of a range of blocks",
+                )
+                .arg(
+                    Arg::with_name("from")
+                        .long("from")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|height| parse_u64(height.as_ref()).map(|_| ()))
+                        .help("The first block of the range"),
+                )
+                .arg(
+                    Arg::with_name("to")
+                        .long("to")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|height| parse_u64(height.as_ref()).map(|_| ()))
+                        .help("The last block of the range"),
+                )
+                .arg(
+                    Arg::with_name("concurrency")
+                        .long("concurrency")
+                        .takes_value(true)
+                        .default_value("10")
+                        .validator(|concurrency| parse_u64(concurrency.as_ref()).map(|_| ()))
+                        .help("The maximum number of blocks requested at the same time"),
+                ),
+        )
+}
+
+/// Chain processor
+pub fn chain_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let mut client = client
+        .set_debug(debug)
+        .set_uri(get_url(sub_matches, config));
+
+    match sub_matches.subcommand() {
+        ("status", _) => {
+            let status = client.chain_status().map_err(|err| format!("{}", err))?;
+            let chain_id = if status.metadata.version == 0 {
+                status.metadata.chain_id.to_string()
+            } else {
+                status.metadata.chain_id_v1.lower_hex_with_0x()
+            };
+            let card = [
+                ("Chain name", status.metadata.chain_name.clone()),
+                ("Chain id", chain_id),
+                ("Operator", status.metadata.operator.clone()),
+                ("Version", status.metadata.version.to_string()),
+                ("Block number", status.block_number.to_string()),
+                (
+                    "Block interval",
+                    format!("{}ms", status.metadata.block_interval),
+                ),
+                ("Consensus nodes", status.node_count.to_string()),
+                ("Block quota limit", status.block_quota_limit.to_string()),
+                ("Quota price", status.quota_price.to_string()),
+                (
+                    "Emergency brake",
+                    if status.emergency_brake_active {
+                        "on".to_string()
+                    } else {
+                        "off".to_string()
+                    },
+                ),
+            ];
+            let width = card.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            let card = card
+                .iter()
+                .map(|(name, value)| format!("{:<width$}  {}", name, value, width = width))
+                .collect::<Vec<String>>();
+            printer.println(&card.join("\n"), false);
+            Ok(())
+        }
+        ("token-info", Some(m)) => {
+            let client: SysConfigClient<Client> = SysConfigExt::create(client);
+            let token = SysConfigExt::get_token_info(&client, m.value_of("height"))
+                .map_err(|err: ToolError| format!("{}", err))?;
+            let card = [
+                ("Token name", token.name),
+                ("Token symbol", token.symbol),
+                ("Token avatar", token.avatar),
+            ];
+            let width = card.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            let card = card
+                .iter()
+                .map(|(name, value)| format!("{:<width$}  {}", name, value, width = width))
+                .collect::<Vec<String>>();
+            printer.println(&card.join("\n"), false);
+            Ok(())
+        }
+        ("stats", Some(m)) => {
+            let from = parse_u64(m.value_of("from").unwrap())?;
+            let to = parse_u64(m.value_of("to").unwrap())?;
+            let concurrency = parse_u64(m.value_of("concurrency").unwrap())? as usize;
+            let stats = client
+                .with_max_concurrent(concurrency)
+                .block_stats(from, to, STATS_BATCH_SIZE)
+                .map_err(|err| format!("{}", err))?;
+            let is_color = !sub_matches.is_present("no-color") && config.color();
+            printer.println(&serde_json::to_value(&stats).unwrap(), is_color);
+            Ok(())
+        }
+        _ => Err(sub_matches.usage().to_owned()),
+    }
+}
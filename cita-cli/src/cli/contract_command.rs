@@ -1,4 +1,5 @@
 use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::json;
 
 use cita_tool::client::basic::Client;
 use cita_tool::client::system_contract::{
@@ -12,11 +13,13 @@ use cita_tool::client::system_contract::{
     NodeManagementExt, PermissionExt, PermissionManagementExt, PriceManagerExt, QuotaManagementExt,
     RoleExt, RoleManagementExt, SysConfigExt, VersionManagerExt,
 };
+use cita_tool::TransactionOptions;
 
 use crate::cli::{
-    encryption, get_url, is_hex, key_validator, parse_address, parse_height, parse_privkey,
-    parse_u256, parse_u64,
+    encryption, get_private_key, get_url, is_hex, key_validator, parse_address, parse_height,
+    parse_privkey, parse_u256, parse_u64,
 };
+use crate::config_file::{ENV_PRIVATE_KEY, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
 use crate::printer::Printer;
 
@@ -33,6 +36,7 @@ pub fn contract_command() -> App<'static, 'static> {
         .required(true);
     let quota_arg = Arg::with_name("quota")
         .long("quota")
+        .env(ENV_QUOTA)
         .takes_value(true)
         .validator(|quota| parse_u64(quota.as_str()).map(|_| ()))
         .help("Transaction quota costs, default 10_000_000");
@@ -93,8 +97,9 @@ pub fn contract_command() -> App<'static, 'static> {
         .help("Function hash list");
     let private_key = Arg::with_name("private-key")
         .long("private-key")
+        .env(ENV_PRIVATE_KEY)
+        .hide_env_values(true)
         .takes_value(true)
-        .required(true)
         .validator(|private_key| key_validator(private_key.as_ref()).map(|_| ()))
         .help("Private key");
     let admin_private = Arg::with_name("admin-private")
@@ -148,6 +153,25 @@ pub fn contract_command() -> App<'static, 'static> {
                         )
                         .arg(quota_arg.clone()),
                 )
+                .subcommand(
+                    SubCommand::with_name("approvePending")
+                        .about(
+                            "Approve the candidates not yet consensus nodes in batch transactions, \
+                             with --dry-run only display them",
+                        )
+                        .arg(admin_private.clone())
+                        .arg(
+                            Arg::with_name("node")
+                                .long("node")
+                                .takes_value(true)
+                                .required(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .validator(|address| parse_address(address.as_str()))
+                                .help("Candidate node address"),
+                        )
+                        .arg(quota_arg.clone()),
+                )
                 .subcommand(
                     SubCommand::with_name("setStake")
                         .arg(admin_private.clone())
@@ -176,6 +200,18 @@ pub fn contract_command() -> App<'static, 'static> {
                 .subcommand(SubCommand::with_name("getDefaultAQL").arg(height_arg.clone()))
                 .subcommand(SubCommand::with_name("getAccounts").arg(height_arg.clone()))
                 .subcommand(SubCommand::with_name("getQuotas").arg(height_arg.clone()))
+                .subcommand(
+                    SubCommand::with_name("suggest-bql")
+                        .about("Suggest a block quota limit from the quota used of recent blocks")
+                        .arg(
+                            Arg::with_name("sample")
+                                .long("sample")
+                                .default_value("100")
+                                .validator(|sample| parse_u64(sample.as_str()).map(|_| ()))
+                                .takes_value(true)
+                                .help("Number of the latest blocks to sample"),
+                        ),
+                )
                 .subcommand(
                     SubCommand::with_name("getAQL").arg(
                         address_arg.clone().help("Account address"),
@@ -792,6 +828,104 @@ pub fn contract_command() -> App<'static, 'static> {
         )
 }
 
+/// Methods only the admin can send, they pass the emergency brake
+const ADMIN_METHODS: [(&str, &str); 15] = [
+    ("NodeManager", "deleteNode"),
+    ("NodeManager", "approveNode"),
+    ("NodeManager", "approvePending"),
+    ("NodeManager", "setStake"),
+    ("QuotaManager", "setBQL"),
+    ("QuotaManager", "setDefaultAQL"),
+    ("QuotaManager", "setAQL"),
+    ("AdminManagement", "update"),
+    ("SysConfig", "setChainName"),
+    ("SysConfig", "setOperator"),
+    ("SysConfig", "setWebsite"),
+    ("SysConfig", "setBlockInterval"),
+    ("EmergencyBrake", "setState"),
+    ("PriceManager", "setQuotaPrice"),
+    ("VersionManager", "setVersion"),
+];
+
+/// Methods of the other transactions, rejected under emergency stop
+const BRAKED_METHODS: [(&str, &str); 24] = [
+    ("GroupManagement", "newGroup"),
+    ("GroupManagement", "deleteGroup"),
+    ("GroupManagement", "updateGroupName"),
+    ("GroupManagement", "addAccounts"),
+    ("GroupManagement", "deleteAccounts"),
+    ("RoleManagement", "newRole"),
+    ("RoleManagement", "deleteRole"),
+    ("RoleManagement", "updateRoleName"),
+    ("RoleManagement", "addPermissions"),
+    ("RoleManagement", "deletePermissions"),
+    ("RoleManagement", "setRole"),
+    ("RoleManagement", "cancelRole"),
+    ("RoleManagement", "clearRole"),
+    ("PermissionManagement", "newPermission"),
+    ("PermissionManagement", "deletePermission"),
+    ("PermissionManagement", "updatePermissionName"),
+    ("PermissionManagement", "addResources"),
+    ("PermissionManagement", "deleteResources"),
+    ("PermissionManagement", "setAuthorization"),
+    ("PermissionManagement", "setAuthorizations"),
+    ("PermissionManagement", "cancelAuthorization"),
+    ("PermissionManagement", "cancelAuthorizations"),
+    ("PermissionManagement", "clearAuthorization"),
+    ("BatchTx", "multiTxs"),
+];
+
+/// Fail early if the method can't be sent: the admin methods need the admin's private key,
+/// the other transactions are rejected under emergency stop. Nothing is checked in dry run,
+/// since nothing is sent.
+fn check_guards(
+    sub_matches: &ArgMatches,
+    config: &GlobalConfig,
+    client: &Client,
+) -> Result<(), String> {
+    if client.dry_run() {
+        return Ok(());
+    }
+    let (contract, m) = match sub_matches.subcommand() {
+        (contract, Some(m)) => (contract, m),
+        _ => return Ok(()),
+    };
+    let (method, m) = match m.subcommand() {
+        (method, Some(m)) => (method, m),
+        _ => return Ok(()),
+    };
+    if ADMIN_METHODS.contains(&(contract, method)) {
+        let mut client = client.clone();
+        client.set_private_key(&parse_privkey(
+            m.value_of("admin-private").unwrap(),
+            encryption(m, config),
+        )?);
+        require_admin(&client)
+    } else if BRAKED_METHODS.contains(&(contract, method)) {
+        require_brake_inactive(client)
+    } else {
+        Ok(())
+    }
+}
+
+/// Fail early if the private key of the client is not the admin's
+fn require_admin(client: &Client) -> Result<(), String> {
+    let signer = client
+        .sender()
+        .ok_or_else(|| "The admin private key is not set".to_string())?;
+    AdminClient::create(client.clone())
+        .require_admin(&signer, None)
+        .map_err(|err| format!("{}", err))
+}
+
+/// Fail early if the chain is under emergency stop
+fn require_brake_inactive(client: &Client) -> Result<(), String> {
+    let client: EmergencyBrakeClient<Client> = EmergencyBrakeExt::create(client.clone());
+    client
+        .require_inactive(None)
+        .map_err(|err| format!("{}", err))
+}
+
 /// System contract processor
 pub fn contract_processor(
     sub_matches: &ArgMatches,
@@ -804,6 +938,8 @@ pub fn contract_processor(
         .set_debug(debug)
         .set_uri(get_url(sub_matches, config));
 
+    check_guards(sub_matches, config, &client)?;
+
     let result = match sub_matches.subcommand() {
         ("NodeManager", Some(m)) => match m.subcommand() {
             ("listNode", Some(m)) => {
@@ -826,9 +962,10 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let address = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let mut client = NodeManageClient::create(client);
-                client.downgrade_consensus_node(address, quota)
+                client.downgrade_consensus_node(address, options)
             }
             ("approveNode", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -837,9 +974,29 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let address = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let mut client = NodeManageClient::create(client);
-                client.approve_node(address, quota)
+                client.approve_node(address, options)
+            }
+            ("approvePending", Some(m)) => {
+                let encryption = encryption(m, config);
+                client.set_private_key(&parse_privkey(
+                    m.value_of("admin-private").unwrap(),
+                    encryption,
+                )?);
+                let nodes: Vec<&str> = m.values_of("node").map(Iterator::collect).unwrap();
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                let approved = NodeManageClient::create(client)
+                    .approve_pending_nodes(&nodes, options)
+                    .map_err(|err| format!("{}", err))?
+                    .iter()
+                    .map(|node| format!("{:?}", node))
+                    .collect::<Vec<String>>();
+                let is_color = !sub_matches.is_present("no-color") && config.color();
+                printer.println(&json!(approved), is_color);
+                return Ok(());
             }
             ("setStake", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -848,13 +1005,14 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let address = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let stake = m
                     .value_of("stake")
                     .map(|stake| parse_u256(stake).unwrap())
                     .unwrap();
                 let mut client = NodeManageClient::create(client);
-                client.set_stake(address, stake, quota)
+                client.set_stake(address, stake, options)
             }
             ("stakePermillage", Some(m)) => {
                 let address = m.value_of("address").unwrap();
@@ -874,6 +1032,15 @@ pub fn contract_processor(
             ("getQuotas", Some(m)) => {
                 QuotaManageClient::create(client).get_quotas(m.value_of("height"))
             }
+            ("suggest-bql", Some(m)) => {
+                let sample = parse_u64(m.value_of("sample").unwrap())?;
+                let suggestion = QuotaManageClient::create(client)
+                    .bql_suggestion(sample)
+                    .map_err(|err| format!("{}", err))?;
+                let is_color = !sub_matches.is_present("no-color") && config.color();
+                printer.println(&json!(suggestion), is_color);
+                return Ok(());
+            }
             ("getAQL", Some(m)) => {
                 let address = m.value_of("address").unwrap();
                 QuotaManageClient::create(client).get_aql(address, m.value_of("height"))
@@ -885,8 +1052,9 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let quota_limit = parse_u256(m.value_of("quota-limit").unwrap())?;
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                QuotaManageClient::create(client).set_bql(quota_limit, quota)
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                QuotaManageClient::create(client).set_bql(quota_limit, options)
             }
             ("setDefaultAQL", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -895,8 +1063,9 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let quota_limit = parse_u256(m.value_of("quota-limit").unwrap())?;
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                QuotaManageClient::create(client).set_default_aql(quota_limit, quota)
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                QuotaManageClient::create(client).set_default_aql(quota_limit, options)
             }
             ("setAQL", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -906,8 +1075,9 @@ pub fn contract_processor(
                 )?);
                 let quota_limit = parse_u256(m.value_of("quota-limit").unwrap())?;
                 let address = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                QuotaManageClient::create(client).set_aql(address, quota_limit, quota)
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                QuotaManageClient::create(client).set_aql(address, quota_limit, options)
             }
             _ => return Err(m.usage().to_owned()),
         },
@@ -952,64 +1122,54 @@ pub fn contract_processor(
                 let origin = m.value_of("origin").unwrap();
                 let name = m.value_of("name").unwrap();
                 let accounts = m.value_of("accounts").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = GroupManageClient::create(client);
-                client.new_group(origin, name, accounts, quota)
+                client.new_group(origin, name, accounts, options)
             }
             ("deleteGroup", Some(m)) => {
                 let encryption = encryption(m, config);
                 let origin = m.value_of("origin").unwrap();
                 let target = m.value_of("target").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = GroupManageClient::create(client);
-                client.delete_group(origin, target, quota)
+                client.delete_group(origin, target, options)
             }
             ("updateGroupName", Some(m)) => {
                 let encryption = encryption(m, config);
                 let origin = m.value_of("origin").unwrap();
                 let target = m.value_of("target").unwrap();
                 let name = m.value_of("name").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = GroupManageClient::create(client);
-                client.update_group_name(origin, target, name, quota)
+                client.update_group_name(origin, target, name, options)
             }
             ("addAccounts", Some(m)) => {
                 let encryption = encryption(m, config);
                 let origin = m.value_of("origin").unwrap();
                 let target = m.value_of("target").unwrap();
                 let accounts = m.value_of("accounts").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = GroupManageClient::create(client);
-                client.add_accounts(origin, target, accounts, quota)
+                client.add_accounts(origin, target, accounts, options)
             }
             ("deleteAccounts", Some(m)) => {
                 let encryption = encryption(m, config);
                 let origin = m.value_of("origin").unwrap();
                 let target = m.value_of("target").unwrap();
                 let accounts = m.value_of("accounts").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = GroupManageClient::create(client);
-                client.delete_accounts(origin, target, accounts, quota)
+                client.delete_accounts(origin, target, accounts, options)
             }
             ("checkScope", Some(m)) => {
                 let origin = m.value_of("origin").unwrap();
@@ -1057,95 +1217,79 @@ pub fn contract_processor(
                 let encryption = encryption(m, config);
                 let name = m.value_of("name").unwrap();
                 let permissions = m.value_of("permissions").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::new_role(&mut client, name, permissions, quota)
+                RoleManagementExt::new_role(&mut client, name, permissions, options)
             }
             ("deleteRole", Some(m)) => {
                 let encryption = encryption(m, config);
                 let role = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::delete_role(&mut client, role, quota)
+                RoleManagementExt::delete_role(&mut client, role, options)
             }
             ("updateRoleName", Some(m)) => {
                 let encryption = encryption(m, config);
                 let role = m.value_of("address").unwrap();
                 let name = m.value_of("name").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::update_role_name(&mut client, role, name, quota)
+                RoleManagementExt::update_role_name(&mut client, role, name, options)
             }
             ("addPermissions", Some(m)) => {
                 let encryption = encryption(m, config);
                 let role = m.value_of("address").unwrap();
                 let permissions = m.value_of("permissions").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::add_permissions(&mut client, role, permissions, quota)
+                RoleManagementExt::add_permissions(&mut client, role, permissions, options)
             }
             ("deletePermissions", Some(m)) => {
                 let encryption = encryption(m, config);
                 let role = m.value_of("address").unwrap();
                 let permissions = m.value_of("permissions").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::delete_permissions(&mut client, role, permissions, quota)
+                RoleManagementExt::delete_permissions(&mut client, role, permissions, options)
             }
             ("setRole", Some(m)) => {
                 let encryption = encryption(m, config);
                 let account = m.value_of("account").unwrap();
                 let role = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::set_role(&mut client, account, role, quota)
+                RoleManagementExt::set_role(&mut client, account, role, options)
             }
             ("cancelRole", Some(m)) => {
                 let encryption = encryption(m, config);
                 let account = m.value_of("account").unwrap();
                 let role = m.value_of("address").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::cancel_role(&mut client, account, role, quota)
+                RoleManagementExt::cancel_role(&mut client, account, role, options)
             }
             ("clearRole", Some(m)) => {
                 let encryption = encryption(m, config);
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = RoleManageClient::create(client);
-                RoleManagementExt::clear_role(&mut client, account, quota)
+                RoleManagementExt::clear_role(&mut client, account, options)
             }
             ("queryRoles", Some(m)) => {
                 let account = m.value_of("account").unwrap();
@@ -1237,46 +1381,40 @@ pub fn contract_processor(
                 let name = m.value_of("name").unwrap();
                 let contracts = m.value_of("contracts").unwrap();
                 let function_hashes = m.value_of("function-hashes").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::new_permission(
                     &mut client,
                     name,
                     contracts,
                     function_hashes,
-                    quota,
+                    options,
                 )
             }
             ("deletePermission", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permission = m.value_of("permission").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
-                PermissionManagementExt::delete_permission(&mut client, permission, quota)
+                PermissionManagementExt::delete_permission(&mut client, permission, options)
             }
             ("updatePermissionName", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permission = m.value_of("permission").unwrap();
                 let name = m.value_of("name").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::update_permission_name(
                     &mut client,
                     permission,
                     name,
-                    quota,
+                    options,
                 )
             }
             ("addResources", Some(m)) => {
@@ -1284,18 +1422,16 @@ pub fn contract_processor(
                 let permission = m.value_of("permission").unwrap();
                 let contracts = m.value_of("contracts").unwrap();
                 let function_hashes = m.value_of("function-hashes").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::add_resources(
                     &mut client,
                     permission,
                     contracts,
                     function_hashes,
-                    quota,
+                    options,
                 )
             }
             ("deleteResources", Some(m)) => {
@@ -1303,93 +1439,86 @@ pub fn contract_processor(
                 let permission = m.value_of("permission").unwrap();
                 let contracts = m.value_of("contracts").unwrap();
                 let function_hashes = m.value_of("function-hashes").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::delete_resources(
                     &mut client,
                     permission,
                     contracts,
                     function_hashes,
-                    quota,
+                    options,
                 )
             }
             ("setAuthorization", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permission = m.value_of("permission").unwrap();
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
-                PermissionManagementExt::set_authorization(&mut client, account, permission, quota)
+                PermissionManagementExt::set_authorization(
+                    &mut client,
+                    account,
+                    permission,
+                    options,
+                )
             }
             ("setAuthorizations", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permissions = m.value_of("permissions").unwrap();
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::set_authorizations(
                     &mut client,
                     account,
                     permissions,
-                    quota,
+                    options,
                 )
             }
             ("cancelAuthorization", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permission = m.value_of("permission").unwrap();
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::cancel_authorization(
                     &mut client,
                     account,
                     permission,
-                    quota,
+                    options,
                 )
             }
             ("cancelAuthorizations", Some(m)) => {
                 let encryption = encryption(m, config);
                 let permissions = m.value_of("permissions").unwrap();
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
                 PermissionManagementExt::cancel_authorizations(
                     &mut client,
                     account,
                     permissions,
-                    quota,
+                    options,
                 )
             }
             ("clearAuthorization", Some(m)) => {
                 let encryption = encryption(m, config);
                 let account = m.value_of("account").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
                 let mut client = PermissionManageClient::create(client);
-                PermissionManagementExt::clear_authorization(&mut client, account, quota)
+                PermissionManagementExt::clear_authorization(&mut client, account, options)
             }
             _ => return Err(m.usage().to_owned()),
         },
@@ -1405,22 +1534,21 @@ pub fn contract_processor(
                     m.value_of("admin-private").unwrap(),
                     encryption,
                 )?);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let address = m.value_of("address").unwrap();
-                AdminClient::create(client).add_admin(address, quota)
+                AdminClient::create(client).add_admin(address, options)
             }
             _ => return Err(m.usage().to_owned()),
         },
         ("BatchTx", Some(m)) => match m.subcommand() {
             ("multiTxs", Some(m)) => {
                 let encryption = encryption(m, config);
-                client.set_private_key(&parse_privkey(
-                    m.value_of("private-key").unwrap(),
-                    encryption,
-                )?);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let txs = m.values_of("tx-code").map(Iterator::collect).unwrap();
-                BatchTxClient::create(client).multi_transactions(txs, quota)
+                BatchTxClient::create(client).multi_transactions(txs, options)
             }
             _ => return Err(m.usage().to_owned()),
         },
@@ -1465,8 +1593,9 @@ pub fn contract_processor(
                 )?);
                 let mut client: SysConfigClient<Client> = SysConfigExt::create(client);
                 let name = m.value_of("chain-name").unwrap();
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
-                SysConfigExt::set_chain_name(&mut client, name, quota)
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
+                SysConfigExt::set_chain_name(&mut client, name, options)
             }
             ("setOperator", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -1475,9 +1604,10 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: SysConfigClient<Client> = SysConfigExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let operator = m.value_of("operator").unwrap();
-                SysConfigExt::set_operator(&mut client, operator, quota)
+                SysConfigExt::set_operator(&mut client, operator, options)
             }
             ("setWebsite", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -1486,9 +1616,10 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: SysConfigClient<Client> = SysConfigExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let website = m.value_of("website").unwrap();
-                SysConfigExt::set_website(&mut client, website, quota)
+                SysConfigExt::set_website(&mut client, website, options)
             }
             ("setBlockInterval", Some(m)) => {
                 let encryption = encryption(m, config);
@@ -1497,13 +1628,14 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: SysConfigClient<Client> = SysConfigExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let block_interval = m
                     .value_of("blockInterval")
                     .map(|interval| parse_u256(interval).unwrap())
                     .unwrap();
 
-                SysConfigExt::set_block_interval(&mut client, block_interval, quota)
+                SysConfigExt::set_block_interval(&mut client, block_interval, options)
             }
             ("getCreateContractPermissionCheck", Some(m)) => {
                 let client: SysConfigClient<Client> = SysConfigExt::create(client);
@@ -1527,12 +1659,13 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: EmergencyBrakeClient<Client> = EmergencyBrakeExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let state = m
                     .value_of("state")
                     .map(|state| state.parse::<bool>().unwrap())
                     .unwrap();
-                EmergencyBrakeExt::set_state(&mut client, state, quota)
+                EmergencyBrakeExt::set_state(&mut client, state, options)
             }
             _ => return Err(sub_matches.usage().to_owned()),
         },
@@ -1548,12 +1681,13 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: PriceManagerClient<Client> = PriceManagerExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let price = m
                     .value_of("price")
                     .map(|price| parse_u256(price).unwrap())
                     .unwrap();
-                PriceManagerExt::set_price(&mut client, price, quota)
+                PriceManagerExt::set_price(&mut client, price, options)
             }
             _ => return Err(sub_matches.usage().to_owned()),
         },
@@ -1569,12 +1703,13 @@ pub fn contract_processor(
                     encryption,
                 )?);
                 let mut client: VersionManagerClient<Client> = VersionManagerExt::create(client);
-                let quota = m.value_of("quota").map(|quota| parse_u64(quota).unwrap());
+                let options = TransactionOptions::new()
+                    .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()));
                 let version = m
                     .value_of("version")
                     .map(|version| parse_u256(version).unwrap())
                     .unwrap();
-                VersionManagerExt::set_version(&mut client, version, quota)
+                VersionManagerExt::set_version(&mut client, version, options)
             }
             _ => return Err(sub_matches.usage().to_owned()),
         },
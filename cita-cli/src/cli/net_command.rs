@@ -0,0 +1,48 @@
+use clap::{App, ArgMatches, SubCommand};
+
+use cita_tool::client::basic::{Client, NetExt};
+
+use crate::cli::get_url;
+use crate::interactive::GlobalConfig;
+use crate::printer::Printer;
+
+/// Network command
+pub fn net_command() -> App<'static, 'static> {
+    App::new("net")
+        .about("Network information, such as connected peers")
+        .subcommand(SubCommand::with_name("peers").about("Display connected peers as a table"))
+}
+
+/// Network processor
+pub fn net_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &mut GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let client = client
+        .set_debug(debug)
+        .set_uri(get_url(sub_matches, config));
+
+    match sub_matches.subcommand() {
+        ("peers", _) => {
+            let peers = client.get_peers().map_err(|err| format!("{}", err))?;
+            let width = peers
+                .iter()
+                .map(|peer| peer.node_id.len())
+                .max()
+                .unwrap_or(0)
+                .max("NODE ID".len());
+            let mut table = vec![format!("{:<width$}  ADDRESS", "NODE ID", width = width)];
+            table.extend(
+                peers.iter().map(|peer| {
+                    format!("{:<width$}  {}", peer.node_id, peer.address, width = width)
+                }),
+            );
+            printer.println(&table.join("\n"), false);
+            Ok(())
+        }
+        _ => Err(sub_matches.usage().to_owned()),
+    }
+}
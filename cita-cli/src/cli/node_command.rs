@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use cita_tool::client::basic::Client;
+use cita_tool::parse_url;
+
+use crate::cli::{get_url, parse_u64};
+use crate::interactive::GlobalConfig;
+use crate::printer::Printer;
+
+/// Node command
+pub fn node_command() -> App<'static, 'static> {
+    App::new("node")
+        .about("Node health, such as the latency of JSON-RPC urls")
+        .subcommand(
+            SubCommand::with_name("ping")
+                .about("Send blockNumber to the urls at the same time, display the latency table")
+                .arg(
+                    Arg::with_name("target")
+                        .multiple(true)
+                        .validator(|url| parse_url(url.as_ref()).map(|_| ()))
+                        .help("JSON-RPC urls, default is the current url"),
+                )
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .takes_value(true)
+                        .default_value("3000")
+                        .validator(|timeout| parse_u64(timeout.as_ref()).map(|_| ()))
+                        .help("Deadline of every url in milliseconds"),
+                ),
+        )
+}
+
+/// Node processor
+pub fn node_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let client = client.set_debug(debug);
+
+    match sub_matches.subcommand() {
+        ("ping", Some(m)) => {
+            let urls = match m.values_of("target") {
+                Some(urls) => urls.map(parse_url).collect::<Result<Vec<_>, _>>()?,
+                None => vec![parse_url(get_url(sub_matches, config))?],
+            };
+            let deadline = Duration::from_millis(parse_u64(m.value_of("timeout").unwrap())?);
+            let healths = client.check_url_health(urls.into_iter(), deadline);
+
+            let width = healths
+                .iter()
+                .map(|health| health.url.len())
+                .max()
+                .unwrap_or(0)
+                .max("URL".len());
+            let mut table = vec![format!(
+                "{:<width$}  {:>10}  {:>10}  ERROR",
+                "URL",
+                "LATENCY",
+                "HEIGHT",
+                width = width
+            )];
+            table.extend(healths.iter().map(|health| {
+                format!(
+                    "{:<width$}  {:>10}  {:>10}  {}",
+                    health.url,
+                    health
+                        .latency
+                        .map(|latency| format!("{}ms", latency.as_millis()))
+                        .unwrap_or_else(|| "-".to_string()),
+                    health
+                        .block_number
+                        .map(|height| height.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    health.error.as_ref().map(String::as_str).unwrap_or(""),
+                    width = width
+                )
+                .trim_end()
+                .to_string()
+            }));
+            printer.println(&table.join("\n"), false);
+            Ok(())
+        }
+        _ => Err(sub_matches.usage().to_owned()),
+    }
+}
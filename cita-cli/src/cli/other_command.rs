@@ -1,13 +1,14 @@
-use clap::{App, Arg, ArgMatches, SubCommand};
+use clap::{App, Arg, ArgMatches, Shell, SubCommand};
 use serde_json::json;
 
 use cita_tool::client::basic::{Client, Transfer};
 use cita_tool::{JsonRpcParams, ParamsValue, TransactionOptions};
 
 use crate::cli::{
-    encryption, get_url, is_hex, key_validator, parse_address, parse_privkey, parse_u256,
-    parse_u32, parse_u64, search_app,
+    encryption, get_private_key, get_url, is_hex, key_validator, parse_address, parse_privkey,
+    parse_u256, parse_u32, parse_u64, search_app,
 };
+use crate::config_file::{ENV_CHAIN_ID, ENV_PRIVATE_KEY, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
 use crate::printer::Printer;
 
@@ -126,9 +127,10 @@ pub fn transfer_command() -> App<'static, 'static> {
         .arg(
             Arg::with_name("private-key")
                 .long("private-key")
+                .env(ENV_PRIVATE_KEY)
+                .hide_env_values(true)
                 .validator(|private| key_validator(private.as_str()).map(|_| ()))
                 .takes_value(true)
-                .required(true)
                 .help("Transfer Account Private Key"),
         )
         .arg(
@@ -142,6 +144,7 @@ pub fn transfer_command() -> App<'static, 'static> {
         .arg(
             Arg::with_name("quota")
                 .long("quota")
+                .env(ENV_QUOTA)
                 .default_value("30000")
                 .validator(|quota| parse_u64(quota.as_str()).map(|_| ()))
                 .takes_value(true)
@@ -163,7 +166,7 @@ pub fn transfer_processor(
 
     let encryption = encryption(sub_matches, config);
     client.set_private_key(&parse_privkey(
-        sub_matches.value_of("private-key").unwrap(),
+        get_private_key(sub_matches, config)?,
         encryption,
     )?);
     let address = sub_matches.value_of("address").unwrap();
@@ -217,6 +220,7 @@ pub fn benchmark_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("chain-id")
                         .long("chain-id")
+                        .env(ENV_CHAIN_ID)
                         .takes_value(true)
                         .validator(|chain_id| parse_u256(chain_id.as_ref()).map(|_| ()))
                         .help("The chain_id of transaction"),
@@ -224,14 +228,16 @@ pub fn benchmark_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("private-key")
                         .long("private-key")
+                        .env(ENV_PRIVATE_KEY)
+                        .hide_env_values(true)
                         .takes_value(true)
-                        .required(true)
                         .validator(|privkey| key_validator(privkey.as_ref()).map(|_| ()))
                         .help("The private key of transaction"),
                 )
                 .arg(
                     Arg::with_name("quota")
                         .long("quota")
+                        .env(ENV_QUOTA)
                         .takes_value(true)
                         .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
                         .help("Transaction quota costs, default 10_000_000"),
@@ -294,7 +300,7 @@ pub fn benchmark_processor(
                 }
                 Err(e) => {
                     // an error occurred!
-                    return Err(format!("Error: {:?}", e));
+                    return Err(format!("Error: {}", e));
                 }
             }
         }
@@ -303,9 +309,7 @@ pub fn benchmark_processor(
             if let Some(chain_id) = m.value_of("chain-id").map(|s| parse_u256(s).unwrap()) {
                 client.set_chain_id(chain_id);
             }
-            if let Some(private_key) = m.value_of("private-key") {
-                client.set_private_key(&parse_privkey(private_key, encryption)?);
-            }
+            client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
             let code = m.value_of("code").unwrap();
             let address = m.value_of("address").unwrap();
             let current_height = m.value_of("height").map(|s| parse_u64(s).unwrap());
@@ -326,14 +330,24 @@ pub fn benchmark_processor(
                 .map(|number| parse_u32(number).unwrap())
                 .unwrap();
 
-            let mut txs = Vec::with_capacity(number as usize);
+            let mut byte_codes = Vec::with_capacity(number as usize);
             for _ in 0..number {
                 let tx = client
                     .generate_transaction(tx_options)
                     .map_err(|err| format!("{}", err))?;
-                let byte_code = client
-                    .generate_sign_transaction(&tx)
-                    .map_err(|err| format!("{}", err))?;
+                byte_codes.push(
+                    client
+                        .generate_sign_transaction(&tx)
+                        .map_err(|err| format!("{}", err))?,
+                );
+            }
+            if client.dry_run() {
+                printer.println(&json!(byte_codes), true);
+                return Ok(());
+            }
+
+            let mut txs = Vec::with_capacity(number as usize);
+            for byte_code in byte_codes {
                 let params = JsonRpcParams::new()
                     .insert(
                         "method",
@@ -360,10 +374,19 @@ pub fn benchmark_processor(
 pub fn completion_command() -> App<'static, 'static> {
     App::new("completions")
         .about("Generates completion scripts for your shell")
+        .after_help(concat!(
+            "Install the script printed to stdout:\n",
+            "    bash:       cita-cli completions bash > /etc/bash_completion.d/cita-cli\n",
+            "    zsh:        cita-cli completions zsh > \"${fpath[1]}/_cita-cli\"\n",
+            "    fish:       cita-cli completions fish > ~/.config/fish/completions/cita-cli.fish\n",
+            "    powershell: cita-cli completions powershell >> $PROFILE\n",
+            "    elvish:     cita-cli completions elvish >> ~/.elvish/rc.elv\n",
+            "then restart the shell."
+        ))
         .arg(
             Arg::with_name("shell")
                 .required(true)
-                .possible_values(&["bash", "fish", "zsh"])
+                .possible_values(&Shell::variants())
                 .help("The shell to generate the script for"),
         )
 }
@@ -8,12 +8,15 @@ use cita_tool::{
 };
 
 use crate::cli::{
-    encryption, get_url, h256_validator, is_hex, key_validator, parse_address, parse_height,
-    parse_privkey, parse_u256, parse_u32, parse_u64,
+    encryption, get_private_key, get_url, h256_validator, is_hex, key_validator, parse_address,
+    parse_height, parse_privkey, parse_u256, parse_u32, parse_u64,
 };
+use crate::config_file::{ENV_CHAIN_ID, ENV_PRIVATE_KEY, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
-use crate::printer::Printer;
+use crate::printer::{Printer, Progress};
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Generate rpc sub command
 pub fn rpc_command() -> App<'static, 'static> {
@@ -54,6 +57,7 @@ pub fn rpc_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("chain-id")
                         .long("chain-id")
+                        .env(ENV_CHAIN_ID)
                         .takes_value(true)
                         .validator(|chain_id| parse_u256(chain_id.as_ref()).map(|_| ()))
                         .help("The chain_id of transaction"),
@@ -61,14 +65,16 @@ pub fn rpc_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("private-key")
                         .long("private-key")
+                        .env(ENV_PRIVATE_KEY)
+                        .hide_env_values(true)
                         .takes_value(true)
-                        .required(true)
                         .validator(|privkey| key_validator(privkey.as_ref()).map(|_| ()))
                         .help("The private key of transaction"),
                 )
                 .arg(
                     Arg::with_name("quota")
                         .long("quota")
+                        .env(ENV_QUOTA)
                         .takes_value(true)
                         .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
                         .help("Transaction quota costs, default 10_000_000"),
@@ -190,6 +196,19 @@ pub fn rpc_command() -> App<'static, 'static> {
                         .required(true)
                         .takes_value(true)
                         .help("The hash of specific transaction"),
+                )
+                .arg(
+                    Arg::with_name("wait")
+                        .long("wait")
+                        .help("Wait until the transaction is committed"),
+                )
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .default_value("60")
+                        .validator(|timeout| parse_u64(timeout.as_str()).map(|_| ()))
+                        .takes_value(true)
+                        .help("Seconds to wait for the transaction"),
                 ),
         )
         .subcommand(
@@ -516,9 +535,7 @@ pub fn rpc_processor(
             if let Some(chain_id) = m.value_of("chain-id").map(|s| parse_u256(s).unwrap()) {
                 client.set_chain_id(chain_id);
             }
-            if let Some(private_key) = m.value_of("private-key") {
-                client.set_private_key(&parse_privkey(private_key, encryption)?);
-            }
+            client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
             let code = m.value_of("code").unwrap();
             let address = m.value_of("address").unwrap();
             let current_height = m.value_of("height").map(|s| parse_u64(s).unwrap());
@@ -560,7 +577,20 @@ pub fn rpc_processor(
         ),
         ("getTransactionReceipt", Some(m)) => {
             let hash = m.value_of("hash").unwrap();
-            client.get_transaction_receipt(hash)
+            if m.is_present("wait") {
+                let timeout = parse_u64(m.value_of("timeout").unwrap())?;
+                let progress = Progress::new(
+                    !sub_matches.is_present("no-progress"),
+                    "Waiting for the transaction",
+                );
+                let result = wait_for_receipt(&client, hash, timeout, &progress);
+                if result.is_err() {
+                    progress.clear();
+                }
+                result
+            } else {
+                client.get_transaction_receipt(hash)
+            }
         }
         ("call", Some(m)) => client.call(
             m.value_of("from"),
@@ -653,3 +683,29 @@ pub fn rpc_processor(
     set_output(&resp, config);
     Ok(())
 }
+
+/// Poll the receipt every second until the transaction is committed,
+/// fail after `timeout` seconds
+pub(crate) fn wait_for_receipt(
+    client: &Client,
+    hash: &str,
+    timeout: u64,
+    progress: &Progress,
+) -> Result<JsonRpcResponse, ToolError> {
+    let start = Instant::now();
+    loop {
+        let response = client.get_transaction_receipt(hash)?;
+        if response.result().is_some() {
+            progress.finish(&format!("Transaction {} is committed", hash));
+            return Ok(response);
+        }
+        if start.elapsed() >= Duration::from_secs(timeout) {
+            return Err(ToolError::Customize(format!(
+                "Transaction {} is not committed in {} seconds",
+                hash, timeout
+            )));
+        }
+        progress.set_block_number(client.get_current_height()?);
+        thread::sleep(Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,134 @@
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+
+use clap::{App, Arg, ArgMatches};
+
+use cita_tool::client::basic::Client;
+use cita_tool::{parse_url, serve, RateLimit, ServerConfig, ServerOptions};
+
+use crate::cli::get_url;
+use crate::config_file::config_path;
+use crate::interactive::GlobalConfig;
+
+/// The secret verifying the JWT of the methods requiring auth
+const ENV_JWT_SECRET: &str = "CITA_CLI_JWT_SECRET";
+/// Shortest JWT secret accepted, the size of the HS256 hash
+const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// JSON-RPC proxy command
+pub fn server_command() -> App<'static, 'static> {
+    App::new("server")
+        .about(
+            "Serve JSON-RPC, forwarding the allowed methods to the node, \
+             the [server] table of the config file sets allow, deny and require-auth, \
+             the JWT secret of require-auth is $CITA_CLI_JWT_SECRET, at least 32 bytes",
+        )
+        .arg(
+            Arg::with_name("listen")
+                .long("listen")
+                .takes_value(true)
+                .default_value("127.0.0.1:8545")
+                .validator(|listen| {
+                    listen
+                        .parse::<SocketAddr>()
+                        .map(|_| ())
+                        .map_err(|err| err.to_string())
+                })
+                .help("The address to listen on"),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .long("backend")
+                .takes_value(true)
+                .validator(|url| parse_url(url.as_ref()).map(|_| ()))
+                .help("JSONRPC server URL of the node, default is the url of the environment"),
+        )
+        .arg(
+            Arg::with_name("allow-methods")
+                .long("allow-methods")
+                .takes_value(true)
+                .use_delimiter(true)
+                .multiple(true)
+                .help("The methods to forward, separated by comma, the others are rejected, default is all, override allow of the config"),
+        )
+        .arg(
+            Arg::with_name("rate-limit")
+                .long("rate-limit")
+                .takes_value(true)
+                .validator(|limit| {
+                    limit
+                        .parse::<RateLimit>()
+                        .map(|_| ())
+                        .map_err(|err| err.to_string())
+                })
+                .help("The requests allowed of all callers, such as 100/s, 6000/m or 360000/h"),
+        )
+}
+
+/// JSON-RPC proxy processor
+pub fn server_processor(
+    sub_matches: &ArgMatches,
+    config: &GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let backend = match sub_matches.value_of("backend") {
+        Some(backend) => backend,
+        None => get_url(sub_matches, config),
+    };
+    let client = client.set_debug(debug).set_uri(backend);
+    let listen = sub_matches
+        .value_of("listen")
+        .unwrap()
+        .parse::<SocketAddr>()
+        .map_err(|err| err.to_string())?;
+    let rate_limit = match sub_matches.value_of("rate-limit") {
+        Some(limit) => Some(limit.parse::<RateLimit>().map_err(|err| err.to_string())?),
+        None => None,
+    };
+    let mut options = ServerOptions::new()
+        .set_config(server_config(sub_matches.value_of("config"))?)
+        .set_jwt_secret(jwt_secret()?)
+        .set_rate_limit(rate_limit);
+    if let Some(methods) = sub_matches.values_of("allow-methods") {
+        options = options.set_allow_methods(Some(methods.map(ToString::to_string).collect()));
+    }
+    serve(listen, client, options).map_err(|err| err.to_string())
+}
+
+/// The JWT secret of the environment, a short one would make the tokens forgeable
+fn jwt_secret() -> Result<Option<Vec<u8>>, String> {
+    match env::var(ENV_JWT_SECRET) {
+        Ok(secret) if secret.len() < MIN_JWT_SECRET_LEN => Err(format!(
+            "${} must be at least {} bytes",
+            ENV_JWT_SECRET, MIN_JWT_SECRET_LEN
+        )),
+        Ok(secret) => Ok(Some(secret.into_bytes())),
+        Err(_) => Ok(None),
+    }
+}
+
+/// The `[server]` table of the TOML config file, found as `FileConfig::load` does,
+/// the default if there is no file or table
+fn server_config(config: Option<&str>) -> Result<ServerConfig, String> {
+    let path = match config_path(config) {
+        Some(path) => path,
+        None => return Ok(ServerConfig::default()),
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|err| format!("Read config file {} failed: {}", path.display(), err))?;
+    let value = content
+        .parse::<toml::Value>()
+        .map_err(|err| format!("Parse config file {} failed: {}", path.display(), err))?;
+    match value.get("server") {
+        Some(server) => server.clone().try_into::<ServerConfig>().map_err(|err| {
+            format!(
+                "Invalid [server] of config file {}: {}",
+                path.display(),
+                err
+            )
+        }),
+        None => Ok(ServerConfig::default()),
+    }
+}
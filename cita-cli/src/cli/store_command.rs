@@ -4,8 +4,10 @@ use cita_tool::client::basic::{Client, StoreExt};
 use cita_tool::remove_0x;
 
 use crate::cli::{
-    encryption, get_url, is_hex, key_validator, parse_address, parse_privkey, parse_u64,
+    encryption, get_private_key, get_url, is_hex, key_validator, parse_address, parse_privkey,
+    parse_u64,
 };
+use crate::config_file::{ENV_CHAIN_ID, ENV_PRIVATE_KEY, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
 use crate::printer::Printer;
 
@@ -17,20 +19,23 @@ pub fn store_command() -> App<'static, 'static> {
     let common_args = [
         Arg::with_name("chain-id")
             .long("chain-id")
+            .env(ENV_CHAIN_ID)
             .takes_value(true)
             .validator(|chain_id| match chain_id.parse::<u32>() {
                 Ok(_) => Ok(()),
-                Err(err) => Err(format!("{:?}", err)),
+                Err(err) => Err(format!("{}", err)),
             })
             .help("The chain_id of transaction"),
         Arg::with_name("private-key")
             .long("private-key")
+            .env(ENV_PRIVATE_KEY)
+            .hide_env_values(true)
             .takes_value(true)
-            .required(true)
             .validator(|privkey| key_validator(privkey.as_ref()).map(|_| ()))
             .help("The private key of transaction"),
         Arg::with_name("quota")
             .long("quota")
+            .env(ENV_QUOTA)
             .takes_value(true)
             .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
             .help("Transaction quota costs, default is 1_000_000"),
@@ -98,9 +103,7 @@ pub fn store_processor(
             let encryption = encryption(m, config);
             let quota = m.value_of("quota").map(|s| parse_u64(s).unwrap());
             let content = remove_0x(m.value_of("content").unwrap());
-            if let Some(private_key) = m.value_of("private-key") {
-                client.set_private_key(&parse_privkey(private_key, encryption)?);
-            }
+            client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
             client.store_data(content, quota)
         }
         ("abi", Some(m)) => {
@@ -118,9 +121,7 @@ pub fn store_processor(
                 }
             };
             let address = m.value_of("address").unwrap();
-            if let Some(private_key) = m.value_of("private-key") {
-                client.set_private_key(&parse_privkey(private_key, encryption)?);
-            }
+            client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
             client.store_abi(address, content, quota)
         }
         _ => {
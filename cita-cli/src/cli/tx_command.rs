@@ -1,12 +1,13 @@
 use clap::{App, Arg, ArgMatches, SubCommand};
 
 use cita_tool::client::basic::Client;
-use cita_tool::{encode, ProtoMessage, TransactionOptions, UnverifiedTransaction};
+use cita_tool::{encode, parse_url, ProtoMessage, TransactionOptions, UnverifiedTransaction};
 
 use crate::cli::{
-    encryption, get_url, is_hex, key_validator, parse_address, parse_privkey, parse_u256,
-    parse_u32, parse_u64,
+    encryption, get_private_key, get_url, is_hex, key_validator, parse_address, parse_privkey,
+    parse_u256, parse_u32, parse_u64,
 };
+use crate::config_file::{ENV_CHAIN_ID, ENV_PRIVATE_KEY, ENV_QUOTA};
 use crate::interactive::{set_output, GlobalConfig};
 use crate::printer::Printer;
 use std::fs::File;
@@ -49,6 +50,7 @@ pub fn tx_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("chain-id")
                         .long("chain-id")
+                        .env(ENV_CHAIN_ID)
                         .takes_value(true)
                         .validator(|chain_id| parse_u256(chain_id.as_ref()).map(|_| ()))
                         .help("The chain_id of transaction, default query to the chain"),
@@ -56,6 +58,7 @@ pub fn tx_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("quota")
                         .long("quota")
+                        .env(ENV_QUOTA)
                         .takes_value(true)
                         .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
                         .help("Transaction quota costs, default is 1_000_000"),
@@ -101,9 +104,10 @@ pub fn tx_command() -> App<'static, 'static> {
                 .arg(
                     Arg::with_name("private-key")
                         .long("private-key")
+                        .env(ENV_PRIVATE_KEY)
+                        .hide_env_values(true)
                         .validator(|private| key_validator(private.as_str()).map(|_| ()))
                         .takes_value(true)
-                        .required(true)
                         .help("Transfer Account Private Key"),
                 ),
         )
@@ -126,6 +130,33 @@ pub fn tx_command() -> App<'static, 'static> {
                         .help("content data file path"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("replay")
+                .about("Replay the transaction as a traced call on the node with the debug API")
+                .arg(
+                    Arg::with_name("hash")
+                        .long("hash")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|hash| is_hex(hash.as_str()))
+                        .help("The hash of the transaction"),
+                )
+                .arg(
+                    Arg::with_name("debug-url")
+                        .long("debug-url")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|url| parse_url(url.as_ref()).map(|_| ()))
+                        .help("JSONRPC server URL of the node with the debug API"),
+                )
+                .arg(
+                    Arg::with_name("block")
+                        .long("block")
+                        .takes_value(true)
+                        .validator(|block| parse_u64(block.as_ref()).map(|_| ()))
+                        .help("Replay at the state of the block, default is the parent block"),
+                ),
+        )
 }
 
 pub fn tx_processor(
@@ -178,9 +209,7 @@ pub fn tx_processor(
         }
         ("sendTransaction", Some(m)) => {
             let encryption = encryption(sub_matches, config);
-            if let Some(private_key) = m.value_of("private-key") {
-                client.set_private_key(&parse_privkey(private_key, encryption)?);
-            }
+            client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
             let byte_code = m.value_of("byte-code").unwrap();
             client.send_transaction(byte_code)
         }
@@ -199,6 +228,16 @@ pub fn tx_processor(
             printer.println(&tx.to_json(encryption)?, is_color);
             return Ok(());
         }
+        ("replay", Some(m)) => {
+            let debug_node = client.clone().set_uri(m.value_of("debug-url").unwrap());
+            let block = m.value_of("block").map(|s| parse_u64(s).unwrap());
+            let trace = client
+                .replay_transaction(m.value_of("hash").unwrap(), &debug_node, block)
+                .map_err(|err| format!("{}", err))?;
+            let trace = serde_json::to_value(&trace).map_err(|err| format!("{}", err))?;
+            printer.println(&trace, is_color);
+            return Ok(());
+        }
         _ => {
             return Err(sub_matches.usage().to_owned());
         }
@@ -0,0 +1,202 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use cita_tool::client::basic::{Client, ClientExt};
+use cita_tool::{
+    contract_decode_output_json, contract_encode_input, load_contract, ParamsValue, ResponseValue,
+    TransactionOptions,
+};
+
+use crate::cli::{
+    encryption, get_private_key, get_url, key_validator, parse_address, parse_height,
+    parse_privkey, parse_u256, parse_u64, wait_for_receipt,
+};
+use crate::config_file::{ENV_PRIVATE_KEY, ENV_QUOTA};
+use crate::interactive::{set_output, GlobalConfig};
+use crate::printer::{Printer, Progress};
+
+/// Call or send transactions to any deployed contract with its ABI
+pub fn user_contract_command() -> App<'static, 'static> {
+    let address_arg = Arg::with_name("address")
+        .long("address")
+        .takes_value(true)
+        .required(true)
+        .validator(|address| parse_address(address.as_str()))
+        .help("The contract address");
+    let abi_arg = Arg::with_name("abi")
+        .long("abi")
+        .takes_value(true)
+        .required(true)
+        .help("ABI json file path");
+    let method_arg = Arg::with_name("method")
+        .long("method")
+        .takes_value(true)
+        .required(true)
+        .help("Function name");
+    let arg_arg = Arg::with_name("arg")
+        .long("arg")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .allow_hyphen_values(true)
+        .help("Function parameter, in order, arrays such as [1,2]");
+    let no_lenient_flag = Arg::with_name("no-lenient")
+        .long("no-lenient")
+        .help("Don't allow short representation of input params");
+
+    App::new("contract")
+        .about("Call or send transactions to any deployed contract with its ABI")
+        .subcommand(
+            SubCommand::with_name("call")
+                .about("Call a function (readonly) and decode the return values as JSON")
+                .arg(address_arg.clone())
+                .arg(abi_arg.clone())
+                .arg(method_arg.clone())
+                .arg(arg_arg.clone())
+                .arg(no_lenient_flag.clone())
+                .arg(
+                    Arg::with_name("from")
+                        .long("from")
+                        .takes_value(true)
+                        .validator(|address| parse_address(address.as_str()))
+                        .help("From address"),
+                )
+                .arg(
+                    Arg::with_name("height")
+                        .long("height")
+                        .takes_value(true)
+                        .default_value("latest")
+                        .validator(|s| parse_height(s.as_str()))
+                        .help("The block number"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("send")
+                .about("Send a transaction calling the function and wait for the receipt")
+                .arg(address_arg)
+                .arg(abi_arg)
+                .arg(method_arg)
+                .arg(arg_arg)
+                .arg(no_lenient_flag)
+                .arg(
+                    Arg::with_name("private-key")
+                        .long("private-key")
+                        .env(ENV_PRIVATE_KEY)
+                        .hide_env_values(true)
+                        .takes_value(true)
+                        .validator(|privkey| key_validator(privkey.as_ref()).map(|_| ()))
+                        .help("The private key of transaction"),
+                )
+                .arg(
+                    Arg::with_name("quota")
+                        .long("quota")
+                        .env(ENV_QUOTA)
+                        .takes_value(true)
+                        .validator(|quota| parse_u64(quota.as_ref()).map(|_| ()))
+                        .help("Transaction quota costs, default 10_000_000"),
+                )
+                .arg(
+                    Arg::with_name("value")
+                        .long("value")
+                        .takes_value(true)
+                        .validator(|value| parse_u256(value.as_ref()).map(|_| ()))
+                        .help("The value to send, default is 0"),
+                )
+                .arg(
+                    Arg::with_name("no-wait")
+                        .long("no-wait")
+                        .help("Don't wait for the receipt, display the transaction hash"),
+                )
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .default_value("60")
+                        .validator(|timeout| parse_u64(timeout.as_str()).map(|_| ()))
+                        .takes_value(true)
+                        .help("Seconds to wait for the receipt"),
+                ),
+        )
+}
+
+/// Contract processor
+pub fn user_contract_processor(
+    sub_matches: &ArgMatches,
+    printer: &Printer,
+    config: &mut GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let is_color = !sub_matches.is_present("no-color") && config.color();
+    let mut client = client
+        .set_debug(debug)
+        .set_uri(get_url(sub_matches, config));
+
+    let m = match sub_matches.subcommand() {
+        ("call", Some(m)) | ("send", Some(m)) => m,
+        _ => return Err(sub_matches.usage().to_owned()),
+    };
+    let contract = load_contract(m.value_of("abi"), None).map_err(|err| err.to_string())?;
+    let method = m.value_of("method").unwrap();
+    let values: Vec<String> = match m.values_of("arg") {
+        None => Vec::new(),
+        Some(args) => args.map(ToOwned::to_owned).collect(),
+    };
+    let data = contract_encode_input(&contract, method, &values, !m.is_present("no-lenient"))
+        .map_err(|err| err.to_string())?;
+    let address = m.value_of("address").unwrap();
+
+    if sub_matches.subcommand_name() == Some("call") {
+        let output = match client
+            .call(
+                m.value_of("from"),
+                address,
+                Some(&format!("0x{}", data)),
+                m.value_of("height").unwrap(),
+            )
+            .and_then(|response| response.into_result())
+            .map_err(|err| err.to_string())?
+        {
+            ResponseValue::Singe(ParamsValue::String(output)) => output,
+            result => return Err(format!("Unexpected call result: {}", result)),
+        };
+        let value = contract_decode_output_json(&contract, method, &output)
+            .map_err(|err| err.to_string())?;
+        printer.println(&value, is_color);
+        return Ok(());
+    }
+
+    let encryption = encryption(m, config);
+    client.set_private_key(&parse_privkey(get_private_key(m, config)?, encryption)?);
+    let tx_options = TransactionOptions::new()
+        .set_code(&data)
+        .set_address(address)
+        .set_quota(m.value_of("quota").map(|quota| parse_u64(quota).unwrap()))
+        .set_value(m.value_of("value").map(|value| parse_u256(value).unwrap()));
+    let response = client
+        .send_raw_transaction(tx_options)
+        .map_err(|err| err.to_string())?;
+    let hash = response
+        .result()
+        .as_ref()
+        .and_then(ResponseValue::as_map)
+        .and_then(|result| result.get("hash"))
+        .and_then(ParamsValue::as_str)
+        .map(ToOwned::to_owned);
+    let response = match hash {
+        Some(ref hash) if !m.is_present("no-wait") => {
+            let timeout = parse_u64(m.value_of("timeout").unwrap())?;
+            let progress = Progress::new(
+                !sub_matches.is_present("no-progress"),
+                "Waiting for the transaction",
+            );
+            wait_for_receipt(&client, hash, timeout, &progress).map_err(|err| {
+                progress.clear();
+                err.to_string()
+            })?
+        }
+        // Dry run or `--no-wait`
+        _ => response,
+    };
+    printer.println(&response, is_color);
+    set_output(&response, config);
+    Ok(())
+}
@@ -2,10 +2,17 @@ use std::str::FromStr;
 
 use clap::{App, ArgMatches};
 
-use cita_tool::{remove_0x, Address, Encryption, PrivateKey, H256, H512, U256};
+use cita_tool::{remove_0x, validate_checksum_address, Encryption, PrivateKey, H256, H512, U256};
 
 use crate::interactive::GlobalConfig;
 
+/// Get private key from arg match, or the private-key of the config file
+pub fn get_private_key<'a>(m: &'a ArgMatches, config: &'a GlobalConfig) -> Result<&'a str, String> {
+    m.value_of("private-key")
+        .or_else(|| config.private_key())
+        .ok_or_else(|| "--private-key is required, or private-key of the config file".to_string())
+}
+
 /// Get url from arg match
 pub fn get_url<'a>(m: &'a ArgMatches, config: &'a GlobalConfig) -> &'a str {
     match m.value_of("url") {
@@ -26,7 +33,7 @@ pub fn parse_u64(height: &str) -> Result<u64, String> {
         Ok(()) => Ok(u64::from_str_radix(remove_0x(height), 16).map_err(|err| format!("{}", err))?),
         _ => match height.parse::<u64>() {
             Ok(number) => Ok(number),
-            Err(e) => Err(format!("{:?}", e)),
+            Err(e) => Err(format!("{}", e)),
         },
     }
 }
@@ -37,7 +44,7 @@ pub fn parse_u32(value: &str) -> Result<u32, String> {
         Ok(()) => Ok(u32::from_str_radix(remove_0x(value), 16).map_err(|err| format!("{}", err))?),
         _ => match value.parse::<u32>() {
             Ok(number) => Ok(number),
-            Err(e) => Err(format!("{:?}", e)),
+            Err(e) => Err(format!("{}", e)),
         },
     }
 }
@@ -73,7 +80,7 @@ pub fn parse_height(height: &str) -> Result<(), String> {
         "latest" | "earliest" | "pending" => Ok(()),
         _ => match parse_u64(height) {
             Ok(_) => Ok(()),
-            Err(e) => Err(format!("{:?}", e)),
+            Err(e) => Err(format!("{}", e)),
         },
     }
 }
@@ -108,7 +115,7 @@ pub fn parse_address(value: &str) -> Result<(), String> {
     if remove_0x(value).is_empty() {
         return Ok(());
     }
-    Address::from_str(remove_0x(value))
+    validate_checksum_address(value)
         .map(|_| ())
         .map_err(|err| err.to_string())
 }
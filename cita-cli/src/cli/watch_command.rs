@@ -0,0 +1,174 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use clap::{App, Arg, ArgMatches};
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+
+use cita_tool::client::basic::Client;
+use cita_tool::{ChainStatus, ToolError};
+
+use crate::cli::{get_url, parse_u64};
+use crate::interactive::GlobalConfig;
+
+/// Time between two refreshes of the display
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+/// The TPS and the block time are measured over the latest blocks
+const THROUGHPUT_BLOCKS: u64 = 10;
+
+/// Watch command
+pub fn watch_command() -> App<'static, 'static> {
+    App::new("watch")
+        .about(
+            "Display the block number, TPS, quota price, consensus nodes, emergency brake \
+             and block time, refreshed every second until interrupted",
+        )
+        .arg(
+            Arg::with_name("alert-tps-below")
+                .long("alert-tps-below")
+                .takes_value(true)
+                .validator(|tps| parse_u64(tps.as_ref()).map(|_| ()))
+                .help("Warn in red when the TPS of the latest 10 blocks drops below the value"),
+        )
+}
+
+/// Watch processor
+pub fn watch_processor(
+    sub_matches: &ArgMatches,
+    config: &GlobalConfig,
+    client: Client,
+) -> Result<(), String> {
+    let debug = sub_matches.is_present("debug") || config.debug();
+    let is_color = !sub_matches.is_present("no-color") && config.color();
+    let mut client = client
+        .set_debug(debug)
+        .set_uri(get_url(sub_matches, config));
+    let alert_tps_below = match sub_matches.value_of("alert-tps-below") {
+        Some(tps) => Some(parse_u64(tps)?),
+        None => None,
+    };
+
+    let mut stdout = io::stdout();
+    let mut throughput = None;
+    loop {
+        let lines = match refresh(&mut client, &mut throughput) {
+            Ok(status) => status_lines(&status, throughput.and_then(|(_, t)| t)),
+            Err(err) => vec![format!("Failed to refresh: {}", err)],
+        };
+        let alert = match (alert_tps_below, throughput.and_then(|(_, t)| t)) {
+            (Some(threshold), Some(throughput)) if throughput.tps < threshold as f64 => Some(
+                format!("WARNING: TPS {:.2} is below {}", throughput.tps, threshold),
+            ),
+            _ => None,
+        };
+        draw(&mut stdout, &lines, alert, is_color).map_err(|err| format!("{}", err))?;
+        thread::sleep(REFRESH_INTERVAL);
+    }
+}
+
+/// Throughput of the latest blocks
+#[derive(Clone, Copy)]
+struct Throughput {
+    /// Transactions per second
+    tps: f64,
+    /// Average milliseconds between two blocks
+    block_time: u64,
+}
+
+/// Query the chain status, the throughput is measured again only when the height changes,
+/// it's kept with the height it is measured at
+fn refresh(
+    client: &mut Client,
+    throughput: &mut Option<(u64, Option<Throughput>)>,
+) -> Result<ChainStatus, ToolError> {
+    let status = client.chain_status()?;
+    let height = status.block_number;
+    if throughput.map_or(true, |(measured_at, _)| measured_at != height) {
+        *throughput = Some((height, measure_throughput(client, height)?));
+    }
+    Ok(status)
+}
+
+/// Throughput of the blocks up to the height, `None` before the first block
+fn measure_throughput(client: &Client, height: u64) -> Result<Option<Throughput>, ToolError> {
+    let blocks = height.min(THROUGHPUT_BLOCKS);
+    if blocks == 0 {
+        return Ok(None);
+    }
+    let stats = client.block_stats(height - blocks + 1, height, blocks)?;
+    // The time of the first block starts from the one before it
+    let start = client
+        .get_decoded_block_header(&format!("{:#x}", height - blocks))?
+        .timestamp;
+    let end = client
+        .get_decoded_block_header(&format!("{:#x}", height))?
+        .timestamp;
+    let elapsed = end.saturating_sub(start);
+    if elapsed == 0 {
+        return Ok(None);
+    }
+    Ok(Some(Throughput {
+        tps: stats.total_transactions as f64 * 1000.0 / elapsed as f64,
+        block_time: elapsed / blocks,
+    }))
+}
+
+fn status_lines(status: &ChainStatus, throughput: Option<Throughput>) -> Vec<String> {
+    let card = [
+        ("Block number", status.block_number.to_string()),
+        (
+            "TPS",
+            throughput.map_or_else(|| "-".to_string(), |t| format!("{:.2}", t.tps)),
+        ),
+        (
+            "Block time",
+            throughput.map_or_else(|| "-".to_string(), |t| format!("{}ms", t.block_time)),
+        ),
+        ("Quota price", status.quota_price.to_string()),
+        ("Consensus nodes", status.node_count.to_string()),
+        (
+            "Emergency brake",
+            if status.emergency_brake_active {
+                "on".to_string()
+            } else {
+                "off".to_string()
+            },
+        ),
+    ];
+    let width = card.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    card.iter()
+        .map(|(name, value)| format!("{:<width$}  {}", name, value, width = width))
+        .collect()
+}
+
+/// Redraw the display in place
+fn draw<W: Write>(
+    out: &mut W,
+    lines: &[String],
+    alert: Option<String>,
+    is_color: bool,
+) -> crossterm::Result<()> {
+    queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+    for line in lines {
+        queue!(out, Print(line), Print("\n"))?;
+    }
+    if let Some(alert) = alert {
+        if is_color {
+            queue!(
+                out,
+                SetForegroundColor(Color::Red),
+                Print(alert),
+                ResetColor,
+                Print("\n")
+            )?;
+        } else {
+            queue!(out, Print(alert), Print("\n"))?;
+        }
+    }
+    queue!(out, Print("\nPress Ctrl-C to exit\n"))?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Path of the config file, overridden by `--config`
+pub const ENV_CONFIG: &str = "CITA_CLI_CONFIG";
+/// Default of `--private-key`
+pub const ENV_PRIVATE_KEY: &str = "CITA_CLI_PRIVATE_KEY";
+/// Default of `--chain-id`
+pub const ENV_CHAIN_ID: &str = "CITA_CLI_CHAIN_ID";
+/// Default of `--quota`
+pub const ENV_QUOTA: &str = "CITA_CLI_QUOTA";
+
+const DEFAULT_CONFIG_FILE: &str = ".cita-cli.toml";
+
+/// Defaults of the flags, from the TOML config file, such as:
+///
+/// ```toml
+/// url = "http://127.0.0.1:1337"
+/// private-key = "0x..."
+/// chain-id = 1
+/// quota = 10000000
+/// ```
+#[derive(Debug, Default)]
+pub struct FileConfig {
+    pub url: Option<String>,
+    pub private_key: Option<String>,
+    pub chain_id: Option<String>,
+    pub quota: Option<String>,
+}
+
+impl FileConfig {
+    /// Load `--config <path>`, or `$CITA_CLI_CONFIG`, or `~/.cita-cli.toml` if it exists
+    pub fn load(args: &[String]) -> Result<Self, String> {
+        let path = match config_path(config_arg(args).as_ref().map(String::as_str)) {
+            Some(path) => path,
+            None => return Ok(FileConfig::default()),
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|err| format!("Read config file {} failed: {}", path.display(), err))?;
+        let value = content
+            .parse::<toml::Value>()
+            .map_err(|err| format!("Parse config file {} failed: {}", path.display(), err))?;
+        let get = |key: &str| {
+            value.get(key).map(|value| match value {
+                toml::Value::String(value) => value.clone(),
+                other => other.to_string(),
+            })
+        };
+
+        let mut private_key = get("private-key");
+        if private_key.is_some() && !is_owner_only(&path) {
+            eprintln!(
+                "Warning: private-key in {} is ignored, the permissions of the file must be 0600",
+                path.display()
+            );
+            private_key = None;
+        }
+        Ok(FileConfig {
+            url: get("url"),
+            private_key,
+            chain_id: get("chain-id"),
+            quota: get("quota"),
+        })
+    }
+
+    /// Export the defaults to the environment variables read by the flags,
+    /// the variables already set take precedence
+    ///
+    /// The private key is not exported, the child processes would inherit it,
+    /// it's the default of `--private-key` through `GlobalConfig`.
+    pub fn export(&self) {
+        for (name, value) in &[(ENV_CHAIN_ID, &self.chain_id), (ENV_QUOTA, &self.quota)] {
+            if let (Some(value), None) = (value, env::var_os(name)) {
+                env::set_var(name, value);
+            }
+        }
+    }
+}
+
+/// Path of the config file, the `--config` value, or `$CITA_CLI_CONFIG`,
+/// or `~/.cita-cli.toml` if it exists
+pub fn config_path(config: Option<&str>) -> Option<PathBuf> {
+    match config
+        .map(ToString::to_string)
+        .or_else(|| env::var(ENV_CONFIG).ok())
+    {
+        Some(path) => Some(PathBuf::from(path)),
+        None => dirs::home_dir()
+            .map(|home| home.join(DEFAULT_CONFIG_FILE))
+            .filter(|path| path.exists()),
+    }
+}
+
+/// The value of `--config <path>` or `--config=<path>`, before the arguments are parsed
+fn config_arg(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(index, arg)| {
+        if arg == "--config" {
+            args.get(index + 1).cloned()
+        } else if arg.starts_with("--config=") {
+            Some(arg["--config=".len()..].to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(unix)]
+fn is_owner_only(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o077 == 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_owner_only(_path: &Path) -> bool {
+    true
+}
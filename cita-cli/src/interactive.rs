@@ -26,9 +26,10 @@ use serde_json::{self, json};
 use shell_words;
 
 use crate::cli::{
-    abi_processor, amend_processor, benchmark_processor, build_interactive, contract_processor,
-    encryption, key_processor, key_validator, rpc_processor, search_processor, store_processor,
-    string_include, transfer_processor, tx_processor,
+    abi_processor, account_processor, amend_processor, audit_processor, benchmark_processor,
+    build_interactive, chain_processor, contract_processor, encryption, key_processor,
+    key_validator, net_processor, node_processor, rpc_processor, search_processor, store_processor,
+    string_include, transfer_processor, tx_processor, user_contract_processor,
 };
 use crate::printer::{OutputFormat, Printable, Printer};
 use cita_tool::client::basic::Client;
@@ -52,8 +53,9 @@ static DEFAULT_BREAK_CHARS: [u8; 17] = [
 static ESCAPE_CHAR: Option<char> = None;
 
 /// Interactive command line
-pub fn start(url: &str, client: &Client) -> io::Result<()> {
+pub fn start(url: &str, private_key: Option<String>, client: &Client) -> io::Result<()> {
     let mut config = GlobalConfig::new(url.to_string());
+    config.set_private_key(private_key);
 
     let mut cita_cli_dir = dirs::home_dir().unwrap();
     cita_cli_dir.push(".cita-cli");
@@ -113,6 +115,24 @@ pub fn start(url: &str, client: &Client) -> io::Result<()> {
     )
 }
 
+/// Prompt with the url and its block height, `?` if the url doesn't answer
+fn prompt(config: &GlobalConfig, height: Option<u64>) -> String {
+    let height = height
+        .map(|height| height.to_string())
+        .unwrap_or_else(|| "?".to_string());
+    format!("cita [{} #{}]> ", config.get_url(), height)
+}
+
+/// Block height of the url of the prompt, queried once per command instead of on every redraw,
+/// so the query and its logs come right after the output of the command
+fn prompt_height(config: &GlobalConfig, client: &Client) -> Option<u64> {
+    client
+        .clone()
+        .set_uri(config.get_url())
+        .get_current_height()
+        .ok()
+}
+
 fn start_rustyline(
     config: &mut GlobalConfig,
     printer: &mut Printer,
@@ -122,7 +142,6 @@ fn start_rustyline(
 ) -> io::Result<()> {
     let env_regex = Regex::new(ENV_PATTERN).unwrap();
     let parser = build_interactive();
-    let colored_prompt = Red.bold().paint("cita> ").to_string();
 
     let rl_mode = |rl: &mut Editor<CitaCompleter>, config: &GlobalConfig| {
         if config.completion_style() {
@@ -152,8 +171,10 @@ fn start_rustyline(
         eprintln!("No previous history.");
     }
 
+    let mut height = prompt_height(config, client);
     loop {
         rl_mode(&mut rl, &config);
+        let colored_prompt = Red.bold().paint(prompt(config, height)).to_string();
         match rl.readline(&colored_prompt) {
             Ok(line) => {
                 match handle_commands(
@@ -173,6 +194,7 @@ fn start_rustyline(
                         printer.eprintln(&err.to_string(), true);
                     }
                 }
+                height = prompt_height(config, client);
                 if config.save_private() {
                     rl.add_history_entry(&line);
                 } else {
@@ -187,7 +209,7 @@ fn start_rustyline(
                 break;
             }
             Err(err) => {
-                eprintln!("Error: {:?}", err);
+                eprintln!("Error: {}", err);
                 break;
             }
         }
@@ -249,7 +271,7 @@ fn handle_commands(
 
                 config.print();
                 let mut file = fs::File::create(config_file.as_path())
-                    .map_err(|err| format!("open config error: {:?}", err))?;
+                    .map_err(|err| format!("open config error: {}", err))?;
                 let content = serde_json::to_string_pretty(&json!({
                     "url": config.get_url().clone(),
                     "encryption": config.encryption().to_string(),
@@ -262,7 +284,7 @@ fn handle_commands(
                 }))
                 .unwrap();
                 file.write_all(content.as_bytes())
-                    .map_err(|err| format!("save config error: {:?}", err))?;
+                    .map_err(|err| format!("save config error: {}", err))?;
                 Ok(())
             }
             ("set", Some(m)) => {
@@ -279,7 +301,9 @@ fn handle_commands(
             ("rpc", Some(m)) => rpc_processor(m, &printer, config, client.clone()),
             ("ethabi", Some(m)) => abi_processor(m, &printer, &config),
             ("key", Some(m)) => key_processor(m, &printer, &config),
+            ("account", Some(m)) => account_processor(m, &printer, &config),
             ("scm", Some(m)) => contract_processor(m, &printer, config, client.clone()),
+            ("contract", Some(m)) => user_contract_processor(m, &printer, config, client.clone()),
             ("transfer", Some(m)) => transfer_processor(m, &printer, config, client.clone()),
             ("store", Some(m)) => store_processor(m, &printer, config, client.clone()),
             ("amend", Some(m)) => amend_processor(m, &printer, config, client.clone()),
@@ -293,6 +317,10 @@ fn handle_commands(
             }
             ("tx", Some(m)) => tx_processor(m, &printer, config, client.clone()),
             ("benchmark", Some(m)) => benchmark_processor(m, &printer, &config, client.clone()),
+            ("net", Some(m)) => net_processor(m, &printer, config, client.clone()),
+            ("node", Some(m)) => node_processor(m, &printer, &config, client.clone()),
+            ("chain", Some(m)) => chain_processor(m, &printer, &config, client.clone()),
+            ("audit", Some(m)) => audit_processor(m, &printer, &config, client.clone()),
             ("exit", _) => {
                 return Ok(true);
             }
@@ -546,6 +574,7 @@ pub struct GlobalConfig {
     completion_style: bool,
     edit_style: bool,
     env_variable: HashMap<String, serde_json::Value>,
+    private_key: Option<String>,
 }
 
 impl GlobalConfig {
@@ -561,6 +590,7 @@ impl GlobalConfig {
             completion_style: true,
             edit_style: true,
             env_variable: HashMap::new(),
+            private_key: None,
         }
     }
 
@@ -614,6 +644,15 @@ impl GlobalConfig {
         &self.url
     }
 
+    /// The default of `--private-key`, kept out of the environment
+    pub fn set_private_key(&mut self, value: Option<String>) {
+        self.private_key = value;
+    }
+
+    pub fn private_key(&self) -> Option<&str> {
+        self.private_key.as_ref().map(String::as_str)
+    }
+
     fn switch_color(&mut self) {
         self.color = !self.color;
     }
@@ -1,8 +1,10 @@
 #![deny(warnings)]
 
 mod cli;
+mod config_file;
 mod interactive;
 mod json_color;
+mod output;
 mod printer;
 
 use std::collections::HashMap;
@@ -14,14 +16,17 @@ use std::rc::Rc;
 use cita_tool::client::basic::Client;
 use clap::crate_version;
 use dotenv::dotenv;
+use futures::Future;
 
 include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
 
 use crate::cli::{
-    abi_processor, amend_processor, benchmark_processor, build_cli, completion_processor,
-    contract_processor, key_processor, rpc_processor, search_processor, store_processor,
-    transfer_processor, tx_processor,
+    abi_processor, account_processor, amend_processor, audit_processor, benchmark_processor,
+    build_cli, chain_processor, completion_processor, contract_processor, key_processor,
+    net_processor, node_processor, rpc_processor, search_processor, store_processor,
+    transfer_processor, tx_processor, user_contract_processor, watch_processor,
 };
+use crate::config_file::FileConfig;
 use crate::interactive::GlobalConfig;
 use crate::printer::Printer;
 
@@ -37,22 +42,55 @@ fn main() {
         feature_version()
     );
 
+    let file_config = match FileConfig::load(&env::args().collect::<Vec<String>>()) {
+        Ok(file_config) => file_config,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    file_config.export();
+
     let mut env_map: HashMap<String, String> = HashMap::from_iter(env::vars());
     let default_jsonrpc_url = env_map
         .remove(ENV_JSONRPC_URL)
+        .or(file_config.url)
         .unwrap_or_else(|| DEFAULT_JSONRPC_URL.to_owned());
 
-    let printer = Printer::default();
+    let mut printer = Printer::default();
     let mut config = GlobalConfig::new(default_jsonrpc_url.to_string());
+    config.set_private_key(file_config.private_key.clone());
     let mut parser = build_cli(version.as_str());
     let matches = parser.clone().get_matches();
-    let client = Client::new();
+
+    let mut logger = env_logger::Builder::from_default_env();
+    if matches.is_present("verbose") {
+        logger.parse_filters("cita_tool=debug");
+    }
+    logger.init();
+
+    if let Some(format) = matches.value_of("output-format") {
+        printer.set_format(format.parse().unwrap());
+    }
+
+    // Validated by the possible values
+    let json_rpc_version = matches
+        .value_of("json-rpc-version")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let client = Client::new()
+        .set_dry_run(matches.is_present("dry-run"))
+        .set_json_rpc_version(json_rpc_version);
+    handle_interrupt(&client);
 
     if let Err(err) = match matches.subcommand() {
         ("rpc", Some(m)) => rpc_processor(m, &printer, &mut config, client),
         ("ethabi", Some(m)) => abi_processor(m, &printer, &config),
         ("key", Some(m)) => key_processor(m, &printer, &config),
+        ("account", Some(m)) => account_processor(m, &printer, &config),
         ("scm", Some(m)) => contract_processor(m, &printer, &mut config, client),
+        ("contract", Some(m)) => user_contract_processor(m, &printer, &mut config, client),
         ("transfer", Some(m)) => transfer_processor(m, &printer, &mut config, client),
         ("store", Some(m)) => store_processor(m, &printer, &mut config, client),
         ("amend", Some(m)) => amend_processor(m, &printer, &mut config, client),
@@ -62,12 +100,24 @@ fn main() {
         }
         ("tx", Some(m)) => tx_processor(m, &printer, &mut config, client),
         ("benchmark", Some(m)) => benchmark_processor(m, &printer, &config, client),
+        ("net", Some(m)) => net_processor(m, &printer, &mut config, client),
+        ("node", Some(m)) => node_processor(m, &printer, &config, client),
+        ("chain", Some(m)) => chain_processor(m, &printer, &config, client),
+        ("audit", Some(m)) => audit_processor(m, &printer, &config, client),
+        ("watch", Some(m)) => watch_processor(m, &config, client),
+        #[cfg(feature = "server")]
+        ("server", Some(m)) => cli::server_processor(m, &config, client),
         ("completions", Some(m)) => {
             completion_processor(&mut parser, m);
             Ok(())
         }
+        // `repl` or no subcommand
         _ => {
-            if let Err(err) = interactive::start(&default_jsonrpc_url, &client) {
+            if let Err(err) = interactive::start(
+                &default_jsonrpc_url,
+                config.private_key().map(ToString::to_string),
+                &client,
+            ) {
                 eprintln!("Something error: kind {:?}, message {}", err.kind(), err)
             }
             Ok(())
@@ -78,6 +128,21 @@ fn main() {
     }
 }
 
+/// On Ctrl-C, stop sending new requests and wait for the ones in flight before exiting,
+/// so a transaction is either sent or not
+fn handle_interrupt(client: &Client) {
+    let client = client.clone();
+    let result = ctrlc::set_handler(move || {
+        client.initiate_shutdown();
+        eprintln!("Interrupted, waiting for the requests in flight");
+        let _ = client.drain_pending().wait();
+        process::exit(130);
+    });
+    if let Err(err) = result {
+        eprintln!("Failed to handle Ctrl-C: {}", err);
+    }
+}
+
 fn feature_version() -> String {
     if cfg!(feature = "openssl") {
         "use openssl".to_owned()
@@ -0,0 +1,121 @@
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::printer::OutputFormat;
+
+/// Render the structured content in the format, `Raw` is the same as `Json`
+pub fn render<T: Serialize>(content: &T, format: OutputFormat) -> String {
+    let value = serde_json::to_value(content).unwrap_or(Value::Null);
+    match format {
+        OutputFormat::Raw | OutputFormat::Json => serde_json::to_string_pretty(&value).unwrap(),
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            let mut rows = rows(&value).into_iter();
+            if let Some(header) = rows.next() {
+                table.set_titles(Row::new(
+                    header.iter().map(|cell| Cell::new(cell)).collect(),
+                ));
+            }
+            for row in rows {
+                table.add_row(Row::new(row.iter().map(|cell| Cell::new(cell)).collect()));
+            }
+            table.to_string().trim_end().to_string()
+        }
+        OutputFormat::Csv => rows(&value)
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| csv_escape(cell))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
+
+/// Header row then data rows:
+///   * list of objects: a column per key
+///   * object: key/value rows
+///   * list of others: a value column
+///   * others: single value
+fn rows(value: &Value) -> Vec<Vec<String>> {
+    match value {
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            let mut keys: Vec<String> = Vec::new();
+            for item in items.iter().filter_map(Value::as_object) {
+                for key in item.keys() {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+            }
+            let mut rows = vec![keys.clone()];
+            rows.extend(items.iter().filter_map(Value::as_object).map(|item| {
+                keys.iter()
+                    .map(|key| item.get(key).map(cell).unwrap_or_default())
+                    .collect()
+            }));
+            rows
+        }
+        Value::Array(items) => {
+            let mut rows = vec![vec!["value".to_string()]];
+            rows.extend(items.iter().map(|item| vec![cell(item)]));
+            rows
+        }
+        Value::Object(object) => object_rows(object),
+        other => vec![vec!["value".to_string()], vec![cell(other)]],
+    }
+}
+
+fn object_rows(object: &Map<String, Value>) -> Vec<Vec<String>> {
+    let mut rows = vec![vec!["key".to_string(), "value".to_string()]];
+    rows.extend(
+        object
+            .iter()
+            .map(|(key, value)| vec![key.clone(), cell(value)]),
+    );
+    rows
+}
+
+/// Strings without quotes, nested values as compact JSON
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(content) => content.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::render;
+    use crate::printer::OutputFormat;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_csv() {
+        let peers = json!([
+            {"node": "0x01", "address": "127.0.0.1:4000"},
+            {"node": "0x02", "address": "a,b"}
+        ]);
+        assert_eq!(
+            render(&peers, OutputFormat::Csv),
+            "address,node\n127.0.0.1:4000,0x01\n\"a,b\",0x02"
+        );
+        assert_eq!(render(&json!("0x10"), OutputFormat::Csv), "value\n0x10");
+        assert_eq!(
+            render(&json!({"height": 1}), OutputFormat::Csv),
+            "key,value\nheight,1"
+        );
+    }
+}
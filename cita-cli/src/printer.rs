@@ -2,13 +2,16 @@ use std::default;
 use std::env;
 use std::io;
 use std::rc::Rc;
+use std::str::FromStr;
 
-use ansi_term::Colour::{Red, Yellow};
+use ansi_term::Colour::{Green, Red, Yellow};
 use atty;
+use indicatif::{ProgressBar, ProgressStyle};
 use serde_json::{self, json};
 
 use crate::json_color::Colorizer;
-use cita_tool::{JsonRpcResponse, KeyPair};
+use crate::output::render;
+use cita_tool::{encode, JsonRpcResponse, KeyPair};
 
 pub fn is_a_tty(stderr: bool) -> bool {
     let stream = if stderr {
@@ -28,6 +31,22 @@ pub enum OutputFormat {
     #[allow(dead_code)]
     Raw,
     Json,
+    Table,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "raw" => Ok(OutputFormat::Raw),
+            "json" => Ok(OutputFormat::Json),
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("Unknown output format: {}", format)),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -74,12 +93,17 @@ impl Printer {
             OutputFormat::Raw => {
                 self.format = OutputFormat::Json;
             }
-            OutputFormat::Json => {
+            _ => {
                 self.format = OutputFormat::Raw;
             }
         }
     }
 
+    pub fn set_format(&mut self, format: OutputFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn set_color(&mut self, color: ColorWhen) -> &mut Self {
         self.color = color;
@@ -124,6 +148,51 @@ impl Printer {
     }
 }
 
+/// Spinner with the elapsed time of a polling operation, on stderr,
+/// nothing is displayed if disabled or stdout is not a tty
+pub struct Progress {
+    bar: Option<ProgressBar>,
+    message: String,
+}
+
+impl Progress {
+    pub fn new(enabled: bool, message: &str) -> Self {
+        let message = message.to_string();
+        if !enabled || !is_a_tty(false) || is_term_dumb() {
+            return Progress { bar: None, message };
+        }
+        let bar = ProgressBar::new_spinner();
+        bar.set_style(ProgressStyle::default_spinner().template("{spinner} [{elapsed}] {msg}"));
+        bar.set_message(&message);
+        bar.enable_steady_tick(100);
+        Progress {
+            bar: Some(bar),
+            message,
+        }
+    }
+
+    pub fn set_block_number(&self, block_number: u64) {
+        if let Some(bar) = self.bar.as_ref() {
+            bar.set_message(&format!("{}, block {}", self.message, block_number));
+        }
+    }
+
+    /// Replace the spinner with a checkmark and the result
+    pub fn finish(&self, result: &str) {
+        if let Some(bar) = self.bar.as_ref() {
+            bar.set_style(ProgressStyle::default_spinner().template("{msg}"));
+            bar.finish_with_message(&format!("{} {}", Green.paint("✔"), result));
+        }
+    }
+
+    /// Remove the spinner, such as on error
+    pub fn clear(&self) {
+        if let Some(bar) = self.bar.as_ref() {
+            bar.finish_and_clear();
+        }
+    }
+}
+
 pub trait Printable {
     fn rc_string(&self, format: OutputFormat, color: bool) -> Rc<String>;
 }
@@ -141,7 +210,14 @@ impl Printable for Rc<String> {
 }
 
 impl Printable for JsonRpcResponse {
-    fn rc_string(&self, _format: OutputFormat, color: bool) -> Rc<String> {
+    fn rc_string(&self, format: OutputFormat, color: bool) -> Rc<String> {
+        if let OutputFormat::Table | OutputFormat::Csv = format {
+            return match (self.result(), self.error()) {
+                (Some(result), _) => Rc::new(render(&result, format)),
+                (None, Some(error)) => Rc::new(render(&error, format)),
+                (None, None) => Rc::new(render(self, format)),
+            };
+        }
         let content = format!("{:?}", self);
         let content = if color {
             Colorizer::arbitrary()
@@ -159,6 +235,9 @@ impl Printable for serde_json::Value {
         if let (OutputFormat::Raw, serde_json::Value::String(content)) = (format, self) {
             return Rc::new(content.clone());
         }
+        if let OutputFormat::Table | OutputFormat::Csv = format {
+            return Rc::new(render(self, format));
+        }
         let content = if color {
             Colorizer::arbitrary().colorize_json_value(self).unwrap()
         } else {
@@ -171,8 +250,8 @@ impl Printable for serde_json::Value {
 impl Printable for KeyPair {
     fn rc_string(&self, format: OutputFormat, color: bool) -> Rc<String> {
         match format {
-            OutputFormat::Json => json!({
-                "private": format!("0x{}", self.privkey()),
+            OutputFormat::Json | OutputFormat::Table | OutputFormat::Csv => json!({
+                "private": format!("0x{}", encode(self.privkey().expose_secret())),
                 "public": format!("0x{}", self.pubkey()),
                 "address": format!("0x{:x}", self.address())
             })
@@ -182,7 +261,7 @@ impl Printable for KeyPair {
                     format!(
                         concat!("{} 0x{}\n", "{} 0x{}\n", "{} 0x{:x}"),
                         Yellow.paint("[ private ]:"),
-                        self.privkey(),
+                        encode(self.privkey().expose_secret()),
                         Yellow.paint("[ public  ]:"),
                         self.pubkey(),
                         Yellow.paint("[ address ]:"),
@@ -192,7 +271,7 @@ impl Printable for KeyPair {
                     format!(
                         concat!("{} 0x{}\n", "{} 0x{}\n", "{} 0x{:x}"),
                         "[ private ]:",
-                        self.privkey(),
+                        encode(self.privkey().expose_secret()),
                         "[ public  ]:",
                         self.pubkey(),
                         "[ address ]:",
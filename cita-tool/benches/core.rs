@@ -0,0 +1,106 @@
+//! Benchmarks of the hot paths, run with `cargo bench -p cita-tool`
+//!
+//! The expected order of magnitude on a CI machine is noted on every benchmark,
+//! ten times slower is worth a look.
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::pack_transactions;
+use cita_tool::contract_abi::{BATCH_TX_CONTRACT, PERMISSION_MANAGEMENT_CONTRACT};
+use cita_tool::{
+    contract_encode_input, privkey_from_str, JsonRpcResponse, TransactionOptions, U256,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ethabi::Contract;
+
+const PRIVATE_KEY: &str = "0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9";
+
+/// A function with 4 args of the usual kinds
+const FOUR_ARGS_ABI: &str = r#"[{"type":"function","name":"transferWithMemo","inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"},{"name":"memo","type":"string"},{"name":"tags","type":"bytes32[]"}],"outputs":[],"constant":false,"payable":false,"stateMutability":"nonpayable"}]"#;
+
+/// ~10 µs: tokenize and encode 4 args, one of them an array
+fn encode_input(c: &mut Criterion) {
+    let contract = Contract::load(FOUR_ARGS_ABI.as_bytes()).unwrap();
+    let values = [
+        "ffffffffffffffffffffffffffffffffff020004".to_string(),
+        "1000000000000000000".to_string(),
+        "payment of invoice 42".to_string(),
+        format!("[{},{}]", "11".repeat(32), "22".repeat(32)),
+    ];
+    c.bench_function("contract_encode_input 4 args", |b| {
+        b.iter(|| {
+            contract_encode_input(&contract, "transferWithMemo", black_box(&values), true).unwrap()
+        })
+    });
+}
+
+/// ~100 µs: protobuf serialization, hashing and a secp256k1 signature
+fn generate_transaction(c: &mut Criterion) {
+    let private_key = privkey_from_str(PRIVATE_KEY).unwrap();
+    let mut client = Client::new();
+    client.set_private_key(&private_key);
+    let code = format!("0x{}", "ab".repeat(256));
+    c.bench_function("generate_transaction and sign", |b| {
+        b.iter(|| {
+            let options = TransactionOptions::new()
+                .set_code(&code)
+                .set_address("0xffffffffffffffffffffffffffffffffff020004")
+                .set_quota(Some(10_000_000))
+                .set_version(Some(2))
+                .set_valid_until_block(Some(100))
+                .set_chain_id_v1(Some(U256::from(1)));
+            client
+                .generate_transaction(black_box(options))
+                .unwrap()
+                .sign(private_key.clone())
+        })
+    });
+}
+
+/// ~50 µs: parse a 10 KB `getBlockByNumber` response
+fn deserialize_response(c: &mut Criterion) {
+    let transactions = (0..150)
+        .map(|index| format!("\"0x{:064x}\"", index))
+        .collect::<Vec<String>>()
+        .join(",");
+    let body = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"version":2,"hash":"0x{:064x}","header":{{"timestamp":1577836800000,"prevHash":"0x{:064x}","number":"0x64","proof":null,"proposer":"0xffffffffffffffffffffffffffffffffff020004"}},"body":{{"transactions":[{}]}}}}}}"#,
+        1, 2, transactions
+    );
+    assert!(body.len() >= 10 * 1024);
+    c.bench_function("JsonRpcResponse from 10 KB", |b| {
+        b.iter(|| serde_json::from_str::<JsonRpcResponse>(black_box(&body)).unwrap())
+    });
+}
+
+/// ~500 µs: pack 50 calls and encode them as the `multiTxs` argument
+fn multi_transactions(c: &mut Criterion) {
+    let data = contract_encode_input(
+        &PERMISSION_MANAGEMENT_CONTRACT,
+        "updatePermissionName",
+        &[
+            "ffffffffffffffffffffffffffffffffff020004".to_string(),
+            "name".to_string(),
+        ],
+        false,
+    )
+    .unwrap();
+    let txs = (0..50)
+        .map(|_| format!("ffffffffffffffffffffffffffffffffff020004{}", data))
+        .collect::<Vec<String>>();
+    c.bench_function("multi_transactions 50 calls", |b| {
+        b.iter(|| {
+            let packed =
+                pack_transactions(black_box(txs.iter().map(String::as_str).collect())).unwrap();
+            contract_encode_input(&BATCH_TX_CONTRACT, "multiTxs", &[packed], false).unwrap()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    encode_input,
+    generate_transaction,
+    deserialize_response,
+    multi_transactions
+);
+criterion_main!(benches);
@@ -0,0 +1,164 @@
+//! Set the stakes of several nodes and the permissions of several accounts, each in
+//! one batch transaction taking effect atomically, run with
+//! `cargo run -p cita-tool --example batch_operations`
+//!
+//! The client answers with canned responses, no node is needed. To run against a
+//! node, replace `canned_client()` with `Client::new().set_uri("http://127.0.0.1:1337")`,
+//! use the key of an admin account, and remove the fixed version, chain id and valid
+//! until block of `options()`.
+
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::{
+    decode_multi_receipt, BatchTxClient, BatchTxExt, ContractCall, NodeManageClient,
+    NodeManagementExt, PermissionManageClient, PermissionManagementExt,
+};
+use cita_tool::{
+    privkey_from_str, remove_0x, JsonRpcResponse, ParamsValue, ToolError, TransactionOptions,
+    TransactionReceipt, U256,
+};
+
+const ADMIN_PRIVATE_KEY: &str =
+    "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+
+/// Consensus nodes with their new stakes
+const STAKES: [(&str, u64); 2] = [
+    ("0x6c8d9b7d6a6c6e36e0e5c7c8f3c7e4e8d0f1a2b3", 3),
+    ("0x9d8e4c5f0a1b2c3d4e5f60718293a4b5c6d7e8f9", 1),
+];
+/// Accounts allowed to send transactions, the `send_tx` built-in permission
+const ACCOUNTS: [&str; 2] = [
+    "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    "0x2a1b5ae4567ad5d9fb92bc9afd6a657e6fa13a25",
+];
+const SEND_TX_PERMISSION: &str = "0xffffffffffffffffffffffffffffffffff021000";
+
+fn response(result: &str) -> JsonRpcResponse {
+    serde_json::from_str(&format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+        result
+    ))
+    .unwrap()
+}
+
+/// Responses of `sendRawTransaction` and `getTransactionReceipt` of the transaction
+fn mined_responses(index: u64) -> Vec<JsonRpcResponse> {
+    let hash = format!("0x{:064x}", index);
+    vec![
+        response(&format!(r#"{{"hash":"{}","status":"OK"}}"#, hash)),
+        response(&format!(
+            r#"{{
+                "transactionHash": "{}",
+                "transactionIndex": "0x0",
+                "blockHash": "0x{:064x}",
+                "blockNumber": "{:#x}",
+                "cumulativeQuotaUsed": "0x20000",
+                "quotaUsed": "0x20000",
+                "contractAddress": null,
+                "logs": [],
+                "root": null,
+                "errorMessage": null
+            }}"#,
+            hash,
+            index + 100,
+            index + 30
+        )),
+    ]
+}
+
+/// The responses of every step, in order
+fn canned_client() -> Client {
+    // `listNode` returns the nodes of `STAKES`
+    let nodes = STAKES
+        .iter()
+        .map(|(node, _)| format!("{:0>64}", remove_0x(node)))
+        .collect::<String>();
+    let mut responses = vec![
+        response(&format!(r#""0x{:064x}{:064x}{}""#, 32, STAKES.len(), nodes)),
+        // `estimateQuota` of every `setStake`
+        response(r#""0x7530""#),
+        response(r#""0x7530""#),
+        // `getBQL`
+        response(&format!(r#""0x{:064x}""#, 1_073_741_824)),
+    ];
+    responses.extend(mined_responses(1));
+    responses.extend(mined_responses(2));
+    Client::with_responses(responses)
+}
+
+/// Options of the canned chain, a node is asked for the ones left unset
+fn options<'a>() -> TransactionOptions<'a> {
+    TransactionOptions::new()
+        .set_version(Some(2))
+        .set_chain_id_v1(Some(U256::from(1)))
+        .set_valid_until_block(Some(100))
+        .skip_simulation()
+}
+
+/// Wait for the receipt of the transaction of the response
+fn wait_receipt(
+    client: &Client,
+    response: JsonRpcResponse,
+) -> Result<TransactionReceipt, ToolError> {
+    let result = response.into_result()?;
+    let hash = match result
+        .as_map()
+        .and_then(|result| result.get("hash"))
+        .and_then(ParamsValue::as_str)
+    {
+        Some(hash) => hash.to_string(),
+        None => return Err(ToolError::Customize(format!("No hash: {}", result))),
+    };
+    loop {
+        match client.get_receipt(&hash)? {
+            Some(receipt) => return Ok(receipt),
+            None => thread::sleep(Duration::from_secs(1)),
+        }
+    }
+}
+
+fn main() -> Result<(), ToolError> {
+    let mut client = canned_client();
+    client.set_private_key(&privkey_from_str(ADMIN_PRIVATE_KEY)?);
+
+    // The stakes are checked against `listNode` and the block quota limit first
+    let response =
+        NodeManageClient::create(client.clone()).set_stake_batch(STAKES.to_vec(), options())?;
+    let receipt = wait_receipt(&client, response)?;
+    match receipt.error_message {
+        Some(message) => println!("Stakes not set, all reverted: {}", message),
+        None => println!("Stakes set in block {}", receipt.block_number),
+    }
+
+    // Any batch of system contract calls is packed the same way, the address of the
+    // contract followed by the call data
+    let permissions = PermissionManageClient::create(client.clone());
+    let mut txs = Vec::with_capacity(ACCOUNTS.len());
+    let mut calls = Vec::with_capacity(ACCOUNTS.len());
+    for account in ACCOUNTS.iter() {
+        let (code, to_address) = permissions.prepare_call_args(
+            "setAuthorization",
+            &[remove_0x(account), remove_0x(SEND_TX_PERMISSION)],
+            None,
+        )?;
+        let to = ethabi::Address::from_str(remove_0x(&to_address))
+            .map_err(|err| ToolError::Customize(err.to_string()))?;
+        txs.push(format!("{}{}", remove_0x(&to_address), remove_0x(&code)));
+        calls.push((to, "setAuthorization"));
+    }
+    let response = BatchTxClient::create(client.clone())
+        .multi_transactions(txs.iter().map(String::as_str).collect(), options())?;
+    let receipt = wait_receipt(&client, response)?;
+    for (account, result) in ACCOUNTS.iter().zip(decode_multi_receipt(&receipt, calls)?) {
+        println!(
+            "{} of {}: {}",
+            result.method,
+            account,
+            if result.success { "done" } else { "reverted" }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! Deploy a storage contract and read the stored value back, run with
+//! `cargo run -p cita-tool --example deploy_and_call`
+//!
+//! The client answers with canned responses, no node is needed. To run against a
+//! node, replace `canned_client()` with `Client::new().set_uri("http://127.0.0.1:1337")`
+//! and remove the fixed version, chain id and valid until block of `options()`.
+
+use std::thread;
+use std::time::Duration;
+
+use cita_tool::client::basic::{Client, ClientExt};
+use cita_tool::{
+    contract_decode_output_json, contract_encode_input, privkey_from_str, JsonRpcResponse,
+    ParamsValue, ResponseValue, ToolError, TransactionOptions, U256,
+};
+use ethabi::Contract;
+
+const PRIVATE_KEY: &str = "0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9";
+
+/// Stores 42 in the slot 0 on creation, returns the slot 0 on any call
+const STORAGE_CODE: &str = "0x602a600055600b6011600039600b6000f360005460005260206000f3";
+const STORAGE_ABI: &str = r#"[{"type":"function","name":"get","inputs":[],"outputs":[{"name":"","type":"uint256"}],"constant":true,"payable":false,"stateMutability":"view"}]"#;
+
+const TX_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+const CONTRACT_ADDRESS: &str = "0x27ec3678e4d61534ab8a87cf8feb8ac110ddeda5";
+
+fn response(result: &str) -> JsonRpcResponse {
+    serde_json::from_str(&format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+        result
+    ))
+    .unwrap()
+}
+
+/// The responses of `sendRawTransaction`, `getTransactionReceipt` and `call`, in order
+fn canned_client() -> Client {
+    Client::with_responses(vec![
+        response(&format!(r#"{{"hash":"{}","status":"OK"}}"#, TX_HASH)),
+        response(&format!(
+            r#"{{
+                "transactionHash": "{}",
+                "transactionIndex": "0x0",
+                "blockHash": "0x{:064x}",
+                "blockNumber": "0x1f",
+                "cumulativeQuotaUsed": "0x6f54",
+                "quotaUsed": "0x6f54",
+                "contractAddress": "{}",
+                "logs": [],
+                "root": null,
+                "errorMessage": null
+            }}"#,
+            TX_HASH, 1, CONTRACT_ADDRESS
+        )),
+        response(&format!(r#""0x{:064x}""#, 42)),
+    ])
+}
+
+/// Options of the canned chain, a node is asked for the ones left unset
+fn options<'a>() -> TransactionOptions<'a> {
+    TransactionOptions::new()
+        .set_version(Some(2))
+        .set_chain_id_v1(Some(U256::from(1)))
+        .set_valid_until_block(Some(100))
+}
+
+fn main() -> Result<(), ToolError> {
+    let mut client = canned_client();
+    client.set_private_key(&privkey_from_str(PRIVATE_KEY)?);
+
+    // An empty address creates a contract
+    let result = client
+        .send_raw_transaction(options().set_code(STORAGE_CODE).set_address(""))?
+        .into_result()?;
+    let hash = match result
+        .as_map()
+        .and_then(|result| result.get("hash"))
+        .and_then(ParamsValue::as_str)
+    {
+        Some(hash) => hash.to_string(),
+        None => return Err(ToolError::Customize(format!("No hash: {}", result))),
+    };
+    println!("Sent transaction {}", hash);
+
+    let receipt = loop {
+        match client.get_receipt(&hash)? {
+            Some(receipt) => break receipt,
+            None => thread::sleep(Duration::from_secs(1)),
+        }
+    };
+    let address = receipt
+        .contract_address
+        .ok_or_else(|| ToolError::Customize(format!("No contract created by {}", hash)))?;
+    let address = format!("{:?}", address);
+    println!(
+        "Contract created at {} in block {}",
+        address, receipt.block_number
+    );
+
+    let contract =
+        Contract::load(STORAGE_ABI.as_bytes()).map_err(|err| ToolError::Abi(err.to_string()))?;
+    let data = format!("0x{}", contract_encode_input(&contract, "get", &[], false)?);
+    let output = match client
+        .call(None, &address, Some(&data), "latest")?
+        .into_result()?
+    {
+        ResponseValue::Singe(ParamsValue::String(output)) => output,
+        result => {
+            return Err(ToolError::Customize(format!(
+                "Unexpected output: {}",
+                result
+            )))
+        }
+    };
+    println!(
+        "get() = {}",
+        contract_decode_output_json(&contract, "get", &output)?
+    );
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! Create a permission of a contract function, put it in a new role, set the role to
+//! an account and verify the account has the permission, run with
+//! `cargo run -p cita-tool --example manage_permissions`
+//!
+//! The client answers with canned responses, no node is needed. To run against a
+//! node, replace `canned_client()` with `Client::new().set_uri("http://127.0.0.1:1337")`,
+//! use the key of an account allowed to manage permissions and roles, and remove the
+//! fixed version, chain id and valid until block of `options()`.
+
+use std::thread;
+use std::time::Duration;
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::{
+    AuthorizationClient, AuthorizationExt, ContractCall, PermissionManageClient,
+    PermissionManagementExt, RoleManageClient, RoleManagementExt,
+};
+use cita_tool::{
+    encode, privkey_from_str, JsonRpcResponse, ParamsValue, Token, ToolError, TransactionOptions,
+    U256,
+};
+
+const ADMIN_PRIVATE_KEY: &str =
+    "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+const ACCOUNT: &str = "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+/// The storage contract of the deploy_and_call example
+const STORAGE_CONTRACT: &str = "27ec3678e4d61534ab8a87cf8feb8ac110ddeda5";
+/// Function hash of `get()`
+const GET_HASH: &str = "6d4ce63c";
+
+/// Addresses of the canned chain
+const PERMISSION: &str = "ca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001";
+const ROLE: &str = "e9e2593c7d1db5ee843c143e9cb52b8d996b2002";
+
+fn response(result: &str) -> JsonRpcResponse {
+    serde_json::from_str(&format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+        result
+    ))
+    .unwrap()
+}
+
+/// Response of a `call` returning one word
+fn word_response(word: &str) -> JsonRpcResponse {
+    response(&format!(r#""0x{:0>64}""#, word))
+}
+
+/// Responses of `sendRawTransaction` and `getTransactionReceipt` of the transaction
+fn mined_responses(index: u64) -> Vec<JsonRpcResponse> {
+    let hash = format!("0x{:064x}", index);
+    vec![
+        response(&format!(r#"{{"hash":"{}","status":"OK"}}"#, hash)),
+        response(&format!(
+            r#"{{
+                "transactionHash": "{}",
+                "transactionIndex": "0x0",
+                "blockHash": "0x{:064x}",
+                "blockNumber": "{:#x}",
+                "cumulativeQuotaUsed": "0x10000",
+                "quotaUsed": "0x10000",
+                "contractAddress": null,
+                "logs": [],
+                "root": null,
+                "errorMessage": null
+            }}"#,
+            hash,
+            index + 100,
+            index + 30
+        )),
+    ]
+}
+
+/// The responses of every step, in order
+fn canned_client() -> Client {
+    let mut responses = vec![word_response(PERMISSION)];
+    responses.extend(mined_responses(1));
+    responses.push(word_response(ROLE));
+    responses.extend(mined_responses(2));
+    responses.extend(mined_responses(3));
+    responses.push(word_response("1"));
+    Client::with_responses(responses)
+}
+
+/// Options of the canned chain, a node is asked for the ones left unset
+///
+/// The transactions are already simulated by the calls returning the new addresses.
+fn options<'a>() -> TransactionOptions<'a> {
+    TransactionOptions::new()
+        .set_version(Some(2))
+        .set_chain_id_v1(Some(U256::from(1)))
+        .set_valid_until_block(Some(100))
+        .skip_simulation()
+}
+
+/// Wait until the transaction of the response is mined without error
+fn wait_mined(client: &Client, response: JsonRpcResponse) -> Result<(), ToolError> {
+    let result = response.into_result()?;
+    let hash = match result
+        .as_map()
+        .and_then(|result| result.get("hash"))
+        .and_then(ParamsValue::as_str)
+    {
+        Some(hash) => hash.to_string(),
+        None => return Err(ToolError::Customize(format!("No hash: {}", result))),
+    };
+    loop {
+        match client.get_receipt(&hash)? {
+            Some(receipt) => {
+                return match receipt.error_message {
+                    Some(message) => Err(ToolError::Customize(message)),
+                    None => Ok(()),
+                };
+            }
+            None => thread::sleep(Duration::from_secs(1)),
+        }
+    }
+}
+
+/// The only address output
+fn decode_address(tokens: Vec<Token>) -> Result<String, ToolError> {
+    match tokens.as_slice() {
+        [Token::Address(address)] => Ok(format!("{:x}", address)),
+        _ => Err(ToolError::Abi(format!("Not an address: {:?}", tokens))),
+    }
+}
+
+fn main() -> Result<(), ToolError> {
+    let mut client = canned_client();
+    client.set_private_key(&privkey_from_str(ADMIN_PRIVATE_KEY)?);
+
+    // The address returned by a call at the latest height is the one the transaction
+    // creates, if no other permission is created in between
+    let name = format!("{:0<64}", encode("storage reader"));
+    let contracts = format!("[{}]", STORAGE_CONTRACT);
+    let functions = format!("[{}]", GET_HASH);
+    let mut permissions = PermissionManageClient::create(client.clone());
+    let permission = decode_address(permissions.contract_call_decoded(
+        "newPermission",
+        &[&name, &contracts, &functions],
+        None,
+        None,
+    )?)?;
+    let response = permissions.new_permission(&name, &contracts, &functions, options())?;
+    wait_mined(&client, response)?;
+    println!("Created permission 0x{}", permission);
+
+    let name = format!("{:0<64}", encode("reader"));
+    let role_permissions = format!("[{}]", permission);
+    let mut roles = RoleManageClient::create(client.clone());
+    let role = decode_address(roles.contract_call_decoded(
+        "newRole",
+        &[&name, &role_permissions],
+        None,
+        None,
+    )?)?;
+    let response = roles.new_role(&name, &role_permissions, options())?;
+    wait_mined(&client, response)?;
+    println!("Created role 0x{}", role);
+
+    let response = roles.set_role(ACCOUNT, &role, options())?;
+    wait_mined(&client, response)?;
+    println!("Set role 0x{} to {}", role, ACCOUNT);
+
+    let authorization = AuthorizationClient::create(client.clone());
+    let account = ACCOUNT.trim_start_matches("0x");
+    match authorization
+        .contract_call_decoded("checkPermission", &[account, &permission], None, None)?
+        .as_slice()
+    {
+        [Token::Bool(true)] => println!("{} has the permission", ACCOUNT),
+        [Token::Bool(false)] => println!("{} doesn't have the permission", ACCOUNT),
+        tokens => return Err(ToolError::Abi(format!("Not a bool: {:?}", tokens))),
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Poll the new blocks and print the statistics of each batch of them, run with
+//! `cargo run -p cita-tool --example watch_blocks`
+//!
+//! The client answers with canned responses, no node is needed. To run against a
+//! node, replace `canned_client()` with `Client::new().set_uri("http://127.0.0.1:1337")`
+//! and remove `stop_after` to watch until interrupted.
+
+use std::time::Duration;
+
+use cita_tool::client::basic::Client;
+use cita_tool::{JsonRpcResponse, ToolError};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// Blocks requested at the same time
+const BATCH_SIZE: u64 = 10;
+
+const PROPOSERS: [&str; 2] = [
+    "0x6c8d9b7d6a6c6e36e0e5c7c8f3c7e4e8d0f1a2b3",
+    "0x9d8e4c5f0a1b2c3d4e5f60718293a4b5c6d7e8f9",
+];
+
+fn response(result: &str) -> JsonRpcResponse {
+    serde_json::from_str(&format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+        result
+    ))
+    .unwrap()
+}
+
+fn block_number(height: u64) -> JsonRpcResponse {
+    response(&format!(r#""{:#x}""#, height))
+}
+
+/// Block of `getBlockByNumber`, a block every 3 seconds with one more transaction
+/// than the previous one
+fn block(height: u64) -> JsonRpcResponse {
+    let transactions = (0..height % 4)
+        .map(|index| format!(r#""0x{:064x}""#, height * 100 + index))
+        .collect::<Vec<String>>()
+        .join(",");
+    response(&format!(
+        r#"{{
+            "version": 2,
+            "hash": "0x{:064x}",
+            "header": {{
+                "timestamp": {},
+                "prevHash": "0x{:064x}",
+                "number": "{:#x}",
+                "stateRoot": "0x{:064x}",
+                "transactionsRoot": "0x{:064x}",
+                "receiptsRoot": "0x{:064x}",
+                "quotaUsed": "{:#x}",
+                "proof": null,
+                "proposer": "{}"
+            }},
+            "body": {{ "transactions": [{}] }}
+        }}"#,
+        height,
+        1_560_000_000_000 + height * 3000,
+        height - 1,
+        height,
+        1,
+        2,
+        3,
+        (height % 4) * 21000,
+        PROPOSERS[(height % 2) as usize],
+        transactions
+    ))
+}
+
+/// Three polls finding block 16, blocks 17 and 18, and block 19
+fn canned_client() -> Client {
+    Client::with_responses(vec![
+        block_number(16),
+        block(16),
+        block_number(18),
+        block(17),
+        block(18),
+        block_number(19),
+        block(19),
+    ])
+}
+
+fn main() -> Result<(), ToolError> {
+    let client = canned_client();
+
+    let mut last = None;
+    for height in client.poll_block_number(POLL_INTERVAL).stop_after(3) {
+        let height = height?;
+        // The first poll only shows the latest block
+        let start = last.map_or(height, |last: u64| last + 1);
+        last = Some(height);
+
+        let stats = client.block_stats(start, height, BATCH_SIZE)?;
+        println!(
+            "Blocks {} to {}: {} transactions, quota used {} on average and {} at most",
+            stats.from,
+            stats.to,
+            stats.total_transactions,
+            stats.average_quota_used,
+            stats.peak_quota_used
+        );
+        if let Some(block_time) = stats.block_time {
+            println!(
+                "  block time {}ms to {}ms, median {}ms",
+                block_time.min, block_time.max, block_time.median
+            );
+        }
+        for (proposer, count) in &stats.proposers {
+            println!("  {} proposed {}", proposer, count);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Fuzz `contract_encode_input` with arbitrary ABI, method and args.
+//!
+//! The input is split on NUL bytes: `<flags><abi json>\0<method>\0<arg>\0<arg>...`,
+//! the lowest bit of the first byte selects lenient parsing.
+//! Errors are expected, only panics are failures.
+
+#![no_main]
+
+use cita_tool::contract_encode_input;
+use ethabi::Contract;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (flags, data) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let lenient = flags & 1 == 1;
+    let mut fields = data
+        .split(|byte| *byte == 0)
+        .map(|field| String::from_utf8_lossy(field).into_owned());
+    let (abi, method) = match (fields.next(), fields.next()) {
+        (Some(abi), Some(method)) => (abi, method),
+        _ => return,
+    };
+    let args: Vec<String> = fields.collect();
+
+    if let Ok(contract) = Contract::load(abi.as_bytes()) {
+        let _ = contract_encode_input(&contract, &method, &args, lenient);
+    }
+});
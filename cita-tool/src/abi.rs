@@ -1,14 +1,19 @@
+use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::Read;
 
 use crate::LowerHex;
 use ethabi::param_type::{ParamType, Reader};
 use ethabi::token::{LenientTokenizer, StrictTokenizer, Token, Tokenizer};
-use ethabi::{decode, encode, Contract, Hash};
+use ethabi::{decode, encode, Contract, Hash, RawLog};
 use hex::{decode as hex_decode, encode as hex_encode};
+use log::warn;
+use serde_json::{Map, Value};
 use types::U256;
 
+use crate::client::remove_0x;
 use crate::error::ToolError;
+use crate::rpctypes::Log;
 
 pub fn parse_tokens(params: &[(ParamType, &str)], lenient: bool) -> Result<Vec<Token>, ToolError> {
     params
@@ -23,11 +28,11 @@ pub fn parse_tokens(params: &[(ParamType, &str)], lenient: bool) -> Result<Vec<T
                     StrictTokenizer::tokenize(param, &y)
                 } else if type_name.starts_with("int") && type_name.find(']').is_none() {
                     let x = if value.starts_with('-') {
-                        let x = (!U256::from_dec_str(&value[1..])
-                            .map_err(|_| "Can't parse into u256")?
-                            + U256::from(1))
-                        .lower_hex();
-                        format!("{:f>64}", x)
+                        // Two's complement, `-0` overflows back to zero
+                        (!U256::from_dec_str(&value[1..]).map_err(|_| "Can't parse into u256")?)
+                            .overflowing_add(U256::from(1))
+                            .0
+                            .completed_lower_hex()
                     } else {
                         U256::from_dec_str(value)
                             .map_err(|_| "Can't parse into u256")?
@@ -54,23 +59,121 @@ pub fn contract_encode_input(
 ) -> Result<String, ToolError> {
     let function = contract
         .function(function)
-        .map_err(|e| ToolError::Abi(e.to_string()))?
-        .clone();
-    let params: Vec<_> = function
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    let expected: Vec<String> = function
         .inputs
         .iter()
-        .map(|param| param.kind.clone())
-        .zip(values.iter().map(|v| v as &str))
+        .map(|param| param.kind.to_string())
         .collect();
+    let encode_error = |reason: String| ToolError::AbiEncode {
+        method: function.name.clone(),
+        expected: expected.clone(),
+        values: values.to_vec(),
+        reason,
+    };
+
+    if expected.len() != values.len() {
+        return Err(encode_error(format!(
+            "expects {} args ({}), got {}",
+            expected.len(),
+            expected.join(", "),
+            values.len()
+        )));
+    }
 
-    let tokens = parse_tokens(&params, lenient)?;
+    let tokens = function
+        .inputs
+        .iter()
+        .zip(values.iter())
+        .enumerate()
+        .map(|(index, (param, value))| {
+            parse_tokens(&[(param.kind.clone(), value.as_str())], lenient)
+                .map(|mut tokens| tokens.remove(0))
+                .map_err(|_| {
+                    encode_error(format!(
+                        "expects {} for arg {}, got \"{}\"",
+                        param.kind, index, value
+                    ))
+                })
+        })
+        .collect::<Result<Vec<Token>, ToolError>>()?;
     let result = function
         .encode_input(&tokens)
-        .map_err(|e| ToolError::Abi(e.to_string()))?;
+        .map_err(|e| encode_error(e.to_string()))?;
 
     Ok(hex_encode(result))
 }
 
+/// According to the contract, decode the return data of the function
+pub fn contract_decode_output(
+    contract: &Contract,
+    function: &str,
+    data: &str,
+) -> Result<Vec<Token>, ToolError> {
+    let function = contract
+        .function(function)
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    let data = hex_decode(remove_0x(data)).map_err(ToolError::Decode)?;
+    function
+        .decode_output(&data)
+        .map_err(|e| ToolError::Abi(e.to_string()))
+}
+
+/// According to the contract, decode the return data of the function into JSON,
+/// the value of the only output, or an object of several outputs by name,
+/// unnamed outputs by index
+pub fn contract_decode_output_json(
+    contract: &Contract,
+    function: &str,
+    data: &str,
+) -> Result<Value, ToolError> {
+    let tokens = contract_decode_output(contract, function, data)?;
+    let outputs = &contract
+        .function(function)
+        .map_err(|e| ToolError::Abi(e.to_string()))?
+        .outputs;
+    if tokens.len() == 1 {
+        return Ok(token_to_json(&tokens[0]));
+    }
+    Ok(Value::Object(
+        outputs
+            .iter()
+            .zip(tokens.iter())
+            .enumerate()
+            .map(|(index, (param, token))| {
+                let name = if param.name.is_empty() {
+                    index.to_string()
+                } else {
+                    param.name.clone()
+                };
+                (name, token_to_json(token))
+            })
+            .collect::<Map<String, Value>>(),
+    ))
+}
+
+/// Convert the token into JSON, addresses and bytes are hex with 0x,
+/// integers are decimal strings since they may not fit in a JSON number
+pub fn token_to_json(token: &Token) -> Value {
+    match token {
+        Token::Address(address) => Value::String(format!("{:?}", address)),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            Value::String(format!("0x{}", hex_encode(bytes)))
+        }
+        Token::Int(int) if int.bit(255) => Value::String(format!(
+            "-{}",
+            (!*int).overflowing_add(ethabi::Int::from(1)).0
+        )),
+        Token::Int(int) => Value::String(int.to_string()),
+        Token::Uint(uint) => Value::String(uint.to_string()),
+        Token::Bool(value) => Value::Bool(*value),
+        Token::String(value) => Value::String(value.clone()),
+        Token::FixedArray(tokens) | Token::Array(tokens) => {
+            Value::Array(tokens.iter().map(token_to_json).collect())
+        }
+    }
+}
+
 /// According to the contract, encode the constructor and parameter values
 pub fn constructor_encode_input(
     contract: &Contract,
@@ -101,6 +204,11 @@ pub fn constructor_encode_input(
     }
 }
 
+/// Load the contract from the abi json string, or else the abi file
+pub fn load_contract(path: Option<&str>, abi: Option<&str>) -> Result<Contract, ToolError> {
+    Contract::load(get_abi(path, abi)?).map_err(|e| ToolError::Abi(format!("{}", e)))
+}
+
 /// According to the given abi file, encode the function and parameter values
 pub fn encode_input(
     path: Option<&str>,
@@ -187,8 +295,9 @@ pub fn decode_input(
     let function = contract
         .function(function)
         .map_err(|e| ToolError::Abi(format!("{}", e)))?;
+    let data = hex_decode(remove_0x(data)).map_err(ToolError::Decode)?;
     let tokens = function
-        .decode_output(data.as_bytes())
+        .decode_output(&data)
         .map_err(|e| ToolError::Abi(format!("{}", e)))?;
     let types = function.outputs.iter().map(|ref param| &param.kind);
 
@@ -224,10 +333,10 @@ pub fn decode_logs(
 
     let topics: Vec<Hash> = topics
         .iter()
-        .map(|t| t.parse())
+        .map(|t| remove_0x(t).parse())
         .collect::<Result<_, _>>()
         .map_err(|e| ToolError::Abi(format!("{}", e)))?;
-    let data = hex_decode(data).map_err(ToolError::Decode)?;
+    let data = hex_decode(remove_0x(data)).map_err(ToolError::Decode)?;
     let decoded = event
         .parse_log((topics, data).into())
         .map_err(|e| ToolError::Abi(format!("{}", e)))?;
@@ -241,6 +350,107 @@ pub fn decode_logs(
     Ok(result)
 }
 
+/// According to the contract, decode the log of the event into named arguments
+pub fn decode_log(
+    contract: &Contract,
+    event_name: &str,
+    log: &Log,
+) -> Result<Vec<(String, Token)>, ToolError> {
+    let event = contract
+        .event(event_name)
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    let raw_log = RawLog {
+        topics: log
+            .topics
+            .iter()
+            .map(|topic| Hash::from_slice(&topic.0))
+            .collect(),
+        data: log.data.clone(),
+    };
+    let decoded = event
+        .parse_log(raw_log)
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    Ok(decoded
+        .params
+        .into_iter()
+        .map(|param| (param.name, param.value))
+        .collect())
+}
+
+/// Functions of a local ABI compared with the ABI stored on chain, by signatures
+/// such as `isAdmin(address) returns (bool)`, sorted
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct AbiValidation {
+    /// Functions in both
+    pub matching: Vec<String>,
+    /// Functions only in the local ABI, calls of them fail on chain
+    pub only_local: Vec<String>,
+    /// Functions only in the on-chain ABI
+    pub only_onchain: Vec<String>,
+}
+
+impl AbiValidation {
+    /// Whether the two ABIs have the same functions
+    pub fn is_consistent(&self) -> bool {
+        self.only_local.is_empty() && self.only_onchain.is_empty()
+    }
+}
+
+/// Compare the functions of the local contract with the on-chain one,
+/// a function with other outputs counts as a different one
+pub fn compare_abi(local: &Contract, onchain: &Contract) -> AbiValidation {
+    let signatures = |contract: &Contract| {
+        contract
+            .functions()
+            .map(|function| {
+                let types = |params: &[ethabi::Param]| {
+                    params
+                        .iter()
+                        .map(|param| param.kind.to_string())
+                        .collect::<Vec<String>>()
+                        .join(",")
+                };
+                format!(
+                    "{}({}) returns ({})",
+                    function.name,
+                    types(&function.inputs),
+                    types(&function.outputs)
+                )
+            })
+            .collect::<BTreeSet<String>>()
+    };
+    let local = signatures(local);
+    let onchain = signatures(onchain);
+    AbiValidation {
+        matching: local.intersection(&onchain).cloned().collect(),
+        only_local: local.difference(&onchain).cloned().collect(),
+        only_onchain: onchain.difference(&local).cloned().collect(),
+    }
+}
+
+/// Compare the local contract with the ABI JSON stored on chain at the address,
+/// with a warning if some functions are only local
+pub fn validate_onchain_abi(
+    local: &Contract,
+    address: &str,
+    onchain_abi: &str,
+) -> Result<AbiValidation, ToolError> {
+    if onchain_abi.is_empty() {
+        return Err(ToolError::Abi(format!("No ABI stored at {}", address)));
+    }
+    let onchain = Contract::load(onchain_abi.as_bytes())
+        .map_err(|err| ToolError::Abi(format!("ABI at {}: {}", address, err)))?;
+    let validation = compare_abi(local, &onchain);
+    if !validation.only_local.is_empty() {
+        warn!(
+            "Functions not in the ABI at {}: {}",
+            address,
+            validation.only_local.join(", ")
+        );
+    }
+    Ok(validation)
+}
+
 fn get_abi(path: Option<&str>, abi: Option<&str>) -> Result<Box<dyn Read>, ToolError> {
     match abi {
         Some(code) => Ok(Box::new(::std::io::Cursor::new(code.to_owned()))),
@@ -256,7 +466,13 @@ fn get_abi(path: Option<&str>, abi: Option<&str>) -> Result<Box<dyn Read>, ToolE
 
 #[cfg(test)]
 mod test {
-    use super::{decode_params, encode_params};
+    use super::{
+        compare_abi, contract_decode_output_json, contract_encode_input, decode_input, decode_log,
+        decode_params, encode_params, token_to_json, validate_onchain_abi,
+    };
+    use crate::rpctypes::Log;
+    use ethabi::{Contract, Token};
+    use types::{Address, H256};
 
     #[test]
     fn test_encode() {
@@ -277,6 +493,12 @@ mod test {
             "fffffffc4a717738acec1362cd61555e7046d08adea4e8f00000000000000001".to_string()
         );
 
+        let zero = encode_params(&["int".to_string()], &["-0".to_string()], true).unwrap();
+        assert_eq!(
+            zero,
+            "0000000000000000000000000000000000000000000000000000000000000000".to_string()
+        );
+
         let c = encode_params(&["uint".to_string()], &["100".to_string()], true).unwrap();
         assert_eq!(
             c,
@@ -302,4 +524,115 @@ mod test {
         let f = decode_params(&["string".to_string()], &e).unwrap();
         assert_eq!(f, ["{\"string\": \"\\\"\"}".to_string()]);
     }
+
+    #[test]
+    fn test_contract_encode_error() {
+        let contract =
+            Contract::load(include_str!("../contract_abi/GroupManagement.abi").as_bytes()).unwrap();
+        let values = ["hello".to_string(), "name".to_string(), "[]".to_string()];
+        let err = contract_encode_input(&contract, "newGroup", &values, false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ABI encode error: method \"newGroup\" expects address for arg 0, got \"hello\", check the arguments against the ABI"
+        );
+
+        let err = contract_encode_input(&contract, "newGroup", &values[..1], false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ABI encode error: method \"newGroup\" expects 3 args (address, bytes32, address[]), got 1, check the arguments against the ABI"
+        );
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let contract =
+            Contract::load(include_str!("../contract_abi/QuotaManager.abi").as_bytes()).unwrap();
+        let signature = contract.event("AqlSetted").unwrap().signature();
+        let mut value = [0u8; 32];
+        value[31] = 0x64;
+        let log = Log {
+            address: Address::default(),
+            topics: vec![H256::from(&signature[..]), H256::from(1), H256::from(2)],
+            data: value.to_vec(),
+            transaction_hash: H256::default(),
+            block_number: 0,
+            log_index: 0,
+        };
+        let decoded = decode_log(&contract, "AqlSetted", &log).unwrap();
+        let names = decoded
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ["_account", "_value", "_sender"]);
+        assert_eq!(decoded[1].1, Token::Uint(100.into()));
+    }
+
+    #[test]
+    fn test_contract_decode_output_json() {
+        let contract =
+            Contract::load(include_str!("../contract_abi/Group.abi").as_bytes()).unwrap();
+        let data = format!("0x{:064x}{:064x}{:064x}{:064x}", 0x6162, 64, 1, 0xff);
+        let output = contract_decode_output_json(&contract, "queryInfo", &data).unwrap();
+        assert_eq!(output["0"], format!("0x{:064x}", 0x6162));
+        assert_eq!(output["1"], serde_json::json!([format!("0x{:040x}", 0xff)]));
+
+        assert_eq!(token_to_json(&Token::Int(ethabi::Int::max_value())), "-1");
+        assert_eq!(token_to_json(&Token::Uint(100.into())), "100");
+        assert_eq!(token_to_json(&Token::Bool(true)), true);
+    }
+
+    #[test]
+    fn test_decode_input() {
+        let abi = include_str!("../contract_abi/Role.abi");
+        let data = format!("0x{:064x}", 1);
+        let output = decode_input(None, Some(abi), "inPermissions", &data).unwrap();
+        assert_eq!(output, ["{\"bool\": true}".to_string()]);
+        assert!(decode_input(None, Some(abi), "inPermissions", &data[2..]).is_ok());
+    }
+
+    #[test]
+    fn test_compare_abi() {
+        let local = Contract::load(
+            br#"[
+                {"type":"function","name":"admin","inputs":[],"outputs":[{"name":"","type":"address"}]},
+                {"type":"function","name":"isAdmin","inputs":[{"name":"_account","type":"address"}],"outputs":[{"name":"","type":"bool"}]},
+                {"type":"function","name":"update","inputs":[{"name":"_account","type":"address"}],"outputs":[{"name":"","type":"bool"}]}
+            ]"# as &[u8],
+        )
+        .unwrap();
+        let onchain = Contract::load(
+            br#"[
+                {"type":"function","name":"admin","inputs":[],"outputs":[{"name":"","type":"address"}]},
+                {"type":"function","name":"isAdmin","inputs":[{"name":"_account","type":"address"}],"outputs":[]},
+                {"type":"function","name":"version","inputs":[],"outputs":[{"name":"","type":"uint32"}]}
+            ]"# as &[u8],
+        )
+        .unwrap();
+        let validation = compare_abi(&local, &onchain);
+        assert_eq!(validation.matching, ["admin() returns (address)"]);
+        assert_eq!(
+            validation.only_local,
+            [
+                "isAdmin(address) returns (bool)",
+                "update(address) returns (bool)"
+            ]
+        );
+        assert_eq!(
+            validation.only_onchain,
+            ["isAdmin(address) returns ()", "version() returns (uint32)"]
+        );
+        assert!(!validation.is_consistent());
+        assert!(compare_abi(&local, &local).is_consistent());
+    }
+
+    #[test]
+    fn test_validate_onchain_abi() {
+        let abi = include_str!("../contract_abi/Role.abi");
+        let local = Contract::load(abi.as_bytes()).unwrap();
+        assert!(validate_onchain_abi(&local, "0x01", abi)
+            .unwrap()
+            .is_consistent());
+        assert!(validate_onchain_abi(&local, "0x01", "").is_err());
+        assert!(validate_onchain_abi(&local, "0x01", "not json").is_err());
+    }
 }
@@ -3,13 +3,36 @@ compile_error!("features `rustls` and `openssl` are mutually exclusive");
 
 /// Basic client api, for Low-level interface
 pub mod basic;
-/// System contract client api, call system contract more easy
+/// System contract client api, call system contract more easy,
+/// every client is behind the cargo feature of its contract, `full` enables all
 pub mod system_contract;
 
+mod circuit_breaker;
+mod contract_client;
+mod deduplicator;
+mod event_listener;
+mod filter_manager;
+mod nonce_manager;
+mod proxy;
+mod response_cache;
+mod rpc_method;
+mod rpc_span;
+mod shutdown;
+#[cfg(feature = "websocket")]
+mod subscription;
+mod transaction_builder;
 mod transaction_option;
 
+pub use self::contract_client::ContractClient;
+pub use self::deduplicator::TransactionDeduplicator;
+pub use self::event_listener::{ContractEventListener, ListenMode, ParsedLog};
+pub use self::filter_manager::{FilterId, FilterManager};
+pub use self::nonce_manager::NonceManager;
+pub use self::rpc_method::{rpc_method_name, KnownMethod};
+pub use self::transaction_builder::{RawTransaction, TransactionBuilder};
 pub use self::transaction_option::TransactionOptions;
 
+use crate::error::ToolError;
 use hyper::Uri;
 use std::str;
 
@@ -39,6 +62,32 @@ pub fn remove_0x(hex: &str) -> &str {
     hex
 }
 
+/// Remove hexadecimal prefix "0x" or "0X", and check the rest is an even length hex string.
+/// Example:
+/// ```rust
+/// extern crate cita_tool;
+///
+/// use cita_tool::remove_0x_validated;
+///
+/// assert_eq!("0b", remove_0x_validated("0x0b").unwrap());
+/// assert_eq!("0b", remove_0x_validated("0b").unwrap());
+/// assert!(remove_0x_validated("0x0").is_err());
+/// assert!(remove_0x_validated("0xzz").is_err());
+/// ```
+pub fn remove_0x_validated(hex: &str) -> Result<&str, ToolError> {
+    let content = remove_0x(hex);
+    if content.len() % 2 != 0 {
+        Err(ToolError::InvalidHex(format!("{} has odd length", hex)))
+    } else if let Some(c) = content.chars().find(|c| !c.is_ascii_hexdigit()) {
+        Err(ToolError::InvalidHex(format!(
+            "{} has invalid character {:?}",
+            hex, c
+        )))
+    } else {
+        Ok(content)
+    }
+}
+
 /// Verify the validity of the url address
 #[inline]
 pub fn parse_url(url: &str) -> Result<Uri, String> {
@@ -1,57 +1,86 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::io::Write;
+use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use std::{str, u64};
 
+#[cfg(feature = "quota")]
+use crate::abi::{contract_decode_output, contract_encode_input};
+#[cfg(feature = "quota")]
+use crate::contract_abi::{
+    EMERGENCY_BRAKE_CONTRACT, NODE_MANAGER_CONTRACT, PRICE_MANAGER_CONTRACT,
+};
 use crate::LowerHex;
+use ethabi::{Contract, Token};
 use failure::Fail;
-use futures::{future::join_all, future::JoinAll, sync, Future, Stream};
+use futures::{
+    future::{self, join_all, lazy},
+    stream, sync, Future, Stream,
+};
 use hex::{decode, encode};
-use hyper::{client::HttpConnector, Body, Client as HyperClient, Request, Uri};
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::{Body, Client as HyperClient, Request, Uri};
+use log::{debug, warn};
 use protobuf::{parse_from_bytes, Message};
 use serde;
 use serde_json;
-use tokio;
-use types::U256;
+use tokio::{self, timer::Timeout};
+use types::{Address, H256, U256};
 use uuid::Uuid;
 
-use crate::client::{remove_0x, TransactionOptions};
-use crate::crypto::PrivateKey;
+use crate::client::circuit_breaker::CircuitBreaker;
+use crate::client::proxy::{Proxy, ProxyConfig, ProxyConnector};
+use crate::client::response_cache::ResponseCache;
+use crate::client::rpc_method::{rpc_method_name, KnownMethod};
+use crate::client::rpc_span::{OtelContext, RpcSpan};
+use crate::client::shutdown::Shutdown;
+#[cfg(feature = "websocket")]
+use crate::client::subscription;
+#[cfg(feature = "quota")]
+use crate::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+use crate::client::{
+    remove_0x, NonceManager, RawTransaction, TransactionBuilder, TransactionOptions,
+};
+use crate::crypto::{address_from_privkey, PrivateKey};
 use crate::error::ToolError;
+use crate::proof::{AccountProof, BlockProof};
 use crate::protos::{Transaction, UnverifiedTransaction};
-use crate::rpctypes::{JsonRpcParams, JsonRpcResponse, ParamsValue, ResponseValue};
-
-const BLOCK_NUMBER: &str = "blockNumber";
-const GET_META_DATA: &str = "getMetaData";
-const SEND_RAW_TRANSACTION: &str = "sendRawTransaction";
-const PEER_COUNT: &str = "peerCount";
-const PEERS_INFO: &str = "peersInfo";
-const GET_BLOCK_BY_HASH: &str = "getBlockByHash";
-const GET_BLOCK_BY_NUMBER: &str = "getBlockByNumber";
-const GET_TRANSACTION: &str = "getTransaction";
-const GET_TRANSACTION_PROOF: &str = "getTransactionProof";
-
-const GET_TRANSACTION_RECEIPT: &str = "getTransactionReceipt";
-const GET_LOGS: &str = "getLogs";
-const CALL: &str = "call";
-const GET_TRANSACTION_COUNT: &str = "getTransactionCount";
-const GET_CODE: &str = "getCode";
-const GET_ABI: &str = "getAbi";
-const GET_BALANCE: &str = "getBalance";
-
-const NEW_FILTER: &str = "newFilter";
-const NEW_BLOCK_FILTER: &str = "newBlockFilter";
-const UNINSTALL_FILTER: &str = "uninstallFilter";
-const GET_FILTER_CHANGES: &str = "getFilterChanges";
-const GET_FILTER_LOGS: &str = "getFilterLogs";
-
-const GET_BLOCK_HEADER: &str = "getBlockHeader";
-const GET_STATE_PROOF: &str = "getStateProof";
-const GET_STORAGE_AT: &str = "getStorageAt";
-
-const GET_VERSION: &str = "getVersion";
-
-const ESTIMATE_QUOTA: &str = "estimateQuota";
+#[cfg(feature = "quota")]
+use crate::rpctypes::ChainStatus;
+#[cfg(feature = "debug-api")]
+use crate::rpctypes::TransactionTrace;
+use crate::rpctypes::{
+    contains_address, parse_bloom, BlockHeader, BlockStats, ChainMetadata, ChainVersion,
+    ConsensusHealth, JsonRpcParams, JsonRpcResponse, JsonRpcVersion, Log, LogFilter, ParamsValue,
+    PeerInfo, ResponseValue, TransactionReceipt, UrlHealth, Version,
+};
+use crate::signer::{InMemorySigner, Signer};
+#[cfg(feature = "quota")]
+use crate::system_addresses::{EMERGENCY_BRAKE, NODE_MANAGER, PRICE_MANAGER};
+
+/// JSON-RPC error code of an unknown method
+const METHOD_NOT_FOUND: i64 = -32601;
+/// Quota estimation of the nodes without `estimateQuota`
+const ESTIMATE_GAS: &str = "eth_estimateGas";
+/// Account and storage proof of EIP-1186
+const GET_PROOF: &str = "eth_getProof";
+/// Wait before resending a transaction that failed on the network
+const SUBMIT_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// How long the urls have to answer `blockNumber` in the divergence check before writes
+const DIVERGENCE_CHECK_DEADLINE: Duration = Duration::from_secs(5);
+/// Error message of `sendRawTransaction` for a transaction already in the pool
+const DUPLICATE_TRANSACTION: &str = "Dup";
+/// Transaction trace of the debug API
+#[cfg(feature = "debug-api")]
+const TRACE_TRANSACTION: &str = "debug_traceTransaction";
+/// Call trace of the debug API
+#[cfg(feature = "debug-api")]
+const TRACE_CALL: &str = "debug_traceCall";
 
 /// Store action target address
 pub const STORE_ADDRESS: &str = "0xffffffffffffffffffffffffffffffffff010000";
@@ -69,14 +98,35 @@ pub const AMEND_KV_H256: &str = "0x03";
 /// amend account balance
 pub const AMEND_BALANCE: &str = "0x05";
 
+type ResponseFuture = Box<dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send>;
+type ResponsesFuture =
+    Box<dyn Future<Item = Vec<JsonRpcResponse>, Error = ToolError> + 'static + Send>;
+
 /// Jsonrpc client, Only to one chain
 pub struct Client {
     id: AtomicUsize,
     url: Uri,
+    http: HttpsClient,
     sender: sync::mpsc::UnboundedSender<Box<dyn Future<Item = (), Error = ()> + Send + 'static>>,
     chain_id: Option<U256>,
     private_key: Option<PrivateKey>,
+    nonce_manager: NonceManager,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    max_concurrent: Option<usize>,
+    cache: Option<Arc<Mutex<ResponseCache>>>,
+    circuit_breaker: Option<Arc<Mutex<CircuitBreaker>>>,
+    consensus_urls: Vec<Uri>,
+    divergence_threshold: Option<u64>,
+    shutdown: Arc<Shutdown>,
+    otel_context: Option<OtelContext>,
+    chain_version: Option<Version>,
+    negotiated_version: Option<ChainVersion>,
+    json_rpc_version: JsonRpcVersion,
+    canned_responses: Option<Arc<Mutex<VecDeque<Result<JsonRpcResponse, ToolError>>>>>,
     debug: bool,
+    dry_run: bool,
+    #[cfg(feature = "websocket")]
+    ws_url: Option<String>,
 }
 
 impl Client {
@@ -99,13 +149,62 @@ impl Client {
         Client {
             id: AtomicUsize::new(0),
             url: "http://127.0.0.1:1337".parse().unwrap(),
+            http: create_client(ProxyConfig::from_env()),
             sender,
             chain_id: None,
             private_key: None,
+            nonce_manager: NonceManager::new(),
+            headers: Vec::new(),
+            max_concurrent: None,
+            cache: None,
+            circuit_breaker: None,
+            consensus_urls: Vec::new(),
+            divergence_threshold: None,
+            shutdown: Arc::new(Shutdown::default()),
+            otel_context: None,
+            chain_version: None,
+            negotiated_version: None,
+            json_rpc_version: JsonRpcVersion::default(),
+            canned_responses: None,
             debug: false,
+            dry_run: false,
+            #[cfg(feature = "websocket")]
+            ws_url: None,
         }
     }
 
+    /// Create a client answering every request with the next of the responses,
+    /// in FIFO order, without any network call, for tests
+    ///
+    /// The responses are shared by the clones of the client, requests fail
+    /// once they run out.
+    pub fn with_responses(responses: Vec<JsonRpcResponse>) -> Self {
+        Client::with_results(responses.into_iter().map(Ok).collect())
+    }
+
+    /// Create a client answering like `with_responses`, a request answered with an error
+    /// fails with it, like a request failing on the network
+    pub(crate) fn with_results(results: Vec<Result<JsonRpcResponse, ToolError>>) -> Self {
+        let mut client = Client::new();
+        client.canned_responses = Some(Arc::new(Mutex::new(results.into_iter().collect())));
+        client
+    }
+
+    /// Take the next canned responses, one for each request
+    fn canned(
+        responses: &Mutex<VecDeque<Result<JsonRpcResponse, ToolError>>>,
+        count: usize,
+    ) -> Result<Vec<JsonRpcResponse>, ToolError> {
+        let mut responses = responses.lock().unwrap();
+        (0..count)
+            .map(|_| {
+                responses.pop_front().unwrap_or_else(|| {
+                    Err(ToolError::Customize("No canned response left".to_string()))
+                })
+            })
+            .collect()
+    }
+
     /// Set url
     /// ---
     /// When the url address is invalid, panic
@@ -119,6 +218,116 @@ impl Client {
         &self.url
     }
 
+    /// Set the WebSocket url of the subscriptions, it's the url with
+    /// the `ws` or `wss` scheme by default
+    #[cfg(feature = "websocket")]
+    pub fn set_ws_uri(mut self, url: &str) -> Self {
+        self.ws_url = Some(url.to_string());
+        self
+    }
+
+    /// Connect through the SOCKS5 proxy, instead of the proxies of
+    /// `http_proxy`, `https_proxy` and `no_proxy` environment variables
+    pub fn with_socks5_proxy(mut self, addr: SocketAddr) -> Self {
+        self.http = create_client(ProxyConfig::all(Proxy::Socks5(addr)));
+        self
+    }
+
+    /// Connect through the HTTP proxy, https urls by `CONNECT` tunnels, instead of
+    /// the proxies of `http_proxy`, `https_proxy` and `no_proxy` environment variables
+    /// ---
+    /// When the proxy url is invalid or can't be resolved, panic
+    pub fn with_http_proxy(mut self, url: &str) -> Self {
+        let proxy = match Proxy::parse(url) {
+            Ok(proxy @ Proxy::Http(_)) => proxy,
+            Ok(_) => panic!("{} is not an HTTP proxy", url),
+            Err(err) => panic!("{}", err),
+        };
+        self.http = create_client(ProxyConfig::all(proxy));
+        self
+    }
+
+    /// Add a header to every request, such as "Authorization",
+    /// the headers added by multiple calls are all sent
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self, ToolError> {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+            ToolError::Customize(format!("Invalid header name {}: {}", name, err))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|err| {
+            ToolError::Customize(format!("Invalid header value of {}: {}", name, err))
+        })?;
+        self.headers.push((name, value));
+        Ok(self)
+    }
+
+    /// Set the maximum number of concurrent requests, default is unlimited
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Cache the responses of read-only queries (`call`, `getAbi`, `getCode`, `getMetaData`)
+    /// for `ttl`, including the ones at `latest`, queries at `pending` are never cached, and
+    /// the cached queries of a contract are dropped after a transaction is sent to it
+    pub fn with_cache(mut self, ttl: Duration) -> Self {
+        self.cache = Some(Arc::new(Mutex::new(ResponseCache::new(ttl))));
+        self
+    }
+
+    /// Skip a url after `failure_threshold` consecutive failed requests, instead of
+    /// waiting for it to fail again, one probe request is allowed after `recovery_interval`
+    /// and the url is used again if it succeeds
+    ///
+    /// The urls skipped by `send_request_with_multiple_url` are left out of the responses,
+    /// other requests to a skipped url fail with `ToolError::CircuitOpen`.
+    pub fn with_circuit_breaker(
+        mut self,
+        failure_threshold: u32,
+        recovery_interval: Duration,
+    ) -> Self {
+        self.circuit_breaker = Some(Arc::new(Mutex::new(CircuitBreaker::new(
+            failure_threshold,
+            recovery_interval,
+        ))));
+        self
+    }
+
+    /// Compare the heights of the urls with `check_consensus_divergence` before sending
+    /// a transaction, with the circuit breaker enabled
+    ///
+    /// The transaction fails with `ToolError::NetworkPartitionDetected` if the highest and
+    /// the lowest url are more than `threshold` blocks apart.
+    pub fn with_divergence_threshold(mut self, urls: Vec<Uri>, threshold: u64) -> Self {
+        self.consensus_urls = urls;
+        self.divergence_threshold = Some(threshold);
+        self
+    }
+
+    /// Stop sending new requests, they fail with `ToolError::ShuttingDown`,
+    /// for the client and all its clones
+    pub fn initiate_shutdown(&self) {
+        self.shutdown.initiate();
+    }
+
+    /// Completes when all the requests in flight of the client and its clones are resolved,
+    /// call `initiate_shutdown` first to stop new ones
+    pub fn drain_pending(&self) -> impl Future<Item = (), Error = ()> + Send {
+        Shutdown::drain(&self.shutdown)
+    }
+
+    /// Set the parent context of the `cita.rpc.send` span of every request,
+    /// the default is the current context
+    #[cfg(feature = "tracing")]
+    pub fn with_otel_context(mut self, context: opentelemetry::Context) -> Self {
+        self.otel_context = Some(context);
+        self
+    }
+
+    /// Get the custom headers
+    pub fn headers(&self) -> &[(HeaderName, HeaderValue)] {
+        &self.headers
+    }
+
     /// Set chain id
     pub fn set_chain_id(&mut self, chain_id: U256) -> &mut Self {
         self.chain_id = Some(chain_id);
@@ -127,9 +336,8 @@ impl Client {
 
     /// Set private key
     pub fn set_private_key(&mut self, private_key: &PrivateKey) -> &mut Self {
-        match private_key {
-            PrivateKey::Null => {}
-            _ => self.private_key = Some(*private_key),
+        if !private_key.is_null() {
+            self.private_key = Some(private_key.clone());
         }
         self
     }
@@ -139,6 +347,17 @@ impl Client {
         self.private_key.as_ref()
     }
 
+    /// Set nonce manager, default generates random nonce
+    pub fn set_nonce_manager(&mut self, nonce_manager: NonceManager) -> &mut Self {
+        self.nonce_manager = nonce_manager;
+        self
+    }
+
+    /// Get nonce manager, mark the transaction confirmed after its receipt is obtained
+    pub fn nonce_manager_mut(&mut self) -> &mut NonceManager {
+        &mut self.nonce_manager
+    }
+
     /// Get debug
     pub fn debug(&self) -> bool {
         self.debug
@@ -150,115 +369,434 @@ impl Client {
         self
     }
 
+    /// Set dry run mode, transactions are signed but not sent,
+    /// the result of `sendRawTransaction` is the signed transaction hex instead
+    ///
+    /// Without a quota, the quota of a transaction is estimated in dry run mode.
+    pub fn set_dry_run(mut self, mode: bool) -> Self {
+        self.dry_run = mode;
+        self
+    }
+
+    /// Get dry run mode
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Set the chain version that decides the method names of requests,
+    /// `None` is the latest naming
+    pub fn set_chain_version(mut self, version: Option<Version>) -> Self {
+        self.chain_version = version;
+        self
+    }
+
+    /// Get the chain version of the method names
+    pub fn chain_version(&self) -> Option<Version> {
+        self.chain_version
+    }
+
+    /// Set the JSON-RPC version of the requests, 1.0 for the old nodes, 2.0 by default
+    pub fn set_json_rpc_version(mut self, version: JsonRpcVersion) -> Self {
+        self.json_rpc_version = version;
+        self
+    }
+
+    /// Get the JSON-RPC version of the requests
+    pub fn json_rpc_version(&self) -> JsonRpcVersion {
+        self.json_rpc_version
+    }
+
+    /// Detect the chain version with `getVersion` and use its method names
+    ///
+    /// Chains before CITA 0.20 don't understand the latest naming,
+    /// set their version with `set_chain_version` instead.
+    pub fn detect_chain_version(&mut self) -> Result<Version, ToolError> {
+        let version = self.get_node_version()?;
+        self.chain_version = Some(version);
+        Ok(version)
+    }
+
+    /// Method name of requests on the chain version
+    pub(crate) fn method(&self, method: KnownMethod) -> ParamsValue {
+        let name = match self.chain_version {
+            Some(ref version) => rpc_method_name(method, version),
+            None => method.name(),
+        };
+        ParamsValue::String(String::from(name))
+    }
+
     /// Send requests
     pub fn send_request<T: Iterator<Item = JsonRpcParams>>(
         &self,
         params: T,
     ) -> Result<Vec<JsonRpcResponse>, ToolError> {
         let params = params.collect::<Vec<JsonRpcParams>>();
+        let cache = match self.cache {
+            Some(ref cache) => cache,
+            None => return self.fetch(params),
+        };
+
+        let url = self.url.to_string();
+        let mut responses = {
+            let mut cache = cache.lock().unwrap();
+            params
+                .iter()
+                .map(|param| cache.get(&url, param))
+                .collect::<Vec<Option<JsonRpcResponse>>>()
+        };
+        let misses = params
+            .into_iter()
+            .zip(responses.iter())
+            .filter(|(_, response)| response.is_none())
+            .map(|(param, _)| param)
+            .collect::<Vec<JsonRpcParams>>();
+        if !misses.is_empty() {
+            let fetched = self.fetch(misses.clone())?;
+            let mut cache = cache.lock().unwrap();
+            for ((response, param), fetched) in responses
+                .iter_mut()
+                .filter(|response| response.is_none())
+                .zip(misses.iter())
+                .zip(fetched)
+            {
+                cache.insert(&url, param, &fetched);
+                *response = Some(fetched);
+            }
+        }
+        Ok(responses.into_iter().map(Option::unwrap).collect())
+    }
 
+    /// Send the requests without the cache, or take the canned responses
+    fn fetch(&self, params: Vec<JsonRpcParams>) -> Result<Vec<JsonRpcResponse>, ToolError> {
+        if let Some(ref responses) = self.canned_responses {
+            return Client::canned(responses, params.len());
+        }
         let reqs = self.make_requests_with_params_list(params.into_iter());
-
         self.run(reqs)
     }
 
+    /// Send `call` of every `(to, data)` at the same time, the responses are in the same order
+    pub fn call_all(
+        &self,
+        calls: Vec<(String, String)>,
+        height: &str,
+    ) -> Result<Vec<JsonRpcResponse>, ToolError> {
+        let params = calls.into_iter().map(|(to, data)| {
+            let mut object = HashMap::new();
+            object.insert(String::from("to"), ParamsValue::String(to));
+            object.insert(String::from("data"), ParamsValue::String(data));
+            JsonRpcParams::new()
+                .insert("method", self.method(KnownMethod::Call))
+                .insert(
+                    "params",
+                    ParamsValue::List(vec![
+                        ParamsValue::Map(object),
+                        ParamsValue::String(String::from(height)),
+                    ]),
+                )
+        });
+        self.send_request(params)
+    }
+
     /// Send multiple params to one node
     pub fn send_request_with_multiple_url<T: Iterator<Item = Uri>>(
         &self,
         url: T,
         params: JsonRpcParams,
     ) -> Result<Vec<JsonRpcResponse>, ToolError> {
+        if let Some(ref responses) = self.canned_responses {
+            return Client::canned(responses, url.count());
+        }
         let reqs = self.make_requests_with_all_url(url, params);
 
         self.run(reqs)
     }
 
+    /// Send `blockNumber` to all urls at the same time and measure the round-trip time,
+    /// the urls failing or not responding within `deadline` are reported with an error
+    pub fn check_url_health<T: Iterator<Item = Uri>>(
+        &self,
+        urls: T,
+        deadline: Duration,
+    ) -> Vec<UrlHealth> {
+        if let Some(ref responses) = self.canned_responses {
+            return urls
+                .map(|url| {
+                    let block_number = Client::canned(responses, 1)
+                        .and_then(|mut responses| u64::try_from(responses.pop().unwrap()))
+                        .map_err(|err| err.to_string());
+                    UrlHealth {
+                        url: url.to_string(),
+                        latency: block_number.as_ref().ok().map(|_| Duration::from_secs(0)),
+                        block_number: block_number.as_ref().ok().cloned(),
+                        error: block_number.err(),
+                    }
+                })
+                .collect();
+        }
+        let id = self.next_id();
+        let params = JsonRpcParams::new()
+            .insert("method", self.method(KnownMethod::BlockNumber))
+            .insert("id", ParamsValue::Int(id))
+            .json_rpc_version(self.json_rpc_version);
+
+        let urls = urls.collect::<Vec<Uri>>();
+        let checks = urls
+            .iter()
+            .cloned()
+            .map(|url| {
+                let mut health = UrlHealth {
+                    url: url.to_string(),
+                    latency: None,
+                    block_number: None,
+                    error: None,
+                };
+                let req = self.request_future(url, id, &params);
+                lazy(move || {
+                    let start = Instant::now();
+                    Timeout::new(req, deadline).then(move |result| {
+                        let block_number = result
+                            .map_err(|err| match err.into_inner() {
+                                Some(err) => err.to_string(),
+                                None => format!("Timeout after {:?}", deadline),
+                            })
+                            .and_then(|response| {
+                                u64::try_from(response).map_err(|err| err.to_string())
+                            });
+                        match block_number {
+                            Ok(block_number) => {
+                                health.latency = Some(start.elapsed());
+                                health.block_number = Some(block_number);
+                            }
+                            Err(err) => health.error = Some(err),
+                        }
+                        Ok::<UrlHealth, ToolError>(health)
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        match self.run(Box::new(join_all(checks))) {
+            Ok(healths) => healths,
+            Err(err) => urls
+                .iter()
+                .map(|url| UrlHealth {
+                    url: url.to_string(),
+                    latency: None,
+                    block_number: None,
+                    error: Some(err.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Compare the heights of the urls of `with_divergence_threshold`, or the url of the
+    /// client, a divergence over `threshold` blocks may be a partitioned network and is
+    /// logged as a warning
+    ///
+    /// The urls failing to answer are left out, it fails if none answers.
+    pub fn check_consensus_divergence(&self, threshold: u64) -> Result<ConsensusHealth, ToolError> {
+        let urls = if self.consensus_urls.is_empty() {
+            vec![self.url.clone()]
+        } else {
+            self.consensus_urls.clone()
+        };
+        let healths = self.check_url_health(urls.into_iter(), DIVERGENCE_CHECK_DEADLINE);
+        let health = ConsensusHealth::from_url_health(&healths, threshold).ok_or_else(|| {
+            ToolError::Customize(format!(
+                "No url answers blockNumber: {}",
+                healths
+                    .iter()
+                    .map(|health| format!(
+                        "{} {}",
+                        health.url,
+                        health.error.as_ref().map_or("", String::as_str)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+        })?;
+        if health.divergence > threshold {
+            warn!(
+                "{}, behind: {}",
+                ToolError::NetworkPartitionDetected {
+                    divergence: health.divergence
+                },
+                health.divergent_urls.join(", ")
+            );
+        }
+        Ok(health)
+    }
+
+    /// Refuse to write while the urls diverge, if the circuit breaker is enabled and
+    /// a divergence threshold is set
+    fn check_partition(&self) -> Result<(), ToolError> {
+        let threshold = match self.divergence_threshold {
+            Some(threshold) if self.circuit_breaker.is_some() && !self.dry_run => threshold,
+            _ => return Ok(()),
+        };
+        let divergence = self.check_consensus_divergence(threshold)?.divergence;
+        if divergence > threshold {
+            Err(ToolError::NetworkPartitionDetected { divergence })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run at most `max_concurrent` requests at the same time, the others wait in order
+    #[inline]
+    fn limit_concurrency(&self, reqs: Vec<ResponseFuture>) -> ResponsesFuture {
+        match self.max_concurrent {
+            Some(max_concurrent) => Box::new(
+                stream::iter_ok::<_, ToolError>(reqs)
+                    .buffered(max_concurrent)
+                    .collect(),
+            ),
+            None => Box::new(join_all(reqs)),
+        }
+    }
+
+    /// Whether the circuit breaker, if any, lets a request to the url through
+    fn circuit_allows(&self, url: &Uri) -> bool {
+        self.circuit_breaker
+            .as_ref()
+            .map(|circuit_breaker| circuit_breaker.lock().unwrap().allow(url))
+            .unwrap_or(true)
+    }
+
+    #[inline]
+    fn next_id(&self) -> u64 {
+        self.id.fetch_add(1, Ordering::Relaxed) as u64 + 1
+    }
+
+    #[inline]
+    fn build_request(&self, url: Uri, params: &JsonRpcParams) -> Request<Body> {
+        let mut req: Request<Body> = Request::builder()
+            .uri(url)
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_string(params).unwrap()))
+            .unwrap();
+        for (name, value) in &self.headers {
+            req.headers_mut().append(name.clone(), value.clone());
+        }
+        req
+    }
+
+    /// Send the request, log it and its response
+    fn request_future(&self, url: Uri, id: u64, params: &JsonRpcParams) -> ResponseFuture {
+        if self.shutdown.is_draining() {
+            return Box::new(future::err(ToolError::ShuttingDown));
+        }
+        let in_flight = Shutdown::start(&self.shutdown);
+        let method = params
+            .get("method")
+            .and_then(ParamsValue::as_str)
+            .unwrap_or_default()
+            .to_string();
+        debug!(
+            "→ {} {}",
+            method,
+            serde_json::to_string(params).unwrap_or_default()
+        );
+        let span = RpcSpan::start(self.otel_context.as_ref(), &method, &url);
+        let circuit = self
+            .circuit_breaker
+            .clone()
+            .map(|circuit_breaker| (circuit_breaker, url.clone()));
+        let req = self.build_request(url, params);
+        let status_span = span.clone();
+        Box::new(
+            self.http
+                .request(req)
+                .and_then(move |res| {
+                    status_span.record_status(res.status().as_u16());
+                    res.into_body().concat2()
+                })
+                .map_err(ToolError::Hyper)
+                .and_then(|response| {
+                    serde_json::from_slice::<JsonRpcResponse>(&response)
+                        .map_err(ToolError::SerdeJson)
+                })
+                .and_then(move |response| check_response_id(id, response))
+                .then(move |result| {
+                    match result {
+                        Ok(ref response) if response.is_ok() => {
+                            debug!("← {} {:?}", method, response)
+                        }
+                        Ok(ref response) => warn!("← {} {:?}", method, response),
+                        Err(ref err) => warn!("← {} {}", method, err),
+                    }
+                    if let Some((circuit_breaker, url)) = circuit {
+                        circuit_breaker.lock().unwrap().record(&url, result.is_ok());
+                    }
+                    span.end();
+                    drop(in_flight);
+                    result
+                }),
+        )
+    }
+
     #[inline]
     fn make_requests_with_all_url<T: Iterator<Item = Uri>>(
         &self,
         urls: T,
         params: JsonRpcParams,
-    ) -> JoinAll<Vec<Box<dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send>>>
-    {
-        self.id.fetch_add(1, Ordering::Relaxed);
-        let params = params.insert(
-            "id",
-            ParamsValue::Int(self.id.load(Ordering::Relaxed) as u64),
-        );
+    ) -> ResponsesFuture {
+        let id = self.next_id();
+        let params = params
+            .insert("id", ParamsValue::Int(id))
+            .json_rpc_version(self.json_rpc_version);
 
         if self.debug {
             Self::debug_request(vec![&params].into_iter())
         }
 
-        let client = create_client();
+        let urls = urls.collect::<Vec<Uri>>();
+        let allowed = urls
+            .iter()
+            .filter(|url| self.circuit_allows(url))
+            .cloned()
+            .collect::<Vec<Uri>>();
+        if allowed.is_empty() && !urls.is_empty() {
+            return Box::new(future::err(ToolError::CircuitOpen {
+                url: urls[0].to_string(),
+            }));
+        }
+
         let mut reqs = Vec::with_capacity(100);
-        urls.for_each(|url| {
-            let req: Request<Body> = Request::builder()
-                .uri(url)
-                .method("POST")
-                .header("Content-Type", "application/json")
-                .body(Body::from(serde_json::to_string(&params).unwrap()))
-                .unwrap();
-            let future: Box<
-                dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send,
-            > = Box::new(
-                client
-                    .request(req)
-                    .and_then(|res| res.into_body().concat2())
-                    .map_err(ToolError::Hyper)
-                    .and_then(|response| {
-                        serde_json::from_slice::<JsonRpcResponse>(&response)
-                            .map_err(ToolError::SerdeJson)
-                    }),
-            );
-            reqs.push(future);
-        });
-        join_all(reqs)
+        allowed
+            .into_iter()
+            .for_each(|url| reqs.push(self.request_future(url, id, &params)));
+        self.limit_concurrency(reqs)
     }
 
     #[inline]
     fn make_requests_with_params_list<T: Iterator<Item = JsonRpcParams>>(
         &self,
         params: T,
-    ) -> JoinAll<Vec<Box<dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send>>>
-    {
-        let client = create_client();
+    ) -> ResponsesFuture {
+        if !self.circuit_allows(&self.url) {
+            return Box::new(future::err(ToolError::CircuitOpen {
+                url: self.url.to_string(),
+            }));
+        }
+
         let mut reqs = Vec::with_capacity(100);
         params
             .map(|param| {
-                self.id.fetch_add(1, Ordering::Relaxed);
-                let param = param.insert(
-                    "id",
-                    ParamsValue::Int(self.id.load(Ordering::Relaxed) as u64),
-                );
+                let id = self.next_id();
+                let param = param
+                    .insert("id", ParamsValue::Int(id))
+                    .json_rpc_version(self.json_rpc_version);
                 if self.debug {
                     Self::debug_request(vec![&param].into_iter())
                 }
-                param
+                (id, param)
             })
-            .for_each(|param| {
-                let req: Request<Body> = Request::builder()
-                    .uri(self.url.clone())
-                    .method("POST")
-                    .header("Content-Type", "application/json")
-                    .body(Body::from(serde_json::to_string(&param).unwrap()))
-                    .unwrap();
-                let future: Box<
-                    dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send,
-                > = Box::new(
-                    client
-                        .request(req)
-                        .and_then(|res| res.into_body().concat2())
-                        .map_err(ToolError::Hyper)
-                        .and_then(|response| {
-                            serde_json::from_slice::<JsonRpcResponse>(&response)
-                                .map_err(ToolError::SerdeJson)
-                        }),
-                );
-                reqs.push(future);
-            });
+            .for_each(|(id, param)| reqs.push(self.request_future(self.url.clone(), id, &param)));
 
-        join_all(reqs)
+        self.limit_concurrency(reqs)
     }
 
     /// Constructing a Transaction
@@ -267,21 +805,42 @@ impl Client {
         transaction_options: TransactionOptions,
     ) -> Result<Transaction, ToolError> {
         let data = decode(remove_0x(transaction_options.code())).map_err(ToolError::Decode)?;
-        let current_height = transaction_options
-            .current_height()
-            .ok_or_else(|| ToolError::Customize("No height input".to_string()))
-            .or_else(|_| self.get_current_height())?;
 
         let mut tx = Transaction::new();
         tx.set_data(data);
 
-        tx.set_nonce(encode(Uuid::new_v4().as_bytes()));
-        tx.set_valid_until_block(current_height + 88);
+        let nonce = match self.private_key {
+            Some(ref private_key) => self.nonce_manager.next_nonce(private_key),
+            None => encode(Uuid::new_v4().as_bytes()),
+        };
+        tx.set_nonce(nonce);
+        let valid_until_block = match (
+            transaction_options.valid_until_block(),
+            transaction_options.expires_in(),
+        ) {
+            (Some(valid_until_block), _) => valid_until_block,
+            (None, Some(blocks)) => self.get_current_height()? + blocks,
+            (None, None) => {
+                let current_height = transaction_options
+                    .current_height()
+                    .ok_or_else(|| ToolError::Customize("No height input".to_string()))
+                    .or_else(|_| self.get_current_height())?;
+                current_height + 88
+            }
+        };
+        tx.set_valid_until_block(valid_until_block);
         tx.set_quota(transaction_options.quota().unwrap_or_else(|| 10_000_000));
-        let value = transaction_options
-            .value()
-            .map(|value| value.completed_lower_hex())
-            .unwrap_or_else(|| U256::zero().completed_lower_hex());
+        let value = transaction_options.value().unwrap_or_else(U256::zero);
+        if value.is_zero()
+            && tx.get_data().is_empty()
+            && !remove_0x(transaction_options.address()).is_empty()
+        {
+            warn!(
+                "Transaction to {} has neither data nor value, it transfers nothing",
+                transaction_options.address()
+            );
+        }
+        let value = value.completed_lower_hex();
         tx.set_value(decode(value).map_err(ToolError::Decode)?);
 
         let version = transaction_options
@@ -291,14 +850,21 @@ impl Client {
         if version == 0 {
             // Create a contract if the target address is empty
             tx.set_to(remove_0x(transaction_options.address()).to_string());
-            tx.set_chain_id(self.get_chain_id()?);
+            tx.set_chain_id(match transaction_options.chain_id() {
+                Some(chain_id) => chain_id,
+                None => self.get_chain_id()?,
+            });
         } else if version < 3 {
             // Create a contract if the target address is empty
             tx.set_to_v1(
                 decode(remove_0x(transaction_options.address())).map_err(ToolError::Decode)?,
             );
+            let chain_id_v1 = match transaction_options.chain_id_v1() {
+                Some(chain_id_v1) => chain_id_v1,
+                None => self.get_chain_id_v1()?,
+            };
             tx.set_chain_id_v1(
-                decode(self.get_chain_id_v1()?.completed_lower_hex()).map_err(ToolError::Decode)?,
+                decode(chain_id_v1.completed_lower_hex()).map_err(ToolError::Decode)?,
             );
         } else {
             return Err(ToolError::Customize("Invalid version".to_string()));
@@ -309,23 +875,136 @@ impl Client {
         Ok(tx)
     }
 
+    /// Estimate the quota used by the transaction at the latest height, with
+    /// `estimateQuota`, or `eth_estimateGas` on the nodes without it
+    pub fn estimate_quota_u64(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        data: &str,
+    ) -> Result<u64, ToolError> {
+        let result = match self
+            .estimate_quota(from, to, Some(data), "latest")?
+            .into_result()
+        {
+            Err(ToolError::RpcError {
+                code: METHOD_NOT_FOUND,
+                ..
+            }) => {
+                let method = ParamsValue::String(String::from(ESTIMATE_GAS));
+                let params = call_params(method, from, to, Some(data), "latest")?;
+                self.send_request(vec![params].into_iter())?
+                    .pop()
+                    .unwrap()
+                    .into_result()?
+            }
+            result => result?,
+        };
+        result
+            .as_u64()
+            .ok_or_else(|| ToolError::Customize(format!("Not a quota: {}", result)))
+    }
+
+    /// The sender of the transactions signed by the private key, with "0x"
+    pub fn sender(&self) -> Option<String> {
+        self.private_key
+            .as_ref()
+            .map(|private_key| address_from_privkey(private_key).completed_lower_hex_with_0x())
+    }
+
+    /// Run the transaction with `call` before signing, return the output
+    ///
+    /// A reverted call fails with `ToolError::SimulationFailed`, carrying the revert data,
+    /// the other errors of the node are returned as they are.
+    pub fn simulate_transaction(
+        &self,
+        transaction_options: &TransactionOptions,
+    ) -> Result<Vec<u8>, ToolError> {
+        let from = self.sender().ok_or_else(|| {
+            ToolError::Customize("No private key to simulate the transaction".to_string())
+        })?;
+        match self.simulate_tx(
+            &from,
+            transaction_options.address(),
+            transaction_options.code(),
+            None,
+        ) {
+            Ok(output) => decode(output).map_err(ToolError::Decode),
+            // The revert data, or the reason, tells an execution revert from other errors
+            Err(ToolError::RpcError {
+                code,
+                message,
+                data,
+            }) => {
+                if data.is_none() && !message.to_lowercase().contains("revert") {
+                    return Err(ToolError::RpcError {
+                        code,
+                        message,
+                        data,
+                    });
+                }
+                Err(ToolError::SimulationFailed {
+                    revert_data: data
+                        .and_then(|data| serde_json::from_str::<String>(&data).ok())
+                        .and_then(|data| decode(remove_0x(&data)).ok())
+                        .unwrap_or_default(),
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Call any contract, return the output in hex with "0x", default height is "latest"
+    pub fn cita_call(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        data: &str,
+        height: Option<&str>,
+    ) -> Result<String, ToolError> {
+        let output = self
+            .call(from, to, Some(data), height.unwrap_or("latest"))?
+            .into_hex_result()?;
+        Ok(format!("0x{}", output))
+    }
+
     /// Constructing a UnverifiedTransaction hex string
     #[inline]
     pub fn generate_sign_transaction(&self, tx: &Transaction) -> Result<String, ToolError> {
+        let private_key = self.private_key().cloned().ok_or_else(|| {
+            ToolError::Customize("The provided private key do not match the algorithm".to_string())
+        })?;
+        self.generate_sign_transaction_with_signer(tx, &mut InMemorySigner(private_key))
+    }
+
+    /// Constructing a UnverifiedTransaction hex string signed by the signer,
+    /// such as a hardware wallet, instead of the private key of the client
+    pub fn generate_sign_transaction_with_signer(
+        &self,
+        tx: &Transaction,
+        signer: &mut dyn Signer,
+    ) -> Result<String, ToolError> {
         Ok(format!(
             "0x{}",
             encode(
-                tx.build_unverified(*self.private_key().ok_or_else(|| ToolError::Customize(
-                    "The provided private key do not match the algorithm".to_string(),
-                ))?)
-                .write_to_bytes()
-                .map_err(ToolError::Proto)?
+                tx.build_with_signer(signer)?
+                    .write_to_bytes()
+                    .map_err(ToolError::Proto)?
             )
         ))
     }
 
-    /// Send a signed transaction
+    /// Send a signed transaction, in dry run mode the re-encoded transaction is returned instead
     pub fn send_signed_transaction(&mut self, param: &str) -> Result<JsonRpcResponse, ToolError> {
+        self.check_partition()?;
+        self.send_signed_transaction_unchecked(param)
+    }
+
+    /// Send a signed transaction without checking for a network partition
+    fn send_signed_transaction_unchecked(
+        &mut self,
+        param: &str,
+    ) -> Result<JsonRpcResponse, ToolError> {
         let byte_code = format!(
             "0x{}",
             encode(
@@ -339,11 +1018,96 @@ impl Client {
                 .map_err(ToolError::Proto)?
             )
         );
+        if self.dry_run {
+            return Ok(JsonRpcResponse::from_result(ResponseValue::Singe(
+                ParamsValue::String(byte_code),
+            )));
+        }
         let params = JsonRpcParams::new()
+            .insert("method", self.method(KnownMethod::SendRawTransaction))
             .insert(
-                "method",
-                ParamsValue::String(String::from(SEND_RAW_TRANSACTION)),
-            )
+                "params",
+                ParamsValue::List(vec![ParamsValue::String(byte_code)]),
+            );
+        Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
+    }
+
+    /// Send the transaction signed by `TransactionBuilder`, resend the same bytes up to
+    /// `retries` times if the request fails on the network
+    ///
+    /// A request lost on the way back may have reached the node, so a resent
+    /// transaction rejected as a duplicate is taken as sent.
+    ///
+    /// `send_raw_transaction` passes no retries, so a failed send is reported at once.
+    /// Callers that build the transaction with `TransactionBuilder::build` themselves,
+    /// such as long running services on an unreliable network, pass the retries they accept.
+    pub fn submit_transaction(
+        &mut self,
+        tx: &RawTransaction,
+        retries: u32,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        // Checked once, the resends of the same transaction follow right after
+        self.check_partition()?;
+        let mut attempt = 0;
+        let response = loop {
+            match self.send_signed_transaction_unchecked(tx.hex()) {
+                Err(ToolError::Hyper(err)) if attempt < retries => {
+                    attempt += 1;
+                    warn!(
+                        "Failed to send transaction 0x{:x}: {}, retry {}/{}",
+                        tx.hash(),
+                        err,
+                        attempt,
+                        retries
+                    );
+                    thread::sleep(SUBMIT_RETRY_DELAY);
+                }
+                Ok(ref response) if attempt > 0 && is_duplicate(response) => {
+                    let mut result = HashMap::new();
+                    result.insert(
+                        String::from("hash"),
+                        ParamsValue::String(format!("0x{:x}", tx.hash())),
+                    );
+                    result.insert(
+                        String::from("status"),
+                        ParamsValue::String("OK".to_string()),
+                    );
+                    break JsonRpcResponse::from_result(ResponseValue::Map(result));
+                }
+                result => break result?,
+            }
+        };
+        if let (true, Some(private_key)) = (response.is_ok(), self.private_key.as_ref()) {
+            self.nonce_manager
+                .track(&format!("0x{:x}", tx.hash()), tx.nonce(), private_key);
+        }
+        Ok(response)
+    }
+
+    /// Drop the cached queries of the contract, a transaction is sent to it
+    pub(crate) fn invalidate_cached(&self, address: &str) {
+        if let Some(ref cache) = self.cache {
+            cache.lock().unwrap().invalidate(address);
+        }
+    }
+
+    /// Send unsigned transactions, in dry run mode the signed transaction is returned instead
+    pub fn send_transaction(&mut self, param: &str) -> Result<JsonRpcResponse, ToolError> {
+        self.check_partition()?;
+        let tx: Transaction = parse_from_bytes(
+            decode(remove_0x(param))
+                .map_err(ToolError::Decode)?
+                .as_slice(),
+        )
+        .map_err(ToolError::Proto)?;
+        let byte_code = self.generate_sign_transaction(&tx)?;
+        if self.dry_run {
+            return Ok(JsonRpcResponse::from_result(ResponseValue::Singe(
+                ParamsValue::String(byte_code),
+            )));
+        }
+        let params = JsonRpcParams::new()
+            .insert("method", self.method(KnownMethod::SendRawTransaction))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(byte_code)]),
@@ -351,82 +1115,854 @@ impl Client {
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
-    /// Send unsigned transactions
-    pub fn send_transaction(&mut self, param: &str) -> Result<JsonRpcResponse, ToolError> {
-        let tx: Transaction = parse_from_bytes(
-            decode(remove_0x(param))
-                .map_err(ToolError::Decode)?
-                .as_slice(),
-        )
-        .map_err(ToolError::Proto)?;
-        let byte_code = self.generate_sign_transaction(&tx)?;
+    /// Get chain id
+    pub fn get_chain_id(&mut self) -> Result<u32, ToolError> {
+        if self.chain_id.is_some() && self.check_chain_id() {
+            Ok(self.chain_id.unwrap().low_u32())
+        } else if let Some(chain_id) = self
+            .get_metadata("latest")?
+            .result()
+            .as_ref()
+            .and_then(ResponseValue::as_map)
+            .and_then(|value| value.get("chainId"))
+            .and_then(ParamsValue::as_u64)
+        {
+            self.chain_id = Some(U256::from(chain_id));
+            Ok(chain_id as u32)
+        } else {
+            Ok(0)
+        }
+    }
+
+    #[inline]
+    fn check_chain_id(&self) -> bool {
+        self.chain_id
+            .map(|id| id > U256::from(u32::max_value()))
+            .unwrap_or(false)
+    }
+
+    /// Get chain id v1
+    pub fn get_chain_id_v1(&mut self) -> Result<U256, ToolError> {
+        if self.chain_id.is_some() {
+            Ok(self.chain_id.unwrap())
+        } else if let Some(chain_id) =
+            self.get_metadata("latest")?
+                .result()
+                .and_then(|value| match value {
+                    ResponseValue::Map(mut value) => value.remove("chainIdV1"),
+                    _ => None,
+                })
+        {
+            let chain_id = chain_id.into_u256()?;
+            self.chain_id = Some(chain_id);
+            Ok(chain_id)
+        } else {
+            Ok(U256::zero())
+        }
+    }
+
+    /// Get chain metadata, default height is "latest", the chain id is cached
+    pub fn get_chain_metadata(&mut self, height: Option<&str>) -> Result<ChainMetadata, ToolError> {
+        let metadata = ChainMetadata::try_from(self.get_metadata(height.unwrap_or("latest"))?)?;
+        self.chain_id = Some(if metadata.version == 0 {
+            U256::from(metadata.chain_id)
+        } else {
+            metadata.chain_id_v1
+        });
+        Ok(metadata)
+    }
+
+    /// Read the transaction version and chain id the chain expects from `getMetaData`,
+    /// the result is kept for `TransactionBuilder`, and the chain id is cached
+    pub fn negotiate_chain_version(&mut self) -> Result<ChainVersion, ToolError> {
+        let version = ChainVersion::from(&self.get_chain_metadata(None)?);
+        self.negotiated_version = Some(version);
+        Ok(version)
+    }
+
+    /// The result of the last `negotiate_chain_version`
+    pub fn negotiated_version(&self) -> Option<ChainVersion> {
+        self.negotiated_version
+    }
+
+    /// Get the chain status, the height, metadata and system contract queries
+    /// are sent at the same time, then the block quota limit at the height is queried,
+    /// the chain id is cached
+    #[cfg(feature = "quota")]
+    pub fn chain_status(&mut self) -> Result<ChainStatus, ToolError> {
+        let system_calls: [(Address, &Contract, &str); 3] = [
+            (NODE_MANAGER, &*NODE_MANAGER_CONTRACT, "listNode"),
+            (PRICE_MANAGER, &*PRICE_MANAGER_CONTRACT, "getQuotaPrice"),
+            (EMERGENCY_BRAKE, &*EMERGENCY_BRAKE_CONTRACT, "state"),
+        ];
+        let mut params = vec![
+            JsonRpcParams::new().insert("method", self.method(KnownMethod::BlockNumber)),
+            JsonRpcParams::new()
+                .insert(
+                    "params",
+                    ParamsValue::List(vec![ParamsValue::String(String::from("latest"))]),
+                )
+                .insert("method", self.method(KnownMethod::GetMetaData)),
+        ];
+        for (address, contract, function) in system_calls.iter() {
+            let data = contract_encode_input(contract, function, &[], false)?;
+            let mut object = HashMap::new();
+            object.insert(
+                String::from("to"),
+                ParamsValue::String(address.completed_lower_hex_with_0x()),
+            );
+            object.insert(
+                String::from("data"),
+                ParamsValue::String(format!("0x{}", data)),
+            );
+            params.push(
+                JsonRpcParams::new()
+                    .insert("method", self.method(KnownMethod::Call))
+                    .insert(
+                        "params",
+                        ParamsValue::List(vec![
+                            ParamsValue::Map(object),
+                            ParamsValue::String(String::from("latest")),
+                        ]),
+                    ),
+            );
+        }
+
+        let mut responses = self.send_request(params.into_iter())?.into_iter();
+        let block_number = u64::try_from(responses.next().unwrap())?;
+        let metadata = ChainMetadata::try_from(responses.next().unwrap())?;
+        self.chain_id = Some(if metadata.version == 0 {
+            U256::from(metadata.chain_id)
+        } else {
+            metadata.chain_id_v1
+        });
+        let outputs = system_calls
+            .iter()
+            .zip(responses)
+            .map(|((_, contract, function), response)| {
+                contract_decode_output(contract, function, &response.into_hex_result()?)
+            })
+            .collect::<Result<Vec<Vec<Token>>, ToolError>>()?;
+        let block_quota_limit = QuotaManageClient::create(self.clone())
+            .get_bql_u64(Some(&format!("{:#x}", block_number)))?;
+
+        match (&outputs[0][..], &outputs[1][..], &outputs[2][..]) {
+            (
+                [Token::Array(nodes)],
+                [Token::Uint(quota_price)],
+                [Token::Bool(emergency_brake_active)],
+            ) => {
+                let mut quota_price_bytes = [0u8; 32];
+                quota_price.to_big_endian(&mut quota_price_bytes);
+                Ok(ChainStatus {
+                    block_number,
+                    metadata,
+                    node_count: nodes.len() as u64,
+                    block_quota_limit,
+                    quota_price: U256::from(&quota_price_bytes[..]),
+                    emergency_brake_active: *emergency_brake_active,
+                })
+            }
+            _ => Err(ToolError::Abi(format!(
+                "Unexpected system contract outputs: {:?}",
+                outputs
+            ))),
+        }
+    }
+
+    /// Get the software version of the node
+    ///
+    /// Nodes without `getVersion` fall back to the protocol version of `getMetaData`,
+    /// as the major version.
+    pub fn get_node_version(&self) -> Result<Version, ToolError> {
+        let response = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_version(self)?;
+        let result = match response.into_result() {
+            Err(ToolError::RpcError { .. }) => {
+                let response = self.get_metadata("latest")?;
+                return Ok(Version {
+                    major: ChainMetadata::try_from(response)?.version,
+                    ..Default::default()
+                });
+            }
+            result => result?,
+        };
+        match result {
+            ResponseValue::Map(ref version) => match version.get("softwareVersion") {
+                Some(ParamsValue::String(version)) => version.parse(),
+                _ => Err(ToolError::Customize(format!(
+                    "Unexpected version: {}",
+                    result
+                ))),
+            },
+            _ => Err(ToolError::Customize(format!(
+                "Unexpected version: {}",
+                result
+            ))),
+        }
+    }
+
+    /// Get block height
+    pub fn get_current_height(&self) -> Result<u64, ToolError> {
+        let params = JsonRpcParams::new().insert("method", self.method(KnownMethod::BlockNumber));
+        let response = self.send_request(vec![params].into_iter())?.pop().unwrap();
+
+        if let Some(ResponseValue::Singe(ParamsValue::String(height))) = response.result() {
+            Ok(u64::from_str_radix(remove_0x(&height), 16).map_err(ToolError::Parse)?)
+        } else {
+            Err(ToolError::Customize(
+                "Corresponding address does not respond".to_string(),
+            ))
+        }
+    }
+
+    /// Get transaction count of an account, default height is "latest"
+    pub fn get_transaction_count_u64(
+        &self,
+        address: &str,
+        height: Option<&str>,
+    ) -> Result<u64, ToolError> {
+        u64::try_from(self.get_transaction_count(address, height.unwrap_or("latest"))?)
+    }
+
+    /// Get the ABI stored by `StoreExt::store_abi`, empty if none, default height is "latest"
+    pub fn get_abi_text(&self, address: &str, height: Option<&str>) -> Result<String, ToolError> {
+        let abi = self
+            .get_abi(address, height.unwrap_or("latest"))?
+            .into_hex_result()?;
+        String::from_utf8(decode(abi).map_err(ToolError::Decode)?)
+            .map_err(|err| ToolError::Abi(format!("ABI is not UTF-8: {}", err)))
+    }
+
+    /// Sum the `quotaUsed` of the receipts of all transactions in the block
+    pub fn get_block_quota_used(&self, height: u64) -> Result<u64, ToolError> {
+        let block = self
+            .get_block_by_number(&format!("{:#x}", height), false)?
+            .into_result()?;
+        let hashes = block
+            .as_map()
+            .and_then(|block| block.get("body"))
+            .and_then(ParamsValue::as_map)
+            .and_then(|body| body.get("transactions"))
+            .ok_or_else(|| ToolError::Customize(format!("Block {} has no body", height)))?;
+        let params = match hashes {
+            ParamsValue::List(hashes) => hashes
+                .iter()
+                .map(|hash| {
+                    let hash = hash.as_str().ok_or_else(|| {
+                        ToolError::Customize(format!("{} is not a transaction hash", hash))
+                    })?;
+                    Ok(JsonRpcParams::new()
+                        .insert("method", self.method(KnownMethod::GetTransactionReceipt))
+                        .insert(
+                            "params",
+                            ParamsValue::List(vec![ParamsValue::String(String::from(hash))]),
+                        ))
+                })
+                .collect::<Result<Vec<JsonRpcParams>, ToolError>>()?,
+            _ => Vec::new(),
+        };
+        if params.is_empty() {
+            return Ok(0);
+        }
+
+        self.send_request(params.into_iter())?
+            .into_iter()
+            .try_fold(0u64, |total, response| {
+                let quota_used = response
+                    .into_result()?
+                    .as_map()
+                    .and_then(|receipt| receipt.get("quotaUsed"))
+                    .and_then(ParamsValue::as_u64)
+                    .ok_or_else(|| ToolError::Customize("Receipt has no quotaUsed".to_string()))?;
+                Ok(total.saturating_add(quota_used))
+            })
+    }
+
+    /// The `quotaUsed` of the block header and the number of transactions of every block
+    /// from `start` to `end`, requested at the same time
+    pub(crate) fn get_blocks_quota_used(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<(u64, usize)>, ToolError> {
+        let params = (start..=end).map(|height| {
+            JsonRpcParams::new()
+                .insert("method", self.method(KnownMethod::GetBlockByNumber))
+                .insert(
+                    "params",
+                    ParamsValue::List(vec![
+                        ParamsValue::String(format!("{:#x}", height)),
+                        ParamsValue::Bool(false),
+                    ]),
+                )
+        });
+        self.send_request(params)?
+            .into_iter()
+            .map(|response| {
+                let block = response.into_result()?;
+                let block = block
+                    .as_map()
+                    .ok_or_else(|| ToolError::Customize(format!("{} is not a block", block)))?;
+                let quota_used = block
+                    .get("header")
+                    .and_then(ParamsValue::as_map)
+                    .and_then(|header| header.get("quotaUsed"))
+                    .and_then(ParamsValue::as_u64)
+                    .ok_or_else(|| ToolError::Customize("Block has no quotaUsed".to_string()))?;
+                let transactions = match block
+                    .get("body")
+                    .and_then(ParamsValue::as_map)
+                    .and_then(|body| body.get("transactions"))
+                {
+                    Some(ParamsValue::List(transactions)) => transactions.len(),
+                    _ => 0,
+                };
+                Ok((quota_used, transactions))
+            })
+            .collect()
+    }
+
+    /// The fraction of the block quota limit used by the block
+    #[cfg(feature = "quota")]
+    pub fn block_quota_utilization(&self, height: u64) -> Result<f64, ToolError> {
+        let quota_used = self.get_block_quota_used(height)?;
+        let limit =
+            QuotaManageClient::create(self.clone()).get_bql_u64(Some(&format!("{:#x}", height)))?;
+        if limit == 0 {
+            return Err(ToolError::Abi("Invalid block quota limit: 0".to_string()));
+        }
+        Ok(quota_used as f64 / limit as f64)
+    }
+
+    /// Get logs matching the filter
+    pub fn get_filtered_logs(&self, filter: LogFilter) -> Result<Vec<Log>, ToolError> {
+        let params = JsonRpcParams::new()
+            .insert("method", self.method(KnownMethod::GetLogs))
+            .insert("params", ParamsValue::List(vec![filter.into_params()]));
+        let response = self.send_request(vec![params].into_iter())?.pop().unwrap();
+
+        match response.into_result()? {
+            ResponseValue::Singe(ParamsValue::List(logs)) => {
+                logs.iter().map(Log::try_from).collect()
+            }
+            result => Err(ToolError::Customize(format!(
+                "Unexpected response: {}",
+                result
+            ))),
+        }
+    }
+
+    /// Get the receipt of the transaction, `None` if it's not in a block yet
+    pub fn get_receipt(&self, hash: &str) -> Result<Option<TransactionReceipt>, ToolError> {
+        match <Client as ClientExt<JsonRpcResponse, ToolError>>::get_transaction_receipt(
+            self, hash,
+        )?
+        .into_result()
+        {
+            Ok(ResponseValue::Singe(ParamsValue::Null)) | Err(ToolError::NullResult) => Ok(None),
+            Ok(ResponseValue::Map(receipt)) => {
+                TransactionReceipt::try_from(&ParamsValue::Map(receipt)).map(Some)
+            }
+            Ok(result) => Err(ToolError::Customize(format!(
+                "Unexpected receipt: {}",
+                result
+            ))),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Trace the execution of the transaction with `debug_traceTransaction`,
+    /// only the nodes with the debug API enabled answer it
+    #[cfg(feature = "debug-api")]
+    pub fn trace_transaction(&self, tx_hash: &str) -> Result<TransactionTrace, ToolError> {
+        let params = JsonRpcParams::new()
+            .insert(
+                "method",
+                ParamsValue::String(String::from(TRACE_TRANSACTION)),
+            )
+            .insert(
+                "params",
+                ParamsValue::List(vec![ParamsValue::String(String::from(tx_hash))]),
+            );
+        self.trace(params)
+    }
+
+    /// Trace the call at the height with `debug_traceCall`, without sending
+    /// a transaction, only the nodes with the debug API enabled answer it
+    #[cfg(feature = "debug-api")]
+    pub fn trace_call(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        data: Option<&str>,
+        value: Option<U256>,
+        quota: Option<u64>,
+        height: &str,
+    ) -> Result<TransactionTrace, ToolError> {
+        let mut object = call_object(from, to, data)?;
+        if let Some(value) = value {
+            object.insert(
+                String::from("value"),
+                ParamsValue::String(value.lower_hex_with_0x()),
+            );
+        }
+        if let Some(quota) = quota {
+            object.insert(
+                String::from("gas"),
+                ParamsValue::String(format!("{:#x}", quota)),
+            );
+        }
+        let params = JsonRpcParams::new()
+            .insert("method", ParamsValue::String(String::from(TRACE_CALL)))
+            .insert(
+                "params",
+                ParamsValue::List(vec![
+                    ParamsValue::Map(object),
+                    ParamsValue::String(String::from(height)),
+                ]),
+            );
+        self.trace(params)
+    }
+
+    /// Replay the committed transaction as a call traced by the debug node,
+    /// at the state of the height, by default the block before the transaction
+    ///
+    /// The transactions before it in its block are not replayed, their changes
+    /// are missing from the state.
+    #[cfg(feature = "debug-api")]
+    pub fn replay_transaction(
+        &self,
+        tx_hash: &str,
+        debug_node: &Client,
+        height: Option<u64>,
+    ) -> Result<TransactionTrace, ToolError> {
+        let result =
+            <Client as ClientExt<JsonRpcResponse, ToolError>>::get_transaction(self, tx_hash)?
+                .into_result()?;
+        let committed = match result {
+            ResponseValue::Map(committed) => committed,
+            other => {
+                return Err(ToolError::Customize(format!(
+                    "Unexpected transaction: {}",
+                    other
+                )))
+            }
+        };
+        let field = |key: &str| {
+            committed
+                .get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Transaction has no {}", key)))
+        };
+        let from = field("from")?.as_address().ok_or_else(|| {
+            ToolError::Customize("Transaction from is not an address".to_string())
+        })?;
+        let height = match height {
+            Some(height) => height,
+            None => field("blockNumber")?
+                .as_u64()
+                .ok_or_else(|| {
+                    ToolError::Customize("Transaction block number is not a number".to_string())
+                })?
+                .saturating_sub(1),
+        };
+        let content = field("content")?
+            .as_str()
+            .ok_or_else(|| ToolError::Customize("Transaction content is not hex".to_string()))?;
+        let unverified = UnverifiedTransaction::from_str(content)?;
+        let tx = unverified.get_transaction();
+
+        let to = if tx.get_version() == 0 {
+            format!("0x{}", remove_0x(tx.get_to()))
+        } else if tx.get_to_v1().is_empty() {
+            String::from("0x")
+        } else {
+            Address::from(tx.get_to_v1()).completed_lower_hex_with_0x()
+        };
+        debug_node.trace_call(
+            Some(&from.completed_lower_hex_with_0x()),
+            &to,
+            Some(&format!("0x{}", encode(tx.get_data()))),
+            Some(U256::from(tx.get_value())),
+            Some(tx.get_quota()),
+            &format!("{:#x}", height),
+        )
+    }
+
+    /// Send the trace request, parse the trace
+    #[cfg(feature = "debug-api")]
+    fn trace(&self, params: JsonRpcParams) -> Result<TransactionTrace, ToolError> {
+        let result = self
+            .send_request(vec![params].into_iter())?
+            .pop()
+            .unwrap()
+            .into_result()?;
+        serde_json::to_value(&result)
+            .and_then(serde_json::from_value)
+            .map_err(ToolError::SerdeJson)
+    }
+
+    /// Stream of the headers of the new blocks, by the `newBlockHeaders` subscription
+    /// over WebSocket, see `set_ws_uri`
+    ///
+    /// The stream doesn't end, `ToolError::Reconnecting` is yielded every time
+    /// the connection is lost, the headers continue after reconnected.
+    #[cfg(feature = "websocket")]
+    pub fn subscribe_block_headers(
+        &self,
+    ) -> Result<impl Stream<Item = Result<BlockHeader, ToolError>, Error = ()>, ToolError> {
+        subscription::subscribe_block_headers(self.ws_url())
+    }
+
+    /// Stream of the hashes of the transactions entering the pool, by the
+    /// `newPendingTransactions` subscription over WebSocket, see `set_ws_uri`
+    ///
+    /// Not every node publishes the pending transactions, the subscription is
+    /// checked when subscribing: `ToolError::Unsupported` if the node rejects it.
+    /// Like `subscribe_block_headers`, `ToolError::Reconnecting` is yielded every
+    /// time the connection is lost.
+    #[cfg(feature = "websocket")]
+    pub fn subscribe_pending_transactions(
+        &self,
+    ) -> Result<impl Stream<Item = Result<H256, ToolError>, Error = ()>, ToolError> {
+        subscription::subscribe_pending_transactions(self.ws_url())
+    }
+
+    /// WebSocket url of the subscriptions
+    #[cfg(feature = "websocket")]
+    pub(crate) fn ws_url(&self) -> String {
+        match self.ws_url {
+            Some(ref url) => url.clone(),
+            None => {
+                let url = self.url.to_string();
+                if url.starts_with("https://") {
+                    format!("wss://{}", &url[8..])
+                } else if url.starts_with("http://") {
+                    format!("ws://{}", &url[7..])
+                } else {
+                    url
+                }
+            }
+        }
+    }
+
+    /// Get the header of the block at the height, parsed from `getBlockByNumber`
+    /// without transactions, since `getBlockHeader` answers the RLP encoded header
+    /// without the block hash
+    pub fn get_decoded_block_header(&self, height: &str) -> Result<BlockHeader, ToolError> {
+        let block = self.get_block_by_number(height, false)?.into_result()?;
+        match block {
+            ResponseValue::Map(block) => BlockHeader::try_from(&ParamsValue::Map(block)),
+            block => Err(ToolError::Customize(format!("Unexpected block: {}", block))),
+        }
+    }
+
+    /// Get the BFT proof of the block at the height, carried by the next block,
+    /// see `verify_block_proof`
+    pub fn get_block_proof(&self, height: u64) -> Result<BlockProof, ToolError> {
+        let next = format!("{:#x}", height + 1);
+        let block = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_block_by_number(
+            self, &next, false,
+        )?
+        .into_result()?;
+        let proof = match block {
+            ResponseValue::Map(block) => BlockProof::try_from(&ParamsValue::Map(block))?,
+            block => return Err(ToolError::Customize(format!("Unexpected block: {}", block))),
+        };
+        if proof.height != height {
+            return Err(ToolError::Customize(format!(
+                "Block {} has the proof of block {}",
+                next, proof.height
+            )));
+        }
+        Ok(proof)
+    }
+
+    /// Get the account and the storage values of the keys with their Merkle proofs
+    /// at the height, with `eth_getProof` of EIP-1186, see `verify_proof`
+    pub fn get_proof(
+        &self,
+        address: &str,
+        storage_keys: Vec<H256>,
+        height: u64,
+    ) -> Result<AccountProof, ToolError> {
+        let keys = storage_keys
+            .into_iter()
+            .map(|key| ParamsValue::String(key.completed_lower_hex_with_0x()))
+            .collect();
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(SEND_RAW_TRANSACTION)),
-            )
+            .insert("method", ParamsValue::String(String::from(GET_PROOF)))
             .insert(
                 "params",
-                ParamsValue::List(vec![ParamsValue::String(byte_code)]),
+                ParamsValue::List(vec![
+                    ParamsValue::address(address)?,
+                    ParamsValue::List(keys),
+                    ParamsValue::String(format!("{:#x}", height)),
+                ]),
             );
-        Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
+        match self
+            .send_request(vec![params].into_iter())?
+            .pop()
+            .unwrap()
+            .into_result()?
+        {
+            ResponseValue::Map(proof) => AccountProof::try_from(&ParamsValue::Map(proof)),
+            proof => Err(ToolError::Customize(format!(
+                "Unexpected account proof: {}",
+                proof
+            ))),
+        }
     }
 
-    /// Get chain id
-    pub fn get_chain_id(&mut self) -> Result<u32, ToolError> {
-        if self.chain_id.is_some() && self.check_chain_id() {
-            Ok(self.chain_id.unwrap().low_u32())
-        } else if let Some(ResponseValue::Map(mut value)) = self.get_metadata("latest")?.result() {
-            match value.remove("chainId").unwrap() {
-                ParamsValue::Int(chain_id) => {
-                    self.chain_id = Some(U256::from(chain_id));
-                    Ok(chain_id as u32)
+    /// Whether the logs of the block may be emitted by the address, checked by
+    /// the logs bloom of the block header, so that blocks which surely have no
+    /// log of the address can be skipped. Blocks without a bloom before CITA 2.0
+    /// always may contain the address.
+    pub fn block_may_contain_address(
+        &self,
+        height: u64,
+        address: &Address,
+    ) -> Result<bool, ToolError> {
+        let block = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_block_by_number(
+            self,
+            &format!("{:#x}", height),
+            false,
+        )?
+        .into_result()?;
+        let bloom = block
+            .as_map()
+            .and_then(|block| block.get("header"))
+            .and_then(ParamsValue::as_map)
+            .and_then(|header| header.get("logsBloom"));
+        match bloom {
+            Some(ParamsValue::Null) | None => Ok(true),
+            Some(bloom) => Ok(contains_address(&parse_bloom(bloom)?, address)),
+        }
+    }
+
+    /// Deploy a contract signed by the private key, wait for the receipt,
+    /// and return the address of the new contract
+    pub fn deploy_contract(
+        &mut self,
+        bytecode: &str,
+        abi: &Contract,
+        constructor_args: &[Token],
+        private_key: &PrivateKey,
+        quota: Option<u64>,
+    ) -> Result<Address, ToolError> {
+        let code = decode(remove_0x(bytecode)).map_err(ToolError::Decode)?;
+        let code = match abi.constructor {
+            Some(ref constructor) => constructor
+                .encode_input(code, constructor_args)
+                .map_err(|e| ToolError::Abi(e.to_string()))?,
+            None if constructor_args.is_empty() => code,
+            None => return Err(ToolError::Abi("No constructor on abi".to_string())),
+        };
+        let code = format!("0x{}", encode(code));
+
+        let previous_key = self.private_key.clone();
+        self.set_private_key(private_key);
+        let response = self.get_current_height().and_then(|height| {
+            self.send_raw_transaction(
+                TransactionOptions::new()
+                    .set_code(&code)
+                    .set_current_height(Some(height))
+                    .set_quota(quota),
+            )
+            .map(|response| (height, response))
+        });
+        self.private_key = previous_key;
+        let (current_height, response) = response?;
+
+        let hash = match response.into_result()? {
+            ResponseValue::Map(mut result) => result.remove("hash"),
+            _ => None,
+        }
+        .ok_or_else(|| ToolError::Customize("Deploy transaction has no hash".to_string()))?
+        .into_string()?;
+
+        // The transaction is only valid until `current_height + 88`
+        let deadline = current_height + 88;
+        loop {
+            thread::sleep(Duration::from_secs(1));
+            if let Some(ResponseValue::Map(mut receipt)) =
+                self.get_transaction_receipt(&hash)?.result()
+            {
+                self.nonce_manager.mark_confirmed(&hash);
+                if let Some(ParamsValue::String(error_message)) = receipt.remove("errorMessage") {
+                    return Err(ToolError::DeployFailed { error_message });
                 }
-                _ => Ok(0),
+                return receipt
+                    .get("contractAddress")
+                    .and_then(ParamsValue::as_address)
+                    .ok_or_else(|| {
+                        ToolError::Customize("Receipt has no contract address".to_string())
+                    });
+            }
+            if self.get_current_height()? > deadline {
+                self.nonce_manager.forget(&hash);
+                return Err(ToolError::Customize(format!(
+                    "Transaction {} is not committed before block {}",
+                    hash, deadline
+                )));
             }
-        } else {
-            Ok(0)
         }
     }
 
-    #[inline]
-    fn check_chain_id(&self) -> bool {
-        self.chain_id
-            .map(|id| id > U256::from(u32::max_value()))
-            .unwrap_or(false)
+    /// Export blocks `start..=end` as JSON lines, blocks are fetched concurrently in batches.
+    /// Return the count of blocks written, lines already written are flushed even on error
+    pub fn export_blocks<W: Write>(
+        &self,
+        start: u64,
+        end: u64,
+        include_txs: bool,
+        batch_size: u64,
+        writer: &mut W,
+    ) -> Result<u64, ToolError> {
+        let mut count = 0;
+        let result = self.write_blocks(
+            start,
+            end,
+            include_txs,
+            batch_size.max(1),
+            writer,
+            &mut count,
+        );
+        let flushed = writer.flush().map_err(ToolError::Stdio);
+        result.and(flushed).map(|_| count)
     }
 
-    /// Get chain id v1
-    pub fn get_chain_id_v1(&mut self) -> Result<U256, ToolError> {
-        if self.chain_id.is_some() {
-            Ok(self.chain_id.unwrap())
-        } else if let Some(ResponseValue::Map(mut value)) = self.get_metadata("latest")?.result() {
-            match value.remove("chainIdV1") {
-                Some(ParamsValue::String(chain_id)) => {
-                    let chain_id = U256::from_str(remove_0x(&chain_id))
-                        .map_err(|e| ToolError::Customize(e.to_string()))?;
-                    self.chain_id = Some(chain_id);
-                    Ok(chain_id)
+    fn write_blocks<W: Write>(
+        &self,
+        start: u64,
+        end: u64,
+        include_txs: bool,
+        batch_size: u64,
+        writer: &mut W,
+        count: &mut u64,
+    ) -> Result<(), ToolError> {
+        self.fetch_blocks(start, end, include_txs, batch_size, |block| {
+            serde_json::to_writer(&mut *writer, &block).map_err(ToolError::SerdeJson)?;
+            writer.write_all(b"\n").map_err(ToolError::Stdio)?;
+            *count += 1;
+            Ok(())
+        })
+    }
+
+    /// Statistics of blocks `start..=end`, such as the quota used and the block time,
+    /// blocks are fetched concurrently in batches
+    pub fn block_stats(
+        &self,
+        start: u64,
+        end: u64,
+        batch_size: u64,
+    ) -> Result<BlockStats, ToolError> {
+        if start > end {
+            return Err(ToolError::Customize(format!(
+                "Invalid block range {} to {}",
+                start, end
+            )));
+        }
+        let mut blocks = Vec::new();
+        self.fetch_blocks(start, end, false, batch_size.max(1), |block| match block {
+            ResponseValue::Map(block) => {
+                blocks.push(ParamsValue::Map(block));
+                Ok(())
+            }
+            block => Err(ToolError::Customize(format!("Unexpected block: {}", block))),
+        })?;
+        BlockStats::from_blocks(&blocks)
+    }
+
+    /// Call the handler with every block `start..=end` in order, a batch of blocks
+    /// is requested at the same time
+    fn fetch_blocks<F>(
+        &self,
+        start: u64,
+        end: u64,
+        include_txs: bool,
+        batch_size: u64,
+        mut handler: F,
+    ) -> Result<(), ToolError>
+    where
+        F: FnMut(ResponseValue) -> Result<(), ToolError>,
+    {
+        let mut from = start;
+        while from <= end {
+            let to = end.min(from.saturating_add(batch_size - 1));
+            let params = (from..=to).map(|height| {
+                JsonRpcParams::new()
+                    .insert("method", self.method(KnownMethod::GetBlockByNumber))
+                    .insert(
+                        "params",
+                        ParamsValue::List(vec![
+                            ParamsValue::String(format!("{:#x}", height)),
+                            ParamsValue::Bool(include_txs),
+                        ]),
+                    )
+            });
+            for response in self.send_request(params)? {
+                handler(response.into_result()?)?;
+            }
+            match to.checked_add(1) {
+                Some(next) => from = next,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Call the handler with every block from `start_height` in order, and poll for new blocks.
+    /// Stop when the handler or a request fails. The next height to process is saved
+    /// to `progress`, so the loop can be resumed from it
+    pub fn process_blocks<F>(
+        &self,
+        start_height: u64,
+        poll_interval: Duration,
+        mut progress: Option<&mut u64>,
+        mut handler: F,
+    ) -> Result<(), ToolError>
+    where
+        F: FnMut(ResponseValue) -> Result<(), ToolError>,
+    {
+        let mut height = start_height;
+        loop {
+            let current_height = self.get_current_height()?;
+            while height <= current_height {
+                let block = self
+                    .get_block_by_number(&format!("{:#x}", height), true)?
+                    .into_result()?;
+                handler(block)?;
+                height += 1;
+                if let Some(progress) = progress.as_mut() {
+                    **progress = height;
                 }
-                _ => Ok(U256::zero()),
             }
-        } else {
-            Ok(U256::zero())
+            thread::sleep(poll_interval);
         }
     }
 
-    /// Get block height
-    pub fn get_current_height(&self) -> Result<u64, ToolError> {
-        let params =
-            JsonRpcParams::new().insert("method", ParamsValue::String(String::from(BLOCK_NUMBER)));
-        let response = self.send_request(vec![params].into_iter())?.pop().unwrap();
+    /// Poll the block number every `interval`, yield every new one,
+    /// the ones not greater than the last yielded are skipped
+    pub fn poll_block_number(&self, interval: Duration) -> BlockNumbers<'_> {
+        BlockNumbers {
+            client: self,
+            interval,
+            polled: false,
+            last: None,
+            remaining: None,
+        }
+    }
 
-        if let Some(ResponseValue::Singe(ParamsValue::String(height))) = response.result() {
-            Ok(u64::from_str_radix(remove_0x(&height), 16).map_err(ToolError::Parse)?)
-        } else {
-            Err(ToolError::Customize(
-                "Corresponding address does not respond".to_string(),
-            ))
+    /// Poll the transaction every `interval`, yield its state when it changes,
+    /// end after it is mined or failed
+    pub fn watch_transaction(&self, hash: &str, interval: Duration) -> TransactionStates<'_> {
+        TransactionStates {
+            client: self,
+            hash: hash.to_string(),
+            interval,
+            polled: false,
+            last_pending: None,
+            done: false,
         }
     }
 
@@ -448,13 +1984,11 @@ impl Client {
     }
 
     /// Start run
-    fn run(
+    fn run<T: Send + 'static>(
         &self,
-        reqs: JoinAll<
-            Vec<Box<dyn Future<Item = JsonRpcResponse, Error = ToolError> + 'static + Send>>,
-        >,
-    ) -> Result<Vec<JsonRpcResponse>, ToolError> {
-        let (tx, rx) = sync::oneshot::channel::<Result<Vec<JsonRpcResponse>, ToolError>>();
+        reqs: Box<dyn Future<Item = T, Error = ToolError> + 'static + Send>,
+    ) -> Result<T, ToolError> {
+        let (tx, rx) = sync::oneshot::channel::<Result<T, ToolError>>();
         let req = reqs
             .then(move |res| tx.send(res))
             .map(|_| ())
@@ -477,10 +2011,27 @@ impl Clone for Client {
         Client {
             id: AtomicUsize::new(self.id.load(Ordering::Relaxed)),
             url: self.url.clone(),
+            http: self.http.clone(),
             sender: self.sender.clone(),
             chain_id: None,
-            private_key: self.private_key,
+            private_key: self.private_key.clone(),
+            nonce_manager: self.nonce_manager.clone(),
+            headers: self.headers.clone(),
+            max_concurrent: self.max_concurrent,
+            cache: self.cache.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            consensus_urls: self.consensus_urls.clone(),
+            divergence_threshold: self.divergence_threshold,
+            shutdown: Arc::clone(&self.shutdown),
+            otel_context: self.otel_context.clone(),
+            chain_version: self.chain_version,
+            negotiated_version: None,
+            json_rpc_version: self.json_rpc_version,
+            canned_responses: self.canned_responses.clone(),
             debug: self.debug,
+            dry_run: self.dry_run,
+            #[cfg(feature = "websocket")]
+            ws_url: self.ws_url.clone(),
         }
     }
 }
@@ -491,6 +2042,127 @@ impl Default for Client {
     }
 }
 
+/// Iterator of new block numbers, created by `Client::poll_block_number`
+pub struct BlockNumbers<'a> {
+    client: &'a Client,
+    interval: Duration,
+    polled: bool,
+    last: Option<u64>,
+    remaining: Option<u64>,
+}
+
+impl<'a> BlockNumbers<'a> {
+    /// Stop after yielding `n` block numbers
+    pub fn stop_after(mut self, n: u64) -> Self {
+        self.remaining = Some(n);
+        self
+    }
+}
+
+impl<'a> Iterator for BlockNumbers<'a> {
+    type Item = Result<u64, ToolError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        loop {
+            if self.polled {
+                thread::sleep(self.interval);
+            }
+            self.polled = true;
+            match self.client.get_current_height() {
+                Ok(height) if self.last.map_or(false, |last| height <= last) => continue,
+                Ok(height) => {
+                    self.last = Some(height);
+                    self.remaining = self.remaining.map(|n| n - 1);
+                    return Some(Ok(height));
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// State of a transaction, yielded by `Client::watch_transaction`
+#[derive(Clone, Debug)]
+pub enum TransactionState {
+    /// Returned by `getTransaction` but without a receipt yet
+    Pending,
+    /// Mined, the receipt has no error
+    Mined(TransactionReceipt),
+    /// Unknown to the node, nodes don't expose their transaction pool,
+    /// so a transaction waiting in the pool is also not found
+    NotFound,
+    /// Mined with the error message of the receipt
+    Failed(String),
+}
+
+/// Iterator of the state changes of a transaction, created by `Client::watch_transaction`
+pub struct TransactionStates<'a> {
+    client: &'a Client,
+    hash: String,
+    interval: Duration,
+    polled: bool,
+    last_pending: Option<bool>,
+    done: bool,
+}
+
+impl<'a> TransactionStates<'a> {
+    fn poll(&self) -> Result<TransactionState, ToolError> {
+        if let Some(receipt) = self.client.get_receipt(&self.hash)? {
+            return Ok(match receipt.error_message.clone() {
+                Some(message) => TransactionState::Failed(message),
+                None => TransactionState::Mined(receipt),
+            });
+        }
+        match <Client as ClientExt<JsonRpcResponse, ToolError>>::get_transaction(
+            self.client,
+            &self.hash,
+        )?
+        .into_result()
+        {
+            Ok(ResponseValue::Singe(ParamsValue::Null)) | Err(ToolError::NullResult) => {
+                Ok(TransactionState::NotFound)
+            }
+            Ok(_) => Ok(TransactionState::Pending),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<'a> Iterator for TransactionStates<'a> {
+    type Item = Result<TransactionState, ToolError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            if self.polled {
+                thread::sleep(self.interval);
+            }
+            self.polled = true;
+            let state = match self.poll() {
+                Ok(state) => state,
+                Err(err) => return Some(Err(err)),
+            };
+            let pending = match state {
+                TransactionState::Pending => true,
+                TransactionState::NotFound => false,
+                TransactionState::Mined(_) | TransactionState::Failed(_) => {
+                    self.done = true;
+                    return Some(Ok(state));
+                }
+            };
+            if self.last_pending != Some(pending) {
+                self.last_pending = Some(pending);
+                return Some(Ok(state));
+            }
+        }
+    }
+}
+
 /// High level jsonrpc call
 ///
 /// [Documentation](https://cryptape.github.io/cita/zh/usage-guide/rpc/index.html)
@@ -533,6 +2205,17 @@ where
     fn get_peers_info(&self) -> Result<T, E>;
     /// blockNumber: Get current height
     fn get_block_number(&self) -> Result<T, E>;
+    /// blockNumber: Get current height as a number
+    fn get_block_number_u64(&self) -> Result<u64, E>
+    where
+        E: From<ToolError>,
+    {
+        let response = self.get_block_number()?;
+        let response: JsonRpcResponse = serde_json::to_value(&response)
+            .and_then(serde_json::from_value)
+            .map_err(ToolError::SerdeJson)?;
+        Ok(u64::try_from(response)?)
+    }
     /// sendTransaction: Send a transaction and return transaction hash
     fn send_raw_transaction(&mut self, transaction_option: TransactionOptions) -> Result<T, E>;
     /// getBlockByHash: Get block by hash
@@ -551,6 +2234,24 @@ where
     ) -> Result<T, E>;
     /// call: (readonly, will not save state change)
     fn call(&self, from: Option<&str>, to: &str, data: Option<&str>, height: &str) -> Result<T, E>;
+    /// call: Simulate a transaction on any deployed contract,
+    /// return the hex encoded output without "0x", default height is "latest"
+    fn simulate_tx(
+        &self,
+        from: &str,
+        to: &str,
+        data: &str,
+        height: Option<&str>,
+    ) -> Result<String, E>
+    where
+        E: From<ToolError>,
+    {
+        let response = self.call(Some(from), to, Some(data), height.unwrap_or("latest"))?;
+        let response: JsonRpcResponse = serde_json::to_value(&response)
+            .and_then(serde_json::from_value)
+            .map_err(ToolError::SerdeJson)?;
+        Ok(response.into_hex_result()?)
+    }
     /// getTransaction: Get transaction by hash
     fn get_transaction(&self, hash: &str) -> Result<T, E>;
     /// getTransactionCount: Get transaction count of an account
@@ -601,20 +2302,17 @@ where
 
 impl ClientExt<JsonRpcResponse, ToolError> for Client {
     fn get_peer_count(&self) -> Result<JsonRpcResponse, ToolError> {
-        let params =
-            JsonRpcParams::new().insert("method", ParamsValue::String(String::from(PEER_COUNT)));
+        let params = JsonRpcParams::new().insert("method", self.method(KnownMethod::PeerCount));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn get_peers_info(&self) -> Result<JsonRpcResponse, ToolError> {
-        let params =
-            JsonRpcParams::new().insert("method", ParamsValue::String(String::from(PEERS_INFO)));
+        let params = JsonRpcParams::new().insert("method", self.method(KnownMethod::PeersInfo));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn get_block_number(&self) -> Result<JsonRpcResponse, ToolError> {
-        let params =
-            JsonRpcParams::new().insert("method", ParamsValue::String(String::from(BLOCK_NUMBER)));
+        let params = JsonRpcParams::new().insert("method", self.method(KnownMethod::BlockNumber));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
@@ -622,9 +2320,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         &mut self,
         transaction_option: TransactionOptions,
     ) -> Result<JsonRpcResponse, ToolError> {
-        let tx = self.generate_transaction(transaction_option)?;
-        let byte_code = self.generate_sign_transaction(&tx)?;
-        Ok(self.send_signed_transaction(&byte_code)?)
+        TransactionBuilder::new(self, transaction_option).send()
     }
 
     fn get_block_by_hash(
@@ -633,10 +2329,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         transaction_info: bool,
     ) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_BLOCK_BY_HASH)),
-            )
+            .insert("method", self.method(KnownMethod::GetBlockByHash))
             .insert(
                 "params",
                 ParamsValue::List(vec![
@@ -653,10 +2346,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         transaction_info: bool,
     ) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_BLOCK_BY_NUMBER)),
-            )
+            .insert("method", self.method(KnownMethod::GetBlockByNumber))
             .insert(
                 "params",
                 ParamsValue::List(vec![
@@ -669,10 +2359,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_transaction_receipt(&self, hash: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_TRANSACTION_RECEIPT)),
-            )
+            .insert("method", self.method(KnownMethod::GetTransactionReceipt))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(hash))]),
@@ -709,11 +2396,19 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
         object.insert(
             String::from("address"),
-            serde_json::from_str::<ParamsValue>(&serde_json::to_string(&address).unwrap()).unwrap(),
+            match address {
+                Some(address) => ParamsValue::List(
+                    address
+                        .into_iter()
+                        .map(ParamsValue::address)
+                        .collect::<Result<_, _>>()?,
+                ),
+                None => ParamsValue::Null,
+            },
         );
 
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_LOGS)))
+            .insert("method", self.method(KnownMethod::GetLogs))
             .insert("params", ParamsValue::List(vec![ParamsValue::Map(object)]));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
@@ -725,36 +2420,13 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         data: Option<&str>,
         height: &str,
     ) -> Result<JsonRpcResponse, ToolError> {
-        let mut object = HashMap::new();
-
-        object.insert(String::from("to"), ParamsValue::String(String::from(to)));
-        if let Some(from) = from {
-            object.insert(
-                String::from("from"),
-                ParamsValue::String(String::from(from)),
-            );
-        }
-        if let Some(data) = data {
-            object.insert(
-                String::from("data"),
-                ParamsValue::String(String::from(data)),
-            );
-        }
-
-        let param = ParamsValue::List(vec![
-            ParamsValue::Map(object),
-            ParamsValue::String(String::from(height)),
-        ]);
-        let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(CALL)))
-            .insert("params", param);
-
+        let params = call_params(self.method(KnownMethod::Call), from, to, data, height)?;
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn get_transaction(&self, hash: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_TRANSACTION)))
+            .insert("method", self.method(KnownMethod::GetTransaction))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(hash))]),
@@ -769,14 +2441,11 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         height: &str,
     ) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_TRANSACTION_COUNT)),
-            )
+            .insert("method", self.method(KnownMethod::GetTransactionCount))
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(height)),
                 ]),
             );
@@ -786,11 +2455,11 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_code(&self, address: &str, height: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_CODE)))
+            .insert("method", self.method(KnownMethod::GetCode))
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(height)),
                 ]),
             );
@@ -800,11 +2469,11 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_abi(&self, address: &str, height: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_ABI)))
+            .insert("method", self.method(KnownMethod::GetAbi))
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(height)),
                 ]),
             );
@@ -814,11 +2483,11 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_balance(&self, address: &str, height: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_BALANCE)))
+            .insert("method", self.method(KnownMethod::GetBalance))
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(height)),
                 ]),
             );
@@ -848,29 +2517,32 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         );
         object.insert(
             String::from("address"),
-            serde_json::from_str::<ParamsValue>(&serde_json::to_string(&address).unwrap()).unwrap(),
+            match address {
+                Some(address) => ParamsValue::List(
+                    address
+                        .into_iter()
+                        .map(ParamsValue::address)
+                        .collect::<Result<_, _>>()?,
+                ),
+                None => ParamsValue::Null,
+            },
         );
 
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(NEW_FILTER)))
+            .insert("method", self.method(KnownMethod::NewFilter))
             .insert("params", ParamsValue::List(vec![ParamsValue::Map(object)]));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn new_block_filter(&self) -> Result<JsonRpcResponse, ToolError> {
-        let params = JsonRpcParams::new().insert(
-            "method",
-            ParamsValue::String(String::from(NEW_BLOCK_FILTER)),
-        );
+        let params =
+            JsonRpcParams::new().insert("method", self.method(KnownMethod::NewBlockFilter));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn uninstall_filter(&self, filter_id: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(UNINSTALL_FILTER)),
-            )
+            .insert("method", self.method(KnownMethod::UninstallFilter))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(filter_id))]),
@@ -881,10 +2553,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_filter_changes(&self, filter_id: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_FILTER_CHANGES)),
-            )
+            .insert("method", self.method(KnownMethod::GetFilterChanges))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(filter_id))]),
@@ -895,7 +2564,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_filter_logs(&self, filter_id: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(GET_FILTER_LOGS)))
+            .insert("method", self.method(KnownMethod::GetFilterLogs))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(filter_id))]),
@@ -905,10 +2574,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
 
     fn get_transaction_proof(&self, hash: &str) -> Result<JsonRpcResponse, ToolError> {
         let params = JsonRpcParams::new()
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_TRANSACTION_PROOF)),
-            )
+            .insert("method", self.method(KnownMethod::GetTransactionProof))
             .insert(
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(hash))]),
@@ -922,7 +2588,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(height))]),
             )
-            .insert("method", ParamsValue::String(String::from(GET_META_DATA)));
+            .insert("method", self.method(KnownMethod::GetMetaData));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
@@ -932,10 +2598,7 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
                 "params",
                 ParamsValue::List(vec![ParamsValue::String(String::from(height))]),
             )
-            .insert(
-                "method",
-                ParamsValue::String(String::from(GET_BLOCK_HEADER)),
-            );
+            .insert("method", self.method(KnownMethod::GetBlockHeader));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
@@ -949,12 +2612,12 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(key)),
                     ParamsValue::String(String::from(height)),
                 ]),
             )
-            .insert("method", ParamsValue::String(String::from(GET_STATE_PROOF)));
+            .insert("method", self.method(KnownMethod::GetStateProof));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
@@ -968,18 +2631,17 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
             .insert(
                 "params",
                 ParamsValue::List(vec![
-                    ParamsValue::String(String::from(address)),
+                    ParamsValue::address(address)?,
                     ParamsValue::String(String::from(key)),
                     ParamsValue::String(String::from(height)),
                 ]),
             )
-            .insert("method", ParamsValue::String(String::from(GET_STORAGE_AT)));
+            .insert("method", self.method(KnownMethod::GetStorageAt));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
     fn get_version(&self) -> Result<JsonRpcResponse, ToolError> {
-        let params =
-            JsonRpcParams::new().insert("method", ParamsValue::String(String::from(GET_VERSION)));
+        let params = JsonRpcParams::new().insert("method", self.method(KnownMethod::GetVersion));
         Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
     }
 
@@ -990,31 +2652,60 @@ impl ClientExt<JsonRpcResponse, ToolError> for Client {
         data: Option<&str>,
         height: &str,
     ) -> Result<JsonRpcResponse, ToolError> {
-        let mut object = HashMap::new();
-
-        object.insert(String::from("to"), ParamsValue::String(String::from(to)));
-        if let Some(from) = from {
-            object.insert(
-                String::from("from"),
-                ParamsValue::String(String::from(from)),
-            );
-        }
-        if let Some(data) = data {
-            object.insert(
-                String::from("data"),
-                ParamsValue::String(String::from(data)),
-            );
-        }
+        let params = call_params(
+            self.method(KnownMethod::EstimateQuota),
+            from,
+            to,
+            data,
+            height,
+        )?;
+        Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
+    }
+}
 
-        let param = ParamsValue::List(vec![
-            ParamsValue::Map(object),
+/// Params of `call` and `estimateQuota`
+fn call_params(
+    method: ParamsValue,
+    from: Option<&str>,
+    to: &str,
+    data: Option<&str>,
+    height: &str,
+) -> Result<JsonRpcParams, ToolError> {
+    Ok(JsonRpcParams::new().insert("method", method).insert(
+        "params",
+        ParamsValue::List(vec![
+            ParamsValue::Map(call_object(from, to, data)?),
             ParamsValue::String(String::from(height)),
-        ]);
-        let params = JsonRpcParams::new()
-            .insert("method", ParamsValue::String(String::from(ESTIMATE_QUOTA)))
-            .insert("params", param);
+        ]),
+    ))
+}
 
-        Ok(self.send_request(vec![params].into_iter())?.pop().unwrap())
+/// Call object of `call`, `estimateQuota` and `debug_traceCall`
+fn call_object(
+    from: Option<&str>,
+    to: &str,
+    data: Option<&str>,
+) -> Result<HashMap<String, ParamsValue>, ToolError> {
+    let mut object = HashMap::new();
+    object.insert(String::from("to"), call_target(to)?);
+    if let Some(from) = from {
+        object.insert(String::from("from"), ParamsValue::address(from)?);
+    }
+    if let Some(data) = data {
+        object.insert(
+            String::from("data"),
+            ParamsValue::String(String::from(data)),
+        );
+    }
+    Ok(object)
+}
+
+/// The `to` of `call` and `estimateQuota`, empty for contract creation
+fn call_target(to: &str) -> Result<ParamsValue, ToolError> {
+    if remove_0x(to).is_empty() {
+        Ok(ParamsValue::String(String::from(to)))
+    } else {
+        ParamsValue::address(to)
     }
 }
 
@@ -1029,7 +2720,8 @@ where
         let tx_options = TransactionOptions::new()
             .set_code(content)
             .set_address(STORE_ADDRESS)
-            .set_quota(quota);
+            .set_quota(quota)
+            .skip_simulation();
         self.send_raw_transaction(tx_options)
     }
 
@@ -1041,7 +2733,8 @@ where
         let tx_options = TransactionOptions::new()
             .set_code(&data)
             .set_address(ABI_ADDRESS)
-            .set_quota(quota);
+            .set_quota(quota)
+            .skip_simulation();
         self.send_raw_transaction(tx_options)
     }
 }
@@ -1125,14 +2818,282 @@ where
 
 impl Transfer<JsonRpcResponse, ToolError> for Client {}
 
+/// Network information with typed results
+pub trait NetExt<T, E>: ClientExt<T, E>
+where
+    T: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// peerCount: Get network peer count as a number
+    fn get_peer_count_u64(&self) -> Result<u64, E>;
+    /// peersInfo: Get connected peers, sorted by node address
+    fn get_peers(&self) -> Result<Vec<PeerInfo>, E>;
+}
+
+impl NetExt<JsonRpcResponse, ToolError> for Client {
+    fn get_peer_count_u64(&self) -> Result<u64, ToolError> {
+        u64::try_from(self.get_peer_count()?)
+    }
+
+    fn get_peers(&self) -> Result<Vec<PeerInfo>, ToolError> {
+        let result = self.get_peers_info()?.into_result()?;
+        let peers = match result {
+            ResponseValue::Map(ref result) => result.get("peers").cloned(),
+            _ => None,
+        };
+        match peers {
+            Some(ParamsValue::Map(peers)) => {
+                let mut peers = peers
+                    .into_iter()
+                    .map(|(node_id, address)| {
+                        Ok(PeerInfo {
+                            node_id,
+                            address: address.into_string()?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ToolError>>()?;
+                peers.sort();
+                Ok(peers)
+            }
+            Some(ParamsValue::Null) => Ok(Vec::new()),
+            _ => Err(ToolError::Customize(format!(
+                "Unexpected response: {}",
+                result
+            ))),
+        }
+    }
+}
+
+/// The node already has the transaction
+fn is_duplicate(response: &JsonRpcResponse) -> bool {
+    response
+        .error()
+        .map_or(false, |error| error.message() == DUPLICATE_TRANSACTION)
+}
+
+/// The response must answer the request with the same id
+fn check_response_id(sent: u64, response: JsonRpcResponse) -> Result<JsonRpcResponse, ToolError> {
+    if response.id() == sent {
+        Ok(response)
+    } else {
+        Err(ToolError::IdMismatch {
+            sent,
+            received: response.id(),
+        })
+    }
+}
+
+#[cfg(feature = "openssl")]
+pub(crate) type HttpsClient = HyperClient<hyper_tls::HttpsConnector<ProxyConnector>>;
+
+#[cfg(feature = "rustls")]
+pub(crate) type HttpsClient = HyperClient<hyper_rustls::HttpsConnector<ProxyConnector>>;
+
 #[cfg(feature = "openssl")]
-pub(crate) fn create_client() -> HyperClient<hyper_tls::HttpsConnector<HttpConnector>> {
-    let https = hyper_tls::HttpsConnector::new(4).unwrap();
+pub(crate) fn create_client(proxy: ProxyConfig) -> HttpsClient {
+    let tls = native_tls::TlsConnector::new().unwrap();
+    let https = hyper_tls::HttpsConnector::from((ProxyConnector::new(proxy), tls));
     HyperClient::builder().build::<_, Body>(https)
 }
 
 #[cfg(feature = "rustls")]
-pub(crate) fn create_client() -> HyperClient<hyper_rustls::HttpsConnector<HttpConnector>> {
-    let https = hyper_rustls::HttpsConnector::new(4);
+pub(crate) fn create_client(proxy: ProxyConfig) -> HttpsClient {
+    let mut tls = rustls_lib::ClientConfig::new();
+    tls.root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    let https = hyper_rustls::HttpsConnector::from((ProxyConnector::new(proxy), tls));
     HyperClient::builder().build::<_, Body>(https)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{check_response_id, Client, ClientExt};
+    use crate::client::{RawTransaction, TransactionBuilder, TransactionOptions};
+    use crate::crypto::privkey_from_str;
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+    use hyper::Client as HyperClient;
+    use hyper::Uri;
+    use std::time::Duration;
+    use tokio::runtime::current_thread::Runtime;
+    use types::U256;
+
+    fn response(result: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+            result
+        ))
+        .unwrap()
+    }
+
+    fn error(error: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"error":{}}}"#, error)).unwrap()
+    }
+
+    /// The error of a connection to the port nothing listens on
+    fn connect_error() -> ToolError {
+        let future = HyperClient::new().get("http://127.0.0.1:1".parse().unwrap());
+        ToolError::Hyper(Runtime::new().unwrap().block_on(future).unwrap_err())
+    }
+
+    fn build_transaction(client: &mut Client) -> RawTransaction {
+        client.set_private_key(
+            &privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap(),
+        );
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020004")
+            .set_code("0xabcd")
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_valid_until_block(Some(100))
+            .skip_simulation();
+        TransactionBuilder::new(client, options).build().unwrap()
+    }
+
+    fn height(height: u64) -> JsonRpcResponse {
+        response(&format!(r#""{:#x}""#, height))
+    }
+
+    /// A client comparing two urls, answering with the canned responses
+    fn partition_client(
+        results: Vec<Result<JsonRpcResponse, ToolError>>,
+        threshold: u64,
+    ) -> Client {
+        let urls = vec![
+            "http://127.0.0.1:1337".parse::<Uri>().unwrap(),
+            "http://127.0.0.1:1338".parse::<Uri>().unwrap(),
+        ];
+        Client::with_results(results)
+            .with_circuit_breaker(3, Duration::from_secs(10))
+            .with_divergence_threshold(urls, threshold)
+    }
+
+    fn hash_of(response: &JsonRpcResponse) -> Option<String> {
+        let result = response.result()?;
+        Some(result.as_map()?["hash"].as_str()?.to_string())
+    }
+
+    #[test]
+    fn test_submit_transaction_retries() {
+        let mut client = Client::with_results(Vec::new());
+        let tx = build_transaction(&mut client);
+        let hash = format!("0x{:x}", tx.hash());
+        let sent = response(&format!(r#"{{"hash":"{}","status":"OK"}}"#, hash));
+
+        let mut client = Client::with_results(vec![Err(connect_error()), Ok(sent)]);
+        let response = client.submit_transaction(&tx, 1).unwrap();
+        assert_eq!(hash_of(&response), Some(hash));
+
+        // Without retries the failure of the network is reported at once
+        let mut client = Client::with_results(vec![Err(connect_error())]);
+        match client.submit_transaction(&tx, 0) {
+            Err(ToolError::Hyper(_)) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_submit_transaction_duplicate() {
+        let mut client = Client::with_results(Vec::new());
+        let tx = build_transaction(&mut client);
+        let duplicate = || error(r#"{"code":-32003,"message":"Dup"}"#);
+
+        // The lost request reached the node, the resent one is a duplicate of it
+        let mut client = Client::with_results(vec![Err(connect_error()), Ok(duplicate())]);
+        let response = client.submit_transaction(&tx, 1).unwrap();
+        assert_eq!(hash_of(&response), Some(format!("0x{:x}", tx.hash())));
+
+        // A duplicate at the first attempt was sent before, it's not taken as sent
+        let mut client = Client::with_responses(vec![duplicate()]);
+        let response = client.submit_transaction(&tx, 1).unwrap();
+        assert!(!response.is_ok());
+    }
+
+    #[test]
+    fn test_check_response_id() {
+        assert_eq!(check_response_id(1, response(r#""0x1""#)).unwrap().id(), 1);
+        match check_response_id(2, response(r#""0x1""#)) {
+            Err(ToolError::IdMismatch {
+                sent: 2,
+                received: 1,
+            }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_check_partition() {
+        let client = partition_client(vec![Ok(height(100)), Ok(height(105))], 5);
+        assert!(client.check_partition().is_ok());
+
+        let client = partition_client(vec![Ok(height(100)), Ok(height(106))], 5);
+        match client.check_partition() {
+            Err(ToolError::NetworkPartitionDetected { divergence: 6 }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        // The url failing to answer is left out
+        let client = partition_client(
+            vec![
+                Ok(height(100)),
+                Ok(error(r#"{"code":-32603,"message":"Internal error"}"#)),
+            ],
+            5,
+        );
+        assert!(client.check_partition().is_ok());
+    }
+
+    #[test]
+    fn test_check_partition_disabled() {
+        // No height is queried, so no canned response is needed
+        let urls = vec!["http://127.0.0.1:1337".parse::<Uri>().unwrap()];
+        let without_circuit_breaker =
+            Client::with_responses(Vec::new()).with_divergence_threshold(urls, 5);
+        assert!(without_circuit_breaker.check_partition().is_ok());
+
+        let without_threshold =
+            Client::with_responses(Vec::new()).with_circuit_breaker(3, Duration::from_secs(10));
+        assert!(without_threshold.check_partition().is_ok());
+
+        let dry_run = partition_client(Vec::new(), 5).set_dry_run(true);
+        assert!(dry_run.check_partition().is_ok());
+
+        let no_answer = partition_client(Vec::new(), 5);
+        assert!(no_answer.check_partition().is_err());
+    }
+
+    #[test]
+    fn test_submit_transaction_checks_partition_once() {
+        let mut client = partition_client(Vec::new(), 5);
+        let tx = build_transaction(&mut client);
+        let hash = format!("0x{:x}", tx.hash());
+        let sent = response(&format!(r#"{{"hash":"{}","status":"OK"}}"#, hash));
+
+        // The heights are compared before the first attempt only
+        let mut client = partition_client(
+            vec![
+                Ok(height(100)),
+                Ok(height(101)),
+                Err(connect_error()),
+                Ok(sent),
+            ],
+            5,
+        );
+        let response = client.submit_transaction(&tx, 1).unwrap();
+        assert_eq!(hash_of(&response), Some(hash));
+
+        let mut client = partition_client(vec![Ok(height(100)), Ok(height(106))], 5);
+        match client.submit_transaction(&tx, 1) {
+            Err(ToolError::NetworkPartitionDetected { divergence: 6 }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_get_block_number_u64() {
+        let client = Client::with_responses(vec![height(100), response("null")]);
+        assert_eq!(client.get_block_number_u64().unwrap(), 100);
+        assert!(client.get_block_number_u64().is_err());
+    }
+}
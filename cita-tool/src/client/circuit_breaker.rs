@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use hyper::Uri;
+use log::info;
+
+/// State of the circuit of one url
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    /// Requests are sent
+    Closed,
+    /// Requests are skipped since the instant
+    Open(Instant),
+    /// One probe request is in flight since the instant, another one is allowed
+    /// after the recovery interval in case it never completes, such as dropped
+    HalfOpen(Instant),
+}
+
+struct Circuit {
+    state: State,
+    failures: u32,
+}
+
+/// Circuit breakers of the urls, a url is skipped after `failure_threshold`
+/// consecutive failures, and probed again after `recovery_interval`
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    recovery_interval: Duration,
+    circuits: HashMap<String, Circuit>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, recovery_interval: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            recovery_interval,
+            circuits: HashMap::new(),
+        }
+    }
+
+    /// Whether a request can be sent to the url, the first one after
+    /// the recovery interval of an open circuit is the probe
+    pub(crate) fn allow(&mut self, url: &Uri) -> bool {
+        let recovery_interval = self.recovery_interval;
+        let key = url.to_string();
+        let circuit = match self.circuits.get_mut(&key) {
+            Some(circuit) => circuit,
+            None => return true,
+        };
+        match circuit.state {
+            State::Closed => true,
+            State::Open(opened) if opened.elapsed() >= recovery_interval => {
+                info!("Circuit of {} is half-open, probing", key);
+                circuit.state = State::HalfOpen(Instant::now());
+                true
+            }
+            State::HalfOpen(probed) if probed.elapsed() >= recovery_interval => {
+                info!("Probe of {} got no result, probing again", key);
+                circuit.state = State::HalfOpen(Instant::now());
+                true
+            }
+            State::Open(_) | State::HalfOpen(_) => false,
+        }
+    }
+
+    /// Record the result of a request to the url
+    pub(crate) fn record(&mut self, url: &Uri, success: bool) {
+        let failure_threshold = self.failure_threshold;
+        let key = url.to_string();
+        let circuit = self.circuits.entry(key.clone()).or_insert(Circuit {
+            state: State::Closed,
+            failures: 0,
+        });
+        if success {
+            if circuit.state != State::Closed {
+                info!("Circuit of {} is closed", key);
+            }
+            circuit.state = State::Closed;
+            circuit.failures = 0;
+            return;
+        }
+
+        circuit.failures = circuit.failures.saturating_add(1);
+        match circuit.state {
+            State::HalfOpen(_) => {
+                info!("Circuit of {} is open again, the probe failed", key);
+                circuit.state = State::Open(Instant::now());
+            }
+            State::Closed if circuit.failures >= failure_threshold => {
+                info!(
+                    "Circuit of {} is open after {} consecutive failures",
+                    key, circuit.failures
+                );
+                circuit.state = State::Open(Instant::now());
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CircuitBreaker;
+    use hyper::Uri;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_open_half_open_closed() {
+        let url: Uri = "http://127.0.0.1:1337".parse().unwrap();
+        let other: Uri = "http://127.0.0.1:1338".parse().unwrap();
+        let mut breaker = CircuitBreaker::new(2, Duration::from_millis(50));
+
+        breaker.record(&url, false);
+        assert!(breaker.allow(&url));
+        breaker.record(&url, false);
+        assert!(!breaker.allow(&url));
+        assert!(breaker.allow(&other));
+
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(&url));
+        assert!(!breaker.allow(&url), "only one probe");
+        breaker.record(&url, false);
+        assert!(!breaker.allow(&url));
+
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(&url));
+        breaker.record(&url, true);
+        assert!(breaker.allow(&url));
+        assert!(breaker.allow(&url));
+    }
+
+    #[test]
+    fn test_probe_without_result() {
+        let url: Uri = "http://127.0.0.1:1337".parse().unwrap();
+        let mut breaker = CircuitBreaker::new(1, Duration::from_millis(50));
+
+        breaker.record(&url, false);
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(&url));
+        // The probe is dropped, nothing is recorded
+        assert!(!breaker.allow(&url));
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(&url));
+        breaker.record(&url, true);
+        assert!(breaker.allow(&url));
+    }
+}
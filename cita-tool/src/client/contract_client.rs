@@ -0,0 +1,128 @@
+use ethabi::{Contract, Token};
+use types::Address;
+
+use crate::abi::{contract_decode_output, contract_encode_input};
+use crate::client::basic::Client;
+use crate::client::{remove_0x, TransactionBuilder, TransactionOptions};
+use crate::crypto::PrivateKey;
+use crate::error::ToolError;
+use crate::rpctypes::ResponseValue;
+use crate::LowerHex;
+
+/// Client of any deployed contract, with the ABI loaded at runtime
+pub struct ContractClient {
+    client: Client,
+    address: Address,
+    contract: Contract,
+}
+
+impl ContractClient {
+    /// Create a client of the contract at the address, with its ABI in JSON
+    pub fn new(abi_json: &str, address: Address, client: Client) -> Result<Self, ToolError> {
+        let contract = Contract::load(abi_json.as_bytes())
+            .map_err(|err| ToolError::Abi(format!("{}", err)))?;
+        Ok(ContractClient {
+            client,
+            address,
+            contract,
+        })
+    }
+
+    /// Get the contract ABI
+    pub fn contract(&self) -> &Contract {
+        &self.contract
+    }
+
+    /// Call the method, decode the output with the ABI, default height is "latest"
+    pub fn call(
+        &self,
+        method: &str,
+        args: &[&str],
+        height: Option<&str>,
+    ) -> Result<Vec<Token>, ToolError> {
+        let data = self.encode_input(method, args)?;
+        let output = self.client.cita_call(
+            None,
+            &self.address.completed_lower_hex_with_0x(),
+            &format!("0x{}", data),
+            height,
+        )?;
+        contract_decode_output(&self.contract, method, remove_0x(&output))
+    }
+
+    /// Send a transaction of the method signed by the private key, return the transaction hash
+    ///
+    /// The code and address of `options` are replaced by the call data and the contract address.
+    /// The private key is only used for this transaction, the client is not changed.
+    pub fn send_tx(
+        &mut self,
+        method: &str,
+        args: &[&str],
+        options: TransactionOptions,
+        privkey: &PrivateKey,
+    ) -> Result<String, ToolError> {
+        let data = self.encode_input(method, args)?;
+        let code = format!("0x{}", data);
+        let address = self.address.completed_lower_hex_with_0x();
+        // Signed by a copy of the client, the private key of the shared client is kept
+        let mut signer = self.client.clone();
+        signer.set_private_key(privkey);
+        let response =
+            TransactionBuilder::new(&mut signer, options.set_code(&code).set_address(&address))
+                .send()?;
+        match response.into_result()? {
+            ResponseValue::Map(mut result) => result.remove("hash"),
+            _ => None,
+        }
+        .ok_or_else(|| ToolError::Customize("Transaction has no hash".to_string()))?
+        .into_string()
+    }
+
+    fn encode_input(&self, method: &str, args: &[&str]) -> Result<String, ToolError> {
+        let values = args
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        contract_encode_input(&self.contract, method, &values, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ContractClient;
+    use crate::client::basic::Client;
+    use crate::client::TransactionOptions;
+    use crate::privkey_from_str;
+    use crate::rpctypes::JsonRpcResponse;
+    use crate::U256;
+    use types::Address;
+
+    #[test]
+    fn test_send_tx_keeps_private_key() {
+        let abi = r#"[{"type":"function","name":"set","inputs":[{"name":"x","type":"uint256"}],
+            "outputs":[],"constant":false,"payable":false,"stateMutability":"nonpayable"}]"#;
+        let response: JsonRpcResponse = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"hash":"0x01","status":"OK"}}"#,
+        )
+        .unwrap();
+        let mut client = ContractClient::new(
+            abi,
+            Address::from(&[1u8; 20][..]),
+            Client::with_responses(vec![response]),
+        )
+        .unwrap();
+        let privkey =
+            privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap();
+        let options = TransactionOptions::new()
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_valid_until_block(Some(100))
+            .skip_simulation();
+        assert_eq!(
+            client.send_tx("set", &["1"], options, &privkey).unwrap(),
+            "0x01"
+        );
+        assert!(client.client.private_key().is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use types::{H256, U256};
+
+use crate::client::{remove_0x, TransactionOptions};
+
+/// Default time between two blocks of CITA
+const DEFAULT_BLOCK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// What makes two transactions the same, whatever their nonces
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct TransactionKey {
+    from: Option<String>,
+    to: String,
+    data: String,
+    value: U256,
+}
+
+impl TransactionKey {
+    /// The transaction of the options sent by the sender
+    pub(crate) fn new(from: Option<String>, options: &TransactionOptions) -> Self {
+        TransactionKey {
+            from: from.map(|from| remove_0x(&from).to_lowercase()),
+            to: remove_0x(options.address()).to_lowercase(),
+            data: remove_0x(options.code()).to_lowercase(),
+            value: options.value().unwrap_or_else(U256::zero),
+        }
+    }
+}
+
+/// Recently sent transactions, to refuse sending the same one twice by accident,
+/// see `TransactionBuilder::prevent_duplicates`
+///
+/// A transaction is remembered until its `valid_until_block` should be reached,
+/// after that it can't be included in a block anymore.
+#[derive(Debug)]
+pub struct TransactionDeduplicator {
+    block_interval: Duration,
+    sent: Mutex<HashMap<TransactionKey, (H256, Instant)>>,
+}
+
+impl TransactionDeduplicator {
+    /// Remember the transactions of a chain producing a block every interval
+    pub fn new(block_interval: Duration) -> Self {
+        TransactionDeduplicator {
+            block_interval,
+            sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Forget all the transactions
+    pub fn clear(&self) {
+        self.sent.lock().unwrap().clear();
+    }
+
+    /// Hash of the same transaction sent before, if it's not expired
+    pub(crate) fn previous(&self, key: &TransactionKey) -> Option<H256> {
+        let mut sent = self.sent.lock().unwrap();
+        let now = Instant::now();
+        sent.retain(|_, (_, expires_at)| *expires_at > now);
+        sent.get(key).map(|(hash, _)| *hash)
+    }
+
+    /// Remember the transaction for the blocks it stays valid before it's sent, checked
+    /// and inserted under one lock, fail with the hash of the same one if it's not expired
+    pub(crate) fn reserve(
+        &self,
+        key: TransactionKey,
+        hash: H256,
+        valid_blocks: u64,
+    ) -> Result<(), H256> {
+        let ttl = self.block_interval * valid_blocks.min(u64::from(u32::max_value())) as u32;
+        let mut sent = self.sent.lock().unwrap();
+        let now = Instant::now();
+        sent.retain(|_, (_, expires_at)| *expires_at > now);
+        if let Some((previous, _)) = sent.get(&key) {
+            return Err(*previous);
+        }
+        sent.insert(key, (hash, now + ttl));
+        Ok(())
+    }
+
+    /// Forget the reserved transaction, it failed to be sent
+    pub(crate) fn release(&self, key: &TransactionKey, hash: H256) {
+        let mut sent = self.sent.lock().unwrap();
+        if sent
+            .get(key)
+            .map_or(false, |(reserved, _)| *reserved == hash)
+        {
+            sent.remove(key);
+        }
+    }
+}
+
+impl Default for TransactionDeduplicator {
+    fn default() -> Self {
+        TransactionDeduplicator::new(DEFAULT_BLOCK_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+    use std::time::Duration;
+
+    use types::{H256, U256};
+
+    use super::{TransactionDeduplicator, TransactionKey};
+    use crate::client::TransactionOptions;
+
+    #[test]
+    fn test_deduplicator() {
+        let options = TransactionOptions::new()
+            .set_address("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF020004")
+            .set_code("0xabcd");
+        let key = TransactionKey::new(Some("0x4b5a".to_string()), &options);
+        let same = TransactionKey::new(
+            Some("4B5A".to_string()),
+            &options.set_address("ffffffffffffffffffffffffffffffffff020004"),
+        );
+        assert_eq!(key, same);
+        let other = TransactionKey::new(
+            Some("0x4b5a".to_string()),
+            &options.set_value(Some(U256::from(1))),
+        );
+
+        let deduplicator = TransactionDeduplicator::new(Duration::from_millis(10));
+        assert_eq!(deduplicator.previous(&key), None);
+        assert_eq!(deduplicator.reserve(key.clone(), H256::from(1), 5), Ok(()));
+        assert_eq!(deduplicator.previous(&same), Some(H256::from(1)));
+        assert_eq!(
+            deduplicator.reserve(same.clone(), H256::from(3), 5),
+            Err(H256::from(1))
+        );
+        assert_eq!(deduplicator.previous(&other), None);
+
+        thread::sleep(Duration::from_millis(60));
+        assert_eq!(deduplicator.previous(&key), None);
+
+        assert_eq!(
+            deduplicator.reserve(key.clone(), H256::from(2), 100),
+            Ok(())
+        );
+        // Only the reserving transaction releases the key
+        deduplicator.release(&key, H256::from(1));
+        assert_eq!(deduplicator.previous(&key), Some(H256::from(2)));
+        deduplicator.release(&key, H256::from(2));
+        assert_eq!(deduplicator.previous(&key), None);
+
+        deduplicator
+            .reserve(key.clone(), H256::from(2), 100)
+            .unwrap();
+        deduplicator.clear();
+        assert_eq!(deduplicator.previous(&key), None);
+    }
+}
@@ -0,0 +1,276 @@
+use std::thread;
+use std::time::Duration;
+
+use ethabi::{Contract, Token};
+use futures::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::Stream;
+use types::{Address, H256};
+
+use crate::abi::decode_log;
+use crate::client::basic::Client;
+#[cfg(feature = "websocket")]
+use crate::client::subscription;
+use crate::error::ToolError;
+use crate::rpctypes::{Log, LogFilter};
+
+/// A log of the event, decoded by the ABI of the contract
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedLog {
+    /// Number of the block the log is in
+    pub block_number: u64,
+    /// Hash of the transaction that emitted the log
+    pub tx_hash: H256,
+    /// Named arguments of the event, in the order of the ABI
+    pub fields: Vec<(String, Token)>,
+}
+
+/// How `ContractEventListener` receives the new logs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListenMode {
+    /// The `logs` subscription over WebSocket, see `Client::set_ws_uri`
+    #[cfg(feature = "websocket")]
+    WebSocket,
+    /// `getLogs` of the new blocks, the height is polled every interval
+    Poll(Duration),
+}
+
+impl Default for ListenMode {
+    fn default() -> Self {
+        ListenMode::Poll(Duration::from_secs(3))
+    }
+}
+
+/// Listen the events of a contract, the logs are decoded by its ABI
+///
+/// The logs since `from_block` are yielded first, then the ones of the new blocks,
+/// in the order they are emitted. A log that fails to decode, or a failed request,
+/// is yielded as an error and the listener goes on.
+pub struct ContractEventListener<'a> {
+    client: Client,
+    contract: &'a Contract,
+    address: Address,
+    mode: ListenMode,
+}
+
+impl<'a> ContractEventListener<'a> {
+    /// Listen the events of the contract deployed at the address, polling every 3 seconds
+    pub fn new(client: Client, contract: &'a Contract, address: Address) -> Self {
+        ContractEventListener {
+            client,
+            contract,
+            address,
+            mode: ListenMode::default(),
+        }
+    }
+
+    /// Set how the new logs are received
+    pub fn set_mode(mut self, mode: ListenMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Stream of the logs of the event since the block, until the stream is dropped
+    ///
+    /// `ToolError::Abi` if the contract has no such event.
+    pub fn listen_event(
+        &self,
+        event_name: &str,
+        from_block: u64,
+    ) -> Result<impl Stream<Item = Result<ParsedLog, ToolError>, Error = ()>, ToolError> {
+        let topic = self
+            .contract
+            .event(event_name)
+            .map_err(|err| ToolError::Abi(err.to_string()))?
+            .signature();
+        let filter = LogFilter::new()
+            .set_address(Some(vec![self.address]))
+            .set_topics(Some(vec![Some(H256::from(topic.0))]));
+        let listener = Listener {
+            client: self.client.clone(),
+            contract: self.contract.clone(),
+            event_name: event_name.to_string(),
+            filter,
+        };
+        match self.mode {
+            #[cfg(feature = "websocket")]
+            ListenMode::WebSocket => listener.subscribe(from_block),
+            ListenMode::Poll(interval) => Ok(listener.poll(from_block, interval)),
+        }
+    }
+}
+
+/// State moved into the thread feeding the stream
+struct Listener {
+    client: Client,
+    contract: Contract,
+    event_name: String,
+    filter: LogFilter,
+}
+
+impl Listener {
+    /// `getLogs` of the new blocks every interval
+    fn poll(
+        self,
+        from_block: u64,
+        interval: Duration,
+    ) -> UnboundedReceiver<Result<ParsedLog, ToolError>> {
+        let (sender, receiver) = mpsc::unbounded();
+        thread::spawn(move || {
+            let mut next = from_block;
+            loop {
+                let sent = match self.client.get_current_height() {
+                    Ok(height) if height >= next => {
+                        let sent = self.send_logs(&sender, next, height);
+                        next = height + 1;
+                        sent
+                    }
+                    Ok(_) => true,
+                    Err(err) => sender.unbounded_send(Err(err)).is_ok(),
+                };
+                // The stream is dropped
+                if !sent {
+                    return;
+                }
+                thread::sleep(interval);
+            }
+        });
+        receiver
+    }
+
+    /// Subscribe the new logs first, then query the ones up to the current height,
+    /// so no block is missed in between
+    #[cfg(feature = "websocket")]
+    fn subscribe(
+        self,
+        from_block: u64,
+    ) -> Result<UnboundedReceiver<Result<ParsedLog, ToolError>>, ToolError> {
+        let filter = serde_json::to_value(self.filter.clone().into_params())
+            .map_err(ToolError::SerdeJson)?;
+        let logs = subscription::subscribe_logs(self.client.ws_url(), filter)?;
+        let height = self.client.get_current_height()?;
+        let (sender, receiver) = mpsc::unbounded();
+        thread::spawn(move || {
+            if height >= from_block && !self.send_logs(&sender, from_block, height) {
+                return;
+            }
+            for log in logs.wait() {
+                let parsed = match log {
+                    Ok(Ok(log)) if log.block_number <= height => continue,
+                    Ok(Ok(log)) => self.parse(&log),
+                    Ok(Err(err)) => Err(err),
+                    Err(()) => return,
+                };
+                if sender.unbounded_send(parsed).is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    /// Send the logs of the blocks, false if the stream is dropped
+    fn send_logs(
+        &self,
+        sender: &UnboundedSender<Result<ParsedLog, ToolError>>,
+        from_block: u64,
+        to_block: u64,
+    ) -> bool {
+        let filter = self
+            .filter
+            .clone()
+            .set_from_block(Some(from_block))
+            .set_to_block(Some(to_block));
+        match self.client.get_filtered_logs(filter) {
+            Ok(mut logs) => {
+                logs.sort_by_key(|log| (log.block_number, log.log_index));
+                logs.iter()
+                    .all(|log| sender.unbounded_send(self.parse(log)).is_ok())
+            }
+            Err(err) => sender.unbounded_send(Err(err)).is_ok(),
+        }
+    }
+
+    fn parse(&self, log: &Log) -> Result<ParsedLog, ToolError> {
+        Ok(ParsedLog {
+            block_number: log.block_number,
+            tx_hash: log.transaction_hash,
+            fields: decode_log(&self.contract, &self.event_name, log)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use ethabi::{Contract, Token};
+    use futures::Stream;
+    use types::{Address, H256};
+
+    use super::{ContractEventListener, ListenMode};
+    use crate::client::basic::Client;
+    use crate::rpctypes::JsonRpcResponse;
+
+    const ABI: &str = r#"[{
+        "anonymous": false,
+        "inputs": [
+            {"indexed": true, "name": "from", "type": "address"},
+            {"indexed": false, "name": "value", "type": "uint256"}
+        ],
+        "name": "Stored",
+        "type": "event"
+    }]"#;
+
+    fn response(result: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+            result
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_listen_event_poll() {
+        let contract = Contract::load(ABI.as_bytes()).unwrap();
+        let topic = H256::from(contract.event("Stored").unwrap().signature().0);
+        let log = |block_number: u64, log_index: u64, value: u64| {
+            format!(
+                r#"{{
+                    "address": "0x27ec3678e4d61534ab8a87cf8feb8ac110ddeda5",
+                    "topics": ["0x{:x}", "0x{:064x}"],
+                    "data": "0x{:064x}",
+                    "transactionHash": "0x{:064x}",
+                    "blockNumber": "{:#x}",
+                    "logIndex": "{:#x}"
+                }}"#,
+                topic, 7, value, block_number, block_number, log_index
+            )
+        };
+        let client = Client::with_responses(vec![
+            response(r#""0x1f""#),
+            response(&format!("[{},{}]", log(31, 1, 2), log(30, 0, 1))),
+        ]);
+
+        let listener = ContractEventListener::new(client, &contract, Address::from(1))
+            .set_mode(ListenMode::Poll(Duration::from_millis(10)));
+        assert!(listener.listen_event("Missing", 0).is_err());
+        let mut logs = listener.listen_event("Stored", 30).unwrap().wait();
+
+        for (block_number, value) in [(30, 1), (31, 2)].iter() {
+            let parsed = logs.next().unwrap().unwrap().unwrap();
+            assert_eq!(parsed.block_number, *block_number);
+            assert_eq!(parsed.tx_hash, H256::from(*block_number));
+            let names = parsed
+                .fields
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<&str>>();
+            assert_eq!(names, ["from", "value"]);
+            match &parsed.fields[0].1 {
+                Token::Address(from) => assert_eq!(Address::from(&from[..]), Address::from(7)),
+                token => panic!("Not an address: {:?}", token),
+            }
+            assert_eq!(parsed.fields[1].1, Token::Uint((*value).into()));
+        }
+    }
+}
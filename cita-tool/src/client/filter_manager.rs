@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::client::basic::{Client, ClientExt};
+use crate::client::rpc_method::KnownMethod;
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcParams, JsonRpcResponse, Log, LogFilter, ParamsValue, ResponseValue};
+
+/// Id of a filter installed on the node by `newFilter`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FilterId(String);
+
+impl FilterId {
+    /// The id as sent to the node
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for FilterId {
+    fn from(id: String) -> Self {
+        FilterId(id)
+    }
+}
+
+impl fmt::Display for FilterId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Log filters installed on the node, polled with `getFilterChanges`
+///
+/// The manager keeps track of the filters it installed until they are uninstalled,
+/// the node also removes a filter that isn't polled for a while.
+pub struct FilterManager {
+    client: Client,
+    installed: HashSet<FilterId>,
+}
+
+impl FilterManager {
+    /// Create a filter manager sending requests with the client
+    pub fn new(client: Client) -> Self {
+        FilterManager {
+            client,
+            installed: HashSet::new(),
+        }
+    }
+
+    /// Install a log filter
+    pub fn new_filter(&mut self, filter: LogFilter) -> Result<FilterId, ToolError> {
+        let params = JsonRpcParams::new()
+            .insert("method", self.client.method(KnownMethod::NewFilter))
+            .insert("params", ParamsValue::List(vec![filter.into_params()]));
+        let id = match self.request(params)? {
+            ResponseValue::Singe(ParamsValue::String(id)) => FilterId(id),
+            ResponseValue::Singe(ParamsValue::Int(id)) => FilterId(format!("{:#x}", id)),
+            result => {
+                return Err(ToolError::Customize(format!(
+                    "Unexpected filter id: {}",
+                    result
+                )))
+            }
+        };
+        self.installed.insert(id.clone());
+        Ok(id)
+    }
+
+    /// Logs of the filter since the last poll
+    pub fn get_filter_changes(&mut self, id: &FilterId) -> Result<Vec<Log>, ToolError> {
+        let response = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_filter_changes(
+            &self.client,
+            id.as_str(),
+        )?;
+        self.logs(response)
+    }
+
+    /// All logs of the filter
+    pub fn get_filter_logs(&mut self, id: &FilterId) -> Result<Vec<Log>, ToolError> {
+        let response = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_filter_logs(
+            &self.client,
+            id.as_str(),
+        )?;
+        self.logs(response)
+    }
+
+    /// Uninstall the filter, `false` if the node doesn't know it
+    pub fn uninstall_filter(&mut self, id: &FilterId) -> Result<bool, ToolError> {
+        let response = <Client as ClientExt<JsonRpcResponse, ToolError>>::uninstall_filter(
+            &self.client,
+            id.as_str(),
+        )?;
+        match response.into_result()? {
+            ResponseValue::Singe(ParamsValue::Bool(uninstalled)) => {
+                self.installed.remove(id);
+                Ok(uninstalled)
+            }
+            result => Err(ToolError::Customize(format!(
+                "Unexpected response: {}",
+                result
+            ))),
+        }
+    }
+
+    /// Filters installed by the manager and not uninstalled yet
+    pub fn installed(&self) -> impl Iterator<Item = &FilterId> {
+        self.installed.iter()
+    }
+
+    /// Uninstall all filters installed by the manager
+    pub fn uninstall_all(&mut self) -> Result<(), ToolError> {
+        let ids = self.installed.iter().cloned().collect::<Vec<FilterId>>();
+        for id in ids {
+            self.uninstall_filter(&id)?;
+        }
+        Ok(())
+    }
+
+    fn request(&self, params: JsonRpcParams) -> Result<ResponseValue, ToolError> {
+        self.client
+            .send_request(vec![params].into_iter())?
+            .pop()
+            .unwrap()
+            .into_result()
+    }
+
+    fn logs(&self, response: JsonRpcResponse) -> Result<Vec<Log>, ToolError> {
+        match response.into_result()? {
+            ResponseValue::Singe(ParamsValue::List(logs)) => {
+                logs.iter().map(Log::try_from).collect()
+            }
+            result => Err(ToolError::Customize(format!(
+                "Unexpected response: {}",
+                result
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FilterId, FilterManager};
+    use crate::client::basic::Client;
+    use crate::rpctypes::{JsonRpcResponse, LogFilter};
+
+    fn response(result: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+            result
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_filter_lifecycle() {
+        let log = r#"{
+            "address": "0xffffffffffffffffffffffffffffffffff020009",
+            "topics": [],
+            "data": "0x",
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000003",
+            "blockNumber": "0x1f",
+            "logIndex": "0x0"
+        }"#;
+        let mut manager = FilterManager::new(Client::with_responses(vec![
+            response(r#""0x16""#),
+            response(&format!("[{}]", log)),
+            response("[]"),
+            response(&format!("[{}]", log)),
+            response("true"),
+        ]));
+
+        let id = manager.new_filter(LogFilter::new()).unwrap();
+        assert_eq!(id, FilterId::from("0x16".to_string()));
+        assert_eq!(manager.installed().count(), 1);
+        assert_eq!(manager.get_filter_changes(&id).unwrap().len(), 1);
+        assert!(manager.get_filter_changes(&id).unwrap().is_empty());
+        assert_eq!(manager.get_filter_logs(&id).unwrap()[0].block_number, 31);
+        assert!(manager.uninstall_filter(&id).unwrap());
+        assert_eq!(manager.installed().count(), 0);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use hex::encode;
+use types::Address;
+use uuid::Uuid;
+
+use crate::crypto::{address_from_privkey, PrivateKey};
+
+/// Transaction nonce generator
+///
+/// The default mode generates a random UUID nonce for every transaction.
+/// The sequential mode maintains a counter per key address: the nonce is
+/// the number of confirmed transactions plus the number of pending ones,
+/// and the counter advances when a transaction is marked confirmed.
+/// A forgotten transaction only gives its nonce back if it's the latest one,
+/// the others leave a gap rather than a nonce that may be in flight.
+#[derive(Clone, Debug, Default)]
+pub struct NonceManager {
+    sequential: bool,
+    confirmed: HashMap<Address, u64>,
+    pending: HashMap<Address, u64>,
+    tracked: HashMap<String, (Address, String)>,
+}
+
+impl NonceManager {
+    /// Random UUID nonce for every transaction
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sequence counter nonce per key address
+    pub fn sequential() -> Self {
+        NonceManager {
+            sequential: true,
+            ..Default::default()
+        }
+    }
+
+    /// Is sequential mode
+    pub fn is_sequential(&self) -> bool {
+        self.sequential
+    }
+
+    /// Get the nonce of next transaction signed by the key
+    pub fn next_nonce(&mut self, key: &PrivateKey) -> String {
+        if !self.sequential {
+            return encode(Uuid::new_v4().as_bytes());
+        }
+        let address = address_from_privkey(key);
+        let confirmed = self.confirmed.get(&address).cloned().unwrap_or(0);
+        let pending = self.pending.entry(address).or_insert(0);
+        let nonce = confirmed + *pending;
+        *pending += 1;
+        nonce.to_string()
+    }
+
+    /// Track the sent transaction hash with its nonce and signing key
+    pub fn track(&mut self, hash: &str, nonce: &str, key: &PrivateKey) {
+        if self.sequential {
+            self.tracked.insert(
+                hash.to_lowercase(),
+                (address_from_privkey(key), nonce.to_string()),
+            );
+        }
+    }
+
+    /// Advance the counter of the key after the receipt of transaction is obtained,
+    /// return false if the hash is not tracked
+    pub fn mark_confirmed(&mut self, hash: &str) -> bool {
+        match self.tracked.remove(&hash.to_lowercase()) {
+            Some((address, _)) => {
+                self.release(address);
+                *self.confirmed.entry(address).or_insert(0) += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget a transaction which will never be confirmed, such as timeout,
+    /// return false if the hash is not tracked
+    pub fn forget(&mut self, hash: &str) -> bool {
+        match self.tracked.remove(&hash.to_lowercase()) {
+            Some((address, nonce)) => {
+                let confirmed = self.confirmed.get(&address).cloned().unwrap_or(0);
+                let pending = self.pending.get(&address).cloned().unwrap_or(0);
+                let latest = (confirmed + pending).checked_sub(1);
+                if latest.is_some() && nonce.parse::<u64>().ok() == latest {
+                    self.release(address);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn release(&mut self, address: Address) {
+        if let Some(pending) = self.pending.get_mut(&address) {
+            *pending = pending.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NonceManager;
+    use crate::crypto::{privkey_from_str, PrivateKey};
+
+    #[test]
+    fn test_sequential_nonce() {
+        let key =
+            privkey_from_str("0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9")
+                .unwrap();
+        let mut manager = NonceManager::sequential();
+        assert_eq!(manager.next_nonce(&key), "0");
+        manager.track("0xAB", "0", &key);
+        assert_eq!(manager.next_nonce(&key), "1");
+        manager.track("0xcd", "1", &key);
+
+        assert!(manager.mark_confirmed("0xab"));
+        assert!(!manager.mark_confirmed("0xab"));
+        assert!(manager.forget("0xcd"));
+        assert_eq!(manager.next_nonce(&key), "1");
+        manager.track("0xef", "1", &key);
+        assert_eq!(manager.next_nonce(&key), "2");
+        manager.track("0x12", "2", &key);
+
+        // Nonce 2 is in flight, forgetting 1 leaves a gap
+        assert!(manager.forget("0xef"));
+        assert_eq!(manager.next_nonce(&key), "3");
+
+        let mut random = NonceManager::new();
+        assert_ne!(
+            random.next_nonce(&key),
+            random.next_nonce(&PrivateKey::null())
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::env;
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+
+use futures::future::{self, loop_fn, Loop};
+use futures::Future;
+use hyper::client::connect::{Connect, Connected, Destination};
+use hyper::client::HttpConnector;
+use hyper::Uri;
+use log::warn;
+use tokio::io::{read_exact, write_all};
+use tokio::net::TcpStream;
+
+use crate::error::ToolError;
+
+/// Longest response header of a `CONNECT` request
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+/// Proxy of the outgoing connections
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Proxy {
+    /// HTTP proxy, http urls are sent to it in absolute-form, https urls by `CONNECT` tunnels
+    Http(SocketAddr),
+    /// SOCKS5 proxy without authentication
+    Socks5(SocketAddr),
+}
+
+impl Proxy {
+    /// Parse `http://host:port` or `socks5://host:port`, the scheme defaults to http
+    pub(crate) fn parse(url: &str) -> Result<Self, ToolError> {
+        let uri = if url.contains("://") {
+            url.parse::<Uri>()
+        } else {
+            format!("http://{}", url).parse::<Uri>()
+        }
+        .map_err(|err| ToolError::Customize(format!("Invalid proxy {}: {}", url, err)))?;
+        let host = uri
+            .host()
+            .ok_or_else(|| ToolError::Customize(format!("Proxy {} has no host", url)))?;
+        let (socks5, default_port) = match uri.scheme_part().map(|scheme| scheme.as_str()) {
+            Some("http") => (false, 80),
+            Some("socks5") | Some("socks5h") => (true, 1080),
+            _ => {
+                return Err(ToolError::Customize(format!(
+                    "Unsupported proxy {}, only http and socks5 are supported",
+                    url
+                )));
+            }
+        };
+        let addr = (host, uri.port_u16().unwrap_or(default_port))
+            .to_socket_addrs()
+            .map_err(ToolError::Stdio)?
+            .next()
+            .ok_or_else(|| ToolError::Customize(format!("Can't resolve proxy {}", url)))?;
+        Ok(if socks5 {
+            Proxy::Socks5(addr)
+        } else {
+            Proxy::Http(addr)
+        })
+    }
+}
+
+/// Proxies of http and https urls, and the hosts connected directly
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ProxyConfig {
+    http: Option<Proxy>,
+    https: Option<Proxy>,
+    no_proxy: Vec<String>,
+}
+
+impl ProxyConfig {
+    /// The same proxy for all urls
+    pub(crate) fn all(proxy: Proxy) -> Self {
+        ProxyConfig {
+            http: Some(proxy.clone()),
+            https: Some(proxy),
+            no_proxy: Vec::new(),
+        }
+    }
+
+    /// From `http_proxy`, `https_proxy` and `no_proxy`, or their upper case
+    pub(crate) fn from_env() -> Self {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// The lower case variable takes precedence, an invalid proxy is ignored with a warning
+    fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Self {
+        let lookup = |name: &str| {
+            var(name)
+                .or_else(|| var(&name.to_uppercase()))
+                .filter(|value| !value.trim().is_empty())
+        };
+        let proxy = |name: &str| {
+            lookup(name).and_then(|url| match Proxy::parse(url.trim()) {
+                Ok(proxy) => Some(proxy),
+                Err(err) => {
+                    warn!("{} is ignored: {}", name, err);
+                    None
+                }
+            })
+        };
+        ProxyConfig {
+            http: proxy("http_proxy"),
+            https: proxy("https_proxy"),
+            no_proxy: lookup("no_proxy")
+                .map(|hosts| {
+                    hosts
+                        .split(',')
+                        .map(|host| host.trim().trim_start_matches('.').to_lowercase())
+                        .filter(|host| !host.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The proxy of the destination, none if connected directly
+    fn proxy_for(&self, scheme: &str, host: &str) -> Option<&Proxy> {
+        let host = host.to_lowercase();
+        let bypass = self
+            .no_proxy
+            .iter()
+            .any(|entry| entry == "*" || host == *entry || host.ends_with(&format!(".{}", entry)));
+        if bypass {
+            None
+        } else if scheme == "https" {
+            self.https.as_ref()
+        } else {
+            self.http.as_ref()
+        }
+    }
+}
+
+/// Connector of hyper, connect directly or through the proxy,
+/// TLS is done by the `HttpsConnector` wrapping it
+#[derive(Clone)]
+pub(crate) struct ProxyConnector {
+    http: HttpConnector,
+    config: Arc<ProxyConfig>,
+}
+
+impl ProxyConnector {
+    pub(crate) fn new(config: ProxyConfig) -> Self {
+        let mut http = HttpConnector::new(4);
+        http.enforce_http(false);
+        ProxyConnector {
+            http,
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl Connect for ProxyConnector {
+    type Transport = TcpStream;
+    type Error = io::Error;
+    type Future = Box<dyn Future<Item = (TcpStream, Connected), Error = io::Error> + Send>;
+
+    fn connect(&self, dst: Destination) -> Self::Future {
+        let host = dst.host().to_string();
+        let port = dst
+            .port()
+            .unwrap_or_else(|| if dst.scheme() == "https" { 443 } else { 80 });
+        match self.config.proxy_for(dst.scheme(), &host) {
+            None => Box::new(self.http.connect(dst)),
+            Some(Proxy::Http(proxy)) => {
+                let tunnel = dst.scheme() == "https";
+                Box::new(
+                    TcpStream::connect(proxy).and_then(move |stream| -> Self::Future {
+                        if tunnel {
+                            Box::new(
+                                http_connect(stream, host, port)
+                                    .map(|stream| (stream, Connected::new())),
+                            )
+                        } else {
+                            Box::new(future::ok((stream, Connected::new().proxy(true))))
+                        }
+                    }),
+                )
+            }
+            Some(Proxy::Socks5(proxy)) => Box::new(
+                TcpStream::connect(proxy)
+                    .and_then(move |stream| socks5_connect(stream, host, port))
+                    .map(|stream| (stream, Connected::new())),
+            ),
+        }
+    }
+}
+
+fn proxy_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+/// Open a tunnel to `host:port` by `CONNECT`
+fn http_connect(
+    stream: TcpStream,
+    host: String,
+    port: u16,
+) -> impl Future<Item = TcpStream, Error = io::Error> + Send {
+    let request = format!(
+        "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n",
+        host = host,
+        port = port
+    );
+    write_all(stream, request.into_bytes())
+        .and_then(|(stream, _)| {
+            loop_fn((stream, Vec::new()), |(stream, mut response)| {
+                read_exact(stream, [0u8; 1]).and_then(move |(stream, byte)| {
+                    response.push(byte[0]);
+                    if response.ends_with(b"\r\n\r\n") {
+                        Ok(Loop::Break((stream, response)))
+                    } else if response.len() > MAX_CONNECT_RESPONSE {
+                        Err(proxy_error("Response of CONNECT is too long".to_string()))
+                    } else {
+                        Ok(Loop::Continue((stream, response)))
+                    }
+                })
+            })
+        })
+        .and_then(|(stream, response)| {
+            let response = String::from_utf8_lossy(&response);
+            let status_line = response.lines().next().unwrap_or("");
+            match status_line.split_whitespace().nth(1) {
+                Some("200") => Ok(stream),
+                _ => Err(proxy_error(format!(
+                    "Proxy refused CONNECT: {}",
+                    status_line
+                ))),
+            }
+        })
+}
+
+/// Connect to `host:port` by SOCKS5 without authentication, the host is resolved by the proxy
+fn socks5_connect(
+    stream: TcpStream,
+    host: String,
+    port: u16,
+) -> impl Future<Item = TcpStream, Error = io::Error> + Send {
+    let mut request = vec![5, 1, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(1);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            request.push(3);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    let too_long = host.len() > 255;
+
+    future::result(if too_long {
+        Err(proxy_error(format!("Host {} is too long for SOCKS5", host)))
+    } else {
+        Ok(stream)
+    })
+    .and_then(|stream| write_all(stream, [5u8, 1, 0]))
+    .and_then(|(stream, _)| read_exact(stream, [0u8; 2]))
+    .and_then(|(stream, reply)| match reply {
+        [5, 0] => Ok(stream),
+        _ => Err(proxy_error(
+            "SOCKS5 proxy requires an unsupported authentication".to_string(),
+        )),
+    })
+    .and_then(move |stream| write_all(stream, request))
+    .and_then(|(stream, _)| read_exact(stream, [0u8; 4]))
+    .and_then(|(stream, reply)| {
+        if reply[1] != 0 {
+            return future::Either::A(future::err(proxy_error(format!(
+                "SOCKS5 proxy failed to connect, reply {}",
+                reply[1]
+            ))));
+        }
+        // Skip the bound address and port
+        let bound: Box<dyn Future<Item = TcpStream, Error = io::Error> + Send> = match reply[3] {
+            1 => Box::new(read_exact(stream, vec![0u8; 4 + 2]).map(|(stream, _)| stream)),
+            4 => Box::new(read_exact(stream, vec![0u8; 16 + 2]).map(|(stream, _)| stream)),
+            3 => Box::new(read_exact(stream, [0u8; 1]).and_then(|(stream, len)| {
+                read_exact(stream, vec![0u8; len[0] as usize + 2]).map(|(stream, _)| stream)
+            })),
+            kind => Box::new(future::err(proxy_error(format!(
+                "SOCKS5 proxy replied an unknown address type {}",
+                kind
+            )))),
+        };
+        future::Either::B(bound)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Proxy, ProxyConfig};
+    use crate::client::basic::Client;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    /// Answer one JSON-RPC request on the stream with height 0x10,
+    /// return the request line
+    fn serve_block_number(stream: TcpStream) -> String {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            let lower = line.to_lowercase();
+            if lower.starts_with("content-length:") {
+                length = lower["content-length:".len()..].trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0u8; length];
+        reader.read_exact(&mut body).unwrap();
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let response = format!(
+            r#"{{"jsonrpc":"2.0","id":{},"result":"0x10"}}"#,
+            request["id"]
+        );
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+        request_line.trim_end().to_string()
+    }
+
+    #[test]
+    fn test_http_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve_block_number(listener.accept().unwrap().0));
+
+        let client = Client::new()
+            .set_uri("http://cita.invalid:1337")
+            .with_http_proxy(&format!("http://{}", proxy));
+        assert_eq!(client.get_current_height().unwrap(), 16);
+        assert_eq!(
+            server.join().unwrap(),
+            "POST http://cita.invalid:1337/ HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_socks5_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let mut stream = listener.accept().unwrap().0;
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).unwrap();
+            assert_eq!(greeting, [5, 1, 0]);
+            stream.write_all(&[5, 0]).unwrap();
+
+            let mut request = [0u8; 5];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(request, [5, 1, 0, 3, "cita.invalid".len() as u8]);
+            let mut target = vec![0u8; request[4] as usize + 2];
+            stream.read_exact(&mut target).unwrap();
+            stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+
+            let port = u16::from_be_bytes([target[target.len() - 2], target[target.len() - 1]]);
+            let host = String::from_utf8(target[..target.len() - 2].to_vec()).unwrap();
+            (host, port, serve_block_number(stream))
+        });
+
+        let client = Client::new()
+            .set_uri("http://cita.invalid:1337")
+            .with_socks5_proxy(proxy);
+        assert_eq!(client.get_current_height().unwrap(), 16);
+        assert_eq!(
+            server.join().unwrap(),
+            (
+                "cita.invalid".to_string(),
+                1337,
+                "POST / HTTP/1.1".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_proxy_from_vars() {
+        let mut vars = HashMap::new();
+        vars.insert("HTTP_PROXY", "127.0.0.1:3128");
+        vars.insert("https_proxy", "socks5://127.0.0.1:1080");
+        vars.insert("HTTPS_PROXY", "http://127.0.0.1:3128");
+        vars.insert("no_proxy", "localhost, .cita.internal");
+        let config = ProxyConfig::from_vars(|name| vars.get(name).map(|value| value.to_string()));
+
+        let http = Proxy::Http("127.0.0.1:3128".parse().unwrap());
+        let socks5 = Proxy::Socks5("127.0.0.1:1080".parse().unwrap());
+        assert_eq!(config.proxy_for("http", "node.cita.io"), Some(&http));
+        assert_eq!(config.proxy_for("https", "node.cita.io"), Some(&socks5));
+        assert_eq!(config.proxy_for("http", "LOCALHOST"), None);
+        assert_eq!(config.proxy_for("https", "node.cita.internal"), None);
+        assert_eq!(config.proxy_for("https", "cita.internal"), None);
+        assert_eq!(config.proxy_for("https", "notcita.internal"), Some(&socks5));
+
+        assert_eq!(ProxyConfig::from_vars(|_| None), ProxyConfig::default());
+        assert!(Proxy::parse("ftp://127.0.0.1:21").is_err());
+    }
+}
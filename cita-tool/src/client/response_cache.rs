@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+
+use log::debug;
+use lru::LruCache;
+
+use crate::client::remove_0x;
+use crate::rpctypes::{JsonRpcParams, JsonRpcResponse, ParamsValue};
+
+/// Read-only methods whose responses can be cached
+const CACHEABLE_METHODS: [&str; 4] = ["call", "getAbi", "getCode", "getMetaData"];
+/// Height whose state changes with every transaction, requests at it are never cached
+const PENDING_HEIGHT: &str = "pending";
+const CAPACITY: usize = 256;
+
+struct CacheEntry {
+    address: Option<String>,
+    created: Instant,
+    response: JsonRpcResponse,
+}
+
+/// Cache of read-only responses, keyed by url, method and params
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    entries: LruCache<String, CacheEntry>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        ResponseCache {
+            ttl,
+            entries: LruCache::new(CAPACITY),
+        }
+    }
+
+    /// Get the response younger than ttl
+    pub(crate) fn get(&mut self, url: &str, params: &JsonRpcParams) -> Option<JsonRpcResponse> {
+        let key = Self::key(url, params)?;
+        let ttl = self.ttl;
+        match self
+            .entries
+            .get(&key)
+            .map(|entry| (entry.created.elapsed(), entry.response.clone()))
+        {
+            Some((age, response)) if age < ttl => {
+                debug!("Cache hit: {}", key);
+                Some(response)
+            }
+            Some(_) => {
+                self.entries.pop(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache the successful response of read-only method
+    pub(crate) fn insert(&mut self, url: &str, params: &JsonRpcParams, response: &JsonRpcResponse) {
+        if !response.is_ok() {
+            return;
+        }
+        if let Some(key) = Self::key(url, params) {
+            self.entries.put(
+                key,
+                CacheEntry {
+                    address: Self::address(params),
+                    created: Instant::now(),
+                    response: response.clone(),
+                },
+            );
+        }
+    }
+
+    /// Remove the entries of the contract address, after it is written
+    pub(crate) fn invalidate(&mut self, address: &str) {
+        let address = remove_0x(address).to_lowercase();
+        let keys = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.address.as_ref() == Some(&address))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<String>>();
+        for key in keys {
+            self.entries.pop(&key);
+        }
+    }
+
+    fn key(url: &str, params: &JsonRpcParams) -> Option<String> {
+        match params.get("method") {
+            Some(ParamsValue::String(method))
+                if CACHEABLE_METHODS.contains(&method.as_str()) && !Self::is_pending(params) =>
+            {
+                // Converted to `serde_json::Value` so that the keys of maps are sorted
+                serde_json::to_value(params)
+                    .map(|value| format!("{} {}", url, value))
+                    .ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the request is at `pending` height
+    fn is_pending(params: &JsonRpcParams) -> bool {
+        match params.get("params") {
+            Some(ParamsValue::List(list)) => list.iter().any(|value| match value {
+                ParamsValue::String(height) => height == PENDING_HEIGHT,
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+
+    /// The contract address of `call`, `getAbi` and `getCode`
+    fn address(params: &JsonRpcParams) -> Option<String> {
+        let address = match params.get("params") {
+            Some(ParamsValue::List(list)) => match list.first() {
+                Some(ParamsValue::Map(object)) => object.get("to"),
+                value => value,
+            },
+            _ => None,
+        };
+        match address {
+            Some(ParamsValue::Address(address)) => Some(format!("{:x}", address)),
+            Some(address) => address
+                .as_str()
+                .map(|address| remove_0x(address).to_lowercase()),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ResponseCache;
+    use crate::rpctypes::{JsonRpcParams, JsonRpcResponse, ParamsValue};
+    use std::time::Duration;
+
+    const URL: &str = "http://127.0.0.1:1337";
+
+    fn get_abi(address: &str, height: &str) -> JsonRpcParams {
+        JsonRpcParams::new()
+            .insert("method", ParamsValue::String(String::from("getAbi")))
+            .insert(
+                "params",
+                ParamsValue::List(vec![
+                    ParamsValue::address(address).unwrap(),
+                    ParamsValue::String(String::from(height)),
+                ]),
+            )
+    }
+
+    #[test]
+    fn test_cache_and_invalidate() {
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":"0x"}"#).unwrap();
+        let mut cache = ResponseCache::new(Duration::from_secs(60));
+        let params = get_abi("0xffffffffffffffffffffffffffffffffff020009", "0x10");
+        cache.insert(URL, &params, &response);
+        assert!(cache.get(URL, &params).is_some());
+        assert!(cache.get("http://127.0.0.1:1338", &params).is_none());
+        assert!(cache
+            .get(
+                URL,
+                &get_abi("0xffffffffffffffffffffffffffffffffff02000a", "0x10")
+            )
+            .is_none());
+
+        cache.invalidate("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF020009");
+        assert!(cache.get(URL, &params).is_none());
+
+        let block_number =
+            JsonRpcParams::new().insert("method", ParamsValue::String(String::from("blockNumber")));
+        cache.insert(URL, &block_number, &response);
+        assert!(cache.get(URL, &block_number).is_none());
+
+        let latest = get_abi("0xffffffffffffffffffffffffffffffffff020009", "latest");
+        cache.insert(URL, &latest, &response);
+        assert!(cache.get(URL, &latest).is_some());
+
+        let pending = get_abi("0xffffffffffffffffffffffffffffffffff020009", "pending");
+        cache.insert(URL, &pending, &response);
+        assert!(cache.get(URL, &pending).is_none());
+
+        let mut cache = ResponseCache::new(Duration::from_secs(0));
+        cache.insert(URL, &latest, &response);
+        assert!(cache.get(URL, &latest).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::rpctypes::Version;
+
+/// The first chain version whose methods have no `cita_`, `eth_` or `net_` prefix
+const UNPREFIXED_SINCE: Version = Version {
+    major: 0,
+    minor: 20,
+    patch: 0,
+};
+
+/// JSON-RPC methods known by the client
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KnownMethod {
+    /// blockNumber
+    BlockNumber,
+    /// getMetaData
+    GetMetaData,
+    /// sendRawTransaction
+    SendRawTransaction,
+    /// peerCount
+    PeerCount,
+    /// peersInfo
+    PeersInfo,
+    /// getBlockByHash
+    GetBlockByHash,
+    /// getBlockByNumber
+    GetBlockByNumber,
+    /// getTransaction
+    GetTransaction,
+    /// getTransactionProof
+    GetTransactionProof,
+    /// getTransactionReceipt
+    GetTransactionReceipt,
+    /// getLogs
+    GetLogs,
+    /// call
+    Call,
+    /// getTransactionCount
+    GetTransactionCount,
+    /// getCode
+    GetCode,
+    /// getAbi
+    GetAbi,
+    /// getBalance
+    GetBalance,
+    /// newFilter
+    NewFilter,
+    /// newBlockFilter
+    NewBlockFilter,
+    /// uninstallFilter
+    UninstallFilter,
+    /// getFilterChanges
+    GetFilterChanges,
+    /// getFilterLogs
+    GetFilterLogs,
+    /// getBlockHeader
+    GetBlockHeader,
+    /// getStateProof
+    GetStateProof,
+    /// getStorageAt
+    GetStorageAt,
+    /// getVersion
+    GetVersion,
+    /// estimateQuota
+    EstimateQuota,
+}
+
+impl KnownMethod {
+    /// Method name since CITA 0.20
+    pub fn name(self) -> &'static str {
+        match self {
+            KnownMethod::BlockNumber => "blockNumber",
+            KnownMethod::GetMetaData => "getMetaData",
+            KnownMethod::SendRawTransaction => "sendRawTransaction",
+            KnownMethod::PeerCount => "peerCount",
+            KnownMethod::PeersInfo => "peersInfo",
+            KnownMethod::GetBlockByHash => "getBlockByHash",
+            KnownMethod::GetBlockByNumber => "getBlockByNumber",
+            KnownMethod::GetTransaction => "getTransaction",
+            KnownMethod::GetTransactionProof => "getTransactionProof",
+            KnownMethod::GetTransactionReceipt => "getTransactionReceipt",
+            KnownMethod::GetLogs => "getLogs",
+            KnownMethod::Call => "call",
+            KnownMethod::GetTransactionCount => "getTransactionCount",
+            KnownMethod::GetCode => "getCode",
+            KnownMethod::GetAbi => "getAbi",
+            KnownMethod::GetBalance => "getBalance",
+            KnownMethod::NewFilter => "newFilter",
+            KnownMethod::NewBlockFilter => "newBlockFilter",
+            KnownMethod::UninstallFilter => "uninstallFilter",
+            KnownMethod::GetFilterChanges => "getFilterChanges",
+            KnownMethod::GetFilterLogs => "getFilterLogs",
+            KnownMethod::GetBlockHeader => "getBlockHeader",
+            KnownMethod::GetStateProof => "getStateProof",
+            KnownMethod::GetStorageAt => "getStorageAt",
+            KnownMethod::GetVersion => "getVersion",
+            KnownMethod::EstimateQuota => "estimateQuota",
+        }
+    }
+
+    /// Method name before CITA 0.20, methods added later keep their name
+    fn legacy_name(self) -> &'static str {
+        match self {
+            KnownMethod::BlockNumber => "cita_blockNumber",
+            KnownMethod::GetMetaData => "cita_getMetaData",
+            KnownMethod::SendRawTransaction => "cita_sendRawTransaction",
+            KnownMethod::PeerCount => "net_peerCount",
+            KnownMethod::GetBlockByHash => "cita_getBlockByHash",
+            KnownMethod::GetBlockByNumber => "cita_getBlockByNumber",
+            KnownMethod::GetTransaction => "cita_getTransaction",
+            KnownMethod::GetTransactionProof => "cita_getTransactionProof",
+            KnownMethod::GetTransactionReceipt => "eth_getTransactionReceipt",
+            KnownMethod::GetLogs => "eth_getLogs",
+            KnownMethod::Call => "eth_call",
+            KnownMethod::GetTransactionCount => "eth_getTransactionCount",
+            KnownMethod::GetCode => "eth_getCode",
+            KnownMethod::GetAbi => "eth_getAbi",
+            KnownMethod::GetBalance => "eth_getBalance",
+            KnownMethod::NewFilter => "eth_newFilter",
+            KnownMethod::NewBlockFilter => "eth_newBlockFilter",
+            KnownMethod::UninstallFilter => "eth_uninstallFilter",
+            KnownMethod::GetFilterChanges => "eth_getFilterChanges",
+            KnownMethod::GetFilterLogs => "eth_getFilterLogs",
+            method => method.name(),
+        }
+    }
+}
+
+/// The name of the method on a chain of the version
+pub fn rpc_method_name(method: KnownMethod, chain_version: &Version) -> &'static str {
+    if *chain_version >= UNPREFIXED_SINCE {
+        method.name()
+    } else {
+        method.legacy_name()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rpc_method_name, KnownMethod};
+    use crate::rpctypes::Version;
+
+    #[test]
+    fn test_rpc_method_name() {
+        let version = |version: &str| version.parse::<Version>().unwrap();
+        assert_eq!(
+            rpc_method_name(KnownMethod::GetTransaction, &version("0.19.1")),
+            "cita_getTransaction"
+        );
+        assert_eq!(
+            rpc_method_name(KnownMethod::Call, &version("0.19.1")),
+            "eth_call"
+        );
+        assert_eq!(
+            rpc_method_name(KnownMethod::GetVersion, &version("0.19.1")),
+            "getVersion"
+        );
+        assert_eq!(
+            rpc_method_name(KnownMethod::GetTransaction, &version("0.20.0")),
+            "getTransaction"
+        );
+        assert_eq!(
+            rpc_method_name(KnownMethod::PeerCount, &version("1.0.0")),
+            "peerCount"
+        );
+    }
+}
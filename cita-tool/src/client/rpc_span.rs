@@ -0,0 +1,63 @@
+use hyper::Uri;
+
+#[cfg(feature = "tracing")]
+use opentelemetry::{
+    global::{self, BoxedSpan},
+    trace::{Span, Tracer},
+    KeyValue,
+};
+#[cfg(feature = "tracing")]
+use std::sync::{Arc, Mutex};
+
+/// The parent context of request spans
+#[cfg(feature = "tracing")]
+pub(crate) type OtelContext = opentelemetry::Context;
+/// The parent context of request spans, nothing without `tracing` feature
+#[cfg(not(feature = "tracing"))]
+pub(crate) type OtelContext = ();
+
+/// Span `cita.rpc.send` of one JSON-RPC request,
+/// a no-op without `tracing` feature
+#[derive(Clone)]
+pub(crate) struct RpcSpan {
+    #[cfg(feature = "tracing")]
+    span: Arc<Mutex<BoxedSpan>>,
+}
+
+#[cfg(feature = "tracing")]
+impl RpcSpan {
+    pub(crate) fn start(context: Option<&OtelContext>, method: &str, url: &Uri) -> Self {
+        let context = context.cloned().unwrap_or_else(OtelContext::current);
+        let mut span = global::tracer("cita-tool").start_with_context("cita.rpc.send", context);
+        span.set_attribute(KeyValue::new("rpc.method", method.to_string()));
+        span.set_attribute(KeyValue::new("net.peer.address", url.to_string()));
+        RpcSpan {
+            span: Arc::new(Mutex::new(span)),
+        }
+    }
+
+    pub(crate) fn record_status(&self, status_code: u16) {
+        self.span
+            .lock()
+            .unwrap()
+            .set_attribute(KeyValue::new("rpc.status_code", i64::from(status_code)));
+    }
+
+    pub(crate) fn end(&self) {
+        self.span.lock().unwrap().end();
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RpcSpan {
+    #[inline]
+    pub(crate) fn start(_context: Option<&OtelContext>, _method: &str, _url: &Uri) -> Self {
+        RpcSpan {}
+    }
+
+    #[inline]
+    pub(crate) fn record_status(&self, _status_code: u16) {}
+
+    #[inline]
+    pub(crate) fn end(&self) {}
+}
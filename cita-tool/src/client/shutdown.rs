@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures::task::{self, Task};
+use futures::{Async, Future, Poll};
+use log::info;
+
+/// Shutdown state of a client and its clones
+#[derive(Default)]
+pub(crate) struct Shutdown {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    waiting: Mutex<Vec<Task>>,
+}
+
+impl Shutdown {
+    /// Stop accepting new requests
+    pub(crate) fn initiate(&self) {
+        if !self.draining.swap(true, Ordering::SeqCst) {
+            info!(
+                "Shutting down, {} requests in flight",
+                self.in_flight.load(Ordering::SeqCst)
+            );
+        }
+    }
+
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Count a request in flight until the guard is dropped
+    pub(crate) fn start(this: &Arc<Self>) -> InFlight {
+        this.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlight(Arc::clone(this))
+    }
+
+    /// Completes when no request is in flight
+    pub(crate) fn drain(this: &Arc<Self>) -> Drain {
+        Drain(Arc::clone(this))
+    }
+}
+
+/// Guard of a request in flight, dropped when the request resolves or is dropped
+pub(crate) struct InFlight(Arc<Shutdown>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            for task in self.0.waiting.lock().unwrap().drain(..) {
+                task.notify();
+            }
+        }
+    }
+}
+
+/// Future of `Client::drain_pending`
+pub(crate) struct Drain(Arc<Shutdown>);
+
+impl Future for Drain {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if self.0.in_flight.load(Ordering::SeqCst) == 0 {
+            return Ok(Async::Ready(()));
+        }
+        self.0.waiting.lock().unwrap().push(task::current());
+        // The last request may have resolved before the task is registered
+        if self.0.in_flight.load(Ordering::SeqCst) == 0 {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Shutdown;
+    use futures::Future;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_drain() {
+        let shutdown = Arc::new(Shutdown::default());
+        assert!(Shutdown::drain(&shutdown).wait().is_ok());
+
+        let first = Shutdown::start(&shutdown);
+        let second = Shutdown::start(&shutdown);
+        shutdown.initiate();
+        assert!(shutdown.is_draining());
+        let requests = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(first);
+            thread::sleep(Duration::from_millis(20));
+            drop(second);
+        });
+        assert!(Shutdown::drain(&shutdown).wait().is_ok());
+        requests.join().unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+use std::convert::TryFrom;
+use std::mem;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use futures::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use log::warn;
+use serde_json::{json, Value};
+use tungstenite::client::AutoStream;
+use tungstenite::{connect, Message, WebSocket};
+
+use crate::client::remove_0x;
+use crate::error::ToolError;
+use crate::rpctypes::{BlockHeader, Log, ParamsValue};
+use types::H256;
+
+/// Subscription of the headers of the new blocks
+const NEW_BLOCK_HEADERS: &str = "newBlockHeaders";
+/// Subscription of the hashes of the transactions entering the pool
+const NEW_PENDING_TRANSACTIONS: &str = "newPendingTransactions";
+/// Subscription of the new logs matching a filter
+const LOGS: &str = "logs";
+/// Longest wait between two reconnections
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+type Socket = WebSocket<AutoStream>;
+
+/// Subscribe the new block headers
+pub(crate) fn subscribe_block_headers(
+    url: String,
+) -> Result<UnboundedReceiver<Result<BlockHeader, ToolError>>, ToolError> {
+    subscribe_items(url, json!([NEW_BLOCK_HEADERS]), parse_header)
+}
+
+/// Subscribe the hashes of the pending transactions
+pub(crate) fn subscribe_pending_transactions(
+    url: String,
+) -> Result<UnboundedReceiver<Result<H256, ToolError>>, ToolError> {
+    subscribe_items(url, json!([NEW_PENDING_TRANSACTIONS]), |result| {
+        result
+            .as_str()
+            .and_then(|hash| H256::from_str(remove_0x(hash)).ok())
+            .ok_or_else(|| ToolError::Customize(format!("{} is not a hash", result)))
+    })
+}
+
+/// Subscribe the new logs matching the filter, `fromBlock` and `toBlock` are ignored
+pub(crate) fn subscribe_logs(
+    url: String,
+    filter: Value,
+) -> Result<UnboundedReceiver<Result<Log, ToolError>>, ToolError> {
+    subscribe_items(url, json!([LOGS, filter]), |result| {
+        serde_json::from_value::<ParamsValue>(result)
+            .map_err(ToolError::SerdeJson)
+            .and_then(|log| Log::try_from(&log))
+    })
+}
+
+/// Subscribe with the params of `eth_subscribe`, the items are received and parsed in a background thread
+///
+/// When the connection is lost, `ToolError::Reconnecting` is yielded and the thread
+/// reconnects until it succeeds. The thread stops at the first item after the
+/// receiver is dropped.
+fn subscribe_items<T, F>(
+    url: String,
+    params: Value,
+    parse: F,
+) -> Result<UnboundedReceiver<Result<T, ToolError>>, ToolError>
+where
+    T: Send + 'static,
+    F: Fn(Value) -> Result<T, ToolError> + Send + 'static,
+{
+    let mut socket = subscribe(&url, &params)?;
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        while forward_items(&mut socket, &sender, &parse) {
+            if sender.unbounded_send(Err(ToolError::Reconnecting)).is_err() {
+                return;
+            }
+            socket = reconnect(&url, &params);
+        }
+    });
+    Ok(receiver)
+}
+
+fn websocket_error(err: tungstenite::Error) -> ToolError {
+    ToolError::Customize(format!("WebSocket error: {}", err))
+}
+
+/// Connect and send `eth_subscribe`, wait for its response,
+/// `ToolError::Unsupported` if the node rejects the subscription
+fn subscribe(url: &str, params: &Value) -> Result<Socket, ToolError> {
+    let (mut socket, _) = connect(url).map_err(websocket_error)?;
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_subscribe",
+        "params": params,
+    });
+    socket
+        .write_message(Message::Text(request.to_string()))
+        .map_err(websocket_error)?;
+    loop {
+        match socket.read_message().map_err(websocket_error)? {
+            Message::Text(text) => {
+                let response: Value = serde_json::from_str(&text).map_err(ToolError::SerdeJson)?;
+                if let Some(error) = response.get("error") {
+                    return Err(ToolError::Unsupported(format!(
+                        "subscription {} rejected: {}",
+                        params, error
+                    )));
+                }
+                if response.get("id") == Some(&json!(1)) {
+                    return Ok(socket);
+                }
+            }
+            Message::Close(_) => {
+                return Err(ToolError::Customize(
+                    "WebSocket closed before subscribing".to_string(),
+                ))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Subscribe again, waiting longer after every failure
+fn reconnect(url: &str, params: &Value) -> Socket {
+    let mut delay = Duration::from_secs(1);
+    loop {
+        thread::sleep(delay);
+        match subscribe(url, params) {
+            Ok(socket) => return socket,
+            Err(err) => warn!("Reconnecting to {} failed: {}", url, err),
+        }
+        delay = ::std::cmp::min(delay * 2, MAX_RECONNECT_DELAY);
+    }
+}
+
+/// Send the items until the connection is lost, `false` if the receiver is dropped
+fn forward_items<T, F>(
+    socket: &mut Socket,
+    sender: &UnboundedSender<Result<T, ToolError>>,
+    parse: &F,
+) -> bool
+where
+    F: Fn(Value) -> Result<T, ToolError>,
+{
+    loop {
+        let text = match socket.read_message() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return true,
+            Ok(_) => continue,
+        };
+        if let Some(item) = parse_notification(&text).map(|result| result.and_then(parse)) {
+            if sender.unbounded_send(item).is_err() {
+                return false;
+            }
+        }
+    }
+}
+
+/// Result of an `eth_subscription` notification, `None` for other messages
+fn parse_notification(text: &str) -> Option<Result<Value, ToolError>> {
+    let mut notification: Value = match serde_json::from_str(text) {
+        Ok(notification) => notification,
+        Err(err) => return Some(Err(ToolError::SerdeJson(err))),
+    };
+    if notification.get("method") != Some(&json!("eth_subscription")) {
+        return None;
+    }
+    Some(Ok(notification
+        .pointer_mut("/params/result")
+        .map(|result| mem::replace(result, Value::Null))
+        .unwrap_or(Value::Null)))
+}
+
+/// The header may be a whole block, or the header fields with the block hash
+fn parse_header(result: Value) -> Result<BlockHeader, ToolError> {
+    let block = if result.get("header").is_some() {
+        result
+    } else {
+        json!({ "hash": result["hash"].clone(), "header": result })
+    };
+    serde_json::from_value::<ParamsValue>(block)
+        .map_err(ToolError::SerdeJson)
+        .and_then(|block| BlockHeader::try_from(&block))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_header, parse_notification};
+
+    #[test]
+    fn test_parse_notification() {
+        let result = parse_notification(
+            r#"{
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": {
+                    "subscription": "0x1",
+                    "result": {
+                        "hash": "0x0000000000000000000000000000000000000000000000000000000000000020",
+                        "timestamp": 1563254433005,
+                        "prevHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        "number": "0x20",
+                        "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                        "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                        "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000004",
+                        "proposer": "0x5a2a1c3b6b0f0e6e3a4e2f7f4c0b1c6e0d1e5b9a"
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+        .unwrap();
+        let header = parse_header(result).unwrap();
+        assert_eq!(header.number, 32);
+        assert_eq!(header.timestamp, 1_563_254_433_005);
+
+        assert!(parse_notification(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#).is_none());
+        assert!(parse_notification("not json").unwrap().is_err());
+    }
+}
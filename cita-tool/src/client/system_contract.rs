@@ -1,33 +1,115 @@
-use crate::client::basic::ClientExt;
-use crate::client::{remove_0x, TransactionOptions};
+use crate::client::{remove_0x_validated, TransactionOptions};
 
-use std::str::{self, FromStr};
+use std::str::FromStr;
 
-use crate::abi::contract_encode_input;
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+use crate::abi::contract_decode_output;
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+use crate::client::basic::Client;
+use crate::client::remove_0x;
 use crate::error::ToolError;
-use crate::rpctypes::JsonRpcResponse;
-use crate::LowerHex;
-use ethabi::{Address, Contract};
+use crate::rpctypes::{JsonRpcResponse, ParamsValue, ResponseValue};
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+use ethabi::Contract;
+use ethabi::{Address, Function, Token};
 use failure::Fail;
-use tool_derive::ContractExt;
+#[cfg(any(feature = "node", feature = "quota", feature = "sysconfig"))]
 use types::U256;
 
-/// Group Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020009")]
-#[contract(path = "../../contract_abi/Group.abi")]
-#[contract(name = "GroupExt")]
-pub struct GroupClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
+#[cfg(feature = "admin")]
+mod admin;
+#[cfg(all(
+    feature = "admin",
+    feature = "group",
+    feature = "node",
+    feature = "permission",
+    feature = "quota",
+    feature = "role"
+))]
+mod audit;
+#[cfg(feature = "batch")]
+mod batch;
+#[cfg(feature = "chain")]
+mod chain;
+#[cfg(feature = "group")]
+mod group;
+#[cfg(all(
+    feature = "admin",
+    feature = "group",
+    feature = "node",
+    feature = "permission",
+    feature = "quota",
+    feature = "role"
+))]
+mod migration;
+#[cfg(feature = "node")]
+mod node;
+#[cfg(feature = "permission")]
+mod permission;
+#[cfg(feature = "quota")]
+mod quota;
+#[cfg(feature = "role")]
+mod role;
+#[cfg(feature = "sysconfig")]
+mod sys_config;
+
+#[cfg(feature = "admin")]
+pub use self::admin::{AdminClient, AdminExt};
+#[cfg(all(
+    feature = "admin",
+    feature = "group",
+    feature = "node",
+    feature = "permission",
+    feature = "quota",
+    feature = "role"
+))]
+pub use self::audit::{
+    GovernanceAudit, GroupAudit, NodeAudit, PermissionAudit, QuotaAudit, RoleAudit,
+};
+#[cfg(feature = "batch")]
+pub use self::batch::{
+    decode_multi_receipt, pack_transactions, BatchTxClient, BatchTxExt, SubCallResult,
+};
+#[cfg(feature = "chain")]
+pub use self::chain::{CrossChainClient, CrossChainExt};
+#[cfg(feature = "group")]
+pub use self::group::{
+    FindOptions, GroupClient, GroupExt, GroupInfo, GroupManageClient, GroupManagementExt,
+    MembershipDiff,
+};
+#[cfg(all(
+    feature = "admin",
+    feature = "group",
+    feature = "node",
+    feature = "permission",
+    feature = "quota",
+    feature = "role"
+))]
+pub use self::migration::{GovernanceExport, ImportReport};
+#[cfg(feature = "node")]
+pub use self::node::{ConsensusStatus, NodeManageClient, NodeManagementExt, StakeChange};
+#[cfg(feature = "permission")]
+pub use self::permission::{
+    AuthorizationClient, AuthorizationExt, IntegrityReport, PermissionClient, PermissionExt,
+    PermissionInfo, PermissionManageClient, PermissionManagementExt, Recommendation, Resource,
+};
+#[cfg(feature = "quota")]
+pub use self::quota::{BqlChange, BqlSuggestion, QuotaManageClient, QuotaManagementExt};
+#[cfg(feature = "role")]
+pub use self::role::{
+    PermissionDiff, RoleClient, RoleExt, RoleInfo, RoleManageClient, RoleManagementExt,
+};
+#[cfg(feature = "sysconfig")]
+pub use self::sys_config::{
+    EmergencyBrakeClient, EmergencyBrakeExt, PriceManagerClient, PriceManagerExt, SysConfigClient,
+    SysConfigExt, TokenInfo, VersionManagerClient, VersionManagerExt,
+};
 
 /// Call/SendTx to a contract method
 pub trait ContractCall<R, E>
 where
     R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
+    E: Fail + From<ToolError>,
 {
     /// Prepare contract call arguments
     fn prepare_call_args(
@@ -37,12 +119,13 @@ where
         to_addr: Option<Address>,
     ) -> Result<(String, String), E>;
 
-    /// SendTx a contract method
+    /// SendTx a contract method, the code and address of `options` are replaced
+    /// by the call data and the contract address
     fn contract_send_tx(
         &mut self,
         name: &str,
         values: &[&str],
-        quota: Option<u64>,
+        options: TransactionOptions,
         to_addr: Option<Address>,
     ) -> Result<R, E>;
 
@@ -55,1054 +138,218 @@ where
         height: Option<&str>,
     ) -> Result<R, E>;
 
-    /// Call a contract method with a to_address
-    fn contract_call_to_address(
-        &self,
-        function_name: &str,
-        values: &[&str],
-        address: &str,
-        height: Option<&str>,
-    ) -> Result<R, E> {
-        let address = Address::from_str(remove_0x(address)).unwrap();
-        self.contract_call(function_name, values, Some(address), height)
-    }
-}
-
-/// Group System Contract
-pub trait GroupExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Query the information of the group
-    fn query_info(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryInfo", &[], address, height)
-    }
-    /// Query the name of the group
-    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryName", &[], address, height)
-    }
-    /// Query the accounts of the group
-    fn query_accounts(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryAccounts", &[], address, height)
-    }
-    /// Alias for query_child
-    fn query_children(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.query_child(address, height)
-    }
-    /// Query the children of the group
-    fn query_child(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryChild", &[], address, height)
-    }
-    /// Alias for query_child_length
-    fn query_children_length(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.query_child_length(address, height)
-    }
-    /// Query the length of children of the group
-    fn query_child_length(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryChildLength", &[], address, height)
-    }
-    /// Query the parent of the group
-    fn query_parent(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryParent", &[], address, height)
-    }
-    /// Check the account in the group
-    fn in_group(&self, address: &str, account_address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("inGroup", &[remove_0x(account_address)], address, height)
-    }
-}
-
-/// Group manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000a")]
-#[contract(path = "../../contract_abi/GroupManagement.abi")]
-#[contract(name = "GroupManagementExt")]
-pub struct GroupManageClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// GroupManagement System Contract
-pub trait GroupManagementExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Create a new group
-    fn new_group(
-        &mut self,
-        origin: &str,
-        name: &str,
-        accounts: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(origin), name, accounts];
-        self.contract_send_tx("newGroup", &values, quota, None)
-    }
-
-    /// Delete the group
-    fn delete_group(&mut self, origin: &str, target: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(origin), remove_0x(target)];
-        self.contract_send_tx("deleteGroup", &values, quota, None)
-    }
-
-    /// Update the group name
-    fn update_group_name(
-        &mut self,
-        origin: &str,
-        target: &str,
-        name: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(origin), remove_0x(target), name];
-        self.contract_send_tx("updateGroupName", &values, quota, None)
-    }
-
-    /// Add accounts
-    fn add_accounts(
-        &mut self,
-        origin: &str,
-        target: &str,
-        accounts: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(origin), remove_0x(target), accounts];
-        self.contract_send_tx("addAccounts", &values, quota, None)
-    }
-
-    /// Delete accounts
-    fn delete_accounts(
-        &mut self,
-        origin: &str,
-        target: &str,
-        accounts: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(origin), remove_0x(target), accounts];
-        self.contract_send_tx("deleteAccounts", &values, quota, None)
-    }
-
-    /// Check the target group in the scope of the origin group
-    ///   Scope: the origin group is the ancestor of the target group
-    fn check_scope(&self, origin: &str, target: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(origin), remove_0x(target)];
-        self.contract_call("checkScope", &values, None, height)
-    }
-
-    /// Query all groups
-    fn query_groups(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("queryGroups", &[], None, height)
-    }
-}
-
-/// Role Client
-#[derive(ContractExt)]
-#[contract(addr = "0x")]
-#[contract(path = "../../contract_abi/Role.abi")]
-#[contract(name = "RoleExt")]
-pub struct RoleClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// Role system contract
-pub trait RoleExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Query the information of the role
-    ///
-    /// return The information of role: name and permissions
-    fn query_role(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryRole", &[], address, height)
-    }
-
-    /// Query the name of the role
-    ///
-    /// return The name of role
-    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryName", &[], address, height)
-    }
-
-    /// Query the permissions of the role
-    ///
-    /// return The permissions of role
-    fn query_permissions(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryPermissions", &[], address, height)
-    }
-
-    /// Query the length of the permissions
-    ///
-    /// return The number of permission
-    fn length_of_permissions(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("lengthOfPermissions", &[], address, height)
-    }
-
-    /// Check the duplicate permission
-    ///
-    /// return true if in permissions, otherwise false
-    fn in_permissions(
-        &self,
-        address: &str,
-        permission: &str,
-        height: Option<&str>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(permission)];
-        self.contract_call_to_address("inPermissions", &values, address, height)
-    }
-}
-
-/// Role manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020007")]
-#[contract(path = "../../contract_abi/RoleManagement.abi")]
-#[contract(name = "RoleManagementExt")]
-pub struct RoleManageClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// RoleManagement system contract
-pub trait RoleManagementExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Create a new role
-    ///
-    /// param name: The name of role
-    /// param permissions: The permissions of role
-    /// return New role's address
-    fn new_role(&mut self, name: &str, permissions: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [name, permissions];
-        self.contract_send_tx("newRole", &values, quota, None)
-    }
-
-    /// Delete the role
-    ///
-    /// param role: The address of role
-    /// return true if successed, otherwise false
-    fn delete_role(&mut self, role: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(role)];
-        self.contract_send_tx("deleteRole", &values, quota, None)
-    }
-
-    /// Update role's name
-    ///
-    /// param role: The address of role
-    /// param name: The new name of role
-    /// return true if successed, otherwise false
-    fn update_role_name(&mut self, role: &str, name: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(role), name];
-        self.contract_send_tx("updateRoleName", &values, quota, None)
-    }
-
-    /// Add permissions of role
-    ///
-    /// param role: The address of role
-    /// param permissions: The permissions of role
-    /// return true if successed, otherwise false
-    fn add_permissions(
-        &mut self,
-        role: &str,
-        permissions: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(role), permissions];
-        self.contract_send_tx("addPermissions", &values, quota, None)
-    }
-
-    /// Delete permissions of role
-    ///
-    /// param role: The address of role
-    /// param permissions: The permissions of role
-    /// return true if successed, otherwise false
-    fn delete_permissions(
-        &mut self,
-        role: &str,
-        permissions: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(role), permissions];
-        self.contract_send_tx("deletePermissions", &values, quota, None)
-    }
-
-    /// Set the role to the account
-    ///
-    /// param account: The account to be setted
-    /// param role: The role to be setted
-    /// return true if successed, otherwise false
-    fn set_role(&mut self, account: &str, role: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(account), remove_0x(role)];
-        self.contract_send_tx("setRole", &values, quota, None)
-    }
-
-    /// Cancel the account's role
-    ///
-    /// param account: The account to be canceled
-    /// param role: The role to be canceled
-    /// return true if successed, otherwise false
-    fn cancel_role(&mut self, account: &str, role: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(account), remove_0x(role)];
-        self.contract_send_tx("cancelRole", &values, quota, None)
-    }
-
-    /// Clear the account's role
-    ///
-    /// param account: The account to be cleared
-    /// return true if successed, otherwise false
-    fn clear_role(&mut self, account: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(account)];
-        self.contract_send_tx("clearRole", &values, quota, None)
-    }
-
-    /// Query the roles of the account
-    ///
-    /// param account: The account to be queried
-    /// return The roles of the account
-    fn query_roles(&self, account: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(account)];
-        let to = "0xffffffffffffffffffffffffffffffffff02000d";
-        self.contract_call_to_address("queryRoles", &values, &to, height)
-    }
-
-    /// Query the accounts that have the role
-    ///
-    /// param role: The role to be queried
-    /// return The accounts that have the role
-    fn query_accounts(&self, role: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(role)];
-        let to = "0xffffffffffffffffffffffffffffffffff02000d";
-        self.contract_call_to_address("queryAccounts", &values, &to, height)
-    }
-}
-
-/// Role manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020006")]
-#[contract(path = "../../contract_abi/Authorization.abi")]
-#[contract(name = "AuthorizationExt")]
-pub struct AuthorizationClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// Authorization system contract
-pub trait AuthorizationExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Query the account's permissions
-    ///
-    /// param account: The account to be queried
-    /// return The permissions of account
-    fn query_permissions(&self, account: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(account)];
-        self.contract_call("queryPermissions", &values, None, height)
-    }
-
-    /// Query the permission's accounts
-    ///
-    /// param permission: The permission to be queried
-    /// return The accounts of permission
-    fn query_accounts(&self, permission: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(permission)];
-        self.contract_call("queryAccounts", &values, None, height)
-    }
-
-    /// Query all accounts
-    ///
-    /// return All the accounts
-    fn query_all_accounts(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("queryAllAccounts", &[], None, height)
-    }
+    /// Get the function of the contract ABI by name
+    fn get_function(&self, name: &str) -> Result<&Function, E>;
 
-    /// Check Resource
-    ///
-    /// param account: The account to be checked
-    /// param contract: The contract of resource
-    /// param func: The function signature of resource
-    /// return true if passed, otherwise false
-    fn check_resource(
+    /// Call a contract method and decode the return data with the contract ABI
+    fn contract_call_decoded(
         &self,
-        account: &str,
-        contract: &str,
-        func: &str,
+        name: &str,
+        values: &[&str],
+        to_addr: Option<Address>,
         height: Option<&str>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account), remove_0x(contract), remove_0x(func)];
-        self.contract_call("checkResource", &values, None, height)
+    ) -> Result<Vec<Token>, E> {
+        let function = self.get_function(name)?;
+        let response = self.contract_call(name, values, to_addr, height)?;
+        let response: JsonRpcResponse = serde_json::to_value(&response)
+            .and_then(serde_json::from_value)
+            .map_err(ToolError::SerdeJson)?;
+        match response.into_result()? {
+            ResponseValue::Singe(ParamsValue::String(data)) => {
+                let data = hex::decode(remove_0x(&data)).map_err(ToolError::Decode)?;
+                function
+                    .decode_output(&data)
+                    .map_err(|err| ToolError::Abi(err.to_string()).into())
+            }
+            result => Err(ToolError::Customize(format!("Unexpected response: {}", result)).into()),
+        }
     }
 
-    /// Check account has a permission
-    ///
-    /// param _account The account to be checked
-    /// param _permission The address of permission
-    /// return true if passed, otherwise false
-    fn check_permission(
-        &self,
-        account: &str,
-        permission: &str,
-        height: Option<&str>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account), remove_0x(permission)];
-        self.contract_call("checkPermission", &values, None, height)
-    }
-}
-
-/// Permission Client
-#[derive(ContractExt)]
-#[contract(addr = "0x")]
-#[contract(path = "../../contract_abi/Permission.abi")]
-#[contract(name = "PermissionExt")]
-pub struct PermissionClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// Permission system contract
-pub trait PermissionExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Check resource in the permission
-    ///
-    /// param contract: The contract address of the resource
-    /// param func: The function signature of the resource
-    /// return true if in permission, otherwise false
-    fn in_permission(
+    /// Call a contract method with a to_address
+    fn contract_call_to_address(
         &self,
+        function_name: &str,
+        values: &[&str],
         address: &str,
-        contract: &str,
-        func: &str,
         height: Option<&str>,
     ) -> Result<R, E> {
-        let values = [remove_0x(contract), remove_0x(func)];
-        self.contract_call_to_address("inPermission", &values, address, height)
+        self.contract_call(function_name, values, Some(parse_address(address)?), height)
     }
-
-    /// Query the information of the permission
-    ///
-    /// return The information of permission: name and resources
-    fn query_info(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryInfo", &[], address, height)
-    }
-
-    /// Query the name of the permission
-    ///
-    /// return The name of permission
-    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryName", &[], address, height)
-    }
-
-    /// Query the resource of the permission
-    ///
-    /// return The resources of permission
-    fn query_resource(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call_to_address("queryResource", &[], address, height)
-    }
-}
-
-/// Permission manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020004")]
-#[contract(path = "../../contract_abi/PermissionManagement.abi")]
-#[contract(name = "PermissionManagementExt")]
-pub struct PermissionManageClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
 }
 
-/// PermissionManagement system contract
-pub trait PermissionManagementExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Create a new permission
-    ///
-    /// param name: The name of permission
-    /// param contracts: The contracts of resource
-    /// param funcs: The function signature of the resource
-    /// return New permission's address
-    fn new_permission(
-        &mut self,
-        name: &str,
-        contracts: &str,
-        funcs: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [name, contracts, funcs];
-        self.contract_send_tx("newPermission", &values, quota, None)
-    }
-
-    /// Delete the permission
-    ///
-    /// param permission: The address of permission
-    /// return true if successed, otherwise false
-    fn delete_permission(&mut self, permission: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(permission)];
-        self.contract_send_tx("deletePermission", &values, quota, None)
-    }
-
-    /// Update the permission name
-    ///
-    /// param permission: The address of permission
-    /// param name: The new name
-    /// return true if successed, otherwise false
-    fn update_permission_name(
-        &mut self,
-        permission: &str,
-        name: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(permission), name];
-        self.contract_send_tx("updatePermissionName", &values, quota, None)
-    }
-
-    /// Add the resources of permission
-    ///
-    /// param permission: The address of permission
-    /// param contracts: The contracts of resource
-    /// param funcs: The function signature of resource
-    /// return true if successed, otherwise false
-    fn add_resources(
-        &mut self,
-        permission: &str,
-        contracts: &str,
-        funcs: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(permission), contracts, funcs];
-        self.contract_send_tx("addResources", &values, quota, None)
-    }
-
-    /// Delete the resources of permission
-    ///
-    /// param permission: The address of permission
-    /// param contracts: The contracts of resource
-    /// param funcs: The function signature of resource
-    /// return true if successed, otherwise false
-    fn delete_resources(
-        &mut self,
-        permission: &str,
-        contracts: &str,
-        funcs: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(permission), contracts, funcs];
-        self.contract_send_tx("deleteResources", &values, quota, None)
-    }
-
-    /// Set permission to the account
-    ///
-    /// param account: The account to be setted
-    /// param permission: The permission to be setted
-    /// return true if success, otherwise false
-    fn set_authorization(
-        &mut self,
-        account_address: &str,
-        permission: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account_address), remove_0x(permission)];
-        self.contract_send_tx("setAuthorization", &values, quota, None)
-    }
-
-    /// Set multiple permissions to the account
-    ///
-    /// param account: The account to be setted
-    /// param permissions: The multiple permissions to be setted
-    /// return true if success, otherwise false
-    fn set_authorizations(
-        &mut self,
-        account_address: &str,
-        permissions: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account_address), permissions];
-        self.contract_send_tx("setAuthorizations", &values, quota, None)
-    }
-
-    /// Cancel the account's permission
-    ///
-    /// param account: The account to be canceled
-    /// param permissions: The permission to be canceled
-    /// return true if success, otherwise false
-    fn cancel_authorization(
-        &mut self,
-        account_address: &str,
-        permission: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account_address), remove_0x(permission)];
-        self.contract_send_tx("cancelAuthorization", &values, quota, None)
-    }
-
-    /// Cancel the account's multiple permission
-    ///
-    /// param account: The account to be canceled
-    /// param permissions: The multiple permissions to be canceled
-    /// return true if success, otherwise false
-    fn cancel_authorizations(
-        &mut self,
-        account_address: &str,
-        permissions: &str,
-        quota: Option<u64>,
-    ) -> Result<R, E> {
-        let values = [remove_0x(account_address), permissions];
-        self.contract_send_tx("cancelAuthorizations", &values, quota, None)
-    }
-
-    /// Clear the account's permissions
-    ///
-    /// param account: The account to be cleared
-    /// return true if success, otherwise false
-    fn clear_authorization(&mut self, account_address: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(account_address)];
-        self.contract_send_tx("clearAuthorization", &values, quota, None)
-    }
+/// Parse the address, with or without "0x"
+fn parse_address(address: &str) -> Result<Address, ToolError> {
+    Address::from_str(remove_0x_validated(address)?)
+        .map_err(|err| ToolError::InvalidHex(format!("{}: {}", address, err)))
 }
 
-/// Node manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020001")]
-#[contract(path = "../../contract_abi/NodeManager.abi")]
-#[contract(name = "NodeManagementExt")]
-pub struct NodeManageClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
+/// Encode the call of the method with the tokens, the decoded outputs of other calls are
+/// passed through as they are instead of being formatted and parsed again
+#[cfg(any(feature = "group", feature = "permission"))]
+fn encode_tokens(contract: &Contract, name: &str, tokens: &[Token]) -> Result<String, ToolError> {
+    contract
+        .function(name)
+        .and_then(|function| function.encode_input(tokens))
+        .map(|code| format!("0x{}", hex::encode(code)))
+        .map_err(|err| ToolError::Abi(format!("{}: {}", name, err)))
 }
 
-/// NodeManager system contract
-pub trait NodeManagementExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Downgrade consensus node to ordinary node
-    fn downgrade_consensus_node(&mut self, address: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_send_tx("deleteNode", &values, quota, None)
-    }
-
-    /// Get node status
-    fn node_status(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_call("getStatus", &values, None, height)
-    }
-
-    /// Get authorities
-    fn get_authorities(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("listNode", &[], None, height)
-    }
-
-    /// Approve node upgrades to consensus nodes
-    fn approve_node(&mut self, address: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_send_tx("approveNode", &values, quota, None)
-    }
-
-    /// Node stake list
-    fn list_stake(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("listStake", &[], None, height)
-    }
-
-    /// Set node stake
-    fn set_stake(&mut self, address: &str, stake: U256, quota: Option<u64>) -> Result<R, E> {
-        let stake = stake.completed_lower_hex();
-        let values = [remove_0x(address), stake.as_str()];
-        self.contract_send_tx("setStake", &values, quota, None)
-    }
-
-    /// Stake permillage
-    fn stake_permillage(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("stakePermillage", &[remove_0x(address)], None, height)
+/// Decode the only `address[]` output
+#[cfg(any(
+    feature = "group",
+    feature = "node",
+    feature = "permission",
+    feature = "quota",
+    feature = "role"
+))]
+fn decode_addresses(tokens: Vec<Token>) -> Result<Vec<Address>, ToolError> {
+    match tokens.as_slice() {
+        [Token::Array(addresses)] => addresses
+            .iter()
+            .map(|address| match address {
+                Token::Address(address) => Ok(*address),
+                other => Err(ToolError::Abi(format!("{} is not an address", other))),
+            })
+            .collect(),
+        _ => Err(ToolError::Abi(format!(
+            "Not an address array: {:?}",
+            tokens
+        ))),
     }
 }
 
-/// Node manage Client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020003")]
-#[contract(path = "../../contract_abi/QuotaManager.abi")]
-#[contract(name = "QuotaManagementExt")]
-pub struct QuotaManageClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// QuotaManager system contract
-pub trait QuotaManagementExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get block quota upper limit
-    fn get_bql(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getBQL", &[], None, height)
-    }
-
-    /// Get account quota upper limit of the specific account
-    fn get_aql(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_call("getAQL", &values, None, height)
-    }
-
-    /// Get default account quota limit
-    fn get_default_aql(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getDefaultAQL", &[], None, height)
-    }
-
-    /// Get accounts
-    fn get_accounts(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getAccounts", &[], None, height)
-    }
-
-    /// Get quotas
-    fn get_quotas(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getQuotas", &[], None, height)
-    }
-
-    /// Set block quota limit
-    fn set_bql(&mut self, quota_limit: U256, quota: Option<u64>) -> Result<R, E> {
-        let quota_limit = quota_limit.completed_lower_hex();
-        let values = [quota_limit.as_str()];
-        self.contract_send_tx("setBQL", &values, quota, None)
-    }
-
-    /// Set default account quota limit
-    fn set_default_aql(&mut self, quota_limit: U256, quota: Option<u64>) -> Result<R, E> {
-        let quota_limit = quota_limit.completed_lower_hex();
-        let values = [quota_limit.as_str()];
-        self.contract_send_tx("setDefaultAQL", &values, quota, None)
-    }
-
-    /// Set account quota upper limit of the specific account
-    fn set_aql(&mut self, address: &str, quota_limit: U256, quota: Option<u64>) -> Result<R, E> {
-        let quota_limit = quota_limit.completed_lower_hex();
-        let values = [remove_0x(address), quota_limit.as_str()];
-        self.contract_send_tx("setAQL", &values, quota, None)
-    }
+/// Call the method of the contract with every `(values, to_address)` at the same time,
+/// decode the outputs in the same order
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+fn call_all_decoded<C: ContractCall<JsonRpcResponse, ToolError>>(
+    client: &Client,
+    contract_call: &C,
+    contract: &Contract,
+    name: &str,
+    calls: Vec<(Vec<&str>, Option<Address>)>,
+    height: Option<&str>,
+) -> Result<Vec<Vec<Token>>, ToolError> {
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+    let calls = calls
+        .iter()
+        .map(|(values, to_address)| {
+            contract_call
+                .prepare_call_args(name, values, *to_address)
+                .map(|(code, to_address)| {
+                    (
+                        format!("0x{}", remove_0x(&to_address)),
+                        format!("0x{}", remove_0x(&code)),
+                    )
+                })
+        })
+        .collect::<Result<Vec<(String, String)>, ToolError>>()?;
+    client
+        .call_all(calls, height.unwrap_or("latest"))?
+        .into_iter()
+        .map(|response| contract_decode_output(contract, name, &response.into_hex_result()?))
+        .collect()
 }
 
-/// Admin manage client
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000c")]
-#[contract(path = "../../contract_abi/Admin.abi")]
-#[contract(name = "AdminExt")]
-pub struct AdminClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
+/// Call the method of the contract with every list of values at the same time,
+/// decode the only `bool` outputs in the same order
+#[cfg(any(feature = "group", feature = "permission"))]
+fn call_all_bool<C: ContractCall<JsonRpcResponse, ToolError>>(
+    client: &Client,
+    contract_call: &C,
+    contract: &Contract,
+    name: &str,
+    values_list: Vec<Vec<&str>>,
+    height: Option<&str>,
+) -> Result<Vec<bool>, ToolError> {
+    let calls = values_list
+        .into_iter()
+        .map(|values| (values, None))
+        .collect();
+    call_all_decoded(client, contract_call, contract, name, calls, height)?
+        .into_iter()
+        .map(decode_bool)
+        .collect()
 }
 
-/// Admin system contract
-pub trait AdminExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get admin address
-    fn admin(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("admin", &[], None, height)
+/// Decode the only `bool` output
+#[cfg(any(feature = "group", feature = "permission"))]
+fn decode_bool(tokens: Vec<Token>) -> Result<bool, ToolError> {
+    match tokens.as_slice() {
+        [Token::Bool(value)] => Ok(*value),
+        _ => Err(ToolError::Abi(format!("Not a bool: {:?}", tokens))),
     }
-
-    /// Check if the account is admin
-    fn is_admin(&self, address: &str, height: Option<&str>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_call("isAdmin", &values, None, height)
-    }
-
-    /// Update admin account
-    fn add_admin(&mut self, address: &str, quota: Option<u64>) -> Result<R, E> {
-        let values = [remove_0x(address)];
-        self.contract_send_tx("update", &values, quota, None)
-    }
-}
-
-/// Batch transaction contract
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000e")]
-#[contract(path = "../../contract_abi/BatchTx.abi")]
-#[contract(name = "BatchTxExt")]
-pub struct BatchTxClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
 }
 
-/// BatchTx system contract
-pub trait BatchTxExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Multi transactions send once
-    fn multi_transactions(&mut self, txs: Vec<&str>, quota: Option<u64>) -> Result<R, E> {
-        let combined_txs = txs
-            .into_iter()
-            .fold(String::with_capacity(100), |mut a, b| {
-                let (address, parameters) = remove_0x(b).split_at(40);
-                a.push_str(address);
-                a.push_str(&format!("{:>08x}", parameters.len() / 2));
-                a.push_str(parameters);
-                a
-            });
-        let value = [combined_txs.as_ref()];
-        self.contract_send_tx("multiTxs", &value, quota, None)
+/// Decode the only `bytes32` output as the name, without trailing zeros
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+fn decode_name_bytes(tokens: &[Token]) -> Result<&[u8], ToolError> {
+    match tokens {
+        [Token::FixedBytes(name)] => {
+            let len = name
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .map_or(0, |i| i + 1);
+            Ok(&name[..len])
+        }
+        _ => Err(ToolError::Abi(format!("Not a bytes32: {:?}", tokens))),
     }
 }
 
-/// System config contract
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020000")]
-#[contract(path = "../../contract_abi/SysConfig.abi")]
-#[contract(name = "SysConfigExt")]
-pub struct SysConfigClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// System config contract
-pub trait SysConfigExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get chain owner
-    fn get_chain_owner(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getChainOwner", &[], None, height)
-    }
-
-    /// Get chain id
-    fn get_chain_id(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getChainId", &[], None, height)
-    }
-
-    /// Get chain id v1
-    fn get_chain_id_v1(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getChainIdV1", &[], None, height)
-    }
-
-    /// Check sender's create contract permission
-    fn get_create_permission_check(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getCreateContractPermissionCheck", &[], None, height)
-    }
-
-    /// Check sender's send transaction permission
-    fn get_send_permission_check(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getSendTxPermissionCheck", &[], None, height)
-    }
-
-    /// Get delay block number
-    fn get_delay_block_number(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getDelayBlockNumber", &[], None, height)
-    }
-
-    /// Whether economic incentives are returned to operators
-    fn get_feeback_platform_check(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getFeeBackPlatformCheck", &[], None, height)
-    }
-
-    /// Whether to open the charging mode
-    fn get_economical_model(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getEconomicalModel", &[], None, height)
-    }
-
-    /// Whether to open the permission check
-    fn get_permission_check(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getPermissionCheck", &[], None, height)
-    }
-
-    /// Whether to open the quota check
-    fn get_quota_check(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getQuotaCheck", &[], None, height)
-    }
-
-    /// Set chain name
-    fn set_chain_name(&mut self, chain_name: &str, quota: Option<u64>) -> Result<R, E> {
-        let value = [chain_name];
-        self.contract_send_tx("setChainName", &value, quota, None)
-    }
-
-    /// Set operator
-    fn set_operator(&mut self, operator: &str, quota: Option<u64>) -> Result<R, E> {
-        let value = [operator];
-        self.contract_send_tx("setOperator", &value, quota, None)
-    }
-
-    /// Set website
-    fn set_website(&mut self, website: &str, quota: Option<u64>) -> Result<R, E> {
-        let value = [website];
-        self.contract_send_tx("setWebsite", &value, quota, None)
-    }
-
-    /// Set block interval
-    fn set_block_interval(&mut self, block_interval: U256, quota: Option<u64>) -> Result<R, E> {
-        let interval = block_interval.completed_lower_hex();
-        let value = [interval.as_str()];
-        self.contract_send_tx("setBlockInterval", &value, quota, None)
-    }
+/// Decode the only `bytes32` output as a string, without trailing zeros
+#[cfg(feature = "group")]
+fn decode_name(tokens: Vec<Token>) -> Result<String, ToolError> {
+    Ok(String::from_utf8_lossy(decode_name_bytes(&tokens)?).into_owned())
 }
 
-/// Emergency brake contract
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000f")]
-#[contract(path = "../../contract_abi/EmergencyBrake.abi")]
-#[contract(name = "EmergencyBrakeExt")]
-pub struct EmergencyBrakeClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
+/// Decode the only `bytes32` output as a UTF-8 string, without trailing zeros,
+/// fail if it's not UTF-8
+#[cfg(any(feature = "group", feature = "permission", feature = "role"))]
+fn decode_name_utf8(tokens: Vec<Token>) -> Result<String, ToolError> {
+    String::from_utf8(decode_name_bytes(&tokens)?.to_vec())
+        .map_err(|err| ToolError::AbiDecode(format!("Name is not UTF-8: {}", err)))
 }
 
-/// Emergency brake contract
-pub trait EmergencyBrakeExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get state
-    fn state(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("state", &[], None, height)
-    }
-
-    /// Set state
-    fn set_state(&mut self, state: bool, quota: Option<u64>) -> Result<R, E> {
-        let state = state.to_string();
-        let value = [state.as_str()];
-        self.contract_send_tx("setState", &value, quota, None)
+/// Decode the only `uint` output
+#[cfg(any(feature = "quota", feature = "sysconfig"))]
+fn decode_uint(tokens: Vec<Token>) -> Result<U256, ToolError> {
+    match tokens.as_slice() {
+        [Token::Uint(uint)] => {
+            let mut bytes = [0u8; 32];
+            uint.to_big_endian(&mut bytes);
+            Ok(U256::from(&bytes[..]))
+        }
+        _ => Err(ToolError::Abi(format!("Not an uint: {:?}", tokens))),
     }
 }
 
-/// Price manager contract
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020010")]
-#[contract(path = "../../contract_abi/PriceManager.abi")]
-#[contract(name = "PriceManagerExt")]
-pub struct PriceManagerClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// Price manager contract
-pub trait PriceManagerExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get quota price
-    fn price(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getQuotaPrice", &[], None, height)
-    }
-
-    /// Set quota price
-    fn set_price(&mut self, price: U256, quota: Option<u64>) -> Result<R, E> {
-        let price = price.completed_lower_hex();
-        let value = [price.as_str()];
-        self.contract_send_tx("setQuotaPrice", &value, quota, None)
+/// Decode the only `uint` output, which must fit in `u64`
+#[cfg(any(feature = "quota", feature = "sysconfig"))]
+fn decode_u64(tokens: Vec<Token>) -> Result<u64, ToolError> {
+    let uint = decode_uint(tokens)?;
+    if uint.bits() <= 64 {
+        Ok(uint.low_u64())
+    } else {
+        Err(ToolError::Abi(format!("{} is not an u64", uint)))
     }
 }
 
-/// Version manager contract
-#[derive(ContractExt)]
-#[contract(addr = "0xffffffffffffffffffffffffffffffffff020011")]
-#[contract(path = "../../contract_abi/VersionManager.abi")]
-#[contract(name = "VersionManagerExt")]
-pub struct VersionManagerClient<T> {
-    client: T,
-    address: Address,
-    contract: Contract,
-}
-
-/// Version manager contract
-pub trait VersionManagerExt<T, R, E>: ContractCall<R, E>
-where
-    T: ClientExt<R, E>,
-    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
-    E: Fail,
-{
-    /// Create a ContractClient
-    fn create(client: T) -> Self;
-
-    /// Get version
-    fn get_version(&self, height: Option<&str>) -> Result<R, E> {
-        self.contract_call("getVersion", &[], None, height)
-    }
-
-    /// Set version
-    fn set_version(&mut self, version: U256, quota: Option<u64>) -> Result<R, E> {
-        let version = version.completed_lower_hex();
-        let value = [version.as_str()];
-        self.contract_send_tx("setVersion", &value, quota, None)
+/// Decode the only `uint[]` output
+#[cfg(any(feature = "node", feature = "quota"))]
+fn decode_uints(tokens: Vec<Token>) -> Result<Vec<U256>, ToolError> {
+    match tokens.as_slice() {
+        [Token::Array(uints)] => uints
+            .iter()
+            .map(|uint| match uint {
+                Token::Uint(uint) => {
+                    let mut bytes = [0u8; 32];
+                    uint.to_big_endian(&mut bytes);
+                    Ok(U256::from(&bytes[..]))
+                }
+                other => Err(ToolError::Abi(format!("{} is not an uint", other))),
+            })
+            .collect(),
+        _ => Err(ToolError::Abi(format!("Not an uint array: {:?}", tokens))),
     }
 }
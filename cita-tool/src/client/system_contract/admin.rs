@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::ContractCall;
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcResponse;
+use ethabi::{Address, Contract, Token};
+use failure::Fail;
+use tool_derive::ContractExt;
+
+/// Admin manage client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000c")]
+#[contract(abi_contract = "crate::contract_abi::ADMIN_CONTRACT")]
+#[contract(name = "AdminExt")]
+pub struct AdminClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Admin system contract
+pub trait AdminExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get admin address
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AdminClient, AdminExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let admin = AdminClient::create(client);
+    /// let response = admin.admin(None).unwrap();
+    /// // The ABI encoded output of `admin`
+    /// println!("{}", response);
+    /// ```
+    fn admin(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("admin", &[], None, height)
+    }
+
+    /// Check if the account is admin
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AdminClient, AdminExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let admin = AdminClient::create(client);
+    /// let response = admin.is_admin("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None).unwrap();
+    /// // The ABI encoded output of `isAdmin`
+    /// println!("{}", response);
+    /// ```
+    fn is_admin(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_call("isAdmin", &values, None, height)
+    }
+
+    /// Fail with `ToolError::NotAdmin` if the signer is not admin,
+    /// before sending a transaction that would be rejected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{AdminClient, AdminExt};
+    /// use cita_tool::{JsonRpcResponse, ToolError};
+    ///
+    /// // The canned answer of `isAdmin`: false
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     0
+    /// ))
+    /// .unwrap();
+    /// let admin = AdminClient::create_with_responses(vec![response]);
+    /// let signer = "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// match admin.require_admin(signer, None) {
+    ///     Err(ToolError::NotAdmin(address)) => assert_eq!(address, signer),
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// ```
+    fn require_admin(&self, signer_address: &str, height: Option<&str>) -> Result<(), E> {
+        let tokens =
+            self.contract_call_decoded("isAdmin", &[remove_0x(signer_address)], None, height)?;
+        match tokens.as_slice() {
+            [Token::Bool(true)] => Ok(()),
+            [Token::Bool(false)] => Err(ToolError::NotAdmin(signer_address.to_string()).into()),
+            _ => Err(ToolError::Abi(format!("Not a bool: {:?}", tokens)).into()),
+        }
+    }
+
+    /// Update admin account
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AdminClient, AdminExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut admin = AdminClient::create(client);
+    /// let response = admin
+    ///     .add_admin("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn add_admin(&mut self, address: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_send_tx("update", &values, options, None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AdminClient, AdminExt};
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+
+    fn response(is_admin: bool) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            is_admin as u8
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_require_admin() {
+        let signer = "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+        let client = AdminClient::create_with_responses(vec![response(true), response(false)]);
+        assert!(client.require_admin(signer, None).is_ok());
+        match client.require_admin(signer, None) {
+            Err(ToolError::NotAdmin(address)) => assert_eq!(address, signer),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeSet;
+
+use hex::encode;
+
+use crate::client::basic::Client;
+use crate::client::system_contract::{
+    decode_addresses, decode_name, decode_uints, AdminClient, AdminExt, AuthorizationClient,
+    AuthorizationExt, ContractCall, GroupClient, GroupExt, GroupManageClient, GroupManagementExt,
+    NodeManageClient, NodeManagementExt, PermissionClient, PermissionExt, QuotaManageClient,
+    QuotaManagementExt, RoleClient, RoleExt, RoleManageClient, RoleManagementExt,
+};
+use crate::error::ToolError;
+use ethabi::{Address, Token};
+
+/// Snapshot of the governance state of the system contracts at one height
+#[derive(Clone, Debug, Serialize)]
+pub struct GovernanceAudit {
+    /// Height of the snapshot
+    pub height: u64,
+    /// Admin account
+    pub admin: String,
+    /// All groups
+    pub groups: Vec<GroupAudit>,
+    /// Roles assigned to any account
+    pub roles: Vec<RoleAudit>,
+    /// Permissions authorized to any account
+    pub permissions: Vec<PermissionAudit>,
+    /// Quota limits
+    pub quota: QuotaAudit,
+    /// Consensus nodes
+    pub nodes: Vec<NodeAudit>,
+}
+
+/// Group of `GovernanceAudit`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupAudit {
+    /// Group address
+    pub address: String,
+    /// Group name
+    pub name: String,
+    /// Parent group address
+    pub parent: String,
+    /// Accounts of the group
+    pub accounts: Vec<String>,
+}
+
+/// Role of `GovernanceAudit`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoleAudit {
+    /// Role address
+    pub address: String,
+    /// Role name
+    pub name: String,
+    /// Permissions of the role
+    pub permissions: Vec<String>,
+    /// Accounts with the role
+    pub accounts: Vec<String>,
+}
+
+/// Permission of `GovernanceAudit`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PermissionAudit {
+    /// Permission address
+    pub address: String,
+    /// Permission name
+    pub name: String,
+    /// Resources of the permission, `contract:function signature`
+    pub resources: Vec<String>,
+    /// Accounts authorized with the permission
+    pub accounts: Vec<String>,
+}
+
+/// Quota limits of `GovernanceAudit`
+#[derive(Clone, Debug, Serialize)]
+pub struct QuotaAudit {
+    /// Block quota limit
+    pub block_quota_limit: String,
+    /// Default account quota limit
+    pub default_account_quota_limit: String,
+    /// Accounts with specific account quota limit, and their limits
+    pub account_quota_limits: Vec<(String, String)>,
+}
+
+/// Consensus node of `GovernanceAudit`
+#[derive(Clone, Debug, Serialize)]
+pub struct NodeAudit {
+    /// Node address
+    pub address: String,
+    /// Stake of the node
+    pub stake: String,
+}
+
+impl Client {
+    /// Collect the groups, roles, permissions, admin, quota limits and nodes
+    /// from the system contracts, all at the same height, default is the current one
+    ///
+    /// Roles and permissions can not be listed directly, the ones assigned to
+    /// the accounts of `Authorization.queryAllAccounts` are collected.
+    pub fn governance_audit(&self, height: Option<u64>) -> Result<GovernanceAudit, ToolError> {
+        let height = match height {
+            Some(height) => height,
+            None => self.get_current_height()?,
+        };
+        let height_hex = format!("{:#x}", height);
+        let at = Some(height_hex.as_str());
+
+        let admin = match AdminClient::create(self.clone())
+            .contract_call_decoded("admin", &[], None, at)?
+            .as_slice()
+        {
+            [Token::Address(admin)] => format_address(admin),
+            tokens => return Err(ToolError::Abi(format!("Not an address: {:?}", tokens))),
+        };
+
+        let group_client = GroupClient::create(self.clone());
+        let groups = decode_addresses(
+            GroupManageClient::create(self.clone()).contract_call_decoded(
+                "queryGroups",
+                &[],
+                None,
+                at,
+            )?,
+        )?
+        .into_iter()
+        .map(|group_address| {
+            let group = format_address(&group_address);
+            Ok(GroupAudit {
+                name: decode_name(group_client.contract_call_decoded(
+                    "queryName",
+                    &[],
+                    Some(group_address),
+                    at,
+                )?)?,
+                parent: format_address(&group_client.query_parent_address(&group, at)?),
+                accounts: format_addresses(group_client.query_accounts_list(&group, at)?),
+                address: group,
+            })
+        })
+        .collect::<Result<Vec<GroupAudit>, ToolError>>()?;
+
+        let authorization_client = AuthorizationClient::create(self.clone());
+        let role_manage_client = RoleManageClient::create(self.clone());
+        let accounts = decode_addresses(authorization_client.contract_call_decoded(
+            "queryAllAccounts",
+            &[],
+            None,
+            at,
+        )?)?;
+        let mut role_addresses = BTreeSet::new();
+        let mut permission_addresses = BTreeSet::new();
+        for account in accounts.iter().map(format_address) {
+            role_addresses.extend(decode_addresses(
+                role_manage_client.contract_call_decoded(
+                    "queryRoles",
+                    &[account.as_str()],
+                    None,
+                    at,
+                )?,
+            )?);
+            permission_addresses.extend(decode_addresses(
+                authorization_client.contract_call_decoded(
+                    "queryPermissions",
+                    &[account.as_str()],
+                    None,
+                    at,
+                )?,
+            )?);
+        }
+
+        let role_client = RoleClient::create(self.clone());
+        let roles = role_addresses
+            .into_iter()
+            .map(|role| {
+                Ok(RoleAudit {
+                    address: format_address(&role),
+                    name: decode_name(role_client.contract_call_decoded(
+                        "queryName",
+                        &[],
+                        Some(role),
+                        at,
+                    )?)?,
+                    permissions: format_addresses(decode_addresses(
+                        role_client.contract_call_decoded(
+                            "queryPermissions",
+                            &[],
+                            Some(role),
+                            at,
+                        )?,
+                    )?),
+                    accounts: format_addresses(decode_addresses(
+                        role_manage_client.contract_call_decoded(
+                            "queryAccounts",
+                            &[format_address(&role).as_str()],
+                            None,
+                            at,
+                        )?,
+                    )?),
+                })
+            })
+            .collect::<Result<Vec<RoleAudit>, ToolError>>()?;
+
+        let permission_client = PermissionClient::create(self.clone());
+        let permissions = permission_addresses
+            .into_iter()
+            .map(|permission| {
+                let address = format_address(&permission);
+                Ok(PermissionAudit {
+                    name: decode_name(permission_client.contract_call_decoded(
+                        "queryName",
+                        &[],
+                        Some(permission),
+                        at,
+                    )?)?,
+                    resources: permission_client
+                        .query_resources(&address, at)?
+                        .iter()
+                        .map(|resource| {
+                            format!(
+                                "{}:0x{}",
+                                format_address(&resource.contract_address),
+                                encode(resource.function_signature)
+                            )
+                        })
+                        .collect(),
+                    accounts: format_addresses(decode_addresses(
+                        authorization_client.contract_call_decoded(
+                            "queryAccounts",
+                            &[address.as_str()],
+                            None,
+                            at,
+                        )?,
+                    )?),
+                    address,
+                })
+            })
+            .collect::<Result<Vec<PermissionAudit>, ToolError>>()?;
+
+        let quota_client = QuotaManageClient::create(self.clone());
+        let quota_limit = |name: &str| -> Result<String, ToolError> {
+            match quota_client
+                .contract_call_decoded(name, &[], None, at)?
+                .as_slice()
+            {
+                [Token::Uint(limit)] => Ok(limit.to_string()),
+                tokens => Err(ToolError::Abi(format!("Not an uint: {:?}", tokens))),
+            }
+        };
+        let quota = QuotaAudit {
+            block_quota_limit: quota_limit("getBQL")?,
+            default_account_quota_limit: quota_limit("getDefaultAQL")?,
+            account_quota_limits: quota_client
+                .get_accounts_and_quotas(at)?
+                .into_iter()
+                .map(|(account, limit)| (format_address(&account), limit.to_string()))
+                .collect(),
+        };
+
+        let node_client = NodeManageClient::create(self.clone());
+        let nodes =
+            decode_addresses(node_client.contract_call_decoded("listNode", &[], None, at)?)?;
+        let stakes =
+            decode_uints(node_client.contract_call_decoded("listStake", &[], None, at)?)?;
+        if nodes.len() != stakes.len() {
+            return Err(ToolError::Abi(format!(
+                "{} nodes but {} stakes",
+                nodes.len(),
+                stakes.len()
+            )));
+        }
+        let nodes = nodes
+            .iter()
+            .zip(stakes)
+            .map(|(node, stake)| NodeAudit {
+                address: format_address(node),
+                stake: stake.to_string(),
+            })
+            .collect();
+
+        Ok(GovernanceAudit {
+            height,
+            admin,
+            groups,
+            roles,
+            permissions,
+            quota,
+            nodes,
+        })
+    }
+}
+
+fn format_address(address: &Address) -> String {
+    format!("{:?}", address)
+}
+
+fn format_addresses(addresses: Vec<Address>) -> Vec<String> {
+    addresses.iter().map(format_address).collect()
+}
@@ -0,0 +1,251 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::ContractCall;
+#[cfg(feature = "quota")]
+use crate::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcResponse, TransactionReceipt};
+use ethabi::{Address, Contract};
+use failure::Fail;
+use tool_derive::ContractExt;
+
+/// Extra quota of the `multiTxs` wrapper, in percent of the sum of the transactions
+#[cfg(feature = "quota")]
+const BATCH_OVERHEAD_PERCENT: u64 = 10;
+
+/// Batch transaction contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000e")]
+#[contract(abi_contract = "crate::contract_abi::BATCH_TX_CONTRACT")]
+#[contract(name = "BatchTxExt")]
+pub struct BatchTxClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// BatchTx system contract
+pub trait BatchTxExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Multi transactions send once
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{BatchTxClient, BatchTxExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut batch = BatchTxClient::create(client);
+    /// // Every transaction is the contract address followed by the call data
+    /// let txs = vec![
+    ///     "0xffffffffffffffffffffffffffffffffff020004a4f7f1bf",
+    ///     "0xffffffffffffffffffffffffffffffffff02000c0a0e5b58",
+    /// ];
+    /// let response = batch.multi_transactions(txs, TransactionOptions::new()).unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn multi_transactions(&mut self, txs: Vec<&str>, options: TransactionOptions) -> Result<R, E> {
+        let combined_txs = pack_transactions(txs)?;
+        let value = [combined_txs.as_ref()];
+        self.contract_send_tx("multiTxs", &value, options, None)
+    }
+}
+
+/// Pack the transactions, each is the address followed by the data, into the
+/// argument of `multiTxs`: address, data length in 4 bytes and data of every one
+pub fn pack_transactions(txs: Vec<&str>) -> Result<String, ToolError> {
+    txs.into_iter()
+        .try_fold(String::with_capacity(100), |mut a, b| {
+            let (address, parameters) = split_transaction(b)?;
+            a.push_str(address);
+            a.push_str(&format!("{:>08x}", parameters.len() / 2));
+            a.push_str(parameters);
+            Ok(a)
+        })
+}
+
+/// Split the transaction into the address and the data
+fn split_transaction(tx: &str) -> Result<(&str, &str), ToolError> {
+    let tx = remove_0x(tx);
+    match (tx.get(..40), tx.get(40..)) {
+        (Some(address), Some(data)) => Ok((address, data)),
+        _ => Err(ToolError::Customize(format!(
+            "{} is not an address followed by the data",
+            tx
+        ))),
+    }
+}
+
+/// Result of a call of a mined `multi_transactions` batch
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubCallResult {
+    /// Target of the call
+    pub to: Address,
+    /// Method of the call
+    pub method: String,
+    /// Whether the call took effect
+    pub success: bool,
+    /// Return data of the call, receipts don't record it, so it's always empty for now
+    pub return_data: Vec<u8>,
+}
+
+/// Split the receipt of a `multi_transactions` batch into the results of its calls,
+/// in the order of `calls`, the `(to, method)` of each packed transaction
+///
+/// The BatchTx contract emits no event per call and reverts the whole batch when
+/// any call fails, so either all calls succeeded or none took effect.
+pub fn decode_multi_receipt(
+    receipt: &TransactionReceipt,
+    calls: Vec<(Address, &str)>,
+) -> Result<Vec<SubCallResult>, ToolError> {
+    if calls.is_empty() {
+        return Err(ToolError::Customize(
+            "A batch transaction has at least one call".to_string(),
+        ));
+    }
+    let success = receipt.error_message.is_none();
+    Ok(calls
+        .into_iter()
+        .map(|(to, method)| SubCallResult {
+            to,
+            method: method.to_string(),
+            success,
+            return_data: Vec::new(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "quota")]
+impl BatchTxClient<Client> {
+    /// Estimate the quota of the transactions of `multi_transactions`, each one is
+    /// estimated alone and the sum is increased by the overhead of the batch wrapper.
+    /// Fail with `ToolError::InsufficientBlockQuota` if it exceeds the block quota limit
+    ///
+    /// It is advisory, the quota used on chain may differ.
+    pub fn estimate_multi_quota(&self, txs: Vec<&str>) -> Result<u64, ToolError> {
+        let mut total = 0u64;
+        for tx in txs {
+            let (address, data) = split_transaction(tx)?;
+            let quota = self.client.estimate_quota_u64(
+                None,
+                &format!("0x{}", address),
+                &format!("0x{}", data),
+            )?;
+            total = total.saturating_add(quota);
+        }
+        let estimated = total.saturating_add(total.saturating_mul(BATCH_OVERHEAD_PERCENT) / 100);
+
+        let limit = QuotaManageClient::create(self.client.clone()).get_bql_u64(None)?;
+        if estimated > limit {
+            return Err(ToolError::InsufficientBlockQuota { estimated, limit });
+        }
+        Ok(estimated)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "quota")]
+    use super::BatchTxClient;
+    use super::{decode_multi_receipt, pack_transactions};
+    #[cfg(feature = "quota")]
+    use crate::error::ToolError;
+    #[cfg(feature = "quota")]
+    use crate::rpctypes::JsonRpcResponse;
+    use crate::rpctypes::TransactionReceipt;
+    use ethabi::Address;
+    use types::{H256, U256};
+
+    #[test]
+    fn test_pack_transactions() {
+        let address = "ffffffffffffffffffffffffffffffffff020004";
+        assert_eq!(
+            pack_transactions(vec![format!("0x{}abcd", address).as_str(), address]).unwrap(),
+            format!("{}00000002abcd{}00000000", address, address)
+        );
+        assert!(pack_transactions(vec!["0x1234"]).is_err());
+        assert!(pack_transactions(vec![format!("{}\u{4e2d}", &address[..39]).as_str()]).is_err());
+    }
+
+    #[test]
+    fn test_decode_multi_receipt() {
+        let mut receipt = TransactionReceipt {
+            transaction_hash: H256::from(1),
+            transaction_index: 0,
+            block_hash: H256::from(2),
+            block_number: 31,
+            cumulative_quota_used: U256::from(60_000),
+            quota_used: U256::from(60_000),
+            contract_address: None,
+            logs: Vec::new(),
+            logs_bloom: None,
+            error_message: None,
+        };
+        let calls = || {
+            vec![
+                (Address::from([0xff; 20]), "setBQL"),
+                (Address::from([0xfe; 20]), "setAQL"),
+            ]
+        };
+
+        let results = decode_multi_receipt(&receipt, calls()).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].method, "setAQL");
+        assert!(results.iter().all(|result| result.success));
+
+        receipt.error_message = Some("Reverted.".to_string());
+        let results = decode_multi_receipt(&receipt, calls()).unwrap();
+        assert!(results.iter().all(|result| !result.success));
+        assert!(decode_multi_receipt(&receipt, Vec::new()).is_err());
+    }
+
+    #[cfg(feature = "quota")]
+    #[test]
+    fn test_estimate_multi_quota() {
+        let response = |result: String| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#,
+                result
+            ))
+            .unwrap()
+        };
+        let responses = |bql: u64| {
+            vec![
+                response("0x10000".to_string()),
+                response("0x10000".to_string()),
+                response(format!("0x{:064x}", bql)),
+            ]
+        };
+        let txs = vec![
+            "0xffffffffffffffffffffffffffffffffff020004a4f7f1bf",
+            "0xffffffffffffffffffffffffffffffffff02000c0a0e5b58",
+        ];
+
+        let client = BatchTxClient::create_with_responses(responses(0x30000));
+        assert_eq!(client.estimate_multi_quota(txs.clone()).unwrap(), 0x23333);
+        let client = BatchTxClient::create_with_responses(responses(0x20000));
+        match client.estimate_multi_quota(txs) {
+            Err(ToolError::InsufficientBlockQuota { estimated, limit }) => {
+                assert_eq!(estimated, 0x23333);
+                assert_eq!(limit, 0x20000);
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::ContractCall;
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcResponse;
+use ethabi::{Address, Contract, Token};
+use failure::Fail;
+use tool_derive::ContractExt;
+
+/// `sideChains` status of an enabled side chain
+const SIDE_CHAIN_ENABLED: u64 = 2;
+
+/// Cross chain client, of the chain manager contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020002")]
+#[contract(abi_contract = "crate::contract_abi::CHAIN_MANAGER_CONTRACT")]
+#[contract(name = "CrossChainExt")]
+pub struct CrossChainClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Chain manager contract, registering the parent and side chains
+///
+/// A cross chain message is an event of the contract on the source chain, proved to
+/// the contract on the target chain by the authorities registered here.
+pub trait CrossChainExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get the id of this chain
+    fn get_chain_id(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getChainId", &[], None, height)
+    }
+
+    /// Get the id of the parent chain, 0 if it's a main chain
+    fn get_parent_chain_id(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getParentChainId", &[], None, height)
+    }
+
+    /// Get the authorities of the chain, the ones signing its cross chain proofs
+    fn get_authorities(&self, chain_id: u32, height: Option<&str>) -> Result<R, E> {
+        let chain_id = chain_id.to_string();
+        self.contract_call("getAuthorities", &[chain_id.as_str()], None, height)
+    }
+
+    /// Get the status of the side chain, 0 unknown, 1 disabled and 2 enabled
+    fn side_chain_status(&self, chain_id: u32, height: Option<&str>) -> Result<R, E> {
+        let chain_id = chain_id.to_string();
+        self.contract_call("sideChains", &[chain_id.as_str()], None, height)
+    }
+
+    /// Register a side chain with its authorities, such as `[0x...,0x...]`
+    fn new_side_chain(
+        &mut self,
+        chain_id: u32,
+        authorities: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let chain_id = chain_id.to_string();
+        let values = [chain_id.as_str(), authorities];
+        self.contract_send_tx("newSideChain", &values, options, None)
+    }
+
+    /// Enable the side chain
+    fn enable_side_chain(&mut self, chain_id: u32, options: TransactionOptions) -> Result<R, E> {
+        let chain_id = chain_id.to_string();
+        self.contract_send_tx("enableSideChain", &[chain_id.as_str()], options, None)
+    }
+
+    /// Disable the side chain
+    fn disable_side_chain(&mut self, chain_id: u32, options: TransactionOptions) -> Result<R, E> {
+        let chain_id = chain_id.to_string();
+        self.contract_send_tx("disableSideChain", &[chain_id.as_str()], options, None)
+    }
+
+    /// Whether the side chain is enabled
+    fn is_side_chain_enabled(&self, chain_id: u32, height: Option<&str>) -> Result<bool, E> {
+        let chain_id = chain_id.to_string();
+        let tokens =
+            self.contract_call_decoded("sideChains", &[chain_id.as_str()], None, height)?;
+        match tokens.as_slice() {
+            [Token::Uint(status)] => Ok(*status == ethabi::Uint::from(SIDE_CHAIN_ENABLED)),
+            _ => Err(ToolError::Abi(format!("Not an uint: {:?}", tokens)).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CrossChainClient, CrossChainExt};
+    use crate::rpctypes::JsonRpcResponse;
+
+    fn response(status: u8) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            status
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_side_chain_enabled() {
+        let client = CrossChainClient::create_with_responses(vec![response(2), response(1)]);
+        assert!(client.is_side_chain_enabled(2, None).unwrap());
+        assert!(!client.is_side_chain_enabled(2, None).unwrap());
+    }
+}
@@ -0,0 +1,1205 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{
+    call_all_bool, call_all_decoded, decode_addresses, decode_bool, decode_name, decode_name_utf8,
+    encode_tokens, parse_address, BatchTxClient, BatchTxExt, ContractCall,
+};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcResponse;
+use ethabi::{Address, Contract, Token};
+use failure::Fail;
+use tool_derive::ContractExt;
+
+/// Group Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020009")]
+#[contract(abi_contract = "crate::contract_abi::GROUP_CONTRACT")]
+#[contract(name = "GroupExt")]
+pub struct GroupClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Name and accounts of a group, decoded from `queryInfo`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupInfo {
+    /// Name of the group, without trailing zeros
+    pub name: String,
+    /// Accounts of the group
+    pub accounts: Vec<Address>,
+}
+
+/// Accounts of a group compared between two block heights, each list is sorted
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MembershipDiff {
+    /// Only in the group at the second height
+    pub added: Vec<Address>,
+    /// Only in the group at the first height
+    pub removed: Vec<Address>,
+    /// In the group at both heights
+    pub unchanged: Vec<Address>,
+}
+
+impl MembershipDiff {
+    /// Compare the accounts before and after
+    pub fn between(before: &[Address], after: &[Address]) -> Self {
+        let before = before.iter().cloned().collect::<HashSet<Address>>();
+        let after = after.iter().cloned().collect::<HashSet<Address>>();
+        let sorted = |accounts: HashSet<&Address>| {
+            let mut accounts = accounts.into_iter().cloned().collect::<Vec<Address>>();
+            accounts.sort();
+            accounts
+        };
+        MembershipDiff {
+            added: sorted(after.difference(&before).collect()),
+            removed: sorted(before.difference(&after).collect()),
+            unchanged: sorted(before.intersection(&after).collect()),
+        }
+    }
+}
+
+/// Group System Contract
+pub trait GroupExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Query the information of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_info("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryInfo`
+    /// println!("{}", response);
+    /// ```
+    fn query_info(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryInfo", &[], address, height)
+    }
+
+    /// Query the information of the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryInfo`: the name "root" and one account
+    /// let account = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}{:064x}{:064x}{:0>64}"}}"#,
+    ///     encode("root"),
+    ///     64,
+    ///     1,
+    ///     account
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let info = group
+    ///     .query_group_info("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(info.name, "root");
+    /// assert_eq!(info.accounts.len(), 1);
+    /// assert_eq!(format!("{:x}", info.accounts[0]), account);
+    /// ```
+    fn query_group_info(&self, address: &str, height: Option<&str>) -> Result<GroupInfo, E> {
+        let mut tokens =
+            self.contract_call_decoded("queryInfo", &[], Some(parse_address(address)?), height)?;
+        if tokens.len() != 2 {
+            return Err(ToolError::Abi(format!("Not a (bytes32, address[]): {:?}", tokens)).into());
+        }
+        let accounts = tokens.pop().unwrap();
+        Ok(GroupInfo {
+            name: decode_name_utf8(tokens)?,
+            accounts: decode_addresses(vec![accounts])?,
+        })
+    }
+
+    /// Query the name of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_name("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryName`
+    /// println!("{}", response);
+    /// ```
+    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryName", &[], address, height)
+    }
+    /// Query the accounts of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_accounts("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryAccounts`
+    /// println!("{}", response);
+    /// ```
+    fn query_accounts(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryAccounts", &[], address, height)
+    }
+    /// Alias for query_child
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_children("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryChild`
+    /// println!("{}", response);
+    /// ```
+    fn query_children(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.query_child(address, height)
+    }
+    /// Query the children of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_child("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryChild`
+    /// println!("{}", response);
+    /// ```
+    fn query_child(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryChild", &[], address, height)
+    }
+    /// Alias for query_child_length
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_children_length("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryChildLength`
+    /// println!("{}", response);
+    /// ```
+    fn query_children_length(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.query_child_length(address, height)
+    }
+    /// Query the length of children of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_child_length("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryChildLength`
+    /// println!("{}", response);
+    /// ```
+    fn query_child_length(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryChildLength", &[], address, height)
+    }
+    /// Query the parent of the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .query_parent("0xe9e2593c7d1db5ee843c143e9cb52b8d996b2380", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryParent`
+    /// println!("{}", response);
+    /// ```
+    fn query_parent(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryParent", &[], address, height)
+    }
+    /// Check the account in the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let group = GroupClient::create(client);
+    /// let response = group
+    ///     .in_group(
+    ///         "0xffffffffffffffffffffffffffffffffff020009",
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// // The ABI encoded output of `inGroup`
+    /// println!("{}", response);
+    /// ```
+    fn in_group(&self, address: &str, account_address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("inGroup", &[remove_0x(account_address)], address, height)
+    }
+    /// Query the name of the group, the `bytes32` without trailing zeros
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryName`, the name "root"
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+    ///     encode("root")
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let name = group
+    ///     .query_name_str("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(name, "root");
+    /// ```
+    #[deprecated(note = "use query_name_string, which fails if the name is not UTF-8")]
+    fn query_name_str(&self, address: &str, height: Option<&str>) -> Result<String, E> {
+        let tokens =
+            self.contract_call_decoded("queryName", &[], Some(parse_address(address)?), height)?;
+        Ok(decode_name(tokens)?)
+    }
+    /// Query the name of the group, the `bytes32` without trailing zeros,
+    /// fail if it's not UTF-8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryName`, the name "root"
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+    ///     encode("root")
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let name = group
+    ///     .query_name_string("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(name, "root");
+    /// ```
+    fn query_name_string(&self, address: &str, height: Option<&str>) -> Result<String, E> {
+        let tokens =
+            self.contract_call_decoded("queryName", &[], Some(parse_address(address)?), height)?;
+        Ok(decode_name_utf8(tokens)?)
+    }
+    /// Query the accounts of the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryAccounts`, one account
+    /// let account = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:0>64}"}}"#,
+    ///     32,
+    ///     1,
+    ///     account
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let accounts = group
+    ///     .query_accounts_list("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(accounts.len(), 1);
+    /// assert_eq!(format!("{:x}", accounts[0]), account);
+    /// ```
+    fn query_accounts_list(&self, address: &str, height: Option<&str>) -> Result<Vec<Address>, E> {
+        let tokens = self.contract_call_decoded(
+            "queryAccounts",
+            &[],
+            Some(parse_address(address)?),
+            height,
+        )?;
+        Ok(decode_addresses(tokens)?)
+    }
+    /// Query the children of the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryChild`, one child group
+    /// let child = "e9e2593c7d1db5ee843c143e9cb52b8d996b2380";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:0>64}"}}"#,
+    ///     32,
+    ///     1,
+    ///     child
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let children = group
+    ///     .query_child_list("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(children.len(), 1);
+    /// assert_eq!(format!("{:x}", children[0]), child);
+    /// ```
+    fn query_child_list(&self, address: &str, height: Option<&str>) -> Result<Vec<Address>, E> {
+        let tokens =
+            self.contract_call_decoded("queryChild", &[], Some(parse_address(address)?), height)?;
+        Ok(decode_addresses(tokens)?)
+    }
+    /// Query the length of children of the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryChildLength`, one child group
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let length = group
+    ///     .query_child_length_u64("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// assert_eq!(length, 1);
+    /// ```
+    fn query_child_length_u64(&self, address: &str, height: Option<&str>) -> Result<u64, E> {
+        let tokens = self.contract_call_decoded(
+            "queryChildLength",
+            &[],
+            Some(parse_address(address)?),
+            height,
+        )?;
+        match tokens.as_slice() {
+            [Token::Uint(length)] if length.bits() <= 64 => Ok(length.low_u64()),
+            _ => Err(ToolError::Abi(format!("Not an u64: {:?}", tokens)).into()),
+        }
+    }
+    /// Query the parent of the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryParent`, the root group
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0>64}"}}"#,
+    ///     "ffffffffffffffffffffffffffffffffff020009"
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let parent = group
+    ///     .query_parent_address("0xe9e2593c7d1db5ee843c143e9cb52b8d996b2380", None)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     format!("{:x}", parent),
+    ///     "ffffffffffffffffffffffffffffffffff020009"
+    /// );
+    /// ```
+    fn query_parent_address(&self, address: &str, height: Option<&str>) -> Result<Address, E> {
+        let tokens =
+            self.contract_call_decoded("queryParent", &[], Some(parse_address(address)?), height)?;
+        match tokens.as_slice() {
+            [Token::Address(parent)] => Ok(*parent),
+            _ => Err(ToolError::Abi(format!("Not an address: {:?}", tokens)).into()),
+        }
+    }
+    /// Check the account in the group, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `inGroup`, the ABI encoded true
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1
+    /// ))
+    /// .unwrap();
+    /// let group = GroupClient::create_with_responses(vec![response]);
+    /// let in_group = group
+    ///     .in_group_bool(
+    ///         "0xffffffffffffffffffffffffffffffffff020009",
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert!(in_group);
+    /// ```
+    fn in_group_bool(
+        &self,
+        address: &str,
+        account_address: &str,
+        height: Option<&str>,
+    ) -> Result<bool, E> {
+        Ok(decode_bool(self.contract_call_decoded(
+            "inGroup",
+            &[remove_0x(account_address)],
+            Some(parse_address(address)?),
+            height,
+        )?)?)
+    }
+    /// Compare the accounts of the group at the two block heights
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// let (removed, added) = (
+    ///     "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///     "e9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    /// );
+    /// // The canned answers of `queryAccounts` at the two heights
+    /// let group = GroupClient::create_with_responses(vec![
+    ///     response(addresses(&[removed])),
+    ///     response(addresses(&[added])),
+    /// ]);
+    /// let diff = group
+    ///     .diff_membership("0xffffffffffffffffffffffffffffffffff020009", 1, 2)
+    ///     .unwrap();
+    /// assert_eq!(format!("{:x}", diff.added[0]), added);
+    /// assert_eq!(format!("{:x}", diff.removed[0]), removed);
+    /// assert!(diff.unchanged.is_empty());
+    /// ```
+    fn diff_membership(
+        &self,
+        group: &str,
+        block_a: u64,
+        block_b: u64,
+    ) -> Result<MembershipDiff, E> {
+        let accounts =
+            |height: u64| self.query_accounts_list(group, Some(format!("{:#x}", height).as_str()));
+        Ok(MembershipDiff::between(
+            &accounts(block_a)?,
+            &accounts(block_b)?,
+        ))
+    }
+    /// Query the accounts of the group and all its descendants, deduplicated and sorted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{GroupClient, GroupExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// let (account_a, account_b) = (
+    ///     "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///     "e9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    /// );
+    /// let child = "ca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001";
+    /// // The canned answers of `queryAccounts` and `queryChild` of the root group,
+    /// // then the ones of its only child group
+    /// let group = GroupClient::create_with_responses(vec![
+    ///     response(addresses(&[account_a])),
+    ///     response(addresses(&[child])),
+    ///     response(addresses(&[account_b])),
+    ///     response(addresses(&[])),
+    /// ]);
+    /// let accounts = group
+    ///     .query_accounts_recursive("0xffffffffffffffffffffffffffffffffff020009", None)
+    ///     .unwrap();
+    /// let accounts = accounts
+    ///     .iter()
+    ///     .map(|account| format!("{:x}", account))
+    ///     .collect::<Vec<String>>();
+    /// assert_eq!(accounts, vec![account_a, account_b]);
+    /// ```
+    fn query_accounts_recursive(
+        &self,
+        root: &str,
+        height: Option<&str>,
+    ) -> Result<Vec<Address>, E> {
+        let query = |name: &str, group: Address| -> Result<Vec<Address>, E> {
+            let tokens = self.contract_call_decoded(name, &[], Some(group), height)?;
+            Ok(decode_addresses(tokens)?)
+        };
+        let mut accounts = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut level = vec![parse_address(root)?];
+        while !level.is_empty() {
+            let mut next_level = Vec::new();
+            for group in level.into_iter().filter(|group| visited.insert(*group)) {
+                accounts.extend(query("queryAccounts", group)?);
+                next_level.extend(query("queryChild", group)?);
+            }
+            level = next_level;
+        }
+        let mut accounts = accounts.into_iter().collect::<Vec<Address>>();
+        accounts.sort();
+        Ok(accounts)
+    }
+}
+
+/// Options of `find_group_by_name`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FindOptions {
+    case_insensitive: bool,
+}
+
+impl FindOptions {
+    /// Default option, the name matches exactly
+    pub fn new() -> Self {
+        FindOptions::default()
+    }
+
+    /// Set case insensitive, default is false
+    pub fn set_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Get case insensitive
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    fn matches(&self, group_name: &str, name: &str) -> bool {
+        if self.case_insensitive {
+            group_name.to_lowercase() == name.to_lowercase()
+        } else {
+            group_name == name
+        }
+    }
+}
+
+/// Group manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000a")]
+#[contract(abi_contract = "crate::contract_abi::GROUP_MANAGEMENT_CONTRACT")]
+#[contract(name = "GroupManagementExt")]
+pub struct GroupManageClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// GroupManagement System Contract
+pub trait GroupManagementExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Create a new group
+    fn new_group(
+        &mut self,
+        origin: &str,
+        name: &str,
+        accounts: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(origin), name, accounts];
+        self.contract_send_tx("newGroup", &values, options, None)
+    }
+
+    /// Delete the group
+    fn delete_group(
+        &mut self,
+        origin: &str,
+        target: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(origin), remove_0x(target)];
+        self.contract_send_tx("deleteGroup", &values, options, None)
+    }
+
+    /// Update the group name
+    fn update_group_name(
+        &mut self,
+        origin: &str,
+        target: &str,
+        name: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(origin), remove_0x(target), name];
+        self.contract_send_tx("updateGroupName", &values, options, None)
+    }
+
+    /// Add accounts
+    fn add_accounts(
+        &mut self,
+        origin: &str,
+        target: &str,
+        accounts: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(origin), remove_0x(target), accounts];
+        self.contract_send_tx("addAccounts", &values, options, None)
+    }
+
+    /// Delete accounts
+    fn delete_accounts(
+        &mut self,
+        origin: &str,
+        target: &str,
+        accounts: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(origin), remove_0x(target), accounts];
+        self.contract_send_tx("deleteAccounts", &values, options, None)
+    }
+
+    /// Check the target group in the scope of the origin group
+    ///   Scope: the origin group is the ancestor of the target group
+    fn check_scope(&self, origin: &str, target: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(origin), remove_0x(target)];
+        self.contract_call("checkScope", &values, None, height)
+    }
+
+    /// Query all groups
+    fn query_groups(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("queryGroups", &[], None, height)
+    }
+}
+
+impl<T> GroupManageClient<T>
+where
+    T: ClientExt<JsonRpcResponse, ToolError> + Clone,
+{
+    /// Move the group under the new parent group
+    ///
+    /// The contract can not reparent a group, so a group with the same name and accounts
+    /// is created under the new parent, and the old one is deleted, in one batch transaction.
+    /// The new parent must be in the scope of the origin group and out of the moved group.
+    /// A group with subgroups is refused, the address of the new group is only known after
+    /// the transaction, so its subgroups can not be created in the same batch.
+    pub fn move_group(
+        &mut self,
+        origin: &str,
+        group: &str,
+        new_parent: &str,
+        options: TransactionOptions,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        let scope = [remove_0x(origin), remove_0x(new_parent)];
+        match self
+            .contract_call_decoded("checkScope", &scope, None, None)?
+            .first()
+        {
+            Some(Token::Bool(true)) => {}
+            _ => {
+                return Err(ToolError::ScopeViolation {
+                    origin: origin.to_string(),
+                    target: new_parent.to_string(),
+                });
+            }
+        }
+
+        let origin_address = parse_address(origin)?;
+        let group_address = parse_address(group)?;
+        let new_parent_address = parse_address(new_parent)?;
+        let subtree = self.subtree_leaves_first(group)?;
+        if subtree.contains(&new_parent_address) {
+            return Err(ToolError::Customize(format!(
+                "Can not move the group {} under {}, which is in the group itself",
+                group, new_parent
+            )));
+        }
+        if subtree.len() > 1 {
+            return Err(ToolError::Customize(format!(
+                "The group {} has {} subgroups, move or delete them first",
+                group,
+                subtree.len() - 1
+            )));
+        }
+
+        let group_client = GroupClient::create(self.client.clone());
+        let name = match group_client
+            .contract_call_decoded("queryName", &[], Some(group_address), None)?
+            .as_slice()
+        {
+            [name @ Token::FixedBytes(_)] => name.clone(),
+            tokens => return Err(ToolError::Abi(format!("Not a bytes32: {:?}", tokens))),
+        };
+        let accounts = match group_client
+            .contract_call_decoded("queryAccounts", &[], Some(group_address), None)?
+            .as_slice()
+        {
+            [accounts @ Token::Array(_)] => accounts.clone(),
+            tokens => {
+                return Err(ToolError::Abi(format!(
+                    "Not an address array: {:?}",
+                    tokens
+                )))
+            }
+        };
+
+        let batch_tx = |name: &str, tokens: &[Token]| {
+            encode_tokens(&self.contract, name, tokens)
+                .map(|code| format!("{:x}{}", self.address, remove_0x(&code)))
+        };
+        let new_group = batch_tx(
+            "newGroup",
+            &[Token::Address(new_parent_address), name, accounts],
+        )?;
+        let delete_group = batch_tx(
+            "deleteGroup",
+            &[
+                Token::Address(origin_address),
+                Token::Address(group_address),
+            ],
+        )?;
+        BatchTxClient::create(self.client.clone())
+            .multi_transactions(vec![new_group.as_str(), delete_group.as_str()], options)
+    }
+
+    /// Create every `(origin, name, accounts)` group with `newGroup` in one batch transaction,
+    /// at most `max_groups` of them to keep the batch within the block quota
+    ///
+    /// No name can be empty, and no origin can be in the accounts of its own group.
+    pub fn batch_new_group(
+        &mut self,
+        groups: Vec<(&str, &str, &str)>,
+        max_groups: usize,
+        options: TransactionOptions,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        if groups.is_empty() {
+            return Err(ToolError::Customize("No group to create".to_string()));
+        }
+        if groups.len() > max_groups {
+            return Err(ToolError::Customize(format!(
+                "{} groups exceed the limit {} of one batch, split them into smaller ones",
+                groups.len(),
+                max_groups
+            )));
+        }
+        for (origin, name, accounts) in &groups {
+            if name.trim().is_empty() {
+                return Err(ToolError::Customize(format!(
+                    "The name of the group under {} is empty",
+                    origin
+                )));
+            }
+            let origin_address = parse_address(origin)?;
+            let in_accounts = accounts
+                .trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .filter(|account| !account.trim().is_empty())
+                .map(|account| parse_address(account.trim()))
+                .collect::<Result<Vec<Address>, ToolError>>()?
+                .contains(&origin_address);
+            if in_accounts {
+                return Err(ToolError::Customize(format!(
+                    "The origin {} of group {} is in its own accounts",
+                    origin, name
+                )));
+            }
+        }
+
+        let txs = groups
+            .iter()
+            .map(|(origin, name, accounts)| {
+                self.prepare_call_args("newGroup", &[remove_0x(origin), name, accounts], None)
+                    .map(|(code, to_address)| {
+                        format!("{}{}", remove_0x(&to_address), remove_0x(&code))
+                    })
+            })
+            .collect::<Result<Vec<String>, ToolError>>()?;
+        BatchTxClient::create(self.client.clone())
+            .multi_transactions(txs.iter().map(String::as_str).collect(), options)
+    }
+
+    /// Submit the deletion of the group and all its descendants with `deleteGroup` in one batch
+    /// transaction, return the response of the transaction and the groups in the order of
+    /// deletion
+    ///
+    /// The contract can not delete a group with children, so the children are deleted
+    /// before their parents. The batch transaction is atomic, no group is deleted if
+    /// any deletion fails. Nothing is deleted until the transaction is committed, watch
+    /// the returned transaction, e.g. with `Client::watch_transaction`, to know the outcome.
+    pub fn submit_delete_group_recursive(
+        &mut self,
+        origin: &str,
+        root: &str,
+        options: TransactionOptions,
+    ) -> Result<(JsonRpcResponse, Vec<Address>), ToolError> {
+        let groups = self.subtree_leaves_first(root)?;
+        let txs = groups
+            .iter()
+            .map(|group| {
+                let group = format!("{:x}", group);
+                self.prepare_call_args("deleteGroup", &[remove_0x(origin), &group], None)
+                    .map(|(code, to_address)| {
+                        format!("{}{}", remove_0x(&to_address), remove_0x(&code))
+                    })
+            })
+            .collect::<Result<Vec<String>, ToolError>>()?;
+        let response = BatchTxClient::create(self.client.clone())
+            .multi_transactions(txs.iter().map(String::as_str).collect(), options)?;
+        // Fail if the transaction is rejected by the node
+        response.clone().into_result()?;
+        Ok((response, groups))
+    }
+
+    /// The group and all its descendants, every group after its children
+    fn subtree_leaves_first(&self, root: &str) -> Result<Vec<Address>, ToolError> {
+        let group_client = GroupClient::create(self.client.clone());
+        let mut groups = Vec::new();
+        let mut visited = HashSet::new();
+        let mut level = vec![parse_address(root)?];
+        while !level.is_empty() {
+            let mut next_level = Vec::new();
+            for group in level.into_iter().filter(|group| visited.insert(*group)) {
+                next_level.extend(decode_addresses(group_client.contract_call_decoded(
+                    "queryChild",
+                    &[],
+                    Some(group),
+                    None,
+                )?)?);
+                groups.push(group);
+            }
+            level = next_level;
+        }
+        groups.reverse();
+        Ok(groups)
+    }
+}
+
+impl GroupManageClient<Client> {
+    /// Search the group tree from the root group for the group with the name,
+    /// the names and children of the groups at the same depth are queried at the same time
+    ///
+    /// The shallowest matching group is returned, `None` if no group has the name.
+    pub fn find_group_by_name(
+        &self,
+        name: &str,
+        options: FindOptions,
+        height: Option<&str>,
+    ) -> Result<Option<Address>, ToolError> {
+        let group_client = GroupClient::create(self.client.clone());
+        let query_all = |method: &str, groups: &[Address]| {
+            let calls = groups
+                .iter()
+                .map(|group| (Vec::new(), Some(*group)))
+                .collect();
+            call_all_decoded(
+                &self.client,
+                &group_client,
+                &group_client.contract,
+                method,
+                calls,
+                height,
+            )
+        };
+        let mut visited = HashSet::new();
+        let mut level = vec![group_client.address];
+        while !level.is_empty() {
+            level.retain(|group| visited.insert(*group));
+            for (group, tokens) in level.iter().zip(query_all("queryName", &level)?) {
+                if options.matches(&decode_name(tokens)?, name) {
+                    return Ok(Some(*group));
+                }
+            }
+            level = query_all("queryChild", &level)?
+                .into_iter()
+                .map(decode_addresses)
+                .collect::<Result<Vec<Vec<Address>>, ToolError>>()?
+                .concat();
+        }
+        Ok(None)
+    }
+
+    /// Check every `(origin, target)` pair of groups with `checkScope` at the same time,
+    /// the results are in the same order as the pairs
+    pub fn check_scope_all(
+        &self,
+        pairs: Vec<(&str, &str)>,
+        height: Option<&str>,
+    ) -> Result<Vec<bool>, ToolError> {
+        let values_list = pairs
+            .iter()
+            .map(|(origin, target)| vec![remove_0x(origin), remove_0x(target)])
+            .collect();
+        call_all_bool(
+            &self.client,
+            self,
+            &self.contract,
+            "checkScope",
+            values_list,
+            height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FindOptions, GroupClient, GroupExt, GroupInfo, GroupManageClient, MembershipDiff};
+    use crate::client::TransactionOptions;
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+    use ethabi::Address;
+
+    /// Response of `queryAccounts`, the accounts are `0x00..0n`
+    fn accounts_response(accounts: &[u8]) -> JsonRpcResponse {
+        let mut output = format!("{:064x}{:064x}", 32, accounts.len());
+        for account in accounts {
+            output.push_str(&format!("{:064x}", account));
+        }
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+            output
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_query_group_info() {
+        let response = |output: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+                output
+            ))
+            .unwrap()
+        };
+        let info = format!(
+            "{:0<64}{:064x}{:064x}{:064x}{:064x}",
+            "726f6f74", 64, 2, 1, 2
+        );
+        let client = GroupClient::create_with_responses(vec![response(&info), response("")]);
+        let group = "0xffffffffffffffffffffffffffffffffff020009";
+        let account = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+        assert_eq!(
+            client.query_group_info(group, None).unwrap(),
+            GroupInfo {
+                name: "root".to_string(),
+                accounts: vec![account(1), account(2)],
+            }
+        );
+        assert!(client.query_group_info(group, None).is_err());
+    }
+
+    #[test]
+    fn test_query_name_string() {
+        let response = |name: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+                name
+            ))
+            .unwrap()
+        };
+        let client = GroupClient::create_with_responses(vec![response("726f6f74"), response("ff")]);
+        let group = "0xffffffffffffffffffffffffffffffffff020009";
+        assert_eq!(client.query_name_string(group, None).unwrap(), "root");
+        match client.query_name_string(group, None) {
+            Err(ToolError::AbiDecode(_)) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_membership_diff() {
+        let account = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+        let diff = MembershipDiff::between(
+            &[account(3), account(1), account(2)],
+            &[account(4), account(2), account(3), account(4)],
+        );
+        assert_eq!(diff.added, vec![account(4)]);
+        assert_eq!(diff.removed, vec![account(1)]);
+        assert_eq!(diff.unchanged, vec![account(2), account(3)]);
+        assert_eq!(MembershipDiff::between(&[], &[]), MembershipDiff::default());
+    }
+
+    #[test]
+    fn test_diff_membership_with_responses() {
+        let group = GroupClient::create_with_responses(vec![
+            accounts_response(&[1, 2]),
+            accounts_response(&[2, 3]),
+        ]);
+        let diff = group
+            .diff_membership("0xffffffffffffffffffffffffffffffffff020009", 1, 2)
+            .unwrap();
+        let account = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+        assert_eq!(diff.added, vec![account(3)]);
+        assert_eq!(diff.removed, vec![account(1)]);
+        assert_eq!(diff.unchanged, vec![account(2)]);
+        assert!(group
+            .query_accounts_list("0xffffffffffffffffffffffffffffffffff020009", None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_subtree_leaves_first() {
+        let client = GroupManageClient::create_with_responses(vec![
+            accounts_response(&[2, 3]),
+            accounts_response(&[4]),
+            accounts_response(&[]),
+            accounts_response(&[]),
+        ]);
+        let group = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+        assert_eq!(
+            client
+                .subtree_leaves_first("0x0000000000000000000000000000000000000001")
+                .unwrap(),
+            vec![group(4), group(3), group(2), group(1)]
+        );
+    }
+
+    #[test]
+    fn test_move_group_checks() {
+        let check_scope: JsonRpcResponse = serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            1
+        ))
+        .unwrap();
+        let origin = "0xffffffffffffffffffffffffffffffffff020009";
+        let group = "0x0000000000000000000000000000000000000001";
+        for new_parent in &[
+            "0x0000000000000000000000000000000000000002",
+            "0x0000000000000000000000000000000000000005",
+        ] {
+            let mut client = GroupManageClient::create_with_responses(vec![
+                check_scope.clone(),
+                accounts_response(&[2]),
+                accounts_response(&[]),
+            ]);
+            assert!(client
+                .move_group(origin, group, new_parent, TransactionOptions::new())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_find_group_by_name() {
+        let name_response = |name: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+                hex::encode(name)
+            ))
+            .unwrap()
+        };
+        let responses = || {
+            vec![
+                name_response("root"),
+                accounts_response(&[1, 2]),
+                name_response("Foo"),
+                name_response("bar"),
+                accounts_response(&[]),
+                accounts_response(&[]),
+            ]
+        };
+        let group = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+
+        let client = GroupManageClient::create_with_responses(responses());
+        let options = FindOptions::new().set_case_insensitive(true);
+        assert_eq!(
+            client.find_group_by_name("BAR", options, None).unwrap(),
+            Some(group(2))
+        );
+        let client = GroupManageClient::create_with_responses(responses());
+        assert_eq!(
+            client
+                .find_group_by_name("BAR", FindOptions::new(), None)
+                .unwrap(),
+            None
+        );
+    }
+}
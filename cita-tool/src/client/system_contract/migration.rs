@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use hex::encode;
+
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{
+    decode_addresses, AuthorizationClient, AuthorizationExt, ContractCall, GroupAudit,
+    GroupManageClient, GroupManagementExt, PermissionAudit, PermissionManageClient,
+    PermissionManagementExt, RoleAudit, RoleManageClient, RoleManagementExt,
+};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::crypto::PrivateKey;
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcResponse, ParamsValue, ResponseValue, TransactionReceipt};
+use crate::system_addresses::{GROUP_CREATOR, PERMISSION_CREATOR, ROLE_CREATOR};
+use ethabi::Token;
+
+/// Blocks a transaction stays valid after the height it's sent at
+const VALID_BLOCKS: u64 = 88;
+
+/// Permissions, roles and groups with their accounts, exported by
+/// `Client::export_governance_state` to be imported to another chain
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GovernanceExport {
+    /// Height of the export
+    pub height: u64,
+    /// Permissions authorized to any account
+    pub permissions: Vec<PermissionAudit>,
+    /// Roles assigned to any account
+    pub roles: Vec<RoleAudit>,
+    /// All groups
+    pub groups: Vec<GroupAudit>,
+}
+
+/// Result of `Client::import_governance_state`, the objects are the permissions,
+/// roles and groups, and the assignments of them to the accounts
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ImportReport {
+    /// Objects created
+    pub created: usize,
+    /// Objects already on the chain
+    pub skipped: usize,
+    /// Objects failed to create
+    pub failed: usize,
+    /// Reasons of the failures
+    pub errors: Vec<String>,
+}
+
+impl ImportReport {
+    /// Map the exported address of the object to the created one,
+    /// or itself if skipped, and record the result
+    fn record_object(
+        &mut self,
+        addresses: &mut HashMap<String, String>,
+        object: &str,
+        address: &str,
+        result: Result<Option<String>, ToolError>,
+    ) {
+        let result = result.map(|created| {
+            let imported = created.clone().unwrap_or_else(|| address.to_string());
+            addresses.insert(address.to_string(), imported);
+            created.is_some()
+        });
+        self.record(&format!("{} {}", object, address), result);
+    }
+
+    fn record(&mut self, object: &str, result: Result<bool, ToolError>) {
+        match result {
+            Ok(true) => self.created += 1,
+            Ok(false) => self.skipped += 1,
+            Err(err) => {
+                self.failed += 1;
+                self.errors.push(format!("{}: {}", object, err));
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Export the permissions, roles and groups with their accounts at the height,
+    /// default is the current one, collected as `governance_audit` does
+    pub fn export_governance_state(
+        &self,
+        height: Option<u64>,
+    ) -> Result<GovernanceExport, ToolError> {
+        let audit = self.governance_audit(height)?;
+        Ok(GovernanceExport {
+            height: audit.height,
+            permissions: audit.permissions,
+            roles: audit.roles,
+            groups: audit.groups,
+        })
+    }
+
+    /// Import the export by sending the transactions signed by the private key,
+    /// one by one, every one is waited to be committed
+    ///
+    /// The permissions, roles and groups deployed on this chain at the same addresses,
+    /// such as the built-in ones, are skipped, the others are created at new addresses
+    /// and referred to by them. Then the roles and the permissions are assigned to the
+    /// accounts not having them. The accounts of the skipped groups are not changed.
+    pub fn import_governance_state(
+        &self,
+        export: &GovernanceExport,
+        privkey: &PrivateKey,
+        options: TransactionOptions,
+    ) -> Result<ImportReport, ToolError> {
+        let mut client = self.clone();
+        client.set_private_key(privkey);
+        let mut report = ImportReport::default();
+        // The addresses on the exported chain to the ones on this chain
+        let mut addresses: HashMap<String, String> = HashMap::new();
+
+        let mut permission_manage_client = PermissionManageClient::create(client.clone());
+        for permission in &export.permissions {
+            let result = client.import_object(&permission.address, || {
+                let (contracts, funcs) = split_resources(&permission.resources)?;
+                let name = encode_name(&permission.name)?;
+                permission_manage_client.new_permission(
+                    &name,
+                    &format!("[{}]", contracts.join(",")),
+                    &format!("[{}]", funcs.join(",")),
+                    options,
+                )
+            });
+            report.record_object(&mut addresses, "permission", &permission.address, result);
+        }
+
+        let mut role_manage_client = RoleManageClient::create(client.clone());
+        for role in &export.roles {
+            let result = client.import_object(&role.address, || {
+                let permissions = map_addresses(&addresses, &role.permissions)?;
+                role_manage_client.new_role(
+                    &encode_name(&role.name)?,
+                    &format!("[{}]", permissions.join(",")),
+                    options,
+                )
+            });
+            report.record_object(&mut addresses, "role", &role.address, result);
+        }
+        for role in export.roles.iter() {
+            let mapped = match addresses.get(&role.address) {
+                Some(mapped) => mapped.clone(),
+                None => continue,
+            };
+            for account in &role.accounts {
+                let result = role_manage_client
+                    .contract_call_decoded("queryRoles", &[remove_0x(account)], None, None)
+                    .and_then(decode_addresses)
+                    .and_then(|roles| {
+                        if roles.iter().any(|role| format!("{:?}", role) == mapped) {
+                            return Ok(false);
+                        }
+                        client
+                            .send_confirmed(|| {
+                                role_manage_client.set_role(account, &mapped, options)
+                            })
+                            .map(|_| true)
+                    });
+                report.record(&format!("role {} of {}", role.address, account), result);
+            }
+        }
+
+        let mut group_manage_client = GroupManageClient::create(client.clone());
+        let mut groups = export.groups.iter().collect::<Vec<&GroupAudit>>();
+        // Create the parent groups first, the remaining ones have no imported parent
+        loop {
+            let (ready, waiting): (Vec<&GroupAudit>, Vec<&GroupAudit>) =
+                groups.into_iter().partition(|group| {
+                    addresses.contains_key(&group.parent) || client.has_code(&group.address)
+                });
+            if ready.is_empty() {
+                groups = waiting;
+                break;
+            }
+            for group in ready {
+                let result = client.import_object(&group.address, || {
+                    let accounts = group
+                        .accounts
+                        .iter()
+                        .map(|account| remove_0x(account))
+                        .collect::<Vec<&str>>();
+                    group_manage_client.new_group(
+                        &addresses[&group.parent],
+                        &encode_name(&group.name)?,
+                        &format!("[{}]", accounts.join(",")),
+                        options,
+                    )
+                });
+                report.record_object(&mut addresses, "group", &group.address, result);
+            }
+            groups = waiting;
+        }
+        for group in groups {
+            report.record(
+                &format!("group {}", group.address),
+                Err(ToolError::Customize(format!(
+                    "parent group {} is not imported",
+                    group.parent
+                ))),
+            );
+        }
+
+        // The accounts of the roles already have their permissions
+        let authorization_client = AuthorizationClient::create(client.clone());
+        for permission in &export.permissions {
+            let mapped = match addresses.get(&permission.address) {
+                Some(mapped) => mapped.clone(),
+                None => continue,
+            };
+            for account in &permission.accounts {
+                let result = authorization_client
+                    .contract_call_decoded(
+                        "checkPermission",
+                        &[remove_0x(account), remove_0x(&mapped)],
+                        None,
+                        None,
+                    )
+                    .and_then(|tokens| match tokens.as_slice() {
+                        [Token::Bool(true)] => Ok(false),
+                        [Token::Bool(false)] => client
+                            .send_confirmed(|| {
+                                permission_manage_client
+                                    .set_authorization(account, &mapped, options)
+                            })
+                            .map(|_| true),
+                        tokens => Err(ToolError::Abi(format!("Not a bool: {:?}", tokens))),
+                    });
+                report.record(
+                    &format!("permission {} of {}", permission.address, account),
+                    result,
+                );
+            }
+        }
+        Ok(report)
+    }
+
+    /// Skip the object deployed at the address, or create it and return the created
+    /// address, which is the first topic of the log of its creator
+    fn import_object<F>(&self, address: &str, create: F) -> Result<Option<String>, ToolError>
+    where
+        F: FnOnce() -> Result<JsonRpcResponse, ToolError>,
+    {
+        if self.has_code(address) {
+            return Ok(None);
+        }
+        let receipt = self.send_confirmed(create)?;
+        let log = receipt
+            .logs
+            .iter()
+            .find(|log| {
+                [PERMISSION_CREATOR, ROLE_CREATOR, GROUP_CREATOR].contains(&log.address)
+                    && log.topics.len() > 1
+            })
+            .ok_or_else(|| ToolError::Customize("Receipt has no creation log".to_string()))?;
+        Ok(Some(format!("0x{}", encode(&log.topics[1].0[12..]))))
+    }
+
+    /// Whether the address has code at the current height
+    fn has_code(&self, address: &str) -> bool {
+        match <Client as ClientExt<JsonRpcResponse, ToolError>>::get_code(self, address, "latest")
+            .and_then(JsonRpcResponse::into_result)
+        {
+            Ok(ResponseValue::Singe(ParamsValue::String(code))) => !remove_0x(&code).is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Send the transaction and wait for its receipt until it's no longer valid
+    fn send_confirmed<F>(&self, send: F) -> Result<TransactionReceipt, ToolError>
+    where
+        F: FnOnce() -> Result<JsonRpcResponse, ToolError>,
+    {
+        let deadline = self.get_current_height()? + VALID_BLOCKS;
+        let hash = match send()?.into_result()? {
+            ResponseValue::Map(mut result) => result.remove("hash"),
+            _ => None,
+        }
+        .ok_or_else(|| ToolError::Customize("Transaction has no hash".to_string()))?
+        .into_string()?;
+        loop {
+            if let Some(receipt) = self.get_receipt(&hash)? {
+                return match receipt.error_message {
+                    Some(error_message) => Err(ToolError::Customize(format!(
+                        "Transaction {} failed: {}",
+                        hash, error_message
+                    ))),
+                    None => Ok(receipt),
+                };
+            }
+            if self.get_current_height()? > deadline {
+                return Err(ToolError::Customize(format!(
+                    "Transaction {} is not committed before block {}",
+                    hash, deadline
+                )));
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
+
+/// Split the `contract:function signature` resources into the contracts and the functions
+fn split_resources(resources: &[String]) -> Result<(Vec<&str>, Vec<&str>), ToolError> {
+    resources
+        .iter()
+        .map(|resource| {
+            let mut parts = resource.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(contract), Some(func)) => Ok((remove_0x(contract), remove_0x(func))),
+                _ => Err(ToolError::Customize(format!(
+                    "Invalid resource: {}",
+                    resource
+                ))),
+            }
+        })
+        .collect::<Result<Vec<(&str, &str)>, ToolError>>()
+        .map(|resources| resources.into_iter().unzip())
+}
+
+/// Encode the name into `bytes32`, padded with zeros
+fn encode_name(name: &str) -> Result<String, ToolError> {
+    if name.len() > 32 {
+        return Err(ToolError::Customize(format!(
+            "Name {} is longer than 32 bytes",
+            name
+        )));
+    }
+    Ok(format!("{:0<64}", encode(name)))
+}
+
+/// The imported addresses of the addresses, without `0x`
+fn map_addresses<'a>(
+    addresses: &'a HashMap<String, String>,
+    exported: &[String],
+) -> Result<Vec<&'a str>, ToolError> {
+    exported
+        .iter()
+        .map(|address| {
+            addresses
+                .get(address)
+                .map(|mapped| remove_0x(mapped))
+                .ok_or_else(|| ToolError::Customize(format!("{} is not imported", address)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode_name, split_resources, GovernanceExport};
+
+    #[test]
+    fn test_split_resources() {
+        let resources = vec![
+            "0xffffffffffffffffffffffffffffffffff020004:0x2b8d2a4b".to_string(),
+            "0xffffffffffffffffffffffffffffffffff020005:0xf036ed56".to_string(),
+        ];
+        let (contracts, funcs) = split_resources(&resources).unwrap();
+        assert_eq!(
+            contracts,
+            vec![
+                "ffffffffffffffffffffffffffffffffff020004",
+                "ffffffffffffffffffffffffffffffffff020005"
+            ]
+        );
+        assert_eq!(funcs, vec!["2b8d2a4b", "f036ed56"]);
+        assert!(split_resources(&["0x2b8d2a4b".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_encode_name() {
+        assert_eq!(
+            encode_name("admin").unwrap(),
+            format!("61646d696e{}", "0".repeat(54))
+        );
+        assert!(encode_name(&"x".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn test_export_json() {
+        let export: GovernanceExport = serde_json::from_str(
+            r#"{
+                "height": 100,
+                "permissions": [{
+                    "address": "0xca645d2b0d2e4c451a2dd546dbd7ab8c29c3dcee",
+                    "name": "transfer",
+                    "resources": ["0x1e6efe78f81d0f6d9bf5c0cd3ab4b7faaa2d9ce1:0xa9059cbb"],
+                    "accounts": ["0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523"]
+                }],
+                "roles": [],
+                "groups": [{
+                    "address": "0xffffffffffffffffffffffffffffffffff020009",
+                    "name": "rootGroup",
+                    "parent": "0x0000000000000000000000000000000000000000",
+                    "accounts": []
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(export.permissions[0].name, "transfer");
+        let json = serde_json::to_value(&export).unwrap();
+        assert_eq!(json["groups"][0]["name"], "rootGroup");
+    }
+}
@@ -0,0 +1,843 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+#[cfg(feature = "quota")]
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{
+    decode_addresses, decode_uints, parse_address, BatchTxClient, BatchTxExt, ContractCall,
+};
+#[cfg(feature = "quota")]
+use crate::client::system_contract::{decode_u64, QuotaManageClient, QuotaManagementExt};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::contract_abi::NODE_MANAGER_CONTRACT;
+use crate::error::ToolError;
+use crate::proof::BlockProof;
+use crate::rpctypes::{ChainMetadata, JsonRpcResponse, Log, LogFilter, ParamsValue, ResponseValue};
+use crate::system_addresses::NODE_MANAGER;
+use crate::LowerHex;
+use ethabi::{Address, Contract};
+use failure::Fail;
+use tool_derive::ContractExt;
+use types::{H256, U256};
+
+/// `getStatus` of a node added by `newNode` and waiting for approval
+#[cfg(feature = "quota")]
+const NODE_STATUS_READY: u64 = 1;
+
+/// Node manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020001")]
+#[contract(abi_contract = "crate::contract_abi::NODE_MANAGER_CONTRACT")]
+#[contract(name = "NodeManagementExt")]
+pub struct NodeManageClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Stake of a node changed by `setStake`
+#[derive(Clone, Debug, PartialEq)]
+pub struct StakeChange {
+    /// Block of the change
+    pub block_number: u64,
+    /// Node address
+    pub node_address: Address,
+    /// Stake before the change
+    pub old_stake: u64,
+    /// Stake after the change
+    pub new_stake: u64,
+}
+
+/// Status of the last committed BFT round
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsensusStatus {
+    /// Height of the committed block
+    pub height: u64,
+    /// Round the block is committed in
+    pub round: u64,
+    /// Step of the round, only committed rounds are known so it's always `commit`
+    pub step: String,
+    /// Proposer of the committed block
+    pub proposer: Address,
+    /// Commit votes in the proof
+    pub votes_received: u32,
+    /// Votes needed to commit, more than two thirds of the consensus nodes
+    pub required_votes: u32,
+}
+
+/// NodeManager system contract
+pub trait NodeManagementExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Downgrade consensus node to ordinary node
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut node_manager = NodeManageClient::create(client);
+    /// let response = node_manager
+    ///     .downgrade_consensus_node(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         TransactionOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn downgrade_consensus_node(
+        &mut self,
+        address: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_send_tx("deleteNode", &values, options, None)
+    }
+
+    /// Get node status
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let node_manager = NodeManageClient::create(client);
+    /// let response = node_manager
+    ///     .node_status("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `getStatus`
+    /// println!("{}", response);
+    /// ```
+    fn node_status(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_call("getStatus", &values, None, height)
+    }
+
+    /// Get authorities
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let node_manager = NodeManageClient::create(client);
+    /// let response = node_manager.get_authorities(None).unwrap();
+    /// // The ABI encoded output of `listNode`
+    /// println!("{}", response);
+    /// ```
+    fn get_authorities(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("listNode", &[], None, height)
+    }
+
+    /// Approve node upgrades to consensus nodes
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut node_manager = NodeManageClient::create(client);
+    /// let response = node_manager
+    ///     .approve_node("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn approve_node(&mut self, address: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_send_tx("approveNode", &values, options, None)
+    }
+
+    /// Node stake list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let node_manager = NodeManageClient::create(client);
+    /// let response = node_manager.list_stake(None).unwrap();
+    /// // The ABI encoded output of `listStake`
+    /// println!("{}", response);
+    /// ```
+    fn list_stake(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("listStake", &[], None, height)
+    }
+
+    /// Set node stake
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut node_manager = NodeManageClient::create(client);
+    /// let response = node_manager
+    ///     .set_stake(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         U256::from(100),
+    ///         TransactionOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_stake(
+        &mut self,
+        address: &str,
+        stake: U256,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let stake = stake.completed_lower_hex();
+        let values = [remove_0x(address), stake.as_str()];
+        self.contract_send_tx("setStake", &values, options, None)
+    }
+
+    /// Stake permillage
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{NodeManageClient, NodeManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let node_manager = NodeManageClient::create(client);
+    /// let response = node_manager
+    ///     .stake_permillage("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `stakePermillage`
+    /// println!("{}", response);
+    /// ```
+    fn stake_permillage(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("stakePermillage", &[remove_0x(address)], None, height)
+    }
+}
+
+impl<T> NodeManageClient<T>
+where
+    T: ClientExt<JsonRpcResponse, ToolError> + Clone,
+{
+    /// Downgrade all consensus nodes without stake, in one batch transaction,
+    /// return the downgraded nodes
+    pub fn downgrade_all_zero_stake(
+        &mut self,
+        options: TransactionOptions,
+    ) -> Result<Vec<Address>, ToolError> {
+        let zero_stake = self
+            .node_stakes(None)?
+            .into_iter()
+            .filter(|(_, stake)| stake.is_zero())
+            .map(|(node, _)| node)
+            .collect::<Vec<Address>>();
+        if zero_stake.is_empty() {
+            return Ok(zero_stake);
+        }
+
+        let txs = zero_stake
+            .iter()
+            .map(|node| {
+                let node = format!("{:x}", node);
+                self.prepare_call_args("deleteNode", &[&node], None)
+                    .map(|(code, to_address)| {
+                        format!("{}{}", remove_0x(&to_address), remove_0x(&code))
+                    })
+            })
+            .collect::<Result<Vec<String>, ToolError>>()?;
+        BatchTxClient::create(self.client.clone())
+            .multi_transactions(txs.iter().map(String::as_str).collect(), options)?
+            .into_result()?;
+        Ok(zero_stake)
+    }
+
+    /// Stake of the consensus node at the height, `latest` by default
+    ///
+    /// Fail if the address is not a consensus node.
+    pub fn get_stake(&self, address: &str, height: Option<&str>) -> Result<u64, ToolError> {
+        let node = parse_address(address)?;
+        match self
+            .node_stakes(height)?
+            .into_iter()
+            .find(|(address, _)| *address == node)
+        {
+            Some((_, stake)) if stake.bits() <= 64 => Ok(stake.low_u64()),
+            Some((_, stake)) => Err(ToolError::Abi(format!("{} is not an u64", stake))),
+            None => Err(ToolError::Customize(format!(
+                "{:?} is not a consensus node",
+                node
+            ))),
+        }
+    }
+
+    /// Consensus nodes with their stakes, `listNode` and `listStake` at the height
+    fn node_stakes(&self, height: Option<&str>) -> Result<Vec<(Address, U256)>, ToolError> {
+        let nodes = decode_addresses(self.contract_call_decoded("listNode", &[], None, height)?)?;
+        let stakes = decode_uints(self.contract_call_decoded("listStake", &[], None, height)?)?;
+        if nodes.len() != stakes.len() {
+            return Err(ToolError::Customize(format!(
+                "{} nodes but {} stakes",
+                nodes.len(),
+                stakes.len()
+            )));
+        }
+        Ok(nodes.into_iter().zip(stakes).collect())
+    }
+}
+
+impl NodeManageClient<Client> {
+    /// Set the stakes of several consensus nodes in one batch transaction
+    ///
+    /// Every address must be a consensus node and appear only once, the sum of
+    /// the estimated quota of the `setStake` calls must not exceed the block quota limit,
+    /// or it fails with `ToolError::InsufficientBlockQuota`.
+    #[cfg(feature = "quota")]
+    pub fn set_stake_batch(
+        &mut self,
+        stakes: Vec<(&str, u64)>,
+        options: TransactionOptions,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        let nodes = decode_addresses(self.contract_call_decoded("listNode", &[], None, None)?)?;
+        let mut seen = HashSet::new();
+        for (address, _) in stakes.iter() {
+            let node = parse_address(address)?;
+            if !seen.insert(node) {
+                return Err(ToolError::Customize(format!(
+                    "{} appears more than once",
+                    address
+                )));
+            }
+            if !nodes.contains(&node) {
+                return Err(ToolError::Customize(format!(
+                    "{} is not a consensus node",
+                    address
+                )));
+            }
+        }
+
+        let mut total_quota = 0u64;
+        let mut txs = Vec::with_capacity(stakes.len());
+        for (address, stake) in stakes {
+            let stake = U256::from(stake).completed_lower_hex();
+            let (code, to_address) =
+                self.prepare_call_args("setStake", &[remove_0x(address), stake.as_str()], None)?;
+            let quota = self.client.estimate_quota_u64(None, &to_address, &code)?;
+            total_quota = total_quota.saturating_add(quota);
+            txs.push(format!("{}{}", remove_0x(&to_address), remove_0x(&code)));
+        }
+        let limit = QuotaManageClient::create(self.client.clone()).get_bql_u64(None)?;
+        if total_quota > limit {
+            return Err(ToolError::InsufficientBlockQuota {
+                estimated: total_quota,
+                limit,
+            });
+        }
+
+        BatchTxClient::create(self.client.clone())
+            .multi_transactions(txs.iter().map(String::as_str).collect(), options)
+    }
+
+    /// Approve the candidates not yet consensus nodes, return the approved ones
+    ///
+    /// The node manager keeps no list of the nodes waiting for approval, so the
+    /// candidates are given and the ones with `getStatus` ready are approved, the
+    /// closed ones were never added by `newNode` or were deleted.
+    /// The `approveNode` calls are batched, split into several batch transactions
+    /// when their estimated quota exceeds the block quota limit. If a batch fails
+    /// after others were sent, `ToolError::PartialApproval` has the approved nodes.
+    /// In dry run mode of the client nothing is sent, the returned nodes are the
+    /// ones to approve.
+    #[cfg(feature = "quota")]
+    pub fn approve_pending_nodes(
+        &mut self,
+        candidates: &[&str],
+        options: TransactionOptions,
+    ) -> Result<Vec<Address>, ToolError> {
+        let mut pending = Vec::new();
+        for candidate in candidates {
+            let node = parse_address(candidate)?;
+            if pending.iter().any(|(address, _, _)| *address == node) {
+                continue;
+            }
+            let node_hex = format!("{:x}", node);
+            let status = decode_u64(self.contract_call_decoded(
+                "getStatus",
+                &[node_hex.as_str()],
+                None,
+                None,
+            )?)?;
+            if status != NODE_STATUS_READY {
+                continue;
+            }
+            let (code, to_address) = self.prepare_call_args("approveNode", &[&node_hex], None)?;
+            let quota = self.client.estimate_quota_u64(None, &to_address, &code)?;
+            let tx = format!("{}{}", remove_0x(&to_address), remove_0x(&code));
+            pending.push((node, tx, quota));
+        }
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let block_quota_limit = QuotaManageClient::create(self.client.clone()).get_bql_u64(None)?;
+        let txs = pending
+            .iter()
+            .map(|(_, tx, quota)| (tx.as_str(), *quota))
+            .collect();
+        let mut approved = Vec::with_capacity(pending.len());
+        for batch in split_by_quota(txs, block_quota_limit)? {
+            let size = batch.len();
+            let sent = BatchTxClient::create(self.client.clone())
+                .multi_transactions(batch, options)
+                .and_then(JsonRpcResponse::into_result);
+            if let Err(error) = sent {
+                if approved.is_empty() {
+                    return Err(error);
+                }
+                return Err(ToolError::PartialApproval {
+                    approved,
+                    error: Box::new(error),
+                });
+            }
+            approved.extend(
+                pending[approved.len()..approved.len() + size]
+                    .iter()
+                    .map(|(node, _, _)| *node),
+            );
+        }
+        Ok(approved)
+    }
+
+    /// The stake changes by `SetStake` logs from `from_block` to `to_block`, in order
+    ///
+    /// The stakes before the range are the ones of `listNode` and `listStake` at
+    /// `from_block - 1`, a node not listed there starts with zero stake.
+    pub fn stake_changes_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<StakeChange>, ToolError> {
+        if from_block > to_block {
+            return Err(ToolError::Customize(format!(
+                "Invalid block range {} to {}",
+                from_block, to_block
+            )));
+        }
+        let mut stakes = HashMap::new();
+        if from_block > 0 {
+            let height = format!("{:#x}", from_block - 1);
+            let nodes = decode_addresses(self.contract_call_decoded(
+                "listNode",
+                &[],
+                None,
+                Some(height.as_str()),
+            )?)?;
+            let node_stakes = decode_uints(self.contract_call_decoded(
+                "listStake",
+                &[],
+                None,
+                Some(height.as_str()),
+            )?)?;
+            for (node, stake) in nodes.into_iter().zip(node_stakes) {
+                stakes.insert(node, stake.low_u64());
+            }
+        }
+
+        let topic = NODE_MANAGER_CONTRACT
+            .event("SetStake")
+            .map_err(|err| ToolError::Abi(err.to_string()))?
+            .signature();
+        let mut logs = self.client.get_filtered_logs(
+            LogFilter::new()
+                .set_from_block(Some(from_block))
+                .set_to_block(Some(to_block))
+                .set_address(Some(vec![NODE_MANAGER]))
+                .set_topics(Some(vec![Some(H256::from(topic.0))])),
+        )?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        logs.iter()
+            .map(|log| {
+                let (node_address, new_stake) = decode_set_stake(log)?;
+                let old_stake = stakes.insert(node_address, new_stake).unwrap_or(0);
+                Ok(StakeChange {
+                    block_number: log.block_number,
+                    node_address,
+                    old_stake,
+                    new_stake,
+                })
+            })
+            .collect()
+    }
+
+    /// Status of the BFT round committing the block before the height, `latest` by default
+    ///
+    /// Nodes don't expose the ongoing round, the status is read from the BFT proof
+    /// in the block at the height. Fail with `ToolError::Unsupported` if the block
+    /// has no BFT proof, such as the genesis block or a chain of another consensus.
+    pub fn get_consensus_status(&self, height: Option<&str>) -> Result<ConsensusStatus, ToolError> {
+        let block = <Client as ClientExt<JsonRpcResponse, ToolError>>::get_block_by_number(
+            &self.client,
+            height.unwrap_or("latest"),
+            false,
+        )?
+        .into_result()?;
+        let proof = match block {
+            ResponseValue::Map(block) => BlockProof::try_from(&ParamsValue::Map(block)),
+            block => Err(ToolError::Customize(format!("Unexpected block: {}", block))),
+        }
+        .map_err(|err| match err {
+            ToolError::Unsupported(_) => ToolError::Unsupported(
+                "consensus status not available on this chain version".to_string(),
+            ),
+            err => err,
+        })?;
+
+        let committed_height = format!("{:#x}", proof.height);
+        let proposer = self
+            .client
+            .get_decoded_block_header(&committed_height)?
+            .proposer;
+        let nodes = decode_addresses(self.contract_call_decoded(
+            "listNode",
+            &[],
+            None,
+            Some(committed_height.as_str()),
+        )?)?;
+        Ok(ConsensusStatus {
+            height: proof.height,
+            round: proof.round,
+            step: "commit".to_string(),
+            proposer: Address::from_slice(&proposer.0),
+            votes_received: proof.signatures.len() as u32,
+            required_votes: (nodes.len() * 2 / 3 + 1) as u32,
+        })
+    }
+
+    /// Expected time between two blocks proposed by the consensus node, at the height,
+    /// `latest` by default
+    ///
+    /// Proposers are chosen in proportion to the stakes, so the interval is
+    /// `block interval * total stake / node stake`, or `block interval * nodes`
+    /// if no node has stake. Fail if the node has no stake while the others have.
+    pub fn estimated_proposal_interval(
+        &self,
+        node: &str,
+        height: Option<&str>,
+    ) -> Result<Duration, ToolError> {
+        let node = parse_address(node)?;
+        let metadata = self.client.get_metadata(height.unwrap_or("latest"))?;
+        let block_interval = ChainMetadata::try_from(metadata)?.block_interval;
+        let stakes = self.node_stakes(height)?;
+        let stake = match stakes.iter().find(|(address, _)| *address == node) {
+            Some((_, stake)) => u128::from(stake.low_u64()),
+            None => {
+                return Err(ToolError::Customize(format!(
+                    "{:?} is not a consensus node",
+                    node
+                )))
+            }
+        };
+        let total_stake = stakes
+            .iter()
+            .map(|(_, stake)| u128::from(stake.low_u64()))
+            .sum::<u128>();
+
+        let interval = if total_stake == 0 {
+            u128::from(block_interval) * stakes.len() as u128
+        } else if stake == 0 {
+            return Err(ToolError::Customize(format!(
+                "{:?} has no stake, it never proposes",
+                node
+            )));
+        } else {
+            u128::from(block_interval) * total_stake / stake
+        };
+        Ok(Duration::from_millis(
+            interval.min(u128::from(u64::max_value())) as u64,
+        ))
+    }
+}
+
+/// Split the transactions, in order, into batches with the sum of the quota not
+/// over the limit, fail if one alone is over it
+#[cfg(feature = "quota")]
+fn split_by_quota(txs: Vec<(&str, u64)>, limit: u64) -> Result<Vec<Vec<&str>>, ToolError> {
+    let mut batches: Vec<Vec<&str>> = Vec::new();
+    let mut batch_quota = 0u64;
+    for (tx, quota) in txs {
+        if quota > limit {
+            return Err(ToolError::InsufficientBlockQuota {
+                estimated: quota,
+                limit,
+            });
+        }
+        match batches.last_mut() {
+            Some(batch) if batch_quota.saturating_add(quota) <= limit => {
+                batch.push(tx);
+                batch_quota += quota;
+            }
+            _ => {
+                batches.push(vec![tx]);
+                batch_quota = quota;
+            }
+        }
+    }
+    Ok(batches)
+}
+
+/// Node and stake of a `SetStake` log
+fn decode_set_stake(log: &Log) -> Result<(Address, u64), ToolError> {
+    match (log.topics.get(1), log.data.len()) {
+        (Some(node), 32) if log.data[..24].iter().all(|byte| *byte == 0) => {
+            let mut stake = [0u8; 8];
+            stake.copy_from_slice(&log.data[24..]);
+            Ok((
+                Address::from_slice(&node.0[12..]),
+                u64::from_be_bytes(stake),
+            ))
+        }
+        _ => Err(ToolError::Abi(format!(
+            "Invalid SetStake log in transaction {:?}",
+            log.transaction_hash
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "quota")]
+    use super::split_by_quota;
+    use super::NodeManageClient;
+    #[cfg(feature = "quota")]
+    use crate::client::TransactionOptions;
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+    use ethabi::Address;
+    use std::time::Duration;
+
+    /// Response of `getBlockByNumber`, the block has the proof
+    fn block_response(number: u64, proof: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{{
+                "version": 1,
+                "hash": "0x{:064x}",
+                "header": {{
+                    "timestamp": 1563254433005,
+                    "prevHash": "0x{:064x}",
+                    "number": "{:#x}",
+                    "stateRoot": "0x{:064x}",
+                    "transactionsRoot": "0x{:064x}",
+                    "receiptsRoot": "0x{:064x}",
+                    "quotaUsed": "0x0",
+                    "proof": {},
+                    "proposer": "0x{:040x}"
+                }},
+                "body": {{"transactions": []}}
+            }}}}"#,
+            number,
+            number - 1,
+            number,
+            2,
+            3,
+            4,
+            proof,
+            number
+        ))
+        .unwrap()
+    }
+
+    /// Response of `listNode` or `listStake`, an array of words
+    fn words_response(words: &[u64]) -> JsonRpcResponse {
+        let mut output = format!("{:064x}{:064x}", 32, words.len());
+        for word in words {
+            output.push_str(&format!("{:064x}", word));
+        }
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+            output
+        ))
+        .unwrap()
+    }
+
+    /// Response of `getMetaData`
+    fn metadata_response(block_interval: u64) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{{
+                "chainId": 1,
+                "genesisTimestamp": 1528700000000,
+                "blockInterval": {}
+            }}}}"#,
+            block_interval
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_estimated_proposal_interval() {
+        let node = "0x0000000000000000000000000000000000000002";
+        let client = NodeManageClient::create_with_responses(vec![
+            metadata_response(3000),
+            words_response(&[1, 2, 3]),
+            words_response(&[2, 1, 1]),
+        ]);
+        assert_eq!(
+            client.estimated_proposal_interval(node, None).unwrap(),
+            Duration::from_secs(12)
+        );
+
+        let client = NodeManageClient::create_with_responses(vec![
+            metadata_response(3000),
+            words_response(&[1, 2, 3]),
+            words_response(&[0, 0, 0]),
+        ]);
+        assert_eq!(
+            client.estimated_proposal_interval(node, None).unwrap(),
+            Duration::from_secs(9)
+        );
+
+        let client = NodeManageClient::create_with_responses(vec![
+            metadata_response(3000),
+            words_response(&[1, 3]),
+            words_response(&[1, 1]),
+        ]);
+        assert!(client.estimated_proposal_interval(node, None).is_err());
+    }
+
+    #[test]
+    fn test_get_stake() {
+        let client = NodeManageClient::create_with_responses(vec![
+            words_response(&[1, 2, 3]),
+            words_response(&[2, 5, 1]),
+            words_response(&[1, 2, 3]),
+            words_response(&[2, 5, 1]),
+        ]);
+        let node = "0x0000000000000000000000000000000000000002";
+        assert_eq!(client.get_stake(node, Some("0x10")).unwrap(), 5);
+        let node = "0x0000000000000000000000000000000000000004";
+        assert!(client.get_stake(node, None).is_err());
+        assert!(client.get_stake("0x02", None).is_err());
+    }
+
+    #[cfg(feature = "quota")]
+    #[test]
+    fn test_split_by_quota() {
+        let txs = vec![("a", 30), ("b", 50), ("c", 40), ("d", 100), ("e", 1)];
+        assert_eq!(
+            split_by_quota(txs, 100).unwrap(),
+            vec![vec!["a", "b"], vec!["c"], vec!["d"], vec!["e"]]
+        );
+        assert!(split_by_quota(vec![("a", 101)], 100).is_err());
+        assert!(split_by_quota(Vec::new(), 100).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "quota")]
+    #[test]
+    fn test_set_stake_batch() {
+        let node = "0x0000000000000000000000000000000000000001";
+        let client = || NodeManageClient::create_with_responses(vec![words_response(&[1, 2])]);
+        match client().set_stake_batch(vec![(node, 1), (node, 2)], TransactionOptions::new()) {
+            Err(ToolError::Customize(message)) => assert!(message.contains("more than once")),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        let other = "0x0000000000000000000000000000000000000003";
+        match client().set_stake_batch(vec![(node, 1), (other, 2)], TransactionOptions::new()) {
+            Err(ToolError::Customize(message)) => {
+                assert!(message.contains("not a consensus node"))
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let quota: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":"0x30000"}"#).unwrap();
+        let bql: JsonRpcResponse = serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            0x50000
+        ))
+        .unwrap();
+        let mut client = NodeManageClient::create_with_responses(vec![
+            words_response(&[1, 2]),
+            quota.clone(),
+            quota,
+            bql,
+        ]);
+        let stakes = vec![(node, 1), ("0x0000000000000000000000000000000000000002", 2)];
+        match client.set_stake_batch(stakes, TransactionOptions::new()) {
+            Err(ToolError::InsufficientBlockQuota { estimated, limit }) => {
+                assert_eq!(estimated, 0x60000);
+                assert_eq!(limit, 0x50000);
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_consensus_status() {
+        let proof = r#"{"Bft": {
+            "proposal": "0x0000000000000000000000000000000000000000000000000000000000000030",
+            "height": 48,
+            "round": 2,
+            "commits": {
+                "0x0000000000000000000000000000000000000001": "0x01",
+                "0x0000000000000000000000000000000000000002": "0x02",
+                "0x0000000000000000000000000000000000000003": "0x03"
+            }
+        }}"#;
+        let nodes: JsonRpcResponse = serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}"}}"#,
+            32, 4, 1, 2, 3, 4
+        ))
+        .unwrap();
+        let client = NodeManageClient::create_with_responses(vec![
+            block_response(49, proof),
+            block_response(48, "null"),
+            nodes,
+        ]);
+        let status = client.get_consensus_status(None).unwrap();
+        assert_eq!(status.height, 48);
+        assert_eq!(status.round, 2);
+        assert_eq!(status.step, "commit");
+        let mut proposer = [0u8; 20];
+        proposer[19] = 48;
+        assert_eq!(status.proposer, Address::from(proposer));
+        assert_eq!(status.votes_received, 3);
+        assert_eq!(status.required_votes, 3);
+
+        let client = NodeManageClient::create_with_responses(vec![block_response(1, "null")]);
+        match client.get_consensus_status(Some("0x1")) {
+            Err(ToolError::Unsupported(_)) => {}
+            other => panic!("Unexpected status: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,1400 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::abi::{contract_encode_input, parse_tokens};
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{
+    call_all_bool, decode_addresses, decode_bool, decode_name_utf8, encode_tokens, parse_address,
+    ContractCall,
+};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::contract_abi::{AUTHORIZATION_CONTRACT, PERMISSION_MANAGEMENT_CONTRACT};
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcResponse, LogFilter, ParamsValue, ResponseValue};
+use crate::system_addresses::{
+    AUTHORIZATION, CREATE_CONTRACT_PERMISSION, PERMISSION_MANAGEMENT, SEND_TX_PERMISSION,
+};
+use crate::LowerHex;
+use ethabi::{Address, Contract, ParamType, Token};
+use failure::Fail;
+use log::{info, warn};
+use tool_derive::ContractExt;
+use types::{H160, H256};
+
+/// Role manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020006")]
+#[contract(abi_contract = "crate::contract_abi::AUTHORIZATION_CONTRACT")]
+#[contract(name = "AuthorizationExt")]
+pub struct AuthorizationClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Authorization system contract
+pub trait AuthorizationExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Query the account's permissions
+    ///
+    /// param account: The account to be queried
+    /// return The permissions of account
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let authorization = AuthorizationClient::create(client);
+    /// let response = authorization
+    ///     .query_permissions("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryPermissions`
+    /// println!("{}", response);
+    /// ```
+    fn query_permissions(&self, account: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(account)];
+        self.contract_call("queryPermissions", &values, None, height)
+    }
+
+    /// Query the permission's accounts
+    ///
+    /// param permission: The permission to be queried
+    /// return The accounts of permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let authorization = AuthorizationClient::create(client);
+    /// let response = authorization
+    ///     .query_accounts("0xffffffffffffffffffffffffffffffffff021000", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryAccounts`
+    /// println!("{}", response);
+    /// ```
+    fn query_accounts(&self, permission: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(permission)];
+        self.contract_call("queryAccounts", &values, None, height)
+    }
+
+    /// Query all accounts
+    ///
+    /// return All the accounts
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let authorization = AuthorizationClient::create(client);
+    /// let response = authorization.query_all_accounts(None).unwrap();
+    /// // The ABI encoded output of `queryAllAccounts`
+    /// println!("{}", response);
+    /// ```
+    fn query_all_accounts(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("queryAllAccounts", &[], None, height)
+    }
+
+    /// Check Resource
+    ///
+    /// param account: The account to be checked
+    /// param contract: The contract of resource
+    /// param func: The function signature of resource
+    /// return true if passed, otherwise false
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let authorization = AuthorizationClient::create(client);
+    /// let response = authorization
+    ///     .check_resource(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         "0xe9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    ///         "0x2e64cec1",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// // The ABI encoded output of `checkResource`
+    /// println!("{}", response);
+    /// ```
+    fn check_resource(
+        &self,
+        account: &str,
+        contract: &str,
+        func: &str,
+        height: Option<&str>,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account), remove_0x(contract), remove_0x(func)];
+        self.contract_call("checkResource", &values, None, height)
+    }
+
+    /// Check account has a permission
+    ///
+    /// param _account The account to be checked
+    /// param _permission The address of permission
+    /// return true if passed, otherwise false
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let authorization = AuthorizationClient::create(client);
+    /// let response = authorization
+    ///     .check_permission(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         "0xffffffffffffffffffffffffffffffffff021000",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// // The ABI encoded output of `checkPermission`
+    /// println!("{}", response);
+    /// ```
+    fn check_permission(
+        &self,
+        account: &str,
+        permission: &str,
+        height: Option<&str>,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account), remove_0x(permission)];
+        self.contract_call("checkPermission", &values, None, height)
+    }
+
+    /// Check account has a resource, decoded
+    ///
+    /// param account: The account to be checked
+    /// param contract: The contract address of the resource
+    /// param func: The function signature of the resource
+    /// return true if the account has the resource
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `checkResource`, the ABI encoded true
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1
+    /// ))
+    /// .unwrap();
+    /// let authorization = AuthorizationClient::create_with_responses(vec![response]);
+    /// let has_resource = authorization
+    ///     .has_resource(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         "0xe9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    ///         "0x2e64cec1",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert!(has_resource);
+    /// ```
+    fn has_resource(
+        &self,
+        account: &str,
+        contract: &str,
+        func: &str,
+        height: Option<&str>,
+    ) -> Result<bool, E> {
+        let values = [remove_0x(account), remove_0x(contract), remove_0x(func)];
+        Ok(decode_bool(self.contract_call_decoded(
+            "checkResource",
+            &values,
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Check account has a permission, decoded
+    ///
+    /// param account: The account to be checked
+    /// param permission: The address of permission
+    /// return true if the account has the permission
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `checkPermission`, the ABI encoded true
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1
+    /// ))
+    /// .unwrap();
+    /// let authorization = AuthorizationClient::create_with_responses(vec![response]);
+    /// let has_permission = authorization
+    ///     .has_permission(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         "0xffffffffffffffffffffffffffffffffff021000",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert!(has_permission);
+    /// ```
+    fn has_permission(
+        &self,
+        account: &str,
+        permission: &str,
+        height: Option<&str>,
+    ) -> Result<bool, E> {
+        let values = [remove_0x(account), remove_0x(permission)];
+        Ok(decode_bool(self.contract_call_decoded(
+            "checkPermission",
+            &values,
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Query the accounts having the permission, decoded
+    ///
+    /// param permission: The address of permission
+    /// return The accounts of permission
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryAccounts`, one account
+    /// let account = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:0>64}"}}"#,
+    ///     32,
+    ///     1,
+    ///     account
+    /// ))
+    /// .unwrap();
+    /// let authorization = AuthorizationClient::create_with_responses(vec![response]);
+    /// let accounts = authorization
+    ///     .check_all_accounts_have_permission("0xffffffffffffffffffffffffffffffffff021000", None)
+    ///     .unwrap();
+    /// assert_eq!(accounts.len(), 1);
+    /// assert_eq!(format!("{:x}", accounts[0]), account);
+    /// ```
+    fn check_all_accounts_have_permission(
+        &self,
+        permission: &str,
+        height: Option<&str>,
+    ) -> Result<Vec<Address>, E> {
+        let values = [remove_0x(permission)];
+        Ok(decode_addresses(self.contract_call_decoded(
+            "queryAccounts",
+            &values,
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Query the accounts having the permission but not in the allowlist
+    ///
+    /// param permission: The address of permission
+    /// param allowed: The accounts allowed to have the permission
+    /// return The accounts violating the allowlist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{AuthorizationClient, AuthorizationExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryAccounts`, an allowed account and another one
+    /// let allowed = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// let other = "e9e2593c7d1db5ee843c143e9cb52b8d996b2380";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:0>64}{:0>64}"}}"#,
+    ///     32,
+    ///     2,
+    ///     allowed,
+    ///     other
+    /// ))
+    /// .unwrap();
+    /// let authorization = AuthorizationClient::create_with_responses(vec![response]);
+    /// let violations = authorization
+    ///     .check_no_account_has_permission(
+    ///         "0xffffffffffffffffffffffffffffffffff021000",
+    ///         &[allowed],
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(format!("{:x}", violations[0]), other);
+    /// ```
+    fn check_no_account_has_permission(
+        &self,
+        permission: &str,
+        allowed: &[&str],
+        height: Option<&str>,
+    ) -> Result<Vec<Address>, E> {
+        let allowed = allowed
+            .iter()
+            .map(|account| parse_address(account))
+            .collect::<Result<HashSet<Address>, ToolError>>()?;
+        let mut accounts = self.check_all_accounts_have_permission(permission, height)?;
+        accounts.retain(|account| !allowed.contains(account));
+        Ok(accounts)
+    }
+}
+
+impl AuthorizationClient<Client> {
+    /// Check the account has every `(contract, func)` resource with `checkResource`
+    /// at the same time, the results are in the same order as the resources
+    pub fn check_resources_batch(
+        &self,
+        account: &str,
+        resources: Vec<(&str, &str)>,
+        height: Option<&str>,
+    ) -> Result<Vec<bool>, ToolError> {
+        self.check_accounts_resources(
+            resources
+                .into_iter()
+                .map(|(contract, func)| (account, contract, func))
+                .collect(),
+            height,
+        )
+    }
+
+    /// Check every `(account, contract, func)` with `checkResource` at the same time,
+    /// the results are in the same order as the input
+    pub fn check_accounts_resources(
+        &self,
+        checks: Vec<(&str, &str, &str)>,
+        height: Option<&str>,
+    ) -> Result<Vec<bool>, ToolError> {
+        let values_list = checks
+            .iter()
+            .map(|(account, contract, func)| {
+                vec![remove_0x(account), remove_0x(contract), remove_0x(func)]
+            })
+            .collect();
+        call_all_bool(
+            &self.client,
+            self,
+            &self.contract,
+            "checkResource",
+            values_list,
+            height,
+        )
+    }
+}
+
+/// Permission Client
+#[derive(ContractExt)]
+#[contract(addr = "0x")]
+#[contract(abi_contract = "crate::contract_abi::PERMISSION_CONTRACT")]
+#[contract(name = "PermissionExt")]
+pub struct PermissionClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Permission system contract
+pub trait PermissionExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Check resource in the permission
+    ///
+    /// param contract: The contract address of the resource
+    /// param func: The function signature of the resource
+    /// return true if in permission, otherwise false
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let permission = PermissionClient::create(client);
+    /// let response = permission
+    ///     .in_permission(
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001",
+    ///         "0xe9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    ///         "0x2e64cec1",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// // The ABI encoded output of `inPermission`
+    /// println!("{}", response);
+    /// ```
+    fn in_permission(
+        &self,
+        address: &str,
+        contract: &str,
+        func: &str,
+        height: Option<&str>,
+    ) -> Result<R, E> {
+        let values = [remove_0x(contract), remove_0x(func)];
+        self.contract_call_to_address("inPermission", &values, address, height)
+    }
+
+    /// Query the information of the permission
+    ///
+    /// return The information of permission: name and resources
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let permission = PermissionClient::create(client);
+    /// let response = permission
+    ///     .query_info("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryInfo`
+    /// println!("{}", response);
+    /// ```
+    fn query_info(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryInfo", &[], address, height)
+    }
+
+    /// Query the name of the permission
+    ///
+    /// return The name of permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let permission = PermissionClient::create(client);
+    /// let response = permission
+    ///     .query_name("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryName`
+    /// println!("{}", response);
+    /// ```
+    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryName", &[], address, height)
+    }
+
+    /// Query the name of the permission, the `bytes32` without trailing zeros,
+    /// fail if it's not UTF-8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryName`, the name "reader"
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+    ///     encode("reader")
+    /// ))
+    /// .unwrap();
+    /// let permission = PermissionClient::create_with_responses(vec![response]);
+    /// let name = permission
+    ///     .query_name_string("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// assert_eq!(name, "reader");
+    /// ```
+    fn query_name_string(&self, address: &str, height: Option<&str>) -> Result<String, E> {
+        let tokens =
+            self.contract_call_decoded("queryName", &[], Some(parse_address(address)?), height)?;
+        Ok(decode_name_utf8(tokens)?)
+    }
+
+    /// Query the resource of the permission
+    ///
+    /// return The resources of permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let permission = PermissionClient::create(client);
+    /// let response = permission
+    ///     .query_resource("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryResource`
+    /// println!("{}", response);
+    /// ```
+    fn query_resource(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryResource", &[], address, height)
+    }
+
+    /// Query the resource of the permission, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `queryResource`, one resource
+    /// let contract = "e9e2593c7d1db5ee843c143e9cb52b8d996b2380";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:064x}{:0>64}{:064x}{:0<64}"}}"#,
+    ///     64,
+    ///     128,
+    ///     1,
+    ///     contract,
+    ///     1,
+    ///     "2e64cec1"
+    /// ))
+    /// .unwrap();
+    /// let permission = PermissionClient::create_with_responses(vec![response]);
+    /// let resources = permission
+    ///     .query_resources("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// assert_eq!(resources.len(), 1);
+    /// assert_eq!(format!("{:x}", resources[0].contract_address), contract);
+    /// assert_eq!(resources[0].function_signature, [0x2e, 0x64, 0xce, 0xc1]);
+    /// ```
+    fn query_resources(&self, address: &str, height: Option<&str>) -> Result<Vec<Resource>, E> {
+        let tokens = self.contract_call_decoded(
+            "queryResource",
+            &[],
+            Some(parse_address(address)?),
+            height,
+        )?;
+        match tokens.as_slice() {
+            [Token::Array(contracts), Token::Array(funcs)] => {
+                Ok(decode_resources(contracts, funcs)?)
+            }
+            _ => Err(ToolError::Abi(format!("Invalid resources: {:?}", tokens)).into()),
+        }
+    }
+
+    /// Query the information of the permission, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryInfo`, the name "reader" and one resource
+    /// let contract = "e9e2593c7d1db5ee843c143e9cb52b8d996b2380";
+    /// let output = format!(
+    ///     "0x{:0<64}{:064x}{:064x}{:064x}{:0>64}{:064x}{:0<64}",
+    ///     encode("reader"),
+    ///     96,
+    ///     160,
+    ///     1,
+    ///     contract,
+    ///     1,
+    ///     "2e64cec1"
+    /// );
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#,
+    ///     output
+    /// ))
+    /// .unwrap();
+    /// let permission = PermissionClient::create_with_responses(vec![response]);
+    /// let info = permission
+    ///     .query_permission_info("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// assert_eq!(info.name, "reader");
+    /// let resources = info.resources;
+    /// assert_eq!(resources.len(), 1);
+    /// assert_eq!(format!("{:x}", resources[0].contract_address), contract);
+    /// assert_eq!(resources[0].function_signature, [0x2e, 0x64, 0xce, 0xc1]);
+    /// ```
+    fn query_permission_info(
+        &self,
+        address: &str,
+        height: Option<&str>,
+    ) -> Result<PermissionInfo, E> {
+        let tokens =
+            self.contract_call_decoded("queryInfo", &[], Some(parse_address(address)?), height)?;
+        match tokens.as_slice() {
+            [name @ Token::FixedBytes(_), Token::Array(contracts), Token::Array(funcs)] => {
+                Ok(PermissionInfo {
+                    name: decode_name_utf8(vec![name.clone()])?,
+                    resources: decode_resources(contracts, funcs)?,
+                })
+            }
+            _ => Err(ToolError::Abi(format!("Invalid permission info: {:?}", tokens)).into()),
+        }
+    }
+
+    /// Query the resources in both permissions, in the order of the first one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{PermissionClient, PermissionExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// let contract = "e9e2593c7d1db5ee843c143e9cb52b8d996b2380";
+    /// // The ABI encoded `(address[], bytes4[])`, the functions of the contract
+    /// let resources = |funcs: &[&str]| {
+    ///     let n = funcs.len();
+    ///     let contracts = format!("{:0>64}", contract).repeat(n);
+    ///     let funcs = funcs.iter().map(|func| format!("{:0<64}", func));
+    ///     let funcs = funcs.collect::<String>();
+    ///     format!("0x{:064x}{:064x}{:064x}{}{:064x}{}", 64, 96 + 32 * n, n, contracts, n, funcs)
+    /// };
+    /// // The canned answers of `queryResource` of the second permission, then the first one
+    /// let permission = PermissionClient::create_with_responses(vec![
+    ///     response(resources(&["2e64cec1"])),
+    ///     response(resources(&["2e64cec1", "6057361d"])),
+    /// ]);
+    /// let resources = permission
+    ///     .overlapping_resources(
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001",
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a002",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(resources.len(), 1);
+    /// assert_eq!(format!("{:x}", resources[0].contract_address), contract);
+    /// assert_eq!(resources[0].function_signature, [0x2e, 0x64, 0xce, 0xc1]);
+    /// ```
+    fn overlapping_resources(
+        &self,
+        a_address: &str,
+        b_address: &str,
+        height: Option<&str>,
+    ) -> Result<Vec<Resource>, E> {
+        let b_resources = self
+            .query_resources(b_address, height)?
+            .into_iter()
+            .collect::<HashSet<Resource>>();
+        let mut overlapping = self.query_resources(a_address, height)?;
+        let mut seen = HashSet::new();
+        overlapping.retain(|resource| b_resources.contains(resource) && seen.insert(*resource));
+        Ok(overlapping)
+    }
+}
+
+/// Resource of permission, a function of contract
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Resource {
+    /// Contract address
+    pub contract_address: Address,
+    /// Function signature, the first 4 bytes of the function hash
+    pub function_signature: [u8; 4],
+}
+
+impl Resource {
+    /// Whether the function signature is the one of the method of the ABI,
+    /// false if the ABI has no such method
+    pub fn matches_selector(&self, abi: &Contract, method: &str) -> bool {
+        abi.function(method)
+            .map(|function| function.short_signature() == self.function_signature)
+            .unwrap_or(false)
+    }
+}
+
+/// Name and resources of a permission, decoded from `queryInfo`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PermissionInfo {
+    /// Name of the permission, without trailing zeros
+    pub name: String,
+    /// Resources of the permission
+    pub resources: Vec<Resource>,
+}
+
+/// Pair the `address[]` contracts with the `bytes4[]` functions
+fn decode_resources(contracts: &[Token], funcs: &[Token]) -> Result<Vec<Resource>, ToolError> {
+    contracts
+        .iter()
+        .zip(funcs.iter())
+        .map(|resource| match resource {
+            (Token::Address(contract), Token::FixedBytes(func)) if func.len() == 4 => {
+                let mut function_signature = [0u8; 4];
+                function_signature.copy_from_slice(func);
+                Ok(Resource {
+                    contract_address: *contract,
+                    function_signature,
+                })
+            }
+            _ => Err(ToolError::Abi(format!("Invalid resource: {:?}", resource))),
+        })
+        .collect()
+}
+
+/// What to do with a resource of `IntegrityReport`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recommendation {
+    /// The contract has no code, delete the resource with `deleteResources`
+    DeleteResource,
+    /// The contract is deployed
+    Keep,
+}
+
+/// Resources of a permission checked by
+/// `PermissionManageClient::verify_permission_integrity`
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntegrityReport {
+    /// Permission address
+    pub permission: Address,
+    /// Resources of deployed contracts
+    pub valid: Vec<Resource>,
+    /// Resources of contracts without code, selfdestructed or never deployed
+    pub orphaned: Vec<Resource>,
+}
+
+impl IntegrityReport {
+    /// Whether no resource is orphaned
+    pub fn is_intact(&self) -> bool {
+        self.orphaned.is_empty()
+    }
+
+    /// The recommendation of every resource, the valid ones first
+    pub fn recommendations(&self) -> Vec<(Resource, Recommendation)> {
+        self.valid
+            .iter()
+            .map(|resource| (*resource, Recommendation::Keep))
+            .chain(
+                self.orphaned
+                    .iter()
+                    .map(|resource| (*resource, Recommendation::DeleteResource)),
+            )
+            .collect()
+    }
+}
+
+/// Permission manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020004")]
+#[contract(abi_contract = "crate::contract_abi::PERMISSION_MANAGEMENT_CONTRACT")]
+#[contract(name = "PermissionManagementExt")]
+pub struct PermissionManageClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// PermissionManagement system contract
+pub trait PermissionManagementExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Create a new permission
+    ///
+    /// param name: The name of permission
+    /// param contracts: The contracts of resource
+    /// param funcs: The function signature of the resource
+    /// return New permission's address
+    fn new_permission(
+        &mut self,
+        name: &str,
+        contracts: &str,
+        funcs: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [name, contracts, funcs];
+        self.contract_send_tx("newPermission", &values, options, None)
+    }
+
+    /// Delete the permission
+    ///
+    /// param permission: The address of permission
+    /// return true if successed, otherwise false
+    fn delete_permission(&mut self, permission: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(permission)];
+        self.contract_send_tx("deletePermission", &values, options, None)
+    }
+
+    /// Update the permission name
+    ///
+    /// param permission: The address of permission
+    /// param name: The new name
+    /// return true if successed, otherwise false
+    fn update_permission_name(
+        &mut self,
+        permission: &str,
+        name: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(permission), name];
+        self.contract_send_tx("updatePermissionName", &values, options, None)
+    }
+
+    /// Transfer the ownership of the permission, the account allowed to modify it
+    ///
+    /// The PermissionManagement contract has no `transferOwnership` yet, a permission is
+    /// managed by the accounts with the permission of managing permissions, so it fails
+    /// with `ToolError::Unsupported` until the contract supports it. A zero `new_owner`
+    /// is warned about, nobody could modify the permission any more.
+    fn transfer_permission_ownership(
+        &mut self,
+        permission: &str,
+        new_owner: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        parse_address(permission)?;
+        if parse_address(new_owner)? == Address::zero() {
+            warn!(
+                "Transferring permission {} to the zero address locks it permanently",
+                permission
+            );
+        }
+        if PERMISSION_MANAGEMENT_CONTRACT
+            .function("transferOwnership")
+            .is_err()
+        {
+            return Err(ToolError::Unsupported(
+                "PermissionManagement has no transferOwnership".to_string(),
+            )
+            .into());
+        }
+        let values = [remove_0x(permission), remove_0x(new_owner)];
+        self.contract_send_tx("transferOwnership", &values, options, None)
+    }
+
+    /// Add the resources of permission
+    ///
+    /// param permission: The address of permission
+    /// param contracts: The contracts of resource
+    /// param funcs: The function signature of resource
+    /// return true if successed, otherwise false
+    fn add_resources(
+        &mut self,
+        permission: &str,
+        contracts: &str,
+        funcs: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(permission), contracts, funcs];
+        self.contract_send_tx("addResources", &values, options, None)
+    }
+
+    /// Delete the resources of permission
+    ///
+    /// param permission: The address of permission
+    /// param contracts: The contracts of resource
+    /// param funcs: The function signature of resource
+    /// return true if successed, otherwise false
+    fn delete_resources(
+        &mut self,
+        permission: &str,
+        contracts: &str,
+        funcs: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(permission), contracts, funcs];
+        self.contract_send_tx("deleteResources", &values, options, None)
+    }
+
+    /// Set permission to the account
+    ///
+    /// param account: The account to be setted
+    /// param permission: The permission to be setted
+    /// return true if success, otherwise false
+    fn set_authorization(
+        &mut self,
+        account_address: &str,
+        permission: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account_address), remove_0x(permission)];
+        self.contract_send_tx("setAuthorization", &values, options, None)
+    }
+
+    /// Set multiple permissions to the account
+    ///
+    /// param account: The account to be setted
+    /// param permissions: The multiple permissions to be setted
+    /// return true if success, otherwise false
+    fn set_authorizations(
+        &mut self,
+        account_address: &str,
+        permissions: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account_address), permissions];
+        self.contract_send_tx("setAuthorizations", &values, options, None)
+    }
+
+    /// Cancel the account's permission
+    ///
+    /// param account: The account to be canceled
+    /// param permissions: The permission to be canceled
+    /// return true if success, otherwise false
+    fn cancel_authorization(
+        &mut self,
+        account_address: &str,
+        permission: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account_address), remove_0x(permission)];
+        self.contract_send_tx("cancelAuthorization", &values, options, None)
+    }
+
+    /// Cancel the account's multiple permission
+    ///
+    /// param account: The account to be canceled
+    /// param permissions: The multiple permissions to be canceled
+    /// return true if success, otherwise false
+    fn cancel_authorizations(
+        &mut self,
+        account_address: &str,
+        permissions: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account_address), permissions];
+        self.contract_send_tx("cancelAuthorizations", &values, options, None)
+    }
+
+    /// Clear the account's permissions
+    ///
+    /// param account: The account to be cleared
+    /// return true if success, otherwise false
+    fn clear_authorization(
+        &mut self,
+        account_address: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account_address)];
+        self.contract_send_tx("clearAuthorization", &values, options, None)
+    }
+    /// Set a built-in permission of `system_addresses` to the account
+    fn grant_builtin(
+        &mut self,
+        account_address: &str,
+        permission: H160,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        self.set_authorization(account_address, &permission.completed_lower_hex(), options)
+    }
+
+    /// Set the built-in `sendTx` permission to the account
+    fn grant_send_tx(
+        &mut self,
+        account_address: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        self.grant_builtin(account_address, SEND_TX_PERMISSION, options)
+    }
+
+    /// Set the built-in `createContract` permission to the account
+    fn grant_create_contract(
+        &mut self,
+        account_address: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        self.grant_builtin(account_address, CREATE_CONTRACT_PERMISSION, options)
+    }
+}
+
+impl<T> PermissionManageClient<T>
+where
+    T: ClientExt<JsonRpcResponse, ToolError> + Clone,
+{
+    /// Create a new permission with the resources of the source permission
+    pub fn clone_permission(
+        &mut self,
+        source: &str,
+        new_name: &str,
+        options: TransactionOptions,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        let tokens = PermissionClient::create(self.client.clone()).contract_call_decoded(
+            "queryResource",
+            &[],
+            Some(parse_address(source)?),
+            None,
+        )?;
+        let (contracts, funcs) = match tokens.as_slice() {
+            [contracts @ Token::Array(_), funcs @ Token::Array(_)] => {
+                (contracts.clone(), funcs.clone())
+            }
+            _ => return Err(ToolError::Abi(format!("Invalid resources: {:?}", tokens))),
+        };
+        let name = parse_tokens(&[(ParamType::FixedBytes(32), new_name)], false)?.remove(0);
+        let code = encode_tokens(&self.contract, "newPermission", &[name, contracts, funcs])?;
+        let address = format!("{:?}", self.address);
+        self.client.send_raw_transaction(
+            options
+                .set_code(code.as_str())
+                .set_address(address.as_str()),
+        )
+    }
+
+    /// Check that the contract of every resource of the permission has code at the height
+    ///
+    /// Resources of reserved addresses `0xffffffffffffffffffffffffffffffffffxxxxxx` are
+    /// valid, some of them are native contracts without code.
+    pub fn verify_permission_integrity(
+        &self,
+        permission: &str,
+        height: Option<&str>,
+    ) -> Result<IntegrityReport, ToolError> {
+        let resources =
+            PermissionClient::create(self.client.clone()).query_resources(permission, height)?;
+
+        let mut deployed = HashMap::new();
+        let mut report = IntegrityReport {
+            permission: parse_address(permission)?,
+            valid: Vec::new(),
+            orphaned: Vec::new(),
+        };
+        for resource in resources {
+            let contract = resource.contract_address;
+            let has_code = if is_reserved(&contract) {
+                true
+            } else if let Some(has_code) = deployed.get(&contract) {
+                *has_code
+            } else {
+                let code = self
+                    .client
+                    .get_code(&format!("{:?}", contract), height.unwrap_or("latest"))?
+                    .into_result()?;
+                let has_code = match code {
+                    ResponseValue::Singe(ParamsValue::String(ref code)) => {
+                        !remove_0x(code).is_empty()
+                    }
+                    result => {
+                        return Err(ToolError::Customize(format!("Unexpected code: {}", result)))
+                    }
+                };
+                deployed.insert(contract, has_code);
+                has_code
+            };
+            if has_code {
+                report.valid.push(resource);
+            } else {
+                report.orphaned.push(resource);
+            }
+        }
+        if !report.is_intact() {
+            warn!(
+                "Permission {:?} has {} orphaned resources",
+                report.permission,
+                report.orphaned.len()
+            );
+        }
+        Ok(report)
+    }
+}
+
+impl PermissionManageClient<Client> {
+    /// Find the permissions without any authorized account at the height, sorted by address
+    ///
+    /// Permissions can not be listed on chain, the candidates are the ones ever authorized
+    /// by `AuthSetted` logs of Authorization, except the built-in and the deleted ones.
+    /// A permission never authorized since creation is not found.
+    pub fn audit_unused_permissions(
+        &self,
+        height: Option<&str>,
+    ) -> Result<Vec<Address>, ToolError> {
+        let to_block = match height {
+            Some(height) if height != "latest" => {
+                Some(u64::from_str_radix(remove_0x(height), 16).map_err(ToolError::Parse)?)
+            }
+            _ => None,
+        };
+        let logs = |address: H160, event: &str, contract: &Contract| {
+            let topic = contract
+                .event(event)
+                .map_err(|err| ToolError::Abi(err.to_string()))?
+                .signature();
+            self.client.get_filtered_logs(
+                LogFilter::new()
+                    .set_from_block(Some(0))
+                    .set_to_block(to_block)
+                    .set_address(Some(vec![address]))
+                    .set_topics(Some(vec![Some(H256::from(topic.0))])),
+            )
+        };
+        let address_of = |word: &[u8]| Address::from_slice(&word[12..32]);
+
+        let deleted = logs(
+            PERMISSION_MANAGEMENT,
+            "PermissionDeleted",
+            &PERMISSION_MANAGEMENT_CONTRACT,
+        )?
+        .iter()
+        .filter(|log| log.data.len() >= 32)
+        .map(|log| address_of(&log.data))
+        .collect::<HashSet<Address>>();
+        let candidates = logs(AUTHORIZATION, "AuthSetted", &AUTHORIZATION_CONTRACT)?
+            .iter()
+            .filter_map(|log| log.topics.get(2).map(|topic| address_of(&topic.0)))
+            .filter(|permission| !is_builtin(permission) && !deleted.contains(permission))
+            .collect::<BTreeSet<Address>>();
+
+        let authorization_client = AuthorizationClient::create(self.client.clone());
+        let mut unused = Vec::new();
+        for permission in candidates {
+            let permission_hex = format!("{:x}", permission);
+            let accounts = decode_addresses(authorization_client.contract_call_decoded(
+                "queryAccounts",
+                &[permission_hex.as_str()],
+                None,
+                height,
+            )?)?;
+            if accounts.is_empty() {
+                unused.push(permission);
+            }
+        }
+        info!(
+            "{} unused permissions: {:?}",
+            unused.len(),
+            unused
+                .iter()
+                .map(|permission| format!("{:?}", permission))
+                .collect::<Vec<String>>()
+        );
+        Ok(unused)
+    }
+
+    /// Delete the permissions found by `audit_unused_permissions` at the latest height,
+    /// one transaction each, the responses are in the order of the permissions
+    ///
+    /// Without `force` nothing is deleted, the permissions to delete are listed in the error.
+    pub fn delete_unused_permissions(
+        &mut self,
+        force: bool,
+        options: TransactionOptions,
+    ) -> Result<Vec<JsonRpcResponse>, ToolError> {
+        let unused = self
+            .audit_unused_permissions(None)?
+            .into_iter()
+            .map(|permission| format!("{:?}", permission))
+            .collect::<Vec<String>>();
+        if unused.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !force {
+            return Err(ToolError::Customize(format!(
+                "{} unused permissions would be deleted, confirm with force:\n{}",
+                unused.len(),
+                unused.join("\n")
+            )));
+        }
+        warn!("Deleting unused permissions: {}", unused.join(", "));
+        unused
+            .iter()
+            .map(|permission| self.delete_permission(permission, options))
+            .collect()
+    }
+}
+
+/// Built-in permissions are system contracts, `0xffffffffffffffffffffffffffffffffff021xxx`
+fn is_builtin(permission: &Address) -> bool {
+    is_reserved(permission) && permission.0[17] == 0x02
+}
+
+/// Addresses reserved for system and native contracts,
+/// `0xffffffffffffffffffffffffffffffffffxxxxxx`
+fn is_reserved(address: &Address) -> bool {
+    address.0[..17].iter().all(|byte| *byte == 0xff)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        AuthorizationClient, AuthorizationExt, PermissionClient, PermissionExt, PermissionInfo,
+        PermissionManageClient, PermissionManagementExt, Recommendation, Resource,
+    };
+    use crate::client::TransactionOptions;
+    use crate::contract_abi::PERMISSION_MANAGEMENT_CONTRACT;
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+    use ethabi::Address;
+
+    fn response(result: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#,
+            result
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify_permission_integrity() {
+        let contract = |last: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = last;
+            Address::from(bytes)
+        };
+        let reserved = Address::from([0xff; 20]);
+        let resources = [
+            (contract(1), 1u8),
+            (contract(2), 2),
+            (contract(1), 3),
+            (reserved, 4),
+        ];
+        // queryResource returns (address[], bytes4[])
+        let mut output = format!("{:064x}{:064x}", 64, 64 + 32 * (resources.len() + 1));
+        output.push_str(&format!("{:064x}", resources.len()));
+        for (address, _) in resources.iter() {
+            output.push_str(&format!("{:0>64}", format!("{:x}", address)));
+        }
+        output.push_str(&format!("{:064x}", resources.len()));
+        for (_, func) in resources.iter() {
+            output.push_str(&format!("{:02x}{:062x}", func, 0));
+        }
+
+        let client = PermissionManageClient::create_with_responses(vec![
+            response(&format!("0x{}", output)),
+            response("0x6080"),
+            response("0x"),
+        ]);
+        let report = client
+            .verify_permission_integrity("0xca645d2b0d2e4c451a2dd546dbd7ab8c29c3dcee", None)
+            .unwrap();
+        let resource = |(contract_address, func): (Address, u8)| Resource {
+            contract_address,
+            function_signature: [func, 0, 0, 0],
+        };
+        assert_eq!(
+            report.valid,
+            vec![
+                resource(resources[0]),
+                resource(resources[2]),
+                resource(resources[3])
+            ]
+        );
+        assert_eq!(report.orphaned, vec![resource(resources[1])]);
+        assert!(!report.is_intact());
+        assert_eq!(
+            report.recommendations()[3],
+            (resource(resources[1]), Recommendation::DeleteResource)
+        );
+    }
+
+    #[test]
+    fn test_check_no_account_has_permission() {
+        let account = |last: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = last;
+            Address::from(bytes)
+        };
+        // queryAccounts returns address[]
+        let mut output = format!("{:064x}{:064x}", 32, 3);
+        for last in 1..=3 {
+            output.push_str(&format!("{:0>64}", format!("{:x}", account(last))));
+        }
+
+        let client =
+            AuthorizationClient::create_with_responses(vec![response(&format!("0x{}", output))]);
+        let allowed = format!("{:x}", account(2));
+        let violations = client
+            .check_no_account_has_permission(
+                "0xffffffffffffffffffffffffffffffffff021000",
+                &[allowed.as_str()],
+                None,
+            )
+            .unwrap();
+        assert_eq!(violations, vec![account(1), account(3)]);
+    }
+
+    #[test]
+    fn test_query_permission_info() {
+        let selector = PERMISSION_MANAGEMENT_CONTRACT
+            .function("newPermission")
+            .unwrap()
+            .short_signature();
+        let contract = "ffffffffffffffffffffffffffffffffff020004";
+        let info = format!(
+            "0x{:0<64}{:064x}{:064x}{:064x}{:0>64}{:064x}{:0<64}",
+            "73656e645478",
+            96,
+            160,
+            1,
+            contract,
+            1,
+            hex::encode(selector)
+        );
+        let client = PermissionClient::create_with_responses(vec![response(&info)]);
+        let info = client
+            .query_permission_info("0xffffffffffffffffffffffffffffffffff021000", None)
+            .unwrap();
+        let resource = Resource {
+            contract_address: contract.parse().unwrap(),
+            function_signature: selector,
+        };
+        assert_eq!(
+            info,
+            PermissionInfo {
+                name: "sendTx".to_string(),
+                resources: vec![resource],
+            }
+        );
+        assert!(resource.matches_selector(&PERMISSION_MANAGEMENT_CONTRACT, "newPermission"));
+        assert!(!resource.matches_selector(&PERMISSION_MANAGEMENT_CONTRACT, "deletePermission"));
+        assert!(!resource.matches_selector(&PERMISSION_MANAGEMENT_CONTRACT, "missing"));
+    }
+
+    #[test]
+    fn test_has_permission() {
+        let client = AuthorizationClient::create_with_responses(vec![
+            response(&format!("0x{:064x}", 1)),
+            response(&format!("0x{:064x}", 0)),
+            response("0x"),
+        ]);
+        let account = "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+        let permission = "0xffffffffffffffffffffffffffffffffff021000";
+        assert!(client.has_permission(account, permission, None).unwrap());
+        assert!(!client
+            .has_resource(account, permission, "0x00000000", Some("0x10"))
+            .unwrap());
+        assert!(client.has_permission(account, permission, None).is_err());
+    }
+
+    #[test]
+    fn test_transfer_permission_ownership() {
+        let mut client = PermissionManageClient::create_with_responses(Vec::new());
+        let permission = "0xffffffffffffffffffffffffffffffffff021000";
+        match client.transfer_permission_ownership(permission, "0x1234", TransactionOptions::new())
+        {
+            Err(ToolError::InvalidHex(_)) => {}
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(response) => panic!("Unexpected response: {}", response),
+        }
+        match client.transfer_permission_ownership(
+            permission,
+            "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+            TransactionOptions::new(),
+        ) {
+            Err(ToolError::Unsupported(_)) => {}
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(response) => panic!("Unexpected response: {}", response),
+        }
+    }
+}
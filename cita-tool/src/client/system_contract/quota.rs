@@ -0,0 +1,678 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::abi::{contract_decode_output, contract_encode_input};
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{decode_addresses, decode_u64, decode_uints, ContractCall};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::contract_abi::QUOTA_MANAGER_CONTRACT;
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcResponse, Log, LogFilter};
+use crate::system_addresses::QUOTA_MANAGER;
+use crate::LowerHex;
+use ethabi::{Address, Contract};
+use failure::Fail;
+use tool_derive::ContractExt;
+use types::{H256, U256};
+
+/// Node manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020003")]
+#[contract(abi_contract = "crate::contract_abi::QUOTA_MANAGER_CONTRACT")]
+#[contract(name = "QuotaManagementExt")]
+pub struct QuotaManageClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Suggested block quota limit of `QuotaManageClient::bql_suggestion`, with its reasoning
+#[derive(Clone, Debug, Serialize)]
+pub struct BqlSuggestion {
+    /// Blocks sampled, the latest ones
+    pub sample_blocks: u64,
+    /// Sampled blocks with any transaction
+    pub blocks_with_transactions: u64,
+    /// Average quota used of the blocks with transactions
+    pub average_quota_used: u64,
+    /// Highest quota used of a block
+    pub peak_quota_used: u64,
+    /// Default account quota limit
+    pub default_aql: u64,
+    /// Current block quota limit
+    pub current_bql: u64,
+    /// `max(peak_quota_used * 1.2, default_aql * 10)`
+    pub suggested_bql: u64,
+}
+
+/// Block quota limit changed by `setBQL`
+#[derive(Clone, Debug, PartialEq)]
+pub struct BqlChange {
+    /// Block of the change
+    pub block_number: u64,
+    /// Block quota limit after the change
+    pub new_bql: u64,
+    /// Sender of the `setBQL` transaction
+    pub changed_by: Address,
+}
+
+/// QuotaManager system contract
+pub trait QuotaManagementExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get block quota upper limit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let quota = QuotaManageClient::create(client);
+    /// let response = quota.get_bql(None).unwrap();
+    /// // The ABI encoded output of `getBQL`
+    /// println!("{}", response);
+    /// ```
+    fn get_bql(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getBQL", &[], None, height)
+    }
+
+    /// Get block quota upper limit, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `getBQL`
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1_073_741_824
+    /// ))
+    /// .unwrap();
+    /// let quota = QuotaManageClient::create_with_responses(vec![response]);
+    /// assert_eq!(quota.get_bql_u64(None).unwrap(), 1_073_741_824);
+    /// ```
+    fn get_bql_u64(&self, height: Option<&str>) -> Result<u64, E> {
+        Ok(decode_u64(self.contract_call_decoded(
+            "getBQL",
+            &[],
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Get account quota upper limit of the specific account
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let quota = QuotaManageClient::create(client);
+    /// let response = quota.get_aql("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None).unwrap();
+    /// // The ABI encoded output of `getAQL`
+    /// println!("{}", response);
+    /// ```
+    fn get_aql(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(address)];
+        self.contract_call("getAQL", &values, None, height)
+    }
+
+    /// Get account quota upper limit of the specific account, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `getAQL`, the ABI encoded limit
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     268_435_456
+    /// ))
+    /// .unwrap();
+    /// let quota = QuotaManageClient::create_with_responses(vec![response]);
+    /// let aql = quota.get_aql_u64("0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523", None).unwrap();
+    /// assert_eq!(aql, 268_435_456);
+    /// ```
+    fn get_aql_u64(&self, address: &str, height: Option<&str>) -> Result<u64, E> {
+        let values = [remove_0x(address)];
+        Ok(decode_u64(
+            self.contract_call_decoded("getAQL", &values, None, height)?,
+        )?)
+    }
+
+    /// Get default account quota limit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let quota = QuotaManageClient::create(client);
+    /// let response = quota.get_default_aql(None).unwrap();
+    /// // The ABI encoded output of `getDefaultAQL`
+    /// println!("{}", response);
+    /// ```
+    fn get_default_aql(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getDefaultAQL", &[], None, height)
+    }
+
+    /// Get default account quota limit, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `getDefaultAQL`, the ABI encoded limit
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     268_435_456
+    /// ))
+    /// .unwrap();
+    /// let quota = QuotaManageClient::create_with_responses(vec![response]);
+    /// let default_aql = quota.get_default_aql_u64(None).unwrap();
+    /// assert_eq!(default_aql, 268_435_456);
+    /// ```
+    fn get_default_aql_u64(&self, height: Option<&str>) -> Result<u64, E> {
+        Ok(decode_u64(self.contract_call_decoded(
+            "getDefaultAQL",
+            &[],
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Get accounts
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let quota = QuotaManageClient::create(client);
+    /// let response = quota.get_accounts(None).unwrap();
+    /// // The ABI encoded output of `getAccounts`
+    /// println!("{}", response);
+    /// ```
+    fn get_accounts(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getAccounts", &[], None, height)
+    }
+
+    /// Get quotas
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let quota = QuotaManageClient::create(client);
+    /// let response = quota.get_quotas(None).unwrap();
+    /// // The ABI encoded output of `getQuotas`
+    /// println!("{}", response);
+    /// ```
+    fn get_quotas(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getQuotas", &[], None, height)
+    }
+
+    /// Set block quota limit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut quota = QuotaManageClient::create(client);
+    /// let response = quota.set_bql(U256::from(1_073_741_824), TransactionOptions::new()).unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_bql(&mut self, quota_limit: U256, options: TransactionOptions) -> Result<R, E> {
+        let quota_limit = quota_limit.completed_lower_hex();
+        let values = [quota_limit.as_str()];
+        self.contract_send_tx("setBQL", &values, options, None)
+    }
+
+    /// Set default account quota limit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut quota = QuotaManageClient::create(client);
+    /// let response = quota
+    ///     .set_default_aql(U256::from(268_435_456), TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_default_aql(&mut self, quota_limit: U256, options: TransactionOptions) -> Result<R, E> {
+        let quota_limit = quota_limit.completed_lower_hex();
+        let values = [quota_limit.as_str()];
+        self.contract_send_tx("setDefaultAQL", &values, options, None)
+    }
+
+    /// Set account quota upper limit of the specific account
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut quota = QuotaManageClient::create(client);
+    /// let response = quota
+    ///     .set_aql(
+    ///         "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///         U256::from(536_870_912),
+    ///         TransactionOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_aql(
+        &mut self,
+        address: &str,
+        quota_limit: U256,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let quota_limit = quota_limit.completed_lower_hex();
+        let values = [remove_0x(address), quota_limit.as_str()];
+        self.contract_send_tx("setAQL", &values, options, None)
+    }
+
+    /// Get the accounts with specific account quota limit, and their limits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::{JsonRpcResponse, U256};
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// // The ABI encoded `uint256[]`
+    /// let uints = |uints: &[u64]| {
+    ///     let words = uints.iter().map(|uint| format!("{:064x}", uint));
+    ///     format!("0x{:064x}{:064x}{}", 32, uints.len(), words.collect::<String>())
+    /// };
+    /// let account = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+    /// // The canned answers of `getAccounts` and `getQuotas`
+    /// let quota = QuotaManageClient::create_with_responses(vec![
+    ///     response(addresses(&[account])),
+    ///     response(uints(&[536_870_912])),
+    /// ]);
+    /// let accounts = quota.get_accounts_and_quotas(None).unwrap();
+    /// assert_eq!(accounts.len(), 1);
+    /// assert_eq!(format!("{:x}", accounts[0].0), account);
+    /// assert_eq!(accounts[0].1, U256::from(536_870_912));
+    /// ```
+    fn get_accounts_and_quotas(&self, height: Option<&str>) -> Result<Vec<(Address, U256)>, E> {
+        let accounts =
+            decode_addresses(self.contract_call_decoded("getAccounts", &[], None, height)?)?;
+        let quotas = decode_uints(self.contract_call_decoded("getQuotas", &[], None, height)?)?;
+        if accounts.len() != quotas.len() {
+            return Err(ToolError::Customize(format!(
+                "{} accounts but {} quotas",
+                accounts.len(),
+                quotas.len()
+            ))
+            .into());
+        }
+        Ok(accounts.into_iter().zip(quotas).collect())
+    }
+
+    /// Get the accounts whose account quota limit is above `max_aql`
+    ///
+    /// Only accounts with specific limit are checked,
+    /// the others use the default limit of `get_default_aql`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{QuotaManageClient, QuotaManagementExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// // The ABI encoded `uint256[]`
+    /// let uints = |uints: &[u64]| {
+    ///     let words = uints.iter().map(|uint| format!("{:064x}", uint));
+    ///     format!("0x{:064x}{:064x}{}", 32, uints.len(), words.collect::<String>())
+    /// };
+    /// let (large, small) = (
+    ///     "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523",
+    ///     "e9e2593c7d1db5ee843c143e9cb52b8d996b2380",
+    /// );
+    /// // The canned answers of `getAccounts` and `getQuotas`
+    /// let quota = QuotaManageClient::create_with_responses(vec![
+    ///     response(addresses(&[large, small])),
+    ///     response(uints(&[536_870_912, 1_000_000])),
+    /// ]);
+    /// let violations = quota.enforce_aql_policy(268_435_456, None).unwrap();
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(format!("{:x}", violations[0]), large);
+    /// ```
+    fn enforce_aql_policy(&self, max_aql: u64, height: Option<&str>) -> Result<Vec<Address>, E> {
+        let max_aql = U256::from(max_aql);
+        Ok(self
+            .get_accounts_and_quotas(height)?
+            .into_iter()
+            .filter(|(_, quota)| *quota > max_aql)
+            .map(|(account, _)| account)
+            .collect())
+    }
+}
+
+impl QuotaManageClient<Client> {
+    /// Suggest a block quota limit from the quota used of the latest `sample_blocks` blocks,
+    /// the larger one of 1.2 times the peak and 10 times the default account quota limit
+    ///
+    /// Fail with `ToolError::InsufficientHistory` if fewer than half
+    /// of the sampled blocks have any transaction.
+    pub fn bql_suggestion(&self, sample_blocks: u64) -> Result<BqlSuggestion, ToolError> {
+        if sample_blocks == 0 {
+            return Err(ToolError::Customize(
+                "Sample at least one block".to_string(),
+            ));
+        }
+        let current_height = self.client.get_current_height()?;
+        let start = current_height.saturating_sub(sample_blocks - 1);
+        let blocks = self.client.get_blocks_quota_used(start, current_height)?;
+
+        let busy = blocks
+            .iter()
+            .filter(|(_, transactions)| *transactions > 0)
+            .map(|(quota_used, _)| *quota_used)
+            .collect::<Vec<u64>>();
+        let required = sample_blocks / 2;
+        if (busy.len() as u64) < required {
+            return Err(ToolError::InsufficientHistory {
+                blocks_with_transactions: busy.len() as u64,
+                required,
+            });
+        }
+        let peak_quota_used = busy.iter().cloned().max().unwrap_or(0);
+        let average_quota_used = if busy.is_empty() {
+            0
+        } else {
+            (busy.iter().map(|quota| u128::from(*quota)).sum::<u128>() / busy.len() as u128) as u64
+        };
+        let default_aql = self.get_default_aql_u64(None)?;
+        let current_bql = self.get_bql_u64(None)?;
+        let suggested_bql =
+            (peak_quota_used.saturating_mul(6) / 5).max(default_aql.saturating_mul(10));
+
+        Ok(BqlSuggestion {
+            sample_blocks: blocks.len() as u64,
+            blocks_with_transactions: busy.len() as u64,
+            average_quota_used,
+            peak_quota_used,
+            default_aql,
+            current_bql,
+            suggested_bql,
+        })
+    }
+
+    /// The suggested block quota limit of `bql_suggestion`
+    pub fn suggest_bql(&self, sample_blocks: u64) -> Result<u64, ToolError> {
+        Ok(self.bql_suggestion(sample_blocks)?.suggested_bql)
+    }
+
+    /// The block quota limit changes by `BqlSetted` logs from `from_block` to `to_block`,
+    /// in order
+    pub fn get_bql_history(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<BqlChange>, ToolError> {
+        if from_block > to_block {
+            return Err(ToolError::Customize(format!(
+                "Invalid block range {} to {}",
+                from_block, to_block
+            )));
+        }
+        let topic = QUOTA_MANAGER_CONTRACT
+            .event("BqlSetted")
+            .map_err(|err| ToolError::Abi(err.to_string()))?
+            .signature();
+        let mut logs = self.client.get_filtered_logs(
+            LogFilter::new()
+                .set_from_block(Some(from_block))
+                .set_to_block(Some(to_block))
+                .set_address(Some(vec![QUOTA_MANAGER]))
+                .set_topics(Some(vec![Some(H256::from(topic.0))])),
+        )?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+        logs.iter().map(decode_bql_setted).collect()
+    }
+
+    /// The account quota limit in effect for the account, its own limit, or the default
+    /// one if it has none, both are queried at the same time
+    pub fn get_effective_aql(&self, account: &str, height: Option<&str>) -> Result<u64, ToolError> {
+        let calls = [
+            ("getAQL", vec![remove_0x(account)]),
+            ("getDefaultAQL", Vec::new()),
+        ]
+        .iter()
+        .map(|(name, values)| {
+            self.prepare_call_args(name, values, None)
+                .map(|(code, to_address)| {
+                    (
+                        format!("0x{}", remove_0x(&to_address)),
+                        format!("0x{}", remove_0x(&code)),
+                    )
+                })
+        })
+        .collect::<Result<Vec<(String, String)>, ToolError>>()?;
+        let responses = self.client.call_all(calls, height.unwrap_or("latest"))?;
+        let mut limits = ["getAQL", "getDefaultAQL"]
+            .iter()
+            .zip(responses)
+            .map(|(name, response)| {
+                decode_u64(contract_decode_output(
+                    &self.contract,
+                    name,
+                    &response.into_hex_result()?,
+                )?)
+            })
+            .collect::<Result<Vec<u64>, ToolError>>()?;
+        let default_aql = limits.pop().unwrap_or(0);
+        match limits.pop() {
+            Some(0) | None => Ok(default_aql),
+            Some(aql) => Ok(aql),
+        }
+    }
+}
+
+/// The change of a `BqlSetted` log, both the value and the sender are indexed
+fn decode_bql_setted(log: &Log) -> Result<BqlChange, ToolError> {
+    match (log.topics.get(1), log.topics.get(2)) {
+        (Some(value), Some(sender)) if value.0[..24].iter().all(|byte| *byte == 0) => {
+            let mut new_bql = [0u8; 8];
+            new_bql.copy_from_slice(&value.0[24..]);
+            Ok(BqlChange {
+                block_number: log.block_number,
+                new_bql: u64::from_be_bytes(new_bql),
+                changed_by: Address::from_slice(&sender.0[12..]),
+            })
+        }
+        _ => Err(ToolError::Abi(format!(
+            "Invalid BqlSetted log in transaction {:?}",
+            log.transaction_hash
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BqlChange, QuotaManageClient, QuotaManagementExt};
+    use crate::client::basic::Client;
+    use crate::client::TransactionOptions;
+    use crate::crypto::privkey_from_str;
+    use crate::rpctypes::JsonRpcResponse;
+    use ethabi::Address;
+    use std::convert::TryFrom;
+    use std::time::Duration;
+    use types::U256;
+
+    fn uint_response(value: u64) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            value
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_bql_cached() {
+        let response = |result: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+                result
+            ))
+            .unwrap()
+        };
+        let mut client = Client::with_responses(vec![
+            uint_response(1_073_741_824),
+            // The simulating `call` and `sendRawTransaction` of `setBQL`
+            response(r#""0x""#),
+            response(&format!(r#"{{"hash":"0x{:064x}","status":"OK"}}"#, 1)),
+            uint_response(2_147_483_648),
+        ])
+        .with_cache(Duration::from_secs(60));
+        let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+        client.set_private_key(&privkey_from_str(private_key).unwrap());
+        let mut quota = QuotaManageClient::create(client);
+        let get_bql = |quota: &QuotaManageClient<Client>| {
+            U256::try_from(quota.get_bql(None).unwrap()).unwrap()
+        };
+
+        // The second query at `latest` is served from the cache
+        assert_eq!(get_bql(&quota), U256::from(1_073_741_824));
+        assert_eq!(get_bql(&quota), U256::from(1_073_741_824));
+
+        // Sending to the contract drops its cached queries
+        let options = TransactionOptions::new()
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_valid_until_block(Some(100));
+        assert!(quota
+            .set_bql(U256::from(2_147_483_648u64), options)
+            .unwrap()
+            .is_ok());
+        assert_eq!(get_bql(&quota), U256::from(2_147_483_648u64));
+    }
+
+    #[test]
+    fn test_get_effective_aql() {
+        let account = "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8";
+        let client = QuotaManageClient::create_with_responses(vec![
+            uint_response(5_000),
+            uint_response(1_000),
+        ]);
+        assert_eq!(client.get_effective_aql(account, None).unwrap(), 5_000);
+
+        let client =
+            QuotaManageClient::create_with_responses(vec![uint_response(0), uint_response(1_000)]);
+        assert_eq!(client.get_effective_aql(account, None).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_get_bql_history() {
+        let topic = |value: &str| format!(r#""0x{:0>64}""#, value);
+        let log = |block: u64, index: u64, bql: u64, sender: &str| {
+            format!(
+                r#"{{
+                    "address": "0xffffffffffffffffffffffffffffffffff020003",
+                    "topics": [{}, {}, {}],
+                    "data": "0x",
+                    "transactionHash": "0x{:064x}",
+                    "blockNumber": "{:#x}",
+                    "logIndex": "{:#x}"
+                }}"#,
+                topic("1"),
+                topic(&format!("{:x}", bql)),
+                topic(sender),
+                block,
+                block,
+                index
+            )
+        };
+        let admin = "4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+        let response = serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":[{},{}]}}"#,
+            log(20, 0, 2_147_483_648, admin),
+            log(12, 1, 1_073_741_824, admin)
+        ))
+        .unwrap();
+        let client = QuotaManageClient::create_with_responses(vec![response]);
+        let admin = admin.parse::<Address>().unwrap();
+        assert_eq!(
+            client.get_bql_history(10, 30).unwrap(),
+            vec![
+                BqlChange {
+                    block_number: 12,
+                    new_bql: 1_073_741_824,
+                    changed_by: admin,
+                },
+                BqlChange {
+                    block_number: 20,
+                    new_bql: 2_147_483_648,
+                    changed_by: admin,
+                },
+            ]
+        );
+        assert!(client.get_bql_history(30, 10).is_err());
+    }
+}
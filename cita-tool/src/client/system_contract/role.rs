@@ -0,0 +1,793 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+#[cfg(feature = "permission")]
+use crate::client::system_contract::PermissionExt;
+use crate::client::system_contract::{
+    call_all_decoded, decode_addresses, decode_name_utf8, parse_address, BatchTxClient, BatchTxExt,
+    ContractCall,
+};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcResponse;
+use crate::system_addresses::ROLE_AUTH;
+use ethabi::{Address, Contract, Token};
+use failure::Fail;
+use tool_derive::ContractExt;
+
+/// Role Client
+#[derive(ContractExt)]
+#[contract(addr = "0x")]
+#[contract(abi_contract = "crate::contract_abi::ROLE_CONTRACT")]
+#[contract(name = "RoleExt")]
+pub struct RoleClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Role system contract
+pub trait RoleExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Query the information of the role
+    ///
+    /// return The information of role: name and permissions
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let role = RoleClient::create(client);
+    /// let response = role.query_role("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None).unwrap();
+    /// // The ABI encoded output of `queryRole`
+    /// println!("{}", response);
+    /// ```
+    fn query_role(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryRole", &[], address, height)
+    }
+
+    /// Query the information of the role, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryRole`, the name "sender" and one permission
+    /// let permission = "ffffffffffffffffffffffffffffffffff021000";
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}{:064x}{:064x}{:0>64}"}}"#,
+    ///     encode("sender"),
+    ///     64,
+    ///     1,
+    ///     permission
+    /// ))
+    /// .unwrap();
+    /// let role = RoleClient::create_with_responses(vec![response]);
+    /// let info = role
+    ///     .query_role_info("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// assert_eq!(info.name, "sender");
+    /// assert_eq!(info.permissions.len(), 1);
+    /// assert_eq!(format!("{:x}", info.permissions[0]), permission);
+    /// ```
+    fn query_role_info(&self, address: &str, height: Option<&str>) -> Result<RoleInfo, E> {
+        let mut tokens =
+            self.contract_call_decoded("queryRole", &[], Some(parse_address(address)?), height)?;
+        if tokens.len() != 2 {
+            return Err(ToolError::Abi(format!("Not a (bytes32, address[]): {:?}", tokens)).into());
+        }
+        let permissions = tokens.pop().unwrap();
+        Ok(RoleInfo {
+            name: decode_name_utf8(tokens)?,
+            permissions: decode_addresses(vec![permissions])?,
+        })
+    }
+
+    /// Query the name of the role
+    ///
+    /// return The name of role
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let role = RoleClient::create(client);
+    /// let response = role.query_name("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None).unwrap();
+    /// // The ABI encoded output of `queryName`
+    /// println!("{}", response);
+    /// ```
+    fn query_name(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryName", &[], address, height)
+    }
+
+    /// Query the name of the role, the `bytes32` without trailing zeros,
+    /// fail if it's not UTF-8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `queryName`, the name "sender"
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+    ///     encode("sender")
+    /// ))
+    /// .unwrap();
+    /// let role = RoleClient::create_with_responses(vec![response]);
+    /// let name = role
+    ///     .query_name_string("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// assert_eq!(name, "sender");
+    /// ```
+    fn query_name_string(&self, address: &str, height: Option<&str>) -> Result<String, E> {
+        let tokens =
+            self.contract_call_decoded("queryName", &[], Some(parse_address(address)?), height)?;
+        Ok(decode_name_utf8(tokens)?)
+    }
+
+    /// Query the permissions of the role
+    ///
+    /// return The permissions of role
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let role = RoleClient::create(client);
+    /// let response = role
+    ///     .query_permissions("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `queryPermissions`
+    /// println!("{}", response);
+    /// ```
+    fn query_permissions(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("queryPermissions", &[], address, height)
+    }
+
+    /// Query the length of the permissions
+    ///
+    /// return The number of permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let role = RoleClient::create(client);
+    /// let response = role
+    ///     .length_of_permissions("0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001", None)
+    ///     .unwrap();
+    /// // The ABI encoded output of `lengthOfPermissions`
+    /// println!("{}", response);
+    /// ```
+    fn length_of_permissions(&self, address: &str, height: Option<&str>) -> Result<R, E> {
+        self.contract_call_to_address("lengthOfPermissions", &[], address, height)
+    }
+
+    /// Check the duplicate permission
+    ///
+    /// return true if in permissions, otherwise false
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let role = RoleClient::create(client);
+    /// let response = role
+    ///     .in_permissions(
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001",
+    ///         "0xffffffffffffffffffffffffffffffffff021000",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// // The ABI encoded output of `inPermissions`
+    /// println!("{}", response);
+    /// ```
+    fn in_permissions(
+        &self,
+        address: &str,
+        permission: &str,
+        height: Option<&str>,
+    ) -> Result<R, E> {
+        let values = [remove_0x(permission)];
+        self.contract_call_to_address("inPermissions", &values, address, height)
+    }
+
+    /// Check all permissions of the sub role are in the super role,
+    /// a role without permission is the subset of any role
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// let (send_tx, create_contract) = (
+    ///     "ffffffffffffffffffffffffffffffffff021000",
+    ///     "ffffffffffffffffffffffffffffffffff021001",
+    /// );
+    /// // The canned answers of `queryPermissions` of the sub role, then the super role
+    /// let role = RoleClient::create_with_responses(vec![
+    ///     response(addresses(&[send_tx])),
+    ///     response(addresses(&[send_tx, create_contract])),
+    /// ]);
+    /// let is_subset = role
+    ///     .is_subset(
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001",
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a002",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert!(is_subset);
+    /// ```
+    fn is_subset(&self, sub_role: &str, super_role: &str, height: Option<&str>) -> Result<bool, E> {
+        let permissions = |role: &str| -> Result<HashSet<Address>, E> {
+            let tokens = self.contract_call_decoded(
+                "queryPermissions",
+                &[],
+                Some(parse_address(role)?),
+                height,
+            )?;
+            Ok(decode_addresses(tokens)?.into_iter().collect())
+        };
+        Ok(permissions(sub_role)?.is_subset(&permissions(super_role)?))
+    }
+
+    /// Check the super role has all permissions of the sub role
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{RoleClient, RoleExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// let response = |result: String| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, result))
+    ///         .unwrap()
+    /// };
+    /// // The ABI encoded `address[]`
+    /// let addresses = |addresses: &[&str]| {
+    ///     let words = addresses.iter().map(|address| format!("{:0>64}", address));
+    ///     format!("0x{:064x}{:064x}{}", 32, addresses.len(), words.collect::<String>())
+    /// };
+    /// let (send_tx, create_contract) = (
+    ///     "ffffffffffffffffffffffffffffffffff021000",
+    ///     "ffffffffffffffffffffffffffffffffff021001",
+    /// );
+    /// // The canned answers of `queryPermissions` of the sub role, then the super role
+    /// let role = RoleClient::create_with_responses(vec![
+    ///     response(addresses(&[send_tx])),
+    ///     response(addresses(&[send_tx, create_contract])),
+    /// ]);
+    /// let is_superset = role
+    ///     .is_superset(
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a002",
+    ///         "0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001",
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// assert!(is_superset);
+    /// ```
+    fn is_superset(
+        &self,
+        super_role: &str,
+        sub_role: &str,
+        height: Option<&str>,
+    ) -> Result<bool, E> {
+        self.is_subset(sub_role, super_role, height)
+    }
+}
+
+impl RoleClient<Client> {
+    /// Query the permissions of all roles at the same time, return their union
+    /// sorted by address, the effective permissions of an account with the roles
+    pub fn merge_roles(
+        &self,
+        role_addresses: &[&str],
+        height: Option<&str>,
+    ) -> Result<Vec<Address>, ToolError> {
+        let calls = role_addresses
+            .iter()
+            .map(|role| Ok((Vec::new(), Some(parse_address(role)?))))
+            .collect::<Result<Vec<(Vec<&str>, Option<Address>)>, ToolError>>()?;
+        let mut permissions = BTreeSet::new();
+        for tokens in call_all_decoded(
+            &self.client,
+            self,
+            &self.contract,
+            "queryPermissions",
+            calls,
+            height,
+        )? {
+            permissions.extend(decode_addresses(tokens)?);
+        }
+        Ok(permissions.into_iter().collect())
+    }
+}
+
+/// Name and permissions of a role, decoded from `queryRole`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoleInfo {
+    /// Name of the role, the `bytes32` without trailing zeros
+    pub name: String,
+    /// Permissions of the role
+    pub permissions: Vec<Address>,
+}
+
+#[cfg(feature = "permission")]
+impl RoleInfo {
+    /// The name of the role, then the address and the name of every permission,
+    /// one per line, such as
+    ///
+    /// ```text
+    /// reader
+    /// ├── 0xca645d2dbc1c0d2b3c5e2d1b3a8f51e8c9b1a001 storage reader
+    /// └── 0xffffffffffffffffffffffffffffffffff021000 sendTx
+    /// ```
+    pub fn display_tree<P, T, R, E>(
+        &self,
+        permission: &P,
+        height: Option<&str>,
+    ) -> Result<String, E>
+    where
+        P: PermissionExt<T, R, E>,
+        T: ClientExt<R, E>,
+        R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+        E: Fail + From<ToolError>,
+    {
+        let mut tree = self.name.clone();
+        for (index, address) in self.permissions.iter().enumerate() {
+            let address = format!("{:x}", address);
+            let name = permission.query_name_string(&address, height)?;
+            let branch = if index + 1 == self.permissions.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            tree.push_str(&format!("\n{} 0x{} {}", branch, address, name));
+        }
+        Ok(tree)
+    }
+}
+
+/// Permissions of a role changed between two heights, sorted by address
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PermissionDiff {
+    /// Permissions only at the later height
+    pub added: Vec<Address>,
+    /// Permissions only at the earlier height
+    pub removed: Vec<Address>,
+}
+
+/// Role manage Client
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020007")]
+#[contract(abi_contract = "crate::contract_abi::ROLE_MANAGEMENT_CONTRACT")]
+#[contract(name = "RoleManagementExt")]
+pub struct RoleManageClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// RoleManagement system contract
+pub trait RoleManagementExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Create a new role
+    ///
+    /// param name: The name of role
+    /// param permissions: The permissions of role
+    /// return New role's address
+    fn new_role(
+        &mut self,
+        name: &str,
+        permissions: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [name, permissions];
+        self.contract_send_tx("newRole", &values, options, None)
+    }
+
+    /// Delete the role
+    ///
+    /// param role: The address of role
+    /// return true if successed, otherwise false
+    fn delete_role(&mut self, role: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(role)];
+        self.contract_send_tx("deleteRole", &values, options, None)
+    }
+
+    /// Update role's name
+    ///
+    /// param role: The address of role
+    /// param name: The new name of role
+    /// return true if successed, otherwise false
+    fn update_role_name(
+        &mut self,
+        role: &str,
+        name: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(role), name];
+        self.contract_send_tx("updateRoleName", &values, options, None)
+    }
+
+    /// Add permissions of role
+    ///
+    /// param role: The address of role
+    /// param permissions: The permissions of role
+    /// return true if successed, otherwise false
+    fn add_permissions(
+        &mut self,
+        role: &str,
+        permissions: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(role), permissions];
+        self.contract_send_tx("addPermissions", &values, options, None)
+    }
+
+    /// Delete permissions of role
+    ///
+    /// param role: The address of role
+    /// param permissions: The permissions of role
+    /// return true if successed, otherwise false
+    fn delete_permissions(
+        &mut self,
+        role: &str,
+        permissions: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(role), permissions];
+        self.contract_send_tx("deletePermissions", &values, options, None)
+    }
+
+    /// Set the role to the account
+    ///
+    /// param account: The account to be setted
+    /// param role: The role to be setted
+    /// return true if successed, otherwise false
+    fn set_role(&mut self, account: &str, role: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(account), remove_0x(role)];
+        self.contract_send_tx("setRole", &values, options, None)
+    }
+
+    /// Cancel the account's role
+    ///
+    /// param account: The account to be canceled
+    /// param role: The role to be canceled
+    /// return true if successed, otherwise false
+    fn cancel_role(
+        &mut self,
+        account: &str,
+        role: &str,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let values = [remove_0x(account), remove_0x(role)];
+        self.contract_send_tx("cancelRole", &values, options, None)
+    }
+
+    /// Clear the account's role
+    ///
+    /// param account: The account to be cleared
+    /// return true if successed, otherwise false
+    fn clear_role(&mut self, account: &str, options: TransactionOptions) -> Result<R, E> {
+        let values = [remove_0x(account)];
+        self.contract_send_tx("clearRole", &values, options, None)
+    }
+
+    /// Query the roles of the account
+    ///
+    /// param account: The account to be queried
+    /// return The roles of the account
+    fn query_roles(&self, account: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(account)];
+        let to = "0xffffffffffffffffffffffffffffffffff02000d";
+        self.contract_call_to_address("queryRoles", &values, &to, height)
+    }
+
+    /// Query the accounts that have the role
+    ///
+    /// param role: The role to be queried
+    /// return The accounts that have the role
+    fn query_accounts(&self, role: &str, height: Option<&str>) -> Result<R, E> {
+        let values = [remove_0x(role)];
+        let to = "0xffffffffffffffffffffffffffffffffff02000d";
+        self.contract_call_to_address("queryAccounts", &values, &to, height)
+    }
+
+    /// Query the roles of the account, decoded
+    ///
+    /// param account: The account to be queried
+    /// return The roles of the account
+    fn list_roles(&self, account: &str, height: Option<&str>) -> Result<Vec<Address>, E> {
+        let values = [remove_0x(account)];
+        Ok(decode_addresses(self.contract_call_decoded(
+            "queryRoles",
+            &values,
+            Some(Address::from(ROLE_AUTH.0)),
+            height,
+        )?)?)
+    }
+
+    /// Query the accounts that have the role, decoded
+    ///
+    /// param role: The role to be queried
+    /// return The accounts that have the role
+    fn list_accounts_with_role(&self, role: &str, height: Option<&str>) -> Result<Vec<Address>, E> {
+        let values = [remove_0x(role)];
+        Ok(decode_addresses(self.contract_call_decoded(
+            "queryAccounts",
+            &values,
+            Some(Address::from(ROLE_AUTH.0)),
+            height,
+        )?)?)
+    }
+}
+
+impl<T> RoleManageClient<T>
+where
+    T: ClientExt<JsonRpcResponse, ToolError> + Clone,
+{
+    /// Replace all roles of the account with the new role, `clearRole` and `setRole`
+    /// in one batch transaction, so the account is never without role
+    ///
+    /// The new role must have permissions.
+    pub fn replace_role(
+        &mut self,
+        account: &str,
+        new_role: &str,
+        options: TransactionOptions,
+    ) -> Result<JsonRpcResponse, ToolError> {
+        match RoleClient::create(self.client.clone())
+            .contract_call_decoded(
+                "lengthOfPermissions",
+                &[],
+                Some(parse_address(new_role)?),
+                None,
+            )?
+            .as_slice()
+        {
+            [Token::Uint(length)] if !length.is_zero() => {}
+            _ => {
+                return Err(ToolError::Customize(format!(
+                    "Role {} does not exist or has no permission",
+                    new_role
+                )));
+            }
+        }
+
+        let batch_tx = |args: Result<(String, String), ToolError>| {
+            args.map(|(code, to_address)| format!("{}{}", remove_0x(&to_address), remove_0x(&code)))
+        };
+        let clear_role =
+            batch_tx(self.prepare_call_args("clearRole", &[remove_0x(account)], None))?;
+        let set_role = batch_tx(self.prepare_call_args(
+            "setRole",
+            &[remove_0x(account), remove_0x(new_role)],
+            None,
+        ))?;
+        BatchTxClient::create(self.client.clone())
+            .multi_transactions(vec![clear_role.as_str(), set_role.as_str()], options)
+    }
+
+    /// Compare the permissions of the role at block A with them at block B,
+    /// to find what changed between two snapshots
+    pub fn role_diff(
+        &self,
+        role: &str,
+        block_a: u64,
+        block_b: u64,
+    ) -> Result<PermissionDiff, ToolError> {
+        let role_client = RoleClient::create(self.client.clone());
+        let address = parse_address(role)?;
+        let permissions = |height: u64| -> Result<BTreeSet<Address>, ToolError> {
+            let tokens = role_client.contract_call_decoded(
+                "queryPermissions",
+                &[],
+                Some(address),
+                Some(&format!("{:#x}", height)),
+            )?;
+            Ok(decode_addresses(tokens)?.into_iter().collect())
+        };
+        let before = permissions(block_a)?;
+        let after = permissions(block_b)?;
+        Ok(PermissionDiff {
+            added: after.difference(&before).cloned().collect(),
+            removed: before.difference(&after).cloned().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        PermissionDiff, RoleClient, RoleExt, RoleInfo, RoleManageClient, RoleManagementExt,
+    };
+    #[cfg(feature = "permission")]
+    use crate::client::system_contract::PermissionClient;
+    use crate::rpctypes::JsonRpcResponse;
+    use ethabi::Address;
+
+    /// Response of `queryPermissions`, the permissions are `0x00..0n`
+    fn permissions_response(permissions: &[u8]) -> JsonRpcResponse {
+        let mut output = format!("{:064x}{:064x}", 32, permissions.len());
+        for permission in permissions {
+            output.push_str(&format!("{:064x}", permission));
+        }
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+            output
+        ))
+        .unwrap()
+    }
+
+    fn permission(last: u8) -> Address {
+        let mut bytes = [0u8; 20];
+        bytes[19] = last;
+        Address::from(bytes)
+    }
+
+    #[test]
+    fn test_merge_roles() {
+        let client = RoleClient::create_with_responses(vec![
+            permissions_response(&[3, 1]),
+            permissions_response(&[2, 3]),
+        ]);
+        let permissions = client
+            .merge_roles(
+                &[
+                    "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8",
+                    "0x558c6a7d9cd1fbdfca1d6ab8cd8c0a0c5e5fa0fd",
+                ],
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            permissions,
+            vec![permission(1), permission(2), permission(3)]
+        );
+        assert!(client.merge_roles(&[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_role_diff() {
+        let role = "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8";
+        let client = RoleManageClient::create_with_responses(vec![
+            permissions_response(&[1, 2]),
+            permissions_response(&[3, 2]),
+        ]);
+        assert_eq!(
+            client.role_diff(role, 10, 20).unwrap(),
+            PermissionDiff {
+                added: vec![permission(3)],
+                removed: vec![permission(1)],
+            }
+        );
+
+        let client = RoleManageClient::create_with_responses(vec![
+            permissions_response(&[1, 2]),
+            permissions_response(&[2, 1]),
+        ]);
+        assert_eq!(
+            client.role_diff(role, 10, 20).unwrap(),
+            PermissionDiff::default()
+        );
+    }
+
+    #[test]
+    fn test_list_roles() {
+        let account = "0x4b5ae4567ad5d9fb92bc9afd6a657e6fa13a2523";
+        let role = "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8";
+        let client = RoleManageClient::create_with_responses(vec![
+            permissions_response(&[2, 1]),
+            permissions_response(&[]),
+        ]);
+        assert_eq!(
+            client.list_roles(account, None).unwrap(),
+            vec![permission(2), permission(1)]
+        );
+        assert!(client
+            .list_accounts_with_role(role, Some("0x10"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_query_role_info() {
+        let response = |output: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+                output
+            ))
+            .unwrap()
+        };
+        let info = format!(
+            "{:0<64}{:064x}{:064x}{:064x}{:064x}",
+            "726561646572", 64, 2, 1, 2
+        );
+        let client = RoleClient::create_with_responses(vec![response(&info), response("")]);
+        let role = "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8";
+        let info = client.query_role_info(role, None).unwrap();
+        assert_eq!(
+            info,
+            RoleInfo {
+                name: "reader".to_string(),
+                permissions: vec![permission(1), permission(2)],
+            }
+        );
+        assert!(client.query_role_info(role, None).is_err());
+
+        #[cfg(feature = "permission")]
+        {
+            let name = |word: &str| response(&format!("{:0<64}", word));
+            let permission_client = PermissionClient::create_with_responses(vec![
+                name("726561644b6579"),
+                name("73656e645478"),
+            ]);
+            assert_eq!(
+                info.display_tree(&permission_client, None).unwrap(),
+                format!("reader\n├── 0x{:040x} readKey\n└── 0x{:040x} sendTx", 1, 2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_name_string() {
+        let name = |word: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:0<64}"}}"#,
+                word
+            ))
+            .unwrap()
+        };
+        let role = "0xe9deeae8b2a43675f113d11573119b9c68e5e3d8";
+        let client = RoleClient::create_with_responses(vec![name("61646d696e"), name("ff")]);
+        assert_eq!(client.query_name_string(role, None).unwrap(), "admin");
+        assert!(client.query_name_string(role, None).is_err());
+    }
+}
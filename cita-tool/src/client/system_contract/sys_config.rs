@@ -0,0 +1,666 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::abi::contract_encode_input;
+use crate::client::basic::{Client, ClientExt};
+use crate::client::system_contract::{decode_u64, decode_uint, ContractCall};
+use crate::client::{remove_0x, TransactionOptions};
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcResponse;
+use crate::LowerHex;
+use ethabi::{Address, Contract, Token};
+use failure::Fail;
+use tool_derive::ContractExt;
+use types::U256;
+
+/// System config contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020000")]
+#[contract(abi_contract = "crate::contract_abi::SYS_CONFIG_CONTRACT")]
+#[contract(name = "SysConfigExt")]
+pub struct SysConfigClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Token of the chain, from `getTokenInfo`
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TokenInfo {
+    /// Token name
+    pub name: String,
+    /// Token symbol
+    pub symbol: String,
+    /// Token avatar url
+    pub avatar: String,
+}
+
+/// System config contract
+pub trait SysConfigExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get chain owner
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_chain_owner(None).unwrap();
+    /// // The ABI encoded output of `getChainOwner`
+    /// println!("{}", response);
+    /// ```
+    fn get_chain_owner(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getChainOwner", &[], None, height)
+    }
+
+    /// Get chain id
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_chain_id(None).unwrap();
+    /// // The ABI encoded output of `getChainId`
+    /// println!("{}", response);
+    /// ```
+    fn get_chain_id(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getChainId", &[], None, height)
+    }
+
+    /// Get chain id v1
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_chain_id_v1(None).unwrap();
+    /// // The ABI encoded output of `getChainIdV1`
+    /// println!("{}", response);
+    /// ```
+    fn get_chain_id_v1(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getChainIdV1", &[], None, height)
+    }
+
+    /// Check sender's create contract permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_create_permission_check(None).unwrap();
+    /// // The ABI encoded output of `getCreateContractPermissionCheck`
+    /// println!("{}", response);
+    /// ```
+    fn get_create_permission_check(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getCreateContractPermissionCheck", &[], None, height)
+    }
+
+    /// Check sender's send transaction permission
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_send_permission_check(None).unwrap();
+    /// // The ABI encoded output of `getSendTxPermissionCheck`
+    /// println!("{}", response);
+    /// ```
+    fn get_send_permission_check(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getSendTxPermissionCheck", &[], None, height)
+    }
+
+    /// Get delay block number
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_delay_block_number(None).unwrap();
+    /// // The ABI encoded output of `getDelayBlockNumber`
+    /// println!("{}", response);
+    /// ```
+    fn get_delay_block_number(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getDelayBlockNumber", &[], None, height)
+    }
+
+    /// Get delay block number, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::JsonRpcResponse;
+    ///
+    /// // The canned answer of `getDelayBlockNumber`, the ABI encoded number
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1
+    /// ))
+    /// .unwrap();
+    /// let sys_config = SysConfigClient::create_with_responses(vec![response]);
+    /// let delay = sys_config.get_delay_block_number_u64(None).unwrap();
+    /// assert_eq!(delay, 1);
+    /// ```
+    fn get_delay_block_number_u64(&self, height: Option<&str>) -> Result<u64, E> {
+        Ok(decode_u64(self.contract_call_decoded(
+            "getDelayBlockNumber",
+            &[],
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Get the name, symbol and avatar of the token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::{encode, JsonRpcResponse};
+    ///
+    /// // The canned answer of `getTokenInfo`, the name, symbol and avatar
+    /// let word = |text: &str| format!("{:064x}{:0<64}", text.len(), encode(text));
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:064x}{}{}{}"}}"#,
+    ///     96,
+    ///     160,
+    ///     224,
+    ///     word("CITA Test Token"),
+    ///     word("CTT"),
+    ///     word("")
+    /// ))
+    /// .unwrap();
+    /// let sys_config = SysConfigClient::create_with_responses(vec![response]);
+    /// let token = sys_config.get_token_info(None).unwrap();
+    /// assert_eq!(token.name, "CITA Test Token");
+    /// assert_eq!(token.symbol, "CTT");
+    /// assert!(token.avatar.is_empty());
+    /// ```
+    fn get_token_info(&self, height: Option<&str>) -> Result<TokenInfo, E> {
+        match self
+            .contract_call_decoded("getTokenInfo", &[], None, height)?
+            .as_slice()
+        {
+            [Token::String(name), Token::String(symbol), Token::String(avatar)] => Ok(TokenInfo {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                avatar: avatar.clone(),
+            }),
+            tokens => Err(ToolError::Abi(format!("Not a token info: {:?}", tokens)).into()),
+        }
+    }
+
+    /// Whether economic incentives are returned to operators
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_feeback_platform_check(None).unwrap();
+    /// // The ABI encoded output of `getFeeBackPlatformCheck`
+    /// println!("{}", response);
+    /// ```
+    fn get_feeback_platform_check(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getFeeBackPlatformCheck", &[], None, height)
+    }
+
+    /// Whether to open the charging mode
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_economical_model(None).unwrap();
+    /// // The ABI encoded output of `getEconomicalModel`
+    /// println!("{}", response);
+    /// ```
+    fn get_economical_model(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getEconomicalModel", &[], None, height)
+    }
+
+    /// Whether to open the permission check
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_permission_check(None).unwrap();
+    /// // The ABI encoded output of `getPermissionCheck`
+    /// println!("{}", response);
+    /// ```
+    fn get_permission_check(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getPermissionCheck", &[], None, height)
+    }
+
+    /// Whether to open the quota check
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.get_quota_check(None).unwrap();
+    /// // The ABI encoded output of `getQuotaCheck`
+    /// println!("{}", response);
+    /// ```
+    fn get_quota_check(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getQuotaCheck", &[], None, height)
+    }
+
+    /// Set chain name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.set_chain_name("test-chain", TransactionOptions::new()).unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_chain_name(&mut self, chain_name: &str, options: TransactionOptions) -> Result<R, E> {
+        let value = [chain_name];
+        self.contract_send_tx("setChainName", &value, options, None)
+    }
+
+    /// Set operator
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut sys_config = SysConfigClient::create(client);
+    /// let response = sys_config.set_operator("test-operator", TransactionOptions::new()).unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_operator(&mut self, operator: &str, options: TransactionOptions) -> Result<R, E> {
+        let value = [operator];
+        self.contract_send_tx("setOperator", &value, options, None)
+    }
+
+    /// Set website
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut sys_config = SysConfigClient::create(client);
+    /// let response = sys_config
+    ///     .set_website("https://www.example.com", TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_website(&mut self, website: &str, options: TransactionOptions) -> Result<R, E> {
+        let value = [website];
+        self.contract_send_tx("setWebsite", &value, options, None)
+    }
+
+    /// Set block interval
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{SysConfigClient, SysConfigExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut sys_config = SysConfigClient::create(client);
+    /// let response = sys_config
+    ///     .set_block_interval(U256::from(3000), TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_block_interval(
+        &mut self,
+        block_interval: U256,
+        options: TransactionOptions,
+    ) -> Result<R, E> {
+        let interval = block_interval.completed_lower_hex();
+        let value = [interval.as_str()];
+        self.contract_send_tx("setBlockInterval", &value, options, None)
+    }
+}
+
+/// Emergency brake contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff02000f")]
+#[contract(abi_contract = "crate::contract_abi::EMERGENCY_BRAKE_CONTRACT")]
+#[contract(name = "EmergencyBrakeExt")]
+pub struct EmergencyBrakeClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Emergency brake contract
+pub trait EmergencyBrakeExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get state
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{EmergencyBrakeClient, EmergencyBrakeExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let emergency_brake = EmergencyBrakeClient::create(client);
+    /// let response = emergency_brake.state(None).unwrap();
+    /// // The ABI encoded output of `state`
+    /// println!("{}", response);
+    /// ```
+    fn state(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("state", &[], None, height)
+    }
+
+    /// Set state
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{EmergencyBrakeClient, EmergencyBrakeExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut emergency_brake = EmergencyBrakeClient::create(client);
+    /// let response = emergency_brake.set_state(true, TransactionOptions::new()).unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_state(&mut self, state: bool, options: TransactionOptions) -> Result<R, E> {
+        let state = state.to_string();
+        let value = [state.as_str()];
+        self.contract_send_tx("setState", &value, options, None)
+    }
+
+    /// Fail with `ToolError::EmergencyBrakeActive` if the emergency brake is on,
+    /// before changing the state of the stopped chain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{EmergencyBrakeClient, EmergencyBrakeExt};
+    /// use cita_tool::{JsonRpcResponse, ToolError};
+    ///
+    /// let response = |state: bool| -> JsonRpcResponse {
+    ///     serde_json::from_str(&format!(
+    ///         r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///         state as u8
+    ///     ))
+    ///     .unwrap()
+    /// };
+    /// // The canned answers of `state`, off and then on
+    /// let emergency_brake =
+    ///     EmergencyBrakeClient::create_with_responses(vec![response(false), response(true)]);
+    /// assert!(emergency_brake.require_inactive(None).is_ok());
+    /// match emergency_brake.require_inactive(None) {
+    ///     Err(ToolError::EmergencyBrakeActive) => {}
+    ///     result => panic!("Unexpected result: {:?}", result),
+    /// }
+    /// ```
+    fn require_inactive(&self, height: Option<&str>) -> Result<(), E> {
+        let tokens = self.contract_call_decoded("state", &[], None, height)?;
+        match tokens.as_slice() {
+            [Token::Bool(false)] => Ok(()),
+            [Token::Bool(true)] => Err(ToolError::EmergencyBrakeActive.into()),
+            _ => Err(ToolError::Abi(format!("Not a bool: {:?}", tokens)).into()),
+        }
+    }
+}
+
+/// Price manager contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020010")]
+#[contract(abi_contract = "crate::contract_abi::PRICE_MANAGER_CONTRACT")]
+#[contract(name = "PriceManagerExt")]
+pub struct PriceManagerClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Price manager contract
+pub trait PriceManagerExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get quota price
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PriceManagerClient, PriceManagerExt};
+    ///
+    /// let client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let price_manager = PriceManagerClient::create(client);
+    /// let response = price_manager.price(None).unwrap();
+    /// // The ABI encoded output of `getQuotaPrice`
+    /// println!("{}", response);
+    /// ```
+    fn price(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getQuotaPrice", &[], None, height)
+    }
+
+    /// Get quota price, decoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cita_tool::client::system_contract::{PriceManagerClient, PriceManagerExt};
+    /// use cita_tool::{JsonRpcResponse, U256};
+    ///
+    /// // The canned answer of `getQuotaPrice`, the ABI encoded price
+    /// let response: JsonRpcResponse = serde_json::from_str(&format!(
+    ///     r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+    ///     1_000_000
+    /// ))
+    /// .unwrap();
+    /// let price_manager = PriceManagerClient::create_with_responses(vec![response]);
+    /// let price = price_manager.price_decoded(None).unwrap();
+    /// assert_eq!(price, U256::from(1_000_000));
+    /// ```
+    fn price_decoded(&self, height: Option<&str>) -> Result<U256, E> {
+        Ok(decode_uint(self.contract_call_decoded(
+            "getQuotaPrice",
+            &[],
+            None,
+            height,
+        )?)?)
+    }
+
+    /// Set quota price
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use cita_tool::client::basic::Client;
+    /// use cita_tool::client::system_contract::{PriceManagerClient, PriceManagerExt};
+    /// use cita_tool::{privkey_from_str, TransactionOptions, U256};
+    ///
+    /// let mut client = Client::new().set_uri("http://127.0.0.1:1337");
+    /// let private_key = "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+    /// client.set_private_key(&privkey_from_str(private_key).unwrap());
+    /// let mut price_manager = PriceManagerClient::create(client);
+    /// let response = price_manager
+    ///     .set_price(U256::from(1_000_000), TransactionOptions::new())
+    ///     .unwrap();
+    /// // The hash and status of the transaction
+    /// println!("{}", response);
+    /// ```
+    fn set_price(&mut self, price: U256, options: TransactionOptions) -> Result<R, E> {
+        let price = price.completed_lower_hex();
+        let value = [price.as_str()];
+        self.contract_send_tx("setQuotaPrice", &value, options, None)
+    }
+}
+
+/// Version manager contract
+#[derive(ContractExt)]
+#[contract(addr = "0xffffffffffffffffffffffffffffffffff020011")]
+#[contract(abi_contract = "crate::contract_abi::VERSION_MANAGER_CONTRACT")]
+#[contract(name = "VersionManagerExt")]
+pub struct VersionManagerClient<T> {
+    client: T,
+    address: Address,
+    contract: Cow<'static, Contract>,
+}
+
+/// Version manager contract
+pub trait VersionManagerExt<T, R, E>: ContractCall<R, E>
+where
+    T: ClientExt<R, E>,
+    R: serde::Serialize + serde::Deserialize<'static> + ::std::fmt::Display,
+    E: Fail + From<ToolError>,
+{
+    /// Create a ContractClient
+    fn create(client: T) -> Self;
+
+    /// Get version
+    fn get_version(&self, height: Option<&str>) -> Result<R, E> {
+        self.contract_call("getVersion", &[], None, height)
+    }
+
+    /// Set version
+    fn set_version(&mut self, version: U256, options: TransactionOptions) -> Result<R, E> {
+        let version = version.completed_lower_hex();
+        let value = [version.as_str()];
+        self.contract_send_tx("setVersion", &value, options, None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        EmergencyBrakeClient, EmergencyBrakeExt, SysConfigClient, SysConfigExt, TokenInfo,
+    };
+    use crate::error::ToolError;
+    use crate::rpctypes::JsonRpcResponse;
+
+    fn response(state: bool) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+            state as u8
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_require_inactive() {
+        let client =
+            EmergencyBrakeClient::create_with_responses(vec![response(false), response(true)]);
+        assert!(client.require_inactive(None).is_ok());
+        match client.require_inactive(None) {
+            Err(ToolError::EmergencyBrakeActive) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_get_token_info() {
+        let word = |text: &str| format!("{:064x}{:0<64}", text.len(), hex::encode(text));
+        let response: JsonRpcResponse = serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}{:064x}{:064x}{}{}{}"}}"#,
+            96,
+            160,
+            224,
+            word("CITA Test Token"),
+            word("CTT"),
+            word("")
+        ))
+        .unwrap();
+        let client = SysConfigClient::create_with_responses(vec![response]);
+        assert_eq!(
+            client.get_token_info(None).unwrap(),
+            TokenInfo {
+                name: "CITA Test Token".to_string(),
+                symbol: "CTT".to_string(),
+                avatar: String::new(),
+            }
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use hex::encode;
+use protobuf::Message;
+use types::H256;
+
+use crate::client::basic::Client;
+use crate::client::deduplicator::TransactionKey;
+use crate::client::{remove_0x, TransactionDeduplicator, TransactionOptions};
+use crate::crypto::{Hashable, PrivateKey};
+use crate::error::ToolError;
+use crate::rpctypes::{JsonRpcResponse, ParamsValue, ResponseValue};
+use crate::signer::InMemorySigner;
+
+/// A transaction signed once, sending it again can't create another transaction
+///
+/// The nonce and the signature are fixed when it is built, so a retried request
+/// submits the same bytes and the node rejects the copy as a duplicate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RawTransaction {
+    hex: String,
+    hash: H256,
+    nonce: String,
+    valid_until_block: u64,
+}
+
+impl RawTransaction {
+    /// The signed `UnverifiedTransaction` in hex with "0x", the param of `sendRawTransaction`
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+
+    /// Hash of the transaction, as returned by `sendRawTransaction`
+    pub fn hash(&self) -> H256 {
+        self.hash
+    }
+
+    /// Nonce of the transaction
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// The last block the transaction can be included in
+    pub fn valid_until_block(&self) -> u64 {
+        self.valid_until_block
+    }
+}
+
+/// Build and sign a transaction with the private key of the client, without sending it
+///
+/// Like `send_raw_transaction`, the transaction is simulated with `call` first unless
+/// the options skip it, and the quota is estimated on a dry-run client.
+/// The version and the chain id missing in the options are the ones the chain expects,
+/// see `Client::negotiate_chain_version`, negotiated on the first build of the client.
+pub struct TransactionBuilder<'a> {
+    client: &'a mut Client,
+    options: TransactionOptions<'a>,
+    deduplicator: Option<&'a TransactionDeduplicator>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    /// Build the transaction of the options
+    pub fn new(client: &'a mut Client, options: TransactionOptions<'a>) -> Self {
+        TransactionBuilder {
+            client,
+            options,
+            deduplicator: None,
+        }
+    }
+
+    /// Refuse to `send` a transaction with the same sender, target, data and value as
+    /// one sent before with the cache, until that one expires
+    pub fn prevent_duplicates(mut self, cache: &'a TransactionDeduplicator) -> Self {
+        self.deduplicator = Some(cache);
+        self
+    }
+
+    /// Generate the nonce, sign the transaction and compute its hash
+    pub fn build(self) -> Result<RawTransaction, ToolError> {
+        let encryption = self
+            .client
+            .private_key()
+            .and_then(PrivateKey::encryption)
+            .ok_or_else(|| {
+                ToolError::Customize("No private key to sign the transaction".to_string())
+            })?;
+        let options = self.options;
+        let needs_chain_id = match options.version() {
+            None => true,
+            Some(0) => options.chain_id().is_none(),
+            Some(_) => options.chain_id_v1().is_none(),
+        };
+        let options = if needs_chain_id {
+            let version = match self.client.negotiated_version() {
+                Some(version) => version,
+                None => self.client.negotiate_chain_version()?,
+            };
+            options
+                .set_version(options.version().or(Some(version.tx_version)))
+                .set_chain_id(options.chain_id().or(Some(version.chain_id)))
+                .set_chain_id_v1(options.chain_id_v1().or(version.chain_id_v1))
+        } else {
+            options
+        };
+        // Contract creation and value transfer can not be simulated by `call`
+        if options.simulation()
+            && !remove_0x(options.address()).is_empty()
+            && options.value().map_or(true, |value| value.is_zero())
+        {
+            self.client.simulate_transaction(&options)?;
+        }
+        let options = if self.client.dry_run() && options.quota().is_none() {
+            options.estimate_and_set_quota(self.client)?
+        } else {
+            options
+        };
+        let tx = self.client.generate_transaction(options)?;
+        let private_key = self.client.private_key().cloned().unwrap();
+        let bytes = tx
+            .build_with_signer(&mut InMemorySigner(private_key))?
+            .write_to_bytes()
+            .map_err(ToolError::Proto)?;
+        Ok(RawTransaction {
+            hex: format!("0x{}", encode(&bytes)),
+            hash: bytes.crypt_hash(encryption),
+            nonce: tx.get_nonce().to_string(),
+            valid_until_block: tx.get_valid_until_block(),
+        })
+    }
+
+    /// Build the transaction and send it, a dry-run client only returns its hex
+    ///
+    /// With `prevent_duplicates`, fail with `ToolError::DuplicateTransaction` if the
+    /// same transaction was sent before and is not expired, or is being sent, without
+    /// sending it.
+    pub fn send(self) -> Result<JsonRpcResponse, ToolError> {
+        let TransactionBuilder {
+            client,
+            options,
+            deduplicator,
+        } = self;
+        let deduplicator = match deduplicator {
+            Some(deduplicator) => {
+                let key = TransactionKey::new(client.sender(), &options);
+                if let Some(previous_hash) = deduplicator.previous(&key) {
+                    return Err(ToolError::DuplicateTransaction { previous_hash });
+                }
+                let height = match options.current_height() {
+                    Some(height) => height,
+                    None => client.get_current_height()?,
+                };
+                Some((deduplicator, key, height))
+            }
+            None => None,
+        };
+        let options = match deduplicator {
+            Some((_, _, height)) => options.set_current_height(Some(height)),
+            None => options,
+        };
+
+        let tx = TransactionBuilder::new(&mut *client, options).build()?;
+        if client.dry_run() {
+            return Ok(JsonRpcResponse::from_result(ResponseValue::Singe(
+                ParamsValue::String(tx.hex().to_string()),
+            )));
+        }
+        // Reserved before sending, a concurrent send of the same transaction is refused
+        let reserved = match deduplicator {
+            Some((deduplicator, key, height)) => {
+                deduplicator
+                    .reserve(
+                        key.clone(),
+                        tx.hash(),
+                        tx.valid_until_block().saturating_sub(height),
+                    )
+                    .map_err(|previous_hash| ToolError::DuplicateTransaction { previous_hash })?;
+                Some((deduplicator, key))
+            }
+            None => None,
+        };
+        let response = client.submit_transaction(&tx, 0);
+        client.invalidate_cached(options.address());
+        if let Some((deduplicator, key)) = reserved {
+            if response.as_ref().map_or(true, |response| !response.is_ok()) {
+                deduplicator.release(&key, tx.hash());
+            }
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::TransactionBuilder;
+    use crate::client::basic::Client;
+    use crate::client::{TransactionDeduplicator, TransactionOptions};
+    use crate::crypto::{Encryption, Hashable};
+    use crate::error::ToolError;
+    use crate::protos::UnverifiedTransaction;
+    use crate::rpctypes::{ChainVersion, JsonRpcResponse};
+    use crate::{privkey_from_str, U256};
+
+    #[test]
+    fn test_build() {
+        let mut client = Client::with_responses(Vec::new());
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020004")
+            .set_code("0xabcd")
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_valid_until_block(Some(100))
+            .skip_simulation();
+        assert!(TransactionBuilder::new(&mut client, options)
+            .build()
+            .is_err());
+
+        client.set_private_key(
+            &privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap(),
+        );
+        let tx = TransactionBuilder::new(&mut client, options)
+            .build()
+            .unwrap();
+        let unverified = UnverifiedTransaction::from_str(tx.hex()).unwrap();
+        assert_eq!(unverified.get_transaction().get_nonce(), tx.nonce());
+        assert_eq!(unverified.get_transaction().get_valid_until_block(), 100);
+        assert_eq!(
+            hex::decode(&tx.hex()[2..])
+                .unwrap()
+                .crypt_hash(Encryption::Secp256k1),
+            tx.hash()
+        );
+
+        let other = TransactionBuilder::new(&mut client, options)
+            .build()
+            .unwrap();
+        assert_ne!(other.nonce(), tx.nonce());
+        assert_ne!(other.hash(), tx.hash());
+    }
+
+    #[test]
+    fn test_build_simulation() {
+        let error = |error: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(r#"{{"jsonrpc":"2.0","id":1,"error":{}}}"#, error))
+                .unwrap()
+        };
+        let mut client = Client::with_responses(vec![
+            error(r#"{"code":-32015,"message":"Reverted.","data":"0x08c379a0"}"#),
+            error(r#"{"code":-32602,"message":"Invalid params"}"#),
+        ]);
+        client.set_private_key(
+            &privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap(),
+        );
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020004")
+            .set_code("0xabcd")
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_valid_until_block(Some(100));
+        match TransactionBuilder::new(&mut client, options).build() {
+            Err(ToolError::SimulationFailed { revert_data }) => {
+                assert_eq!(revert_data, [0x08, 0xc3, 0x79, 0xa0])
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        match TransactionBuilder::new(&mut client, options).build() {
+            Err(ToolError::RpcError { code: -32602, .. }) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(
+            TransactionBuilder::new(&mut client, options.skip_simulation())
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_build_negotiates_chain_version() {
+        let metadata: JsonRpcResponse = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"result":{
+                "chainId": 0,
+                "chainIdV1": "0x2",
+                "genesisTimestamp": 1528700000000,
+                "blockInterval": 3000,
+                "version": 2
+            }}"#,
+        )
+        .unwrap();
+        let mut client = Client::with_responses(vec![metadata]);
+        client.set_private_key(
+            &privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap(),
+        );
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020004")
+            .set_code("0xabcd")
+            .set_valid_until_block(Some(100))
+            .skip_simulation();
+        assert_eq!(client.negotiated_version(), None);
+
+        // Negotiated once, the other builds don't query the chain again
+        for _ in 0..2 {
+            let tx = TransactionBuilder::new(&mut client, options)
+                .build()
+                .unwrap();
+            let unverified = UnverifiedTransaction::from_str(tx.hex()).unwrap();
+            assert_eq!(unverified.get_transaction().get_version(), 2);
+            assert_eq!(
+                U256::from(unverified.get_transaction().get_chain_id_v1()),
+                U256::from(2)
+            );
+        }
+        assert_eq!(
+            client.negotiated_version(),
+            Some(ChainVersion {
+                tx_version: 2,
+                chain_id: 0,
+                chain_id_v1: Some(U256::from(2)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_send_prevent_duplicates() {
+        let sent = |hash: &str| -> JsonRpcResponse {
+            serde_json::from_str(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{{"hash":"{}","status":"OK"}}}}"#,
+                hash
+            ))
+            .unwrap()
+        };
+        let mut client = Client::with_responses(vec![sent("0x01"), sent("0x02"), sent("0x03")]);
+        client.set_private_key(
+            &privkey_from_str("0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6")
+                .unwrap(),
+        );
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020004")
+            .set_code("0xabcd")
+            .set_version(Some(2))
+            .set_chain_id_v1(Some(U256::from(1)))
+            .set_current_height(Some(90))
+            .skip_simulation();
+        let deduplicator = TransactionDeduplicator::default();
+
+        assert!(TransactionBuilder::new(&mut client, options)
+            .prevent_duplicates(&deduplicator)
+            .send()
+            .unwrap()
+            .is_ok());
+        match TransactionBuilder::new(&mut client, options)
+            .prevent_duplicates(&deduplicator)
+            .send()
+        {
+            Err(ToolError::DuplicateTransaction { .. }) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        // Not checked without the cache, or with other data
+        assert!(TransactionBuilder::new(&mut client, options)
+            .send()
+            .unwrap()
+            .is_ok());
+        assert!(
+            TransactionBuilder::new(&mut client, options.set_code("0xabce"))
+                .prevent_duplicates(&deduplicator)
+                .send()
+                .unwrap()
+                .is_ok()
+        );
+    }
+}
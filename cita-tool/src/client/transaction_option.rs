@@ -1,5 +1,10 @@
+use crate::client::basic::Client;
+use crate::error::ToolError;
 use types::U256;
 
+/// Default multiplier of the estimated quota
+const DEFAULT_QUOTA_MARGIN: f64 = 1.2;
+
 /// Transaction parameter option
 #[derive(Clone, Copy, Debug)]
 pub struct TransactionOptions<'a> {
@@ -9,6 +14,12 @@ pub struct TransactionOptions<'a> {
     quota: Option<u64>,
     value: Option<U256>,
     version: Option<u32>,
+    valid_until_block: Option<u64>,
+    expires_in: Option<u64>,
+    chain_id: Option<u32>,
+    chain_id_v1: Option<U256>,
+    simulation: bool,
+    quota_margin: f64,
 }
 
 impl<'a> TransactionOptions<'a> {
@@ -21,6 +32,12 @@ impl<'a> TransactionOptions<'a> {
             quota: None,
             value: None,
             version: None,
+            valid_until_block: None,
+            expires_in: None,
+            chain_id: None,
+            chain_id_v1: None,
+            simulation: true,
+            quota_margin: DEFAULT_QUOTA_MARGIN,
         }
     }
 
@@ -71,6 +88,30 @@ impl<'a> TransactionOptions<'a> {
         self.quota
     }
 
+    /// Set quota margin. Multiplier of the estimated quota set by
+    /// `estimate_and_set_quota`, default is 1.2
+    pub fn set_quota_margin(mut self, quota_margin: f64) -> Self {
+        self.quota_margin = quota_margin;
+        self
+    }
+
+    /// Get quota margin
+    pub fn quota_margin(&self) -> f64 {
+        self.quota_margin
+    }
+
+    /// Estimate the quota of the transaction sent by the client's private key,
+    /// and set the quota to the estimate multiplied by the quota margin
+    pub fn estimate_and_set_quota(self, client: &Client) -> Result<Self, ToolError> {
+        let sender = client.sender();
+        let estimate = client.estimate_quota_u64(
+            sender.as_ref().map(String::as_str),
+            self.address,
+            self.code,
+        )?;
+        Ok(self.set_quota(Some(margined_quota(estimate, self.quota_margin))))
+    }
+
     /// Set value. Transaction transfer amount
     pub fn set_value(mut self, value: Option<U256>) -> Self {
         self.value = value;
@@ -93,6 +134,64 @@ impl<'a> TransactionOptions<'a> {
         self.version
     }
 
+    /// Set valid until block, default is the current height + 88
+    pub fn set_valid_until_block(mut self, valid_until_block: Option<u64>) -> Self {
+        self.valid_until_block = valid_until_block;
+        self
+    }
+
+    /// Get valid until block
+    pub fn valid_until_block(&self) -> Option<u64> {
+        self.valid_until_block
+    }
+
+    /// Set the blocks the transaction is valid for, after the height queried when it's
+    /// signed rather than the height set by `set_current_height`, so the options built
+    /// long before signing don't expire. Ignored if valid until block is set
+    pub fn set_expires_in(mut self, blocks: Option<u64>) -> Self {
+        self.expires_in = blocks;
+        self
+    }
+
+    /// Get the blocks the transaction is valid for
+    pub fn expires_in(&self) -> Option<u64> {
+        self.expires_in
+    }
+
+    /// Set chain id of version 0 transaction, default is queried from the chain
+    pub fn set_chain_id(mut self, chain_id: Option<u32>) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Get chain id of version 0 transaction
+    pub fn chain_id(&self) -> Option<u32> {
+        self.chain_id
+    }
+
+    /// Set chain id of version 1 and later transaction, default is queried from the chain
+    pub fn set_chain_id_v1(mut self, chain_id_v1: Option<U256>) -> Self {
+        self.chain_id_v1 = chain_id_v1;
+        self
+    }
+
+    /// Get chain id of version 1 and later transaction
+    pub fn chain_id_v1(&self) -> Option<U256> {
+        self.chain_id_v1
+    }
+
+    /// Skip the pre-flight `call` before signing, for the transaction whose `call`
+    /// behaves differently, such as permission checked ones
+    pub fn skip_simulation(mut self) -> Self {
+        self.simulation = false;
+        self
+    }
+
+    /// Get whether to simulate the transaction before signing
+    pub fn simulation(&self) -> bool {
+        self.simulation
+    }
+
     /// Restore initialization status
     pub fn clear(&mut self) {
         self.value = None;
@@ -100,7 +199,13 @@ impl<'a> TransactionOptions<'a> {
         self.current_height = None;
         self.address = "0x";
         self.code = "0x";
-        self.version = None
+        self.version = None;
+        self.valid_until_block = None;
+        self.expires_in = None;
+        self.chain_id = None;
+        self.chain_id_v1 = None;
+        self.simulation = true;
+        self.quota_margin = DEFAULT_QUOTA_MARGIN;
     }
 }
 
@@ -109,3 +214,58 @@ impl Default for TransactionOptions<'static> {
         TransactionOptions::new()
     }
 }
+
+/// The estimate multiplied by the margin, never below the estimate
+fn margined_quota(estimate: u64, margin: f64) -> u64 {
+    let quota = (estimate as f64 * margin).ceil();
+    if quota >= u64::max_value() as f64 {
+        u64::max_value()
+    } else {
+        (quota as u64).max(estimate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{margined_quota, TransactionOptions};
+    use crate::client::basic::Client;
+    use crate::rpctypes::JsonRpcResponse;
+
+    #[test]
+    fn test_margined_quota() {
+        assert_eq!(margined_quota(21_000, 1.2), 25_200);
+        assert_eq!(margined_quota(21_001, 1.2), 25_202);
+        assert_eq!(margined_quota(21_000, 0.5), 21_000);
+        assert_eq!(margined_quota(u64::max_value(), 1.2), u64::max_value());
+    }
+
+    #[test]
+    fn test_estimate_and_set_quota() {
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":"0x5208"}"#).unwrap();
+        let client = Client::with_responses(vec![response]);
+        let options = TransactionOptions::new()
+            .set_address("0xffffffffffffffffffffffffffffffffff020009")
+            .estimate_and_set_quota(&client)
+            .unwrap();
+        assert_eq!(options.quota(), Some(25_200));
+    }
+
+    #[test]
+    fn test_expires_in() {
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":"0x64"}"#).unwrap();
+        let mut client = Client::with_responses(vec![response]);
+        let options = TransactionOptions::new()
+            .set_version(Some(0))
+            .set_chain_id(Some(1))
+            .set_current_height(Some(5));
+        let tx = client.generate_transaction(options).unwrap();
+        assert_eq!(tx.get_valid_until_block(), 93);
+
+        let tx = client
+            .generate_transaction(options.set_expires_in(Some(10)))
+            .unwrap();
+        assert_eq!(tx.get_valid_until_block(), 110);
+    }
+}
@@ -0,0 +1,167 @@
+use ethabi::Contract;
+use lazy_static::lazy_static;
+
+// The ABIs are compiled in with the feature of the system contract client using them,
+// NodeManager, QuotaManager, PriceManager and EmergencyBrake are always there
+// for the chain status of `Client`
+
+/// ABI of Admin system contract
+#[cfg(feature = "admin")]
+pub const ADMIN_ABI: &str = include_str!("../contract_abi/Admin.abi");
+/// ABI of Authorization system contract
+#[cfg(feature = "permission")]
+pub const AUTHORIZATION_ABI: &str = include_str!("../contract_abi/Authorization.abi");
+/// ABI of BatchTx system contract
+#[cfg(feature = "batch")]
+pub const BATCH_TX_ABI: &str = include_str!("../contract_abi/BatchTx.abi");
+/// ABI of ChainManager system contract
+#[cfg(feature = "chain")]
+pub const CHAIN_MANAGER_ABI: &str = include_str!("../contract_abi/ChainManager.abi");
+/// ABI of EmergencyBrake system contract
+pub const EMERGENCY_BRAKE_ABI: &str = include_str!("../contract_abi/EmergencyBrake.abi");
+/// ABI of Group system contract
+#[cfg(feature = "group")]
+pub const GROUP_ABI: &str = include_str!("../contract_abi/Group.abi");
+/// ABI of GroupManagement system contract
+#[cfg(feature = "group")]
+pub const GROUP_MANAGEMENT_ABI: &str = include_str!("../contract_abi/GroupManagement.abi");
+/// ABI of NodeManager system contract
+pub const NODE_MANAGER_ABI: &str = include_str!("../contract_abi/NodeManager.abi");
+/// ABI of Permission system contract
+#[cfg(feature = "permission")]
+pub const PERMISSION_ABI: &str = include_str!("../contract_abi/Permission.abi");
+/// ABI of PermissionManagement system contract
+#[cfg(feature = "permission")]
+pub const PERMISSION_MANAGEMENT_ABI: &str =
+    include_str!("../contract_abi/PermissionManagement.abi");
+/// ABI of PriceManager system contract
+pub const PRICE_MANAGER_ABI: &str = include_str!("../contract_abi/PriceManager.abi");
+/// ABI of QuotaManager system contract
+pub const QUOTA_MANAGER_ABI: &str = include_str!("../contract_abi/QuotaManager.abi");
+/// ABI of Role system contract
+#[cfg(feature = "role")]
+pub const ROLE_ABI: &str = include_str!("../contract_abi/Role.abi");
+/// ABI of RoleManagement system contract
+#[cfg(feature = "role")]
+pub const ROLE_MANAGEMENT_ABI: &str = include_str!("../contract_abi/RoleManagement.abi");
+/// ABI of SysConfig system contract
+#[cfg(feature = "sysconfig")]
+pub const SYS_CONFIG_ABI: &str = include_str!("../contract_abi/SysConfig.abi");
+/// ABI of VersionManager system contract
+#[cfg(feature = "sysconfig")]
+pub const VERSION_MANAGER_ABI: &str = include_str!("../contract_abi/VersionManager.abi");
+
+// `#[cfg]` inside `lazy_static!` only drops the marker type, not the `Deref` impl,
+// so every feature gets a `lazy_static!` of its own
+lazy_static! {
+    /// EmergencyBrake system contract, parsed once on first use
+    pub static ref EMERGENCY_BRAKE_CONTRACT: Contract = load(EMERGENCY_BRAKE_ABI);
+
+    /// NodeManager system contract, parsed once on first use
+    pub static ref NODE_MANAGER_CONTRACT: Contract = load(NODE_MANAGER_ABI);
+
+    /// PriceManager system contract, parsed once on first use
+    pub static ref PRICE_MANAGER_CONTRACT: Contract = load(PRICE_MANAGER_ABI);
+
+    /// QuotaManager system contract, parsed once on first use
+    pub static ref QUOTA_MANAGER_CONTRACT: Contract = load(QUOTA_MANAGER_ABI);
+}
+
+#[cfg(feature = "admin")]
+lazy_static! {
+    /// Admin system contract, parsed once on first use
+    pub static ref ADMIN_CONTRACT: Contract = load(ADMIN_ABI);
+}
+
+#[cfg(feature = "batch")]
+lazy_static! {
+    /// BatchTx system contract, parsed once on first use
+    pub static ref BATCH_TX_CONTRACT: Contract = load(BATCH_TX_ABI);
+}
+
+#[cfg(feature = "chain")]
+lazy_static! {
+    /// ChainManager system contract, parsed once on first use
+    pub static ref CHAIN_MANAGER_CONTRACT: Contract = load(CHAIN_MANAGER_ABI);
+}
+
+#[cfg(feature = "group")]
+lazy_static! {
+    /// Group system contract, parsed once on first use
+    pub static ref GROUP_CONTRACT: Contract = load(GROUP_ABI);
+
+    /// GroupManagement system contract, parsed once on first use
+    pub static ref GROUP_MANAGEMENT_CONTRACT: Contract = load(GROUP_MANAGEMENT_ABI);
+}
+
+#[cfg(feature = "permission")]
+lazy_static! {
+    /// Authorization system contract, parsed once on first use
+    pub static ref AUTHORIZATION_CONTRACT: Contract = load(AUTHORIZATION_ABI);
+
+    /// Permission system contract, parsed once on first use
+    pub static ref PERMISSION_CONTRACT: Contract = load(PERMISSION_ABI);
+
+    /// PermissionManagement system contract, parsed once on first use
+    pub static ref PERMISSION_MANAGEMENT_CONTRACT: Contract = load(PERMISSION_MANAGEMENT_ABI);
+}
+
+#[cfg(feature = "role")]
+lazy_static! {
+    /// Role system contract, parsed once on first use
+    pub static ref ROLE_CONTRACT: Contract = load(ROLE_ABI);
+
+    /// RoleManagement system contract, parsed once on first use
+    pub static ref ROLE_MANAGEMENT_CONTRACT: Contract = load(ROLE_MANAGEMENT_ABI);
+}
+
+#[cfg(feature = "sysconfig")]
+lazy_static! {
+    /// SysConfig system contract, parsed once on first use
+    pub static ref SYS_CONFIG_CONTRACT: Contract = load(SYS_CONFIG_ABI);
+
+    /// VersionManager system contract, parsed once on first use
+    pub static ref VERSION_MANAGER_CONTRACT: Contract = load(VERSION_MANAGER_ABI);
+}
+
+#[inline]
+fn load(abi: &str) -> Contract {
+    Contract::load(abi.as_bytes()).expect("embedded system contract ABI is valid")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_system_contracts() {
+        #[allow(unused_mut)]
+        let mut contracts: Vec<&Contract> = vec![
+            &*EMERGENCY_BRAKE_CONTRACT,
+            &*NODE_MANAGER_CONTRACT,
+            &*PRICE_MANAGER_CONTRACT,
+            &*QUOTA_MANAGER_CONTRACT,
+        ];
+        #[cfg(feature = "admin")]
+        contracts.push(&*ADMIN_CONTRACT);
+        #[cfg(feature = "batch")]
+        contracts.push(&*BATCH_TX_CONTRACT);
+        #[cfg(feature = "chain")]
+        contracts.push(&*CHAIN_MANAGER_CONTRACT);
+        #[cfg(feature = "group")]
+        contracts.extend_from_slice(&[&*GROUP_CONTRACT, &*GROUP_MANAGEMENT_CONTRACT]);
+        #[cfg(feature = "permission")]
+        contracts.extend_from_slice(&[
+            &*AUTHORIZATION_CONTRACT,
+            &*PERMISSION_CONTRACT,
+            &*PERMISSION_MANAGEMENT_CONTRACT,
+        ]);
+        #[cfg(feature = "role")]
+        contracts.extend_from_slice(&[&*ROLE_CONTRACT, &*ROLE_MANAGEMENT_CONTRACT]);
+        #[cfg(feature = "sysconfig")]
+        contracts.extend_from_slice(&[&*SYS_CONFIG_CONTRACT, &*VERSION_MANAGER_CONTRACT]);
+        assert!(contracts
+            .iter()
+            .all(|contract| !contract.functions.is_empty()));
+    }
+}
@@ -4,13 +4,18 @@ mod cita_sm2;
 mod crypto_trait;
 
 use hex::encode;
+use rand_core::OsRng;
 use std::fmt;
+use std::path::Path;
 use std::str::FromStr;
+use zeroize::{Zeroize, Zeroizing};
 
 pub use self::cita_ed25519::{ed25519_sign, Ed25519KeyPair, Ed25519Signature};
 pub use self::cita_secp256k1::{secp256k1_sign, Secp256k1KeyPair, Secp256k1Signature};
 pub use self::cita_sm2::{sm2_sign, Sm2KeyPair, Sm2Signature};
 pub use self::crypto_trait::{CreateKey, Error, Hashable};
+use crate::client::{remove_0x, remove_0x_validated};
+use crate::error::ToolError;
 use crate::LowerHex;
 use types::{Address, H256, H512};
 
@@ -40,16 +45,233 @@ pub fn pubkey_to_address(pubkey: &PubKey) -> Address {
     }
 }
 
+/// Generate Address from private key
+pub fn address_from_privkey(privkey: &PrivateKey) -> Address {
+    KeyPair::from_privkey(privkey).address()
+}
+
+/// Parse a secp256k1 private key from hex string, with or without "0x"
+pub fn privkey_from_str(hex: &str) -> Result<PrivateKey, ToolError> {
+    let privkey = Secp256k1PrivKey::from_str(remove_0x(hex))
+        .map_err(|err| ToolError::Customize(format!("Invalid private key: {}", err)))?;
+    Secp256k1KeyPair::from_privkey(privkey)
+        .map_err(|err| ToolError::Customize(format!("Invalid private key: {}", err)))?;
+    Ok(PrivateKey(Secret::Secp256k1(privkey)))
+}
+
+/// Convert address to EIP-55 mixed-case checksum string, with "0x"
+pub fn to_checksum_address(addr: Address) -> String {
+    let lower = format!("{:x}", addr);
+    let hash = lower.crypt_hash(Encryption::Secp256k1);
+    let checksum: String = lower
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let nibble = if index % 2 == 0 {
+                hash.0[index / 2] >> 4
+            } else {
+                hash.0[index / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksum)
+}
+
+/// Parse address from hex string, with or without "0x".
+/// If it is mixed-case, the EIP-55 checksum must match
+pub fn validate_checksum_address(addr: &str) -> Result<Address, ToolError> {
+    let content = remove_0x_validated(addr)?;
+    let address = Address::from_str(content)
+        .map_err(|err| ToolError::InvalidHex(format!("{}: {}", addr, err)))?;
+    let mixed_case = content.chars().any(|c| c.is_ascii_lowercase())
+        && content.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case {
+        let expected = to_checksum_address(address);
+        if remove_0x(&expected) != content {
+            return Err(ToolError::ChecksumMismatch {
+                provided: addr.to_string(),
+                expected,
+            });
+        }
+    }
+    Ok(address)
+}
+
+/// Default BIP44 derivation path of Ethereum-compatible chains
+#[cfg(feature = "bip39")]
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Derive a secp256k1 private key from BIP39 mnemonic with the BIP44 derivation path
+#[cfg(feature = "bip39")]
+pub fn privkey_from_mnemonic(
+    mnemonic: &str,
+    derivation_path: &str,
+) -> Result<PrivateKey, ToolError> {
+    use bip39::{Language, Mnemonic, Seed};
+    use tiny_hderive::bip32::ExtendedPrivKey;
+
+    let mnemonic = Mnemonic::from_phrase(mnemonic, Language::English)
+        .map_err(|err| ToolError::Customize(format!("Invalid mnemonic: {}", err)))?;
+    let seed = Seed::new(&mnemonic, "");
+    let extended_key =
+        ExtendedPrivKey::derive(seed.as_bytes(), derivation_path).map_err(|err| {
+            ToolError::Customize(format!(
+                "Invalid derivation path {}: {:?}",
+                derivation_path, err
+            ))
+        })?;
+    Ok(PrivateKey(Secret::Secp256k1(Secp256k1PrivKey::from(
+        extended_key.secret(),
+    ))))
+}
+
+/// Save private key to an Ethereum compatible UTC/JSON keystore file
+pub fn save_keystore(privkey: &PrivateKey, password: &str, path: &Path) -> Result<(), ToolError> {
+    if privkey.is_null() {
+        return Err(ToolError::Customize("Empty private key".to_string()));
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            ToolError::Customize(format!("Invalid keystore path: {}", path.display()))
+        })?;
+    eth_keystore::encrypt_key(
+        dir,
+        &mut OsRng,
+        privkey.expose_secret(),
+        password,
+        Some(name),
+    )
+    .map(|_| ())
+    .map_err(|err| ToolError::Customize(format!("Keystore error: {}", err)))
+}
+
+/// Load private key from an Ethereum compatible UTC/JSON keystore file
+pub fn load_keystore(
+    path: &Path,
+    password: &str,
+    encryption: Encryption,
+) -> Result<PrivateKey, ToolError> {
+    let secret = Zeroizing::new(
+        eth_keystore::decrypt_key(path, password)
+            .map_err(|err| ToolError::Customize(format!("Keystore error: {}", err)))?,
+    );
+    PrivateKey::from_slice(&secret, encryption).map_err(ToolError::Customize)
+}
+
 /// Sign data
 pub fn sign(privkey: &PrivateKey, message: &Message) -> Signature {
-    match privkey {
-        PrivateKey::Secp256k1(pk) => Signature::Secp256k1(secp256k1_sign(pk, message).unwrap()),
-        PrivateKey::Ed25519(pk) => Signature::Ed25519(ed25519_sign(pk, message).unwrap()),
-        PrivateKey::Sm2(pk) => Signature::Sm2(sm2_sign(pk, message).unwrap()),
-        PrivateKey::Null => Signature::Null,
+    match &privkey.0 {
+        Secret::Secp256k1(pk) => Signature::Secp256k1(secp256k1_sign(pk, message).unwrap()),
+        Secret::Ed25519(pk) => Signature::Ed25519(ed25519_sign(pk, message).unwrap()),
+        Secret::Sm2(pk) => Signature::Sm2(sm2_sign(pk, message).unwrap()),
+        Secret::Null => Signature::Null,
     }
 }
 
+/// Hash algorithm of the message digest of `sign_message`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HashAlgorithm {
+    /// Keccak-256
+    Keccak256,
+    /// Blake2b, keyed as the CITA hash
+    Blake2b,
+    /// Sm3
+    Sm3,
+}
+
+impl HashAlgorithm {
+    /// Digest of the message
+    pub fn digest(self, message: &[u8]) -> Message {
+        let mut digest = Message::default();
+        match self {
+            HashAlgorithm::Keccak256 => message.sha3_crypt_hash_into(&mut digest.0),
+            HashAlgorithm::Blake2b => message.blake2b_crypt_hash_into(&mut digest.0),
+            HashAlgorithm::Sm3 => message.sm3_crypt_hash_into(&mut digest.0),
+        }
+        digest
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keccak256" | "sha3" => Ok(HashAlgorithm::Keccak256),
+            "blake2b" => Ok(HashAlgorithm::Blake2b),
+            "sm3" => Ok(HashAlgorithm::Sm3),
+            _ => Err("Unsupported hash algorithm".to_string()),
+        }
+    }
+}
+
+/// Prefix of the messages signed by `sign_message`
+const SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19CITA Signed Message:\n";
+
+/// Digest of `"\x19CITA Signed Message:\n" + len(message) + message`,
+/// it never equals the hash of a transaction, so a signed message can't be replayed as one
+fn signed_message_digest(message: &[u8], algorithm: HashAlgorithm) -> Message {
+    let mut payload = SIGNED_MESSAGE_PREFIX.to_vec();
+    payload.extend_from_slice(message.len().to_string().as_bytes());
+    payload.extend_from_slice(message);
+    algorithm.digest(&payload)
+}
+
+/// Sign the digest of an arbitrary message, such as the challenge of an off-chain authentication
+pub fn sign_message(
+    message: &[u8],
+    privkey: &PrivateKey,
+    algorithm: HashAlgorithm,
+) -> Result<Signature, ToolError> {
+    let digest = signed_message_digest(message, algorithm);
+    let signature = match &privkey.0 {
+        Secret::Secp256k1(pk) => secp256k1_sign(pk, &digest).map(Signature::Secp256k1),
+        Secret::Ed25519(pk) => ed25519_sign(pk, &digest).map(Signature::Ed25519),
+        Secret::Sm2(pk) => sm2_sign(pk, &digest).map(Signature::Sm2),
+        Secret::Null => return Err(ToolError::Customize("Empty private key".to_string())),
+    };
+    signature.map_err(|err| ToolError::Customize(err.to_string()))
+}
+
+/// Verify the signature of `sign_message` is made by the account of `expected_address`
+pub fn verify_signature(
+    message: &[u8],
+    signature: &Signature,
+    expected_address: Address,
+    algorithm: HashAlgorithm,
+) -> Result<bool, ToolError> {
+    if let Signature::Null = signature {
+        return Err(ToolError::Customize("Invalid signature length".to_string()));
+    }
+    match recover_address(message, signature, algorithm) {
+        Ok(address) => Ok(address == expected_address),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Recover the address of the account signing the message with `sign_message`
+pub fn recover_address(
+    message: &[u8],
+    signature: &Signature,
+    algorithm: HashAlgorithm,
+) -> Result<Address, ToolError> {
+    signature
+        .recover(&signed_message_digest(message, algorithm))
+        .map(|pubkey| pubkey_to_address(&pubkey))
+        .map_err(ToolError::Customize)
+}
+
 /// Encryption enum
 #[derive(Clone, Copy)]
 pub enum Encryption {
@@ -96,57 +318,109 @@ impl fmt::Display for Encryption {
     }
 }
 
-/// Private key of Secp256k1/Ed25519/Sm2
-#[derive(Clone, Copy)]
-pub enum PrivateKey {
-    /// Secp256k1
+/// Private key of Secp256k1/Ed25519/Sm2.
+///
+/// It is wiped from memory on drop, `expose_secret` is the only way to read it.
+#[derive(Clone)]
+pub struct PrivateKey(Secret);
+
+#[derive(Clone)]
+enum Secret {
     Secp256k1(Secp256k1PrivKey),
-    /// Ed25519
     Ed25519(Ed25519PrivKey),
-    /// Sm2
     Sm2(Sm2Privkey),
-    /// null
     Null,
 }
 
 impl PrivateKey {
     /// Create private key
     pub fn from_str(hex: &str, encryption: Encryption) -> Result<Self, String> {
-        match encryption {
-            Encryption::Secp256k1 => Ok(PrivateKey::Secp256k1(
+        let secret = match encryption {
+            Encryption::Secp256k1 => Secret::Secp256k1(
                 Secp256k1PrivKey::from_str(hex).map_err(|err| format!("{}", err))?,
-            )),
-            Encryption::Ed25519 => Ok(PrivateKey::Ed25519(
-                Ed25519PrivKey::from_str(hex).map_err(|err| format!("{}", err))?,
-            )),
-            Encryption::Sm2 => Ok(PrivateKey::Sm2(
-                Sm2Privkey::from_str(hex).map_err(|err| format!("{}", err))?,
-            )),
+            ),
+            Encryption::Ed25519 => {
+                Secret::Ed25519(Ed25519PrivKey::from_str(hex).map_err(|err| format!("{}", err))?)
+            }
+            Encryption::Sm2 => {
+                Secret::Sm2(Sm2Privkey::from_str(hex).map_err(|err| format!("{}", err))?)
+            }
+        };
+        Ok(PrivateKey(secret))
+    }
+
+    /// Create private key from its raw bytes, without the hex string of `from_str`
+    pub fn from_slice(bytes: &[u8], encryption: Encryption) -> Result<Self, String> {
+        let len = match encryption {
+            Encryption::Secp256k1 | Encryption::Sm2 => 32,
+            Encryption::Ed25519 => 64,
+        };
+        if bytes.len() != len {
+            return Err(format!(
+                "Invalid private key length: {}, expected {}",
+                bytes.len(),
+                len
+            ));
+        }
+        let secret = match encryption {
+            Encryption::Secp256k1 => Secret::Secp256k1(Secp256k1PrivKey::from_slice(bytes)),
+            Encryption::Ed25519 => Secret::Ed25519(Ed25519PrivKey::from_slice(bytes)),
+            Encryption::Sm2 => Secret::Sm2(Sm2Privkey::from_slice(bytes)),
+        };
+        Ok(PrivateKey(secret))
+    }
+
+    /// Empty private key, nothing can be signed with it
+    pub fn null() -> Self {
+        PrivateKey(Secret::Null)
+    }
+
+    /// Whether it is the empty private key
+    pub fn is_null(&self) -> bool {
+        match self.0 {
+            Secret::Null => true,
+            _ => false,
+        }
+    }
+
+    /// Encryption algorithm of the key, `None` for the empty key
+    pub fn encryption(&self) -> Option<Encryption> {
+        match self.0 {
+            Secret::Secp256k1(_) => Some(Encryption::Secp256k1),
+            Secret::Ed25519(_) => Some(Encryption::Ed25519),
+            Secret::Sm2(_) => Some(Encryption::Sm2),
+            Secret::Null => None,
+        }
+    }
+
+    /// Get the raw bytes of private key.
+    ///
+    /// Private key doesn't implement `Display` and its `Debug` is redacted,
+    /// this is the only way to read the secret.
+    pub fn expose_secret(&self) -> &[u8] {
+        match &self.0 {
+            Secret::Secp256k1(private_key) => &private_key.0,
+            Secret::Ed25519(private_key) => &private_key.0,
+            Secret::Sm2(private_key) => &private_key.0,
+            Secret::Null => &[],
         }
     }
 }
 
-impl fmt::Debug for PrivateKey {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let msg = match *self {
-            PrivateKey::Secp256k1(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Ed25519(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Sm2(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Null => "".to_string(),
-        };
-        write!(f, "{}", msg)
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        match &mut self.0 {
+            Secret::Secp256k1(private_key) => private_key.0.zeroize(),
+            Secret::Ed25519(private_key) => private_key.0.zeroize(),
+            Secret::Sm2(private_key) => private_key.0.zeroize(),
+            Secret::Null => {}
+        }
     }
 }
 
-impl fmt::Display for PrivateKey {
+impl fmt::Debug for PrivateKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let msg = match *self {
-            PrivateKey::Secp256k1(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Ed25519(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Sm2(private_key) => encode(private_key.to_vec()),
-            PrivateKey::Null => "".to_string(),
-        };
-        write!(f, "{}", msg)
+        write!(f, "PrivateKey(***)")
     }
 }
 
@@ -223,24 +497,24 @@ impl KeyPair {
     }
 
     /// New with private key
-    pub fn from_privkey(private_key: PrivateKey) -> Self {
-        match private_key {
-            PrivateKey::Secp256k1(pk) => {
-                KeyPair::Secp256k1(Secp256k1KeyPair::from_privkey(pk).unwrap())
+    pub fn from_privkey(private_key: &PrivateKey) -> Self {
+        match &private_key.0 {
+            Secret::Secp256k1(pk) => {
+                KeyPair::Secp256k1(Secp256k1KeyPair::from_privkey(*pk).unwrap())
             }
-            PrivateKey::Ed25519(pk) => KeyPair::Ed25519(Ed25519KeyPair::from_privkey(pk).unwrap()),
-            PrivateKey::Sm2(pk) => KeyPair::Sm2(Sm2KeyPair::from_privkey(pk).unwrap()),
-            PrivateKey::Null => KeyPair::Null,
+            Secret::Ed25519(pk) => KeyPair::Ed25519(Ed25519KeyPair::from_privkey(*pk).unwrap()),
+            Secret::Sm2(pk) => KeyPair::Sm2(Sm2KeyPair::from_privkey(*pk).unwrap()),
+            Secret::Null => KeyPair::Null,
         }
     }
 
     /// Get private key
     pub fn privkey(&self) -> PrivateKey {
         match self {
-            KeyPair::Secp256k1(key_pair) => PrivateKey::Secp256k1(*key_pair.privkey()),
-            KeyPair::Ed25519(key_pair) => PrivateKey::Ed25519(*key_pair.privkey()),
-            KeyPair::Sm2(key_pair) => PrivateKey::Sm2(*key_pair.privkey()),
-            KeyPair::Null => PrivateKey::Null,
+            KeyPair::Secp256k1(key_pair) => PrivateKey(Secret::Secp256k1(*key_pair.privkey())),
+            KeyPair::Ed25519(key_pair) => PrivateKey(Secret::Ed25519(*key_pair.privkey())),
+            KeyPair::Sm2(key_pair) => PrivateKey(Secret::Sm2(*key_pair.privkey())),
+            KeyPair::Null => PrivateKey::null(),
         }
     }
 
@@ -268,17 +542,17 @@ impl KeyPair {
 impl KeyPair {
     /// New from private key
     pub fn from_str(private_key: &str, encryption: Encryption) -> Result<Self, String> {
-        match PrivateKey::from_str(private_key, encryption)? {
-            PrivateKey::Secp256k1(private) => Ok(KeyPair::Secp256k1(
-                Secp256k1KeyPair::from_privkey(private).map_err(|err| format!("{}", err))?,
+        match &PrivateKey::from_str(private_key, encryption)?.0 {
+            Secret::Secp256k1(private) => Ok(KeyPair::Secp256k1(
+                Secp256k1KeyPair::from_privkey(*private).map_err(|err| format!("{}", err))?,
             )),
-            PrivateKey::Ed25519(private) => Ok(KeyPair::Ed25519(
-                Ed25519KeyPair::from_privkey(private).map_err(|err| format!("{}", err))?,
+            Secret::Ed25519(private) => Ok(KeyPair::Ed25519(
+                Ed25519KeyPair::from_privkey(*private).map_err(|err| format!("{}", err))?,
             )),
-            PrivateKey::Sm2(private) => Ok(KeyPair::Sm2(
-                Sm2KeyPair::from_privkey(private).map_err(|err| format!("{}", err))?,
+            Secret::Sm2(private) => Ok(KeyPair::Sm2(
+                Sm2KeyPair::from_privkey(*private).map_err(|err| format!("{}", err))?,
             )),
-            PrivateKey::Null => Ok(KeyPair::Null),
+            Secret::Null => Ok(KeyPair::Null),
         }
     }
 }
@@ -366,9 +640,27 @@ impl fmt::Display for Signature {
     }
 }
 
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Signature({})", self)
+    }
+}
+
+impl Clone for Signature {
+    fn clone(&self) -> Self {
+        Signature::from(&self.to_vec())
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{Encryption, KeyPair};
+    use super::{
+        address_from_privkey, load_keystore, privkey_from_str, pubkey_to_address, recover_address,
+        save_keystore, sign_message, to_checksum_address, validate_checksum_address,
+        verify_signature, Encryption, HashAlgorithm, KeyPair, Signature,
+    };
+    use crate::protos::{Transaction, UnverifiedTransaction};
+    use protobuf::Message;
 
     #[test]
     fn secp256k1_generate_from_private_key() {
@@ -389,6 +681,120 @@ mod test {
         );
     }
 
+    #[test]
+    fn secp256k1_address_from_private_key() {
+        let privkey =
+            privkey_from_str("0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9")
+                .unwrap();
+        assert_eq!(
+            format!("{:x}", address_from_privkey(&privkey)).as_str(),
+            "eea5c3cbb32fec85bc9b9bffa65fc027e4b1c6d5"
+        );
+        assert!(privkey_from_str("0x1234").is_err());
+    }
+
+    #[cfg(feature = "bip39")]
+    #[test]
+    fn secp256k1_private_key_from_mnemonic() {
+        use super::{privkey_from_mnemonic, DEFAULT_DERIVATION_PATH};
+
+        // The BIP39 vector of all zero entropy, with an empty passphrase
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                        abandon abandon about";
+        let privkey = privkey_from_mnemonic(mnemonic, DEFAULT_DERIVATION_PATH).unwrap();
+        let expected =
+            privkey_from_str("0x1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727")
+                .unwrap();
+        assert_eq!(
+            address_from_privkey(&privkey),
+            address_from_privkey(&expected)
+        );
+        assert_eq!(
+            format!("{:x}", address_from_privkey(&privkey)).as_str(),
+            "9858effd232b4033e47d90003d41ec34ecaeda94"
+        );
+        assert!(privkey_from_mnemonic("abandon abandon", DEFAULT_DERIVATION_PATH).is_err());
+    }
+
+    #[test]
+    fn checksum_address() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let address = validate_checksum_address(checksummed).unwrap();
+        assert_eq!(to_checksum_address(address), checksummed);
+        assert!(validate_checksum_address(&checksummed.to_lowercase()).is_ok());
+        assert!(validate_checksum_address(&checksummed.to_uppercase()[2..]).is_ok());
+
+        let err =
+            validate_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Checksum mismatch: provided 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD, expected 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed, check the address for typos"
+        );
+        assert!(validate_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA").is_err());
+    }
+
+    #[test]
+    fn sign_and_verify_message() {
+        let privkey =
+            privkey_from_str("0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9")
+                .unwrap();
+        let address = address_from_privkey(&privkey);
+        for algorithm in &[HashAlgorithm::Keccak256, HashAlgorithm::Blake2b] {
+            let signature = sign_message(b"challenge", &privkey, *algorithm).unwrap();
+            let bytes = signature.to_vec();
+            assert_eq!(bytes.len(), 65);
+            let signature = Signature::from(&bytes[..]);
+            assert!(verify_signature(b"challenge", &signature, address, *algorithm).unwrap());
+            assert!(!verify_signature(b"other", &signature, address, *algorithm).unwrap());
+            assert_eq!(
+                recover_address(b"challenge", &signature, *algorithm).unwrap(),
+                address
+            );
+        }
+        let signature = sign_message(b"challenge", &privkey, HashAlgorithm::Keccak256).unwrap();
+        assert!(
+            !verify_signature(b"challenge", &signature, address, HashAlgorithm::Blake2b).unwrap()
+        );
+    }
+
+    #[test]
+    fn message_signature_is_not_transaction_signature() {
+        let privkey =
+            privkey_from_str("0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9")
+                .unwrap();
+        let mut tx = Transaction::new();
+        tx.set_nonce("challenge".to_string());
+        tx.set_quota(1_000_000);
+        tx.set_valid_until_block(100);
+        let bytes = tx.write_to_bytes().unwrap();
+
+        let signature = sign_message(&bytes, &privkey, HashAlgorithm::Keccak256).unwrap();
+        let mut unverified_tx = UnverifiedTransaction::new();
+        unverified_tx.set_transaction(tx);
+        unverified_tx.set_signature(signature.to_vec());
+        let sender = unverified_tx
+            .public_key(Encryption::Secp256k1)
+            .map(|pubkey| pubkey_to_address(&pubkey));
+        assert_ne!(sender, Ok(address_from_privkey(&privkey)));
+    }
+
+    #[test]
+    fn keystore_save_and_load() {
+        let privkey =
+            privkey_from_str("0x8ee6aa885d9598f9c4e010b659aeecfc3f113beb646166414756568ab656f0f9")
+                .unwrap();
+        let dir = std::env::temp_dir().join(format!("cita-tool-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("keystore.json");
+        save_keystore(&privkey, "password", &path).unwrap();
+        let loaded = load_keystore(&path, "password", Encryption::Secp256k1).unwrap();
+        assert_eq!(privkey.expose_secret(), loaded.expose_secret());
+        assert!(load_keystore(&path, "wrong", Encryption::Secp256k1).is_err());
+        // A 32 bytes secret isn't an Ed25519 key
+        assert!(load_keystore(&path, "password", Encryption::Ed25519).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn sm2_generate_from_private_key() {
         let key_pair = KeyPair::from_str(
@@ -244,6 +244,18 @@ impl<'a> From<&'a [u8]> for Secp256k1Signature {
     }
 }
 
+impl From<[u8; 65]> for Secp256k1Signature {
+    fn from(bytes: [u8; 65]) -> Secp256k1Signature {
+        Secp256k1Signature(bytes)
+    }
+}
+
+impl From<Secp256k1Signature> for [u8; 65] {
+    fn from(signature: Secp256k1Signature) -> [u8; 65] {
+        signature.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
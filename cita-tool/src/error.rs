@@ -1,35 +1,333 @@
-use failure::Fail;
 use hex::FromHexError;
 use hyper;
 use protobuf::error::ProtobufError;
-use serde_json;
+use serde_json::{self, error::Category};
+use std::error::Error;
+use std::fmt;
+use std::io;
 use std::num::ParseIntError;
+use types::{H160, H256};
 
 /// Error summary information
-#[derive(Debug, Fail)]
+#[derive(Debug)]
 pub enum ToolError {
     /// IO error
-    #[fail(display = "Std's io error: {}", _0)]
     Stdio(::std::io::Error),
     /// Parsing json data error
-    #[fail(display = "Serde_json error: {}", _0)]
     SerdeJson(serde_json::error::Error),
     /// Hyper error
-    #[fail(display = "Hyper error: {}", _0)]
     Hyper(hyper::Error),
     /// ABI error
-    #[fail(display = "ABI error: {}", _0)]
     Abi(String),
+    /// ABI encode error of contract method arguments
+    AbiEncode {
+        /// Method name
+        method: String,
+        /// Expected parameter types from the ABI
+        expected: Vec<String>,
+        /// Actual values provided
+        values: Vec<String>,
+        /// Why the encoding failed
+        reason: String,
+    },
+    /// ABI decode error of an output that doesn't hold the expected value
+    AbiDecode(String),
     /// Protobuf error
-    #[fail(display = "Protobuf error: {}", _0)]
     Proto(ProtobufError),
     /// Hex decode error
-    #[fail(display = "Hex decode error: {}", _0)]
     Decode(FromHexError),
     /// Parse error
-    #[fail(display = "Parse int error: {}", _0)]
     Parse(ParseIntError),
+    /// Invalid hex string
+    InvalidHex(String),
+    /// EIP-55 address checksum mismatch
+    ChecksumMismatch {
+        /// Address provided
+        provided: String,
+        /// Correctly checksummed address
+        expected: String,
+    },
+    /// Contract deployment failed on chain
+    DeployFailed {
+        /// Error message of the receipt
+        error_message: String,
+    },
+    /// Error object of JSON-RPC response
+    RpcError {
+        /// Error code
+        code: i64,
+        /// Error message
+        message: String,
+        /// Additional data, in JSON
+        data: Option<String>,
+    },
+    /// JSON-RPC response has neither result nor error
+    NullResult,
+    /// The response does not answer the request
+    IdMismatch {
+        /// Id of the request
+        sent: u64,
+        /// Id of the response
+        received: u64,
+    },
+    /// The target group is not in the scope of the origin group
+    ScopeViolation {
+        /// Origin group
+        origin: String,
+        /// Target group
+        target: String,
+    },
+    /// The pre-flight `call` of transaction reverted
+    SimulationFailed {
+        /// Revert data of the call
+        revert_data: Vec<u8>,
+    },
+    /// The estimated quota of a batch transaction exceeds the block quota limit
+    InsufficientBlockQuota {
+        /// Estimated quota of the batch
+        estimated: u64,
+        /// Block quota limit
+        limit: u64,
+    },
+    /// Too few recent blocks have transactions to base a suggestion on
+    InsufficientHistory {
+        /// Sampled blocks with any transaction
+        blocks_with_transactions: u64,
+        /// Blocks with transactions required
+        required: u64,
+    },
+    /// The circuit breaker of the url is open, the request is not sent
+    CircuitOpen {
+        /// The skipped url
+        url: String,
+    },
+    /// The signer of an admin operation is not admin
+    NotAdmin(String),
+    /// The client is shutting down, the request is not sent
+    ShuttingDown,
+    /// The chain doesn't provide the requested data
+    Unsupported(String),
+    /// The connection of a subscription is lost, it's reconnecting
+    Reconnecting,
+    /// The emergency brake is on, the chain only accepts the transactions of admin
+    EmergencyBrakeActive,
+    /// The same transaction was sent recently and may still be included in a block
+    DuplicateTransaction {
+        /// Hash of the transaction sent before
+        previous_hash: H256,
+    },
+    /// The heights of the urls diverge, the network may be partitioned
+    NetworkPartitionDetected {
+        /// Blocks between the highest and the lowest url
+        divergence: u64,
+    },
+    /// A later batch of the node approvals failed, the earlier ones were sent
+    PartialApproval {
+        /// Nodes approved by the batches sent before the failure
+        approved: Vec<H160>,
+        /// Error of the failed batch
+        error: Box<ToolError>,
+    },
     /// Customize error
-    #[fail(display = "Customize error: {}", _0)]
     Customize(String),
 }
+
+/// `{:#}` also prints the causes of the wrapped error, one per line
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ToolError::Stdio(err) => write!(f, "IO error: {}{}", err, self.hint()),
+            ToolError::SerdeJson(err) => write!(f, "JSON error: {}{}", err, self.hint()),
+            ToolError::Hyper(err) => write!(f, "HTTP error: {}{}", err, self.hint()),
+            ToolError::Abi(err) => write!(f, "ABI error: {}", err),
+            ToolError::AbiEncode { method, reason, .. } => write!(
+                f,
+                "ABI encode error: method \"{}\" {}, check the arguments against the ABI",
+                method, reason
+            ),
+            ToolError::AbiDecode(err) => write!(f, "ABI decode error: {}", err),
+            ToolError::Proto(err) => write!(f, "Protobuf error: {}", err),
+            ToolError::Decode(err) => write!(f, "Hex decode error: {}{}", err, self.hint()),
+            ToolError::Parse(err) => write!(f, "Parse int error: {}", err),
+            ToolError::InvalidHex(err) => write!(f, "Invalid hex: {}", err),
+            ToolError::ChecksumMismatch { provided, expected } => write!(
+                f,
+                "Checksum mismatch: provided {}, expected {}, check the address for typos",
+                provided, expected
+            ),
+            ToolError::DeployFailed { error_message } => write!(
+                f,
+                "Deploy failed: {}, check the constructor arguments and the quota",
+                error_message
+            ),
+            ToolError::RpcError { code, message, .. } => {
+                write!(f, "JSON-RPC error {}: {}{}", code, message, self.hint())
+            }
+            ToolError::NullResult => write!(
+                f,
+                "JSON-RPC response has null result, the queried item may not exist yet"
+            ),
+            ToolError::IdMismatch { sent, received } => write!(
+                f,
+                "Response id mismatch: sent {}, received {}, check that no proxy mixes up responses",
+                sent, received
+            ),
+            ToolError::ScopeViolation { target, origin } => write!(
+                f,
+                "Group {} is not in the scope of origin group {}, use an ancestor group as origin",
+                target, origin
+            ),
+            ToolError::SimulationFailed { .. } => write!(
+                f,
+                "Transaction simulation reverted, check the arguments and the permissions of sender, or skip the simulation"
+            ),
+            ToolError::InsufficientBlockQuota { estimated, limit } => write!(
+                f,
+                "Estimated quota {} exceeds the block quota limit {}, split the batch into smaller ones",
+                estimated, limit
+            ),
+            ToolError::InsufficientHistory {
+                blocks_with_transactions,
+                required,
+            } => write!(
+                f,
+                "Only {} of the sampled blocks have transactions, at least {} are required, sample more blocks",
+                blocks_with_transactions, required
+            ),
+            ToolError::CircuitOpen { url } => write!(
+                f,
+                "{} failed repeatedly and is skipped until the circuit breaker probes it again",
+                url
+            ),
+            ToolError::NotAdmin(signer) => write!(
+                f,
+                "{} is not admin, sign with the admin private key",
+                signer
+            ),
+            ToolError::ShuttingDown => write!(f, "Client is shutting down, the request is not sent"),
+            ToolError::Unsupported(what) => write!(f, "Unsupported: {}", what),
+            ToolError::Reconnecting => write!(f, "Connection lost, reconnecting"),
+            ToolError::EmergencyBrakeActive => write!(
+                f,
+                "Chain is under emergency stop. \
+                 Use 'cita-cli scm EmergencyBrake setState --state false' to resume operations."
+            ),
+            ToolError::DuplicateTransaction { previous_hash } => write!(
+                f,
+                "Transaction 0x{:x} with the same sender, target, data and value was sent recently, \
+                 wait for its receipt instead of sending it again",
+                previous_hash
+            ),
+            ToolError::NetworkPartitionDetected { divergence } => write!(
+                f,
+                "The heights of the urls diverge by {} blocks, the network may be partitioned, \
+                 wait for the nodes to catch up before sending transactions",
+                divergence
+            ),
+            ToolError::PartialApproval { approved, error } => write!(
+                f,
+                "Approval failed after the nodes {:?} were approved: {}",
+                approved, error
+            ),
+            ToolError::Customize(err) => write!(f, "{}", err),
+        }?;
+        if f.alternate() {
+            // The wrapped error itself is already in the message
+            let mut source = self.source().and_then(Error::source);
+            while let Some(err) = source {
+                write!(f, "\ncaused by: {}", err)?;
+                source = err.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for ToolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ToolError::Stdio(err) => Some(err),
+            ToolError::SerdeJson(err) => Some(err),
+            ToolError::Hyper(err) => Some(err),
+            ToolError::Proto(err) => Some(err),
+            ToolError::Decode(err) => Some(err),
+            ToolError::Parse(err) => Some(err),
+            ToolError::PartialApproval { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl ToolError {
+    /// What to check for the wrapped error, empty if nothing specific is known about its cause
+    fn hint(&self) -> &'static str {
+        match self {
+            ToolError::Stdio(err) => match err.kind() {
+                io::ErrorKind::NotFound => ", check that the file exists",
+                io::ErrorKind::PermissionDenied => ", check the permissions of the file",
+                _ => "",
+            },
+            ToolError::SerdeJson(err) => match err.classify() {
+                Category::Syntax | Category::Eof => ", check that the input is well-formed JSON",
+                _ => "",
+            },
+            ToolError::Hyper(err) if err.is_connect() => {
+                ", check that the node url is correct and the node is running"
+            }
+            ToolError::Decode(FromHexError::OddLength) => ", check that the value has even length",
+            ToolError::Decode(FromHexError::InvalidHexCharacter { .. }) => {
+                ", check that the value contains only 0-9 and a-f characters"
+            }
+            ToolError::RpcError { code: -32601, .. } => ", the node may not support the method",
+            ToolError::RpcError { code: -32602, .. } => ", check the params of the method",
+            _ => "",
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl ToolError {
+    /// Convert into `anyhow::Error`, the same as `?` in a function returning `anyhow::Result`
+    pub fn into_anyhow(self) -> anyhow::Error {
+        anyhow::Error::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ToolError;
+    use std::error::Error;
+    use std::io;
+
+    fn assert_std_error<E: Error + Send + Sync + 'static>() {}
+
+    #[test]
+    fn test_std_error() {
+        assert_std_error::<ToolError>();
+    }
+
+    #[test]
+    fn test_hint() {
+        let not_found = ToolError::Stdio(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert_eq!(
+            not_found.to_string(),
+            "IO error: no such file, check that the file exists"
+        );
+        let broken_pipe = ToolError::Stdio(io::Error::new(io::ErrorKind::BrokenPipe, "closed"));
+        assert_eq!(broken_pipe.to_string(), "IO error: closed");
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_into_anyhow() {
+        fn parse_height(height: &str) -> anyhow::Result<u64> {
+            Ok(u64::from_str_radix(height, 16).map_err(ToolError::Parse)?)
+        }
+        let err = parse_height("0xzz").unwrap_err();
+        assert!(err.downcast_ref::<ToolError>().is_some());
+        assert!(err.source().is_some());
+
+        let err = ToolError::Reconnecting.into_anyhow();
+        assert_eq!(err.to_string(), ToolError::Reconnecting.to_string());
+    }
+}
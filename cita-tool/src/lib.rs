@@ -5,33 +5,76 @@
 
 #[macro_use]
 extern crate serde_derive;
+// The code generated by `ContractExt` names `::cita_tool`, in this crate too
+extern crate self as cita_tool;
 
 /// Ethabi
 mod abi;
 /// The Jsonrpc Client
 pub mod client;
+/// Built-in system contract ABIs
+pub mod contract_abi;
 /// Encryption algorithm library
 pub mod crypto;
 /// Error of cita tool
 pub mod error;
+/// M-of-N signing of transactions through a multi-sig contract
+#[cfg(feature = "multisig")]
+pub mod multisig;
+/// Merkle proofs of accounts and storage, BFT proofs of blocks
+pub mod proof;
 /// Transaction protobuf code
 pub mod protos;
 /// Request and Response type
 pub mod rpctypes;
+/// JSON-RPC proxy forwarding the allowed requests to a node
+#[cfg(feature = "server")]
+pub mod server;
+/// Transaction signers, keeping the private key in memory or on a device
+pub mod signer;
+/// Addresses of the built-in system contracts and permissions
+pub mod system_addresses;
 
-pub use crate::abi::{decode_input, decode_logs, decode_params, encode_input, encode_params};
-pub use crate::client::{parse_url, remove_0x, TransactionOptions};
+pub use crate::abi::{
+    compare_abi, contract_decode_output_json, contract_encode_input, decode_input, decode_log,
+    decode_logs, decode_params, encode_input, encode_params, load_contract, token_to_json,
+    validate_onchain_abi, AbiValidation,
+};
+pub use crate::client::{
+    parse_url, remove_0x, remove_0x_validated, rpc_method_name, ContractClient,
+    ContractEventListener, FilterId, FilterManager, KnownMethod, ListenMode, NonceManager,
+    ParsedLog, RawTransaction, TransactionBuilder, TransactionDeduplicator, TransactionOptions,
+};
 pub use crate::crypto::{
-    ed25519_sign, Ed25519KeyPair, Ed25519PrivKey, Ed25519PubKey, Ed25519Signature,
+    address_from_privkey, load_keystore, privkey_from_str, pubkey_to_address, recover_address,
+    save_keystore, secp256k1_sign, sign, sign_message, sm2_sign, to_checksum_address,
+    validate_checksum_address, verify_signature, CreateKey, Encryption, HashAlgorithm, Hashable,
+    KeyPair, Message, PrivateKey, PubKey, Secp256k1KeyPair, Secp256k1PrivKey, Secp256k1PubKey,
+    Signature, Sm2KeyPair, Sm2Privkey, Sm2Pubkey, Sm2Signature,
 };
 pub use crate::crypto::{
-    pubkey_to_address, secp256k1_sign, sign, sm2_sign, CreateKey, Encryption, Hashable, KeyPair,
-    Message, PrivateKey, PubKey, Secp256k1KeyPair, Secp256k1PrivKey, Secp256k1PubKey, Signature,
-    Sm2KeyPair, Sm2Privkey, Sm2Pubkey, Sm2Signature,
+    ed25519_sign, Ed25519KeyPair, Ed25519PrivKey, Ed25519PubKey, Ed25519Signature,
 };
+#[cfg(feature = "bip39")]
+pub use crate::crypto::{privkey_from_mnemonic, DEFAULT_DERIVATION_PATH};
 pub use crate::error::ToolError;
+#[cfg(feature = "multisig")]
+pub use crate::multisig::{
+    add_signature, create_multisig_proposal, finalize_multisig, MultiSigProposal,
+};
+pub use crate::proof::{verify_block_proof, verify_proof, AccountProof, BlockProof, StorageProof};
 pub use crate::protos::{Crypto, SignedTransaction, Transaction, UnverifiedTransaction};
-pub use crate::rpctypes::{JsonRpcParams, JsonRpcResponse, ParamsValue, ResponseValue};
+pub use crate::rpctypes::{
+    contains_address, BlockHeader, BlockStats, ChainMetadata, ChainStatus, ChainVersion,
+    ConsensusHealth, Distribution, JsonRpcParams, JsonRpcResponse, JsonRpcVersion, Log, LogFilter,
+    ParamsValue, PeerInfo, ResponseValue, TransactionReceipt, UrlHealth, Version,
+};
+#[cfg(feature = "debug-api")]
+pub use crate::rpctypes::{TraceLog, TransactionTrace};
+#[cfg(feature = "server")]
+pub use crate::server::{serve, RateLimit, ServerConfig, ServerOptions};
+pub use crate::signer::{HardwareSigner, HardwareWalletBackend, InMemorySigner, Signer};
+pub use ethabi::Token;
 pub use hex::{decode, encode};
 pub use protobuf::Message as ProtoMessage;
 pub use types::{Address, H128, H160, H256, H264, H32, H512, H520, H64};
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use ethabi::{Contract, ParamType, Token};
+use protobuf::Message as MessageTrait;
+use types::Address;
+
+use crate::crypto::{pubkey_to_address, Encryption, Hashable, Signature};
+use crate::error::ToolError;
+use crate::protos::Transaction;
+use crate::signer::Signer;
+use crate::LowerHex;
+
+/// Transaction waiting for the signatures of `threshold` of the `signers`
+#[derive(Clone, Debug)]
+pub struct MultiSigProposal {
+    transaction: Transaction,
+    threshold: u32,
+    signers: Vec<Address>,
+    signatures: BTreeMap<Address, Vec<u8>>,
+}
+
+impl MultiSigProposal {
+    /// The transaction to sign
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Number of signatures needed
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Accounts allowed to sign
+    pub fn signers(&self) -> &[Address] {
+        &self.signers
+    }
+
+    /// Accounts already signed, in ascending order
+    pub fn signed(&self) -> Vec<Address> {
+        self.signatures.keys().cloned().collect()
+    }
+}
+
+/// Propose the transaction to the signers, it executes once `threshold` of them signed
+pub fn create_multisig_proposal(
+    tx: Transaction,
+    threshold: u32,
+    signers: Vec<Address>,
+) -> MultiSigProposal {
+    MultiSigProposal {
+        transaction: tx,
+        threshold,
+        signers,
+        signatures: BTreeMap::new(),
+    }
+}
+
+/// Sign the proposal, the signer is recovered from the signature
+/// and must be one of the signers of the proposal
+pub fn add_signature(
+    proposal: &mut MultiSigProposal,
+    signer: &mut dyn Signer,
+) -> Result<(), ToolError> {
+    let signature = signer.sign_transaction(&proposal.transaction)?;
+    let bytes = proposal
+        .transaction
+        .write_to_bytes()
+        .map_err(ToolError::Proto)?;
+    let sig = Signature::from(&signature);
+    let hash = match sig {
+        Signature::Secp256k1(_) => bytes.crypt_hash(Encryption::Secp256k1),
+        Signature::Ed25519(_) => bytes.crypt_hash(Encryption::Ed25519),
+        Signature::Sm2(_) => bytes.crypt_hash(Encryption::Sm2),
+        Signature::Null => {
+            return Err(ToolError::Customize(format!(
+                "Invalid signature length {}",
+                signature.len()
+            )))
+        }
+    };
+    let address = pubkey_to_address(&sig.recover(&hash).map_err(ToolError::Customize)?);
+
+    if !proposal.signers.contains(&address) {
+        return Err(ToolError::Customize(format!(
+            "{} is not a signer of the proposal",
+            address.lower_hex_with_0x()
+        )));
+    }
+    if proposal.signatures.contains_key(&address) {
+        return Err(ToolError::Customize(format!(
+            "{} has already signed the proposal",
+            address.lower_hex_with_0x()
+        )));
+    }
+    proposal.signatures.insert(address, signature);
+    Ok(())
+}
+
+/// Finalize the proposal into the input data of the method of the multi-sig contract,
+/// carrying the transaction and the signatures in ascending order of the signers
+///
+/// CITA has no multi-sig system contract, so the contract is the caller's: its ABI is given,
+/// the method must take the encoded transaction and the signatures as `(bytes, bytes[])`, and
+/// the caller sends the data to the address of the contract.
+/// Fail if fewer than `threshold` signers signed.
+pub fn finalize_multisig(
+    proposal: MultiSigProposal,
+    contract: &Contract,
+    method: &str,
+) -> Result<String, ToolError> {
+    if proposal.threshold == 0 || proposal.threshold as usize > proposal.signers.len() {
+        return Err(ToolError::Customize(format!(
+            "Threshold {} is out of 1 to {} signers",
+            proposal.threshold,
+            proposal.signers.len()
+        )));
+    }
+    if proposal.signatures.len() < proposal.threshold as usize {
+        return Err(ToolError::Customize(format!(
+            "Proposal has {} of {} signatures",
+            proposal.signatures.len(),
+            proposal.threshold
+        )));
+    }
+    let function = contract
+        .function(method)
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    let inputs = function
+        .inputs
+        .iter()
+        .map(|param| param.kind.clone())
+        .collect::<Vec<ParamType>>();
+    if inputs
+        != [
+            ParamType::Bytes,
+            ParamType::Array(Box::new(ParamType::Bytes)),
+        ]
+    {
+        return Err(ToolError::Abi(format!(
+            "{} takes {:?} instead of the transaction and the signatures (bytes, bytes[])",
+            method, inputs
+        )));
+    }
+
+    let transaction = proposal
+        .transaction
+        .write_to_bytes()
+        .map_err(ToolError::Proto)?;
+    let signatures = proposal
+        .signatures
+        .into_iter()
+        .map(|(_, signature)| Token::Bytes(signature))
+        .collect();
+    let data = function
+        .encode_input(&[Token::Bytes(transaction), Token::Array(signatures)])
+        .map_err(|e| ToolError::Abi(e.to_string()))?;
+    Ok(format!("0x{}", hex::encode(data)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_signature, create_multisig_proposal, finalize_multisig};
+    use crate::crypto::{Encryption, KeyPair};
+    use crate::protos::Transaction;
+    use crate::signer::InMemorySigner;
+    use ethabi::{Contract, ParamType, Token};
+    use protobuf::Message as MessageTrait;
+
+    /// A multi-sig wallet of the caller, `submit` checks the signatures before running
+    const WALLET_ABI: &str = r#"[{"constant":false,"inputs":[{"name":"transaction","type":"bytes"},
+        {"name":"signatures","type":"bytes[]"}],"name":"submit","outputs":[],"payable":false,
+        "stateMutability":"nonpayable","type":"function"},{"constant":false,"inputs":
+        [{"name":"transaction","type":"bytes"}],"name":"propose","outputs":[],"payable":false,
+        "stateMutability":"nonpayable","type":"function"}]"#;
+
+    #[test]
+    fn test_multisig() {
+        let key_pairs: Vec<KeyPair> = (0..3)
+            .map(|_| KeyPair::new(Encryption::Secp256k1))
+            .collect();
+        let mut tx = Transaction::new();
+        tx.set_nonce("1".to_string());
+        tx.set_quota(21_000);
+
+        let mut proposal = create_multisig_proposal(
+            tx.clone(),
+            2,
+            key_pairs.iter().map(KeyPair::address).collect(),
+        );
+        add_signature(&mut proposal, &mut InMemorySigner(key_pairs[2].privkey())).unwrap();
+        assert!(add_signature(&mut proposal, &mut InMemorySigner(key_pairs[2].privkey())).is_err());
+        let wallet = Contract::load(WALLET_ABI.as_bytes()).unwrap();
+        assert!(finalize_multisig(proposal.clone(), &wallet, "submit").is_err());
+
+        let outsider = KeyPair::new(Encryption::Secp256k1);
+        assert!(add_signature(&mut proposal, &mut InMemorySigner(outsider.privkey())).is_err());
+
+        add_signature(&mut proposal, &mut InMemorySigner(key_pairs[0].privkey())).unwrap();
+        let mut signed = vec![key_pairs[0].address(), key_pairs[2].address()];
+        signed.sort();
+        assert_eq!(proposal.signed(), signed);
+
+        assert!(finalize_multisig(proposal.clone(), &wallet, "propose").is_err());
+        assert!(finalize_multisig(proposal.clone(), &wallet, "execute").is_err());
+        let data =
+            hex::decode(&finalize_multisig(proposal, &wallet, "submit").unwrap()[2..]).unwrap();
+        let function = wallet.function("submit").unwrap();
+        assert_eq!(&data[..4], &function.short_signature()[..]);
+        let params = [
+            ParamType::Bytes,
+            ParamType::Array(Box::new(ParamType::Bytes)),
+        ];
+        match ethabi::decode(&params, &data[4..]).unwrap().as_slice() {
+            [Token::Bytes(transaction), Token::Array(signatures)] => {
+                assert_eq!(transaction, &tx.write_to_bytes().unwrap());
+                assert_eq!(signatures.len(), 2);
+            }
+            other => panic!("Unexpected tokens: {:?}", other),
+        }
+    }
+}
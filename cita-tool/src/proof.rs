@@ -0,0 +1,672 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use hex::{decode, encode};
+use types::{Address, H256, U256};
+
+use crate::client::remove_0x;
+use crate::crypto::{pubkey_to_address, Encryption, Hashable, Signature};
+use crate::error::ToolError;
+use crate::rpctypes::ParamsValue;
+
+/// Account with its Merkle proof, the result of `eth_getProof` (EIP-1186)
+#[derive(Clone, Debug)]
+pub struct AccountProof {
+    /// Address of the account
+    pub address: Address,
+    /// Balance
+    pub balance: U256,
+    /// Hash of the code
+    pub code_hash: H256,
+    /// Nonce
+    pub nonce: U256,
+    /// Root of the storage trie
+    pub storage_hash: H256,
+    /// RLP encoded trie nodes from the state root to the account
+    pub account_proof: Vec<Vec<u8>>,
+    /// Proofs of the requested storage keys
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Storage value with its Merkle proof
+#[derive(Clone, Debug)]
+pub struct StorageProof {
+    /// Storage key
+    pub key: H256,
+    /// Value at the key, zero if not set
+    pub value: U256,
+    /// RLP encoded trie nodes from the storage root to the value
+    pub proof: Vec<Vec<u8>>,
+}
+
+impl TryFrom<&ParamsValue> for AccountProof {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let map = value
+            .as_map()
+            .ok_or_else(|| ToolError::Customize(format!("{} is not an account proof", value)))?;
+        let field = |key: &str| {
+            map.get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Account proof has no {}", key)))
+        };
+
+        let address = field("address")?;
+        let storage_proof = match field("storageProof")? {
+            ParamsValue::List(proofs) => proofs
+                .iter()
+                .map(StorageProof::try_from)
+                .collect::<Result<_, _>>()?,
+            other => return Err(ToolError::Customize(format!("{} is not proofs", other))),
+        };
+        Ok(AccountProof {
+            address: address
+                .as_address()
+                .ok_or_else(|| ToolError::Customize(format!("{} is not an address", address)))?,
+            balance: field("balance")?.clone().into_u256()?,
+            code_hash: parse_hash(field("codeHash")?)?,
+            nonce: field("nonce")?.clone().into_u256()?,
+            storage_hash: parse_hash(field("storageHash")?)?,
+            account_proof: parse_nodes(field("accountProof")?)?,
+            storage_proof,
+        })
+    }
+}
+
+impl TryFrom<&ParamsValue> for StorageProof {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let map = value
+            .as_map()
+            .ok_or_else(|| ToolError::Customize(format!("{} is not a storage proof", value)))?;
+        let field = |key: &str| {
+            map.get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Storage proof has no {}", key)))
+        };
+        Ok(StorageProof {
+            key: parse_hash(field("key")?)?,
+            value: field("value")?.clone().into_u256()?,
+            proof: parse_nodes(field("proof")?)?,
+        })
+    }
+}
+
+/// Parse a hash, the storage keys may be shorter than 32 bytes
+fn parse_hash(value: &ParamsValue) -> Result<H256, ToolError> {
+    value
+        .as_str()
+        .and_then(|hash| H256::from_str(&format!("{:0>64}", remove_0x(hash))).ok())
+        .ok_or_else(|| ToolError::Customize(format!("{} is not a hash", value)))
+}
+
+fn parse_nodes(value: &ParamsValue) -> Result<Vec<Vec<u8>>, ToolError> {
+    match value {
+        ParamsValue::List(nodes) => nodes
+            .iter()
+            .map(|node| {
+                node.as_str()
+                    .and_then(|node| decode(remove_0x(node)).ok())
+                    .ok_or_else(|| ToolError::Customize(format!("{} is not a trie node", node)))
+            })
+            .collect(),
+        other => Err(ToolError::Customize(format!("{} is not trie nodes", other))),
+    }
+}
+
+/// Verify the account and all its storage values against the trusted state root
+///
+/// `Ok(false)` means the proof doesn't match the root or the values, malformed
+/// trie nodes are an error. The trie is hashed with keccak-256, as in EIP-1186.
+pub fn verify_proof(proof: &AccountProof, state_root: H256) -> Result<bool, ToolError> {
+    let account = match walk(&state_root, &proof.address, &proof.account_proof)? {
+        Walk::Mismatch => return Ok(false),
+        Walk::Absent => {
+            // An absent account has no balance, nonce or storage
+            return Ok(proof.balance.is_zero()
+                && proof.nonce.is_zero()
+                && proof
+                    .storage_proof
+                    .iter()
+                    .all(|storage| storage.value.is_zero()));
+        }
+        Walk::Value(account) => account,
+    };
+    match decode_rlp(&account)? {
+        Rlp::List(ref fields) if fields.len() == 4 => {
+            if fields[0].1 != Rlp::Bytes(&minimal_bytes(proof.nonce))
+                || fields[1].1 != Rlp::Bytes(&minimal_bytes(proof.balance))
+                || fields[2].1 != Rlp::Bytes(&proof.storage_hash)
+                || fields[3].1 != Rlp::Bytes(&proof.code_hash)
+            {
+                return Ok(false);
+            }
+        }
+        _ => return Err(ToolError::Customize("Invalid account in proof".to_string())),
+    }
+
+    for storage in &proof.storage_proof {
+        let valid = match walk(&proof.storage_hash, &storage.key, &storage.proof)? {
+            Walk::Mismatch => false,
+            Walk::Absent => storage.value.is_zero(),
+            Walk::Value(value) => {
+                decode_rlp(&value)? == Rlp::Bytes(&minimal_bytes(storage.value))
+                    && !storage.value.is_zero()
+            }
+        };
+        if !valid {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// BFT proof of a committed block, the precommit votes of the validators
+#[derive(Clone, Debug)]
+pub struct BlockProof {
+    /// Hash of the committed block, the proposal of the votes
+    pub block_hash: H256,
+    /// Height of the committed block
+    pub height: u64,
+    /// Round the block is committed in
+    pub round: u64,
+    /// Validators with their precommit signatures
+    pub signatures: Vec<(Address, Signature)>,
+}
+
+/// Parsed from the BFT proof in the header of a block of `getBlockByNumber`,
+/// which commits the previous block
+///
+/// `ToolError::Unsupported` if the block has no BFT proof, such as the genesis
+/// block or a block of another consensus.
+impl TryFrom<&ParamsValue> for BlockProof {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let proof = value
+            .as_map()
+            .and_then(|block| block.get("header"))
+            .and_then(ParamsValue::as_map)
+            .and_then(|header| header.get("proof"))
+            .and_then(ParamsValue::as_map)
+            .and_then(|proof| proof.get("Bft"))
+            .and_then(ParamsValue::as_map)
+            .ok_or_else(|| ToolError::Unsupported("the block has no BFT proof".to_string()))?;
+        let field = |key: &str| {
+            proof
+                .get(key)
+                .ok_or_else(|| ToolError::Customize(format!("BFT proof has no {}", key)))
+        };
+        let number = |key: &str| {
+            field(key)?
+                .as_u64()
+                .ok_or_else(|| ToolError::Customize(format!("BFT proof {} is not a number", key)))
+        };
+
+        let signatures = field("commits")?
+            .as_map()
+            .ok_or_else(|| ToolError::Customize("BFT proof commits is not a map".to_string()))?
+            .iter()
+            .map(|(sender, signature)| {
+                let sender = Address::from_str(remove_0x(sender))
+                    .map_err(|_| ToolError::Customize(format!("{} is not an address", sender)))?;
+                let signature = signature
+                    .as_str()
+                    .and_then(|signature| decode(remove_0x(signature)).ok())
+                    .ok_or_else(|| {
+                        ToolError::Customize(format!("{} is not a signature", signature))
+                    })?;
+                Ok((sender, Signature::from(&signature)))
+            })
+            .collect::<Result<Vec<_>, ToolError>>()?;
+        Ok(BlockProof {
+            block_hash: parse_hash(field("proposal")?)?,
+            height: number("height")?,
+            round: number("round")?,
+            signatures,
+        })
+    }
+}
+
+/// Index of `Step::Precommit` in the BFT step enum
+const PRECOMMIT_STEP: u32 = 5;
+
+/// Hash signed by a precommit vote, of the bincode encoded
+/// `(height, round, Step::Precommit, sender, Some(proposal))` as CITA-BFT does,
+/// the address and the hash are serialized as `0x` prefixed hex strings
+fn precommit_hash(proof: &BlockProof, sender: &Address, encryption: Encryption) -> H256 {
+    let string = |hex: String| {
+        let hex = format!("0x{}", hex);
+        [&(hex.len() as u64).to_le_bytes()[..], hex.as_bytes()].concat()
+    };
+    [
+        &proof.height.to_le_bytes()[..],
+        &proof.round.to_le_bytes()[..],
+        &PRECOMMIT_STEP.to_le_bytes()[..],
+        &string(encode(&sender.0[..]))[..],
+        &[1u8][..],
+        &string(encode(&proof.block_hash.0[..]))[..],
+    ]
+    .concat()
+    .crypt_hash(encryption)
+}
+
+/// Verify the block is committed by the validators: more than `threshold` of
+/// `validator_set` signed valid precommit votes, `2.0 / 3.0` for BFT
+///
+/// Signatures of unknown accounts or not recovering to their sender don't count,
+/// a validator counts once however many of its signatures there are.
+pub fn verify_block_proof(
+    proof: &BlockProof,
+    validator_set: &[Address],
+    threshold: f64,
+) -> Result<bool, ToolError> {
+    if validator_set.is_empty() {
+        return Err(ToolError::Customize("Empty validator set".to_string()));
+    }
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(ToolError::Customize(format!(
+            "Threshold {} is out of (0, 1]",
+            threshold
+        )));
+    }
+
+    let validators = validator_set.iter().collect::<HashSet<&Address>>();
+    let signers = proof
+        .signatures
+        .iter()
+        .filter(|(sender, signature)| {
+            let encryption = match signature {
+                Signature::Secp256k1(_) => Encryption::Secp256k1,
+                Signature::Ed25519(_) => Encryption::Ed25519,
+                Signature::Sm2(_) => Encryption::Sm2,
+                Signature::Null => return false,
+            };
+            validators.contains(sender)
+                && signature
+                    .recover(&precommit_hash(proof, sender, encryption))
+                    .map(|pubkey| pubkey_to_address(&pubkey) == *sender)
+                    .unwrap_or(false)
+        })
+        .map(|(sender, _)| sender)
+        .collect::<HashSet<&Address>>();
+    Ok(signers.len() as f64 > threshold * validators.len() as f64)
+}
+
+/// Big endian bytes without leading zeros, as RLP encodes integers
+fn minimal_bytes(value: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(32);
+    bytes[start..].to_vec()
+}
+
+/// Decoded RLP item
+#[derive(Debug, PartialEq)]
+enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    /// Items with their own encoding
+    List(Vec<(&'a [u8], Rlp<'a>)>),
+}
+
+/// Decode the only RLP item of the data
+fn decode_rlp(data: &[u8]) -> Result<Rlp, ToolError> {
+    let (item, len) = decode_item(data)?;
+    if len == data.len() {
+        Ok(item)
+    } else {
+        Err(ToolError::Customize(
+            "Trailing bytes after RLP item".to_string(),
+        ))
+    }
+}
+
+/// Decode the first RLP item of the data, return it with its encoded length
+fn decode_item(data: &[u8]) -> Result<(Rlp, usize), ToolError> {
+    let truncated = || ToolError::Customize("Truncated RLP item".to_string());
+    let prefix = *data.first().ok_or_else(truncated)?;
+    // (offset of the payload, length of the payload)
+    let (offset, len) = match prefix {
+        0x00..=0x7f => return Ok((Rlp::Bytes(&data[..1]), 1)),
+        0x80..=0xb7 => (1, usize::from(prefix - 0x80)),
+        0xc0..=0xf7 => (1, usize::from(prefix - 0xc0)),
+        _ => {
+            let len_of_len = usize::from(if prefix < 0xc0 {
+                prefix - 0xb7
+            } else {
+                prefix - 0xf7
+            });
+            let len_bytes = data.get(1..=len_of_len).ok_or_else(truncated)?;
+            if len_of_len > 8 {
+                return Err(ToolError::Customize("RLP item is too long".to_string()));
+            }
+            let len = len_bytes
+                .iter()
+                .fold(0usize, |len, byte| (len << 8) | usize::from(*byte));
+            (1 + len_of_len, len)
+        }
+    };
+    let payload = data
+        .get(offset..offset.checked_add(len).ok_or_else(truncated)?)
+        .ok_or_else(truncated)?;
+    if prefix < 0xc0 {
+        return Ok((Rlp::Bytes(payload), offset + len));
+    }
+    let mut items = Vec::new();
+    let mut rest = payload;
+    while !rest.is_empty() {
+        let (item, item_len) = decode_item(rest)?;
+        items.push((&rest[..item_len], item));
+        rest = &rest[item_len..];
+    }
+    Ok((Rlp::List(items), offset + len))
+}
+
+/// Result of walking a proof along a key
+enum Walk {
+    /// RLP encoded value at the key
+    Value(Vec<u8>),
+    /// The proof shows the key is not in the trie
+    Absent,
+    /// The proof doesn't match the root
+    Mismatch,
+}
+
+/// Reference to a child node
+enum NodeRef<'a> {
+    Hash(H256),
+    /// Node shorter than 32 bytes, embedded in its parent
+    Inline(&'a [u8]),
+}
+
+/// Walk the proof nodes from the root along the keccak-256 hash of the key
+fn walk<K: AsRef<[u8]>>(root: &H256, key: &K, proof: &[Vec<u8>]) -> Result<Walk, ToolError> {
+    let mut hash = [0u8; 32];
+    key.sha3_crypt_hash_into(&mut hash);
+    let nibbles = to_nibbles(&hash);
+    let mut path = &nibbles[..];
+    let mut nodes = proof.iter();
+    let mut next = NodeRef::Hash(*root);
+    loop {
+        let node: &[u8] = match next {
+            NodeRef::Hash(expected) => match nodes.next() {
+                Some(node) => {
+                    let mut hash = [0u8; 32];
+                    node.sha3_crypt_hash_into(&mut hash);
+                    if H256(hash) != expected {
+                        return Ok(Walk::Mismatch);
+                    }
+                    node
+                }
+                None => return Ok(Walk::Mismatch),
+            },
+            NodeRef::Inline(node) => node,
+        };
+        let items = match decode_rlp(node)? {
+            Rlp::List(items) => items,
+            Rlp::Bytes(_) => return Err(invalid_node()),
+        };
+        match items.len() {
+            // Branch node
+            17 => match path.split_first() {
+                None => return value_of(&items[16].1),
+                Some((nibble, rest)) => {
+                    path = rest;
+                    next = match child(&items[usize::from(*nibble)])? {
+                        Some(child) => child,
+                        None => return Ok(Walk::Absent),
+                    };
+                }
+            },
+            // Leaf or extension node
+            2 => {
+                let (is_leaf, node_path) = match items[0].1 {
+                    Rlp::Bytes(encoded) => decode_compact(encoded)?,
+                    Rlp::List(_) => return Err(invalid_node()),
+                };
+                if is_leaf {
+                    return if path == node_path.as_slice() {
+                        value_of(&items[1].1)
+                    } else {
+                        Ok(Walk::Absent)
+                    };
+                }
+                if !path.starts_with(&node_path) {
+                    return Ok(Walk::Absent);
+                }
+                path = &path[node_path.len()..];
+                next = child(&items[1])?.ok_or_else(invalid_node)?;
+            }
+            _ => return Err(invalid_node()),
+        }
+    }
+}
+
+fn invalid_node() -> ToolError {
+    ToolError::Customize("Invalid trie node in proof".to_string())
+}
+
+fn value_of(item: &Rlp) -> Result<Walk, ToolError> {
+    match item {
+        Rlp::Bytes(value) if value.is_empty() => Ok(Walk::Absent),
+        Rlp::Bytes(value) => Ok(Walk::Value(value.to_vec())),
+        Rlp::List(_) => Err(invalid_node()),
+    }
+}
+
+fn child<'a>(item: &(&'a [u8], Rlp<'a>)) -> Result<Option<NodeRef<'a>>, ToolError> {
+    match *item {
+        (_, Rlp::Bytes(hash)) if hash.is_empty() => Ok(None),
+        (_, Rlp::Bytes(hash)) if hash.len() == 32 => {
+            Ok(Some(NodeRef::Hash(H256::from_slice(hash))))
+        }
+        (encoded, Rlp::List(_)) if encoded.len() < 32 => Ok(Some(NodeRef::Inline(encoded))),
+        _ => Err(invalid_node()),
+    }
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+/// Decode the hex-prefix encoded path of a leaf or extension node,
+/// return whether it's a leaf with the nibbles of the path
+fn decode_compact(encoded: &[u8]) -> Result<(bool, Vec<u8>), ToolError> {
+    let flag = encoded.first().ok_or_else(invalid_node)? >> 4;
+    if flag > 3 {
+        return Err(invalid_node());
+    }
+    let nibbles = to_nibbles(encoded);
+    // An odd path has its first nibble in the flag byte
+    let start = if flag & 1 == 1 { 1 } else { 2 };
+    Ok((flag & 2 == 2, nibbles[start..].to_vec()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        decode_rlp, precommit_hash, verify_block_proof, verify_proof, AccountProof, BlockProof,
+        Rlp, StorageProof,
+    };
+    use crate::crypto::{sign, Encryption, Hashable, KeyPair};
+    use crate::rpctypes::ParamsValue;
+    use std::convert::TryFrom;
+    use types::{Address, H256, U256};
+
+    fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+        match bytes.len() {
+            1 if bytes[0] < 0x80 => bytes.to_vec(),
+            len if len < 56 => [&[0x80 + len as u8][..], bytes].concat(),
+            len => [&[0xb8, len as u8][..], bytes].concat(),
+        }
+    }
+
+    fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        match payload.len() {
+            len if len < 56 => [&[0xc0 + len as u8][..], &payload].concat(),
+            len => [&[0xf8, len as u8][..], &payload].concat(),
+        }
+    }
+
+    fn keccak<T: AsRef<[u8]>>(data: T) -> H256 {
+        let mut hash = [0u8; 32];
+        data.sha3_crypt_hash_into(&mut hash);
+        H256(hash)
+    }
+
+    /// Leaf node of the remaining nibbles, in hex-prefix encoding
+    fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut path = if nibbles.len() % 2 == 1 {
+            vec![0x30 | nibbles[0]]
+        } else {
+            vec![0x20]
+        };
+        let even = &nibbles[nibbles.len() % 2..];
+        path.extend(even.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        rlp_list(&[rlp_bytes(&path), rlp_bytes(value)])
+    }
+
+    fn nibbles(hash: &H256) -> Vec<u8> {
+        super::to_nibbles(&hash[..])
+    }
+
+    #[test]
+    fn test_decode_rlp() {
+        assert_eq!(decode_rlp(&[0x05]).unwrap(), Rlp::Bytes(&[0x05]));
+        assert_eq!(decode_rlp(&[0x80]).unwrap(), Rlp::Bytes(&[]));
+        assert_eq!(
+            decode_rlp(&[0xc3, 0x01, 0x82, 0x02, 0x03]).unwrap(),
+            Rlp::List(vec![
+                (&[0x01][..], Rlp::Bytes(&[0x01])),
+                (&[0x82, 0x02, 0x03][..], Rlp::Bytes(&[0x02, 0x03]))
+            ])
+        );
+        assert!(decode_rlp(&[0x82, 0x02]).is_err());
+        assert!(decode_rlp(&[0x01, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let address = Address::from(0xff);
+        let key_0 = H256::from(0);
+        let key_1 = H256::from(1);
+
+        // Storage trie of two values, a branch node with two leaves
+        let value_leaf =
+            |key: &H256, value: u8| leaf(&nibbles(&keccak(key))[1..], &rlp_bytes(&[value]));
+        let leaf_0 = value_leaf(&key_0, 7);
+        let leaf_1 = value_leaf(&key_1, 9);
+        let mut branch = vec![rlp_bytes(&[]); 17];
+        branch[usize::from(nibbles(&keccak(&key_0))[0])] = rlp_bytes(&keccak(&leaf_0));
+        branch[usize::from(nibbles(&keccak(&key_1))[0])] = rlp_bytes(&keccak(&leaf_1));
+        let branch = rlp_list(&branch);
+        let storage_hash = keccak(&branch);
+
+        // State trie of the only account
+        let code_hash = keccak(&[]);
+        let account = rlp_list(&[
+            rlp_bytes(&[1]),
+            rlp_bytes(&[0x03, 0xe8]),
+            rlp_bytes(&storage_hash),
+            rlp_bytes(&code_hash),
+        ]);
+        let account_leaf = leaf(&nibbles(&keccak(&address)), &account);
+        let state_root = keccak(&account_leaf);
+
+        let mut proof = AccountProof {
+            address,
+            balance: U256::from(1000),
+            code_hash,
+            nonce: U256::from(1),
+            storage_hash,
+            account_proof: vec![account_leaf],
+            storage_proof: vec![
+                StorageProof {
+                    key: key_0,
+                    value: U256::from(7),
+                    proof: vec![branch.clone(), leaf_0],
+                },
+                StorageProof {
+                    key: key_1,
+                    value: U256::from(9),
+                    proof: vec![branch.clone(), leaf_1],
+                },
+                // Proof of absence ends at the empty slot of the branch, the keccak-256
+                // hashes of the keys 0, 1 and 2 start with the nibbles 2, b and 4
+                StorageProof {
+                    key: H256::from(2),
+                    value: U256::zero(),
+                    proof: vec![branch],
+                },
+            ],
+        };
+        assert!(verify_proof(&proof, state_root).unwrap());
+        assert!(!verify_proof(&proof, H256::from(1)).unwrap());
+
+        proof.storage_proof[1].value = U256::from(8);
+        assert!(!verify_proof(&proof, state_root).unwrap());
+        proof.storage_proof[1].value = U256::from(9);
+        proof.balance = U256::from(1001);
+        assert!(!verify_proof(&proof, state_root).unwrap());
+    }
+
+    #[test]
+    fn test_block_proof() {
+        let block: ParamsValue = serde_json::from_str(
+            r#"{
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "header": {
+                    "number": "0x2",
+                    "proof": {"Bft": {
+                        "proposal": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        "height": 1,
+                        "round": 0,
+                        "commits": {}
+                    }}
+                }
+            }"#,
+        )
+        .unwrap();
+        let mut proof = BlockProof::try_from(&block).unwrap();
+        assert_eq!(proof.height, 1);
+        assert_eq!(proof.block_hash, H256::from(1));
+
+        let validators: Vec<KeyPair> = (0..4)
+            .map(|_| KeyPair::new(Encryption::Secp256k1))
+            .collect();
+        let validator_set: Vec<Address> = validators.iter().map(KeyPair::address).collect();
+        let outsider = KeyPair::new(Encryption::Secp256k1);
+        for key_pair in validators[..2].iter().chain(Some(&outsider)) {
+            let hash = precommit_hash(&proof, &key_pair.address(), Encryption::Secp256k1);
+            proof
+                .signatures
+                .push((key_pair.address(), sign(&key_pair.privkey(), &hash)));
+        }
+        // The outsider doesn't count
+        assert!(!verify_block_proof(&proof, &validator_set, 2.0 / 3.0).unwrap());
+        assert!(verify_block_proof(&proof, &validator_set, 0.4).unwrap());
+
+        // Repeated signatures of a validator count once
+        let repeated = proof.signatures[0].clone();
+        proof.signatures.push(repeated.clone());
+        proof.signatures.push(repeated);
+        assert!(!verify_block_proof(&proof, &validator_set, 2.0 / 3.0).unwrap());
+
+        let hash = precommit_hash(&proof, &validator_set[2], Encryption::Secp256k1);
+        proof
+            .signatures
+            .push((validator_set[2], sign(&validators[2].privkey(), &hash)));
+        assert!(verify_block_proof(&proof, &validator_set, 2.0 / 3.0).unwrap());
+
+        // The votes are of another round
+        proof.round = 1;
+        assert!(!verify_block_proof(&proof, &validator_set, 0.1).unwrap());
+        assert!(verify_block_proof(&proof, &[], 2.0 / 3.0).is_err());
+        assert!(verify_block_proof(&proof, &validator_set, 1.5).is_err());
+    }
+}
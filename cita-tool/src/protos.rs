@@ -4,9 +4,8 @@ pub mod blockchain;
 pub use self::blockchain::{Crypto, SignedTransaction, Transaction, UnverifiedTransaction};
 use crate::client::remove_0x;
 use crate::crypto::PubKey;
-use crate::crypto::{
-    pubkey_to_address, sign, Encryption, Hashable, KeyPair, PrivateKey, Signature,
-};
+use crate::crypto::{pubkey_to_address, Encryption, Hashable, KeyPair, PrivateKey, Signature};
+use crate::signer::{InMemorySigner, Signer};
 use crate::LowerHex;
 use hex;
 use protobuf::Message as MessageTrait;
@@ -77,8 +76,9 @@ impl FromStr for UnverifiedTransaction {
 impl Transaction {
     /// Sign data
     pub fn sign(&self, sk: PrivateKey) -> SignedTransaction {
-        let key_pair = KeyPair::from_privkey(sk);
+        let key_pair = KeyPair::from_privkey(&sk);
         let pubkey = key_pair.pubkey();
+        let encryption = sk.encryption();
 
         let unverified_tx = self.build_unverified(sk);
 
@@ -87,11 +87,9 @@ impl Transaction {
         signed_tx.set_signer(pubkey.to_vec());
         let bytes: Vec<u8> = (&unverified_tx).write_to_bytes().unwrap();
 
-        let hash = match sk {
-            PrivateKey::Secp256k1(_) => bytes.crypt_hash(Encryption::Secp256k1),
-            PrivateKey::Ed25519(_) => bytes.crypt_hash(Encryption::Ed25519),
-            PrivateKey::Sm2(_) => bytes.crypt_hash(Encryption::Sm2),
-            PrivateKey::Null => H256::default(),
+        let hash = match encryption {
+            Some(encryption) => bytes.crypt_hash(encryption),
+            None => H256::default(),
         };
 
         signed_tx.set_tx_hash(hash.to_vec());
@@ -101,21 +99,20 @@ impl Transaction {
 
     /// Build unverified transaction
     pub fn build_unverified(&self, sk: PrivateKey) -> UnverifiedTransaction {
-        let mut unverified_tx = UnverifiedTransaction::new();
-        let bytes: Vec<u8> = self.write_to_bytes().unwrap();
-
-        let hash = match sk {
-            PrivateKey::Secp256k1(_) => bytes.crypt_hash(Encryption::Secp256k1),
-            PrivateKey::Ed25519(_) => bytes.crypt_hash(Encryption::Ed25519),
-            PrivateKey::Sm2(_) => bytes.crypt_hash(Encryption::Sm2),
-            PrivateKey::Null => H256::default(),
-        };
+        self.build_with_signer(&mut InMemorySigner(sk)).unwrap()
+    }
 
+    /// Build unverified transaction signed by the signer,
+    /// the private key may be kept out of the process
+    pub fn build_with_signer(
+        &self,
+        signer: &mut dyn Signer,
+    ) -> Result<UnverifiedTransaction, ToolError> {
+        let mut unverified_tx = UnverifiedTransaction::new();
+        unverified_tx.set_signature(signer.sign_transaction(self)?);
         unverified_tx.set_transaction(self.clone());
-        let signature = sign(&sk, &hash);
-        unverified_tx.set_signature(signature.to_vec());
         unverified_tx.set_crypto(Crypto::DEFAULT);
-        unverified_tx
+        Ok(unverified_tx)
     }
 }
 
@@ -1,9 +1,21 @@
-use std::{collections::HashMap, convert::Into, default::Default, fmt};
+use std::{
+    collections::BTreeMap, collections::HashMap, convert::Into, convert::TryFrom, default::Default,
+    fmt, str::FromStr, time::Duration,
+};
 
+use ethabi::Address as AbiAddress;
+use hex::decode;
+use serde::Serializer;
 use serde_json::{self, json};
+use types::{Address, H256, U256};
+
+use crate::client::{remove_0x, remove_0x_validated};
+use crate::crypto::Hashable;
+use crate::error::ToolError;
+use crate::LowerHex;
 
 /// JsonRpc params
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct JsonRpcParams {
     #[serde(flatten)]
     extra: HashMap<String, ParamsValue>,
@@ -30,6 +42,56 @@ impl JsonRpcParams {
     pub fn get<T: Into<String>>(&self, key: T) -> Option<&ParamsValue> {
         self.extra.get(&key.into())
     }
+
+    /// Format the request in the version, 1.0 has no `jsonrpc` key
+    pub fn json_rpc_version(mut self, version: JsonRpcVersion) -> Self {
+        match version {
+            JsonRpcVersion::V1 => {
+                self.extra.remove("jsonrpc");
+                self
+            }
+            JsonRpcVersion::V2 => self.insert("jsonrpc", ParamsValue::String("2.0".to_string())),
+        }
+    }
+}
+
+/// Version of the JSON-RPC format, 1.0 for the old nodes not expecting `"jsonrpc":"2.0"`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonRpcVersion {
+    /// JSON-RPC 1.0
+    V1,
+    /// JSON-RPC 2.0
+    V2,
+}
+
+impl Default for JsonRpcVersion {
+    fn default() -> Self {
+        JsonRpcVersion::V2
+    }
+}
+
+impl FromStr for JsonRpcVersion {
+    type Err = ToolError;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        match version {
+            "1.0" | "1" => Ok(JsonRpcVersion::V1),
+            "2.0" | "2" => Ok(JsonRpcVersion::V2),
+            _ => Err(ToolError::Customize(format!(
+                "Invalid JSON-RPC version {}, expected 1.0 or 2.0",
+                version
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for JsonRpcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            JsonRpcVersion::V1 => write!(f, "1.0"),
+            JsonRpcVersion::V2 => write!(f, "2.0"),
+        }
+    }
 }
 
 impl Default for JsonRpcParams {
@@ -56,11 +118,21 @@ impl fmt::Display for JsonRpcParams {
 }
 
 /// The params value of jsonrpc params
-#[derive(Clone, Deserialize, Serialize)]
+///
+/// It is untagged and survives the JSON round trip, but only integers within `u64`
+/// can be deserialized, negative numbers and floats fail. Addresses are deserialized
+/// as strings.
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum ParamsValue {
     /// Single string parameter
     String(String),
+    /// Address parameter, serialized as a "0x" prefixed lowercase hex string
+    #[serde(serialize_with = "serialize_address", skip_deserializing)]
+    Address(AbiAddress),
+    /// Large integer parameter, serialized as a "0x" prefixed 64 characters hex string
+    #[serde(serialize_with = "serialize_u256", skip_deserializing)]
+    U256(U256),
     /// Singe int parameter
     Int(u64),
     /// Multiple parameters
@@ -73,6 +145,92 @@ pub enum ParamsValue {
     Null,
 }
 
+fn serialize_address<S: Serializer>(
+    address: &AbiAddress,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{:x}", address))
+}
+
+fn serialize_u256<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.completed_lower_hex_with_0x())
+}
+
+impl ParamsValue {
+    /// Large integer parameter
+    pub fn from_u256(value: U256) -> Self {
+        ParamsValue::U256(value)
+    }
+
+    /// Address parameter of the hex string, with or without "0x",
+    /// fails if it's not 20 bytes of hex
+    pub fn address(address: &str) -> Result<Self, ToolError> {
+        AbiAddress::from_str(remove_0x_validated(address)?)
+            .map(ParamsValue::Address)
+            .map_err(|err| ToolError::InvalidHex(format!("{}: {}", address, err)))
+    }
+
+    /// Get the map of values
+    pub fn as_map(&self) -> Option<&HashMap<String, ParamsValue>> {
+        match self {
+            ParamsValue::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Get the string value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ParamsValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Get the int value, string will be parsed as hex with `0x` prefix, decimal without
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ParamsValue::Int(value) => Some(*value),
+            ParamsValue::String(value) => {
+                if value.starts_with("0x") || value.starts_with("0X") {
+                    u64::from_str_radix(&value[2..], 16).ok()
+                } else {
+                    value.parse().ok()
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the address value, hex string will be parsed
+    pub fn as_address(&self) -> Option<Address> {
+        match self {
+            ParamsValue::Address(address) => Some(Address::from(address.0)),
+            value => value
+                .as_str()
+                .and_then(|value| Address::from_str(remove_0x(value)).ok()),
+        }
+    }
+
+    /// Convert into string
+    pub fn into_string(self) -> Result<String, ToolError> {
+        match self {
+            ParamsValue::String(value) => Ok(value),
+            other => Err(ToolError::Customize(format!("{} is not a string", other))),
+        }
+    }
+
+    /// Convert into u256, hex string will be parsed
+    pub fn into_u256(self) -> Result<U256, ToolError> {
+        match self {
+            ParamsValue::U256(value) => Ok(value),
+            ParamsValue::Int(value) => Ok(U256::from(value)),
+            ParamsValue::String(value) => U256::from_str(remove_0x(&value))
+                .map_err(|_| ToolError::Customize(format!("{} can't parse into u256", value))),
+            other => Err(ToolError::Customize(format!("{} is not a u256", other))),
+        }
+    }
+}
+
 impl fmt::Debug for ParamsValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{}", serde_json::to_string_pretty(self).unwrap())
@@ -95,6 +253,56 @@ pub enum ResponseValue {
     Singe(ParamsValue),
 }
 
+impl ResponseValue {
+    /// Get the map result
+    pub fn as_map(&self) -> Option<&HashMap<String, ParamsValue>> {
+        match self {
+            ResponseValue::Map(map) => Some(map),
+            ResponseValue::Singe(value) => value.as_map(),
+        }
+    }
+
+    /// Get the string result
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ResponseValue::Singe(value) => value.as_str(),
+            _ => None,
+        }
+    }
+
+    /// Get the int result, string will be parsed as hex with `0x` prefix, decimal without
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ResponseValue::Singe(value) => value.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// Get the address result
+    pub fn as_address(&self) -> Option<Address> {
+        match self {
+            ResponseValue::Singe(value) => value.as_address(),
+            _ => None,
+        }
+    }
+
+    /// Convert into string
+    pub fn into_string(self) -> Result<String, ToolError> {
+        match self {
+            ResponseValue::Singe(value) => value.into_string(),
+            other => Err(ToolError::Customize(format!("{} is not a string", other))),
+        }
+    }
+
+    /// Convert into u256, hex string will be parsed
+    pub fn into_u256(self) -> Result<U256, ToolError> {
+        match self {
+            ResponseValue::Singe(value) => value.into_u256(),
+            other => Err(ToolError::Customize(format!("{} is not a u256", other))),
+        }
+    }
+}
+
 impl fmt::Debug for ResponseValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{}", serde_json::to_string_pretty(self).unwrap())
@@ -110,15 +318,30 @@ impl fmt::Display for ResponseValue {
 /// Jsonrpc response
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct JsonRpcResponse {
+    // Empty in the responses of JSON-RPC 1.0
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     jsonrpc: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     result: Option<ResponseValue>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_error",
+        skip_serializing_if = "Option::is_none"
+    )]
     error: Option<ErrorResponse>,
     id: u64,
 }
 
 impl JsonRpcResponse {
+    /// A response of the result, not received from a node
+    pub(crate) fn from_result(result: ResponseValue) -> Self {
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(result),
+            ..Default::default()
+        }
+    }
+
     /// Get result
     pub fn result(&self) -> Option<ResponseValue> {
         self.result.clone()
@@ -133,6 +356,24 @@ impl JsonRpcResponse {
     pub fn is_ok(&self) -> bool {
         self.result.is_some()
     }
+
+    /// Get id
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Take the result, the error object is returned as `ToolError::RpcError`
+    pub fn into_result(self) -> Result<ResponseValue, ToolError> {
+        match (self.error, self.result) {
+            (Some(err), _) => Err(ToolError::RpcError {
+                code: err.code,
+                message: err.message,
+                data: err.data.map(|data| data.to_string()),
+            }),
+            (None, Some(result)) => Ok(result),
+            (None, None) => Err(ToolError::NullResult),
+        }
+    }
 }
 
 impl fmt::Debug for JsonRpcResponse {
@@ -147,6 +388,843 @@ impl fmt::Display for JsonRpcResponse {
     }
 }
 
+impl JsonRpcResponse {
+    /// Take the hex string of the result, for example the return data of `call`
+    pub(crate) fn into_hex_result(self) -> Result<String, ToolError> {
+        match self.into_result()? {
+            ResponseValue::Singe(ParamsValue::String(hex)) => Ok(remove_0x(&hex).to_string()),
+            result => Err(ToolError::Customize(format!(
+                "Not a hex string result: {}",
+                result
+            ))),
+        }
+    }
+}
+
+impl TryFrom<JsonRpcResponse> for U256 {
+    type Error = ToolError;
+
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let hex = response.into_hex_result()?;
+        if hex.len() > 64 {
+            return Err(ToolError::Customize(format!(
+                "Value 0x{} overflows u256",
+                hex
+            )));
+        }
+        if hex.is_empty() {
+            return Ok(U256::zero());
+        }
+        hex.parse::<U256>()
+            .map_err(|err| ToolError::Customize(format!("{:?}", err)))
+    }
+}
+
+impl TryFrom<JsonRpcResponse> for u64 {
+    type Error = ToolError;
+
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let value = U256::try_from(response)?;
+        if value > U256::from(u64::max_value()) {
+            Err(ToolError::Customize(format!(
+                "Value {} overflows u64",
+                value
+            )))
+        } else {
+            Ok(value.low_u64())
+        }
+    }
+}
+
+impl TryFrom<JsonRpcResponse> for bool {
+    type Error = ToolError;
+
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let value = U256::try_from(response)?;
+        if value.is_zero() {
+            Ok(false)
+        } else if value == U256::one() {
+            Ok(true)
+        } else {
+            Err(ToolError::Customize(format!(
+                "Value {} is not a bool",
+                value
+            )))
+        }
+    }
+}
+
+impl TryFrom<JsonRpcResponse> for Address {
+    type Error = ToolError;
+
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let hex = response.into_hex_result()?;
+        // Abi encoded address is left padded to 32 bytes
+        let hex = if hex.len() == 64 { &hex[24..] } else { &hex };
+        if hex.len() != 40 {
+            return Err(ToolError::Customize(format!(
+                "Value 0x{} is not an address",
+                hex
+            )));
+        }
+        Ok(Address::from(
+            decode(hex).map_err(ToolError::Decode)?.as_slice(),
+        ))
+    }
+}
+
+impl TryFrom<JsonRpcResponse> for Vec<Address> {
+    type Error = ToolError;
+
+    /// Decode abi encoded `address[]`: offset, length, then the items
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let data = decode(response.into_hex_result()?).map_err(ToolError::Decode)?;
+        let word = |index: usize| -> Result<&[u8], ToolError> {
+            data.get(index * 32..(index + 1) * 32)
+                .ok_or_else(|| ToolError::Customize("Insufficient data for address[]".to_string()))
+        };
+        let words = data.len() / 32;
+        // The offset and the length are checked against the data before anything is indexed
+        // or allocated, so a malformed response can not overflow or allocate too much
+        let index = |value: U256, limit: usize| -> Result<usize, ToolError> {
+            if value > U256::from(limit) {
+                Err(ToolError::Customize(format!(
+                    "{} exceeds the {} words of address[] data",
+                    value, words
+                )))
+            } else {
+                Ok(value.low_u64() as usize)
+            }
+        };
+        let offset = index(U256::from(word(0)?) / U256::from(32), words)?;
+        let length = index(U256::from(word(offset)?), words)?;
+        let end = offset
+            .checked_add(1)
+            .and_then(|start| start.checked_add(length))
+            .filter(|end| *end <= words)
+            .ok_or_else(|| ToolError::Customize("Insufficient data for address[]".to_string()))?;
+        let mut addresses = Vec::with_capacity(length);
+        for index in offset + 1..end {
+            addresses.push(Address::from(&word(index)?[12..]));
+        }
+        Ok(addresses)
+    }
+}
+
+/// Filter of `getLogs`, `None` means no restriction,
+/// the default block range is "latest"
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    /// Start block number
+    pub from_block: Option<u64>,
+    /// End block number
+    pub to_block: Option<u64>,
+    /// Contract addresses that emit the logs
+    pub address: Option<Vec<Address>>,
+    /// Topics by position, `None` matches any topic at that position
+    pub topics: Option<Vec<Option<H256>>>,
+}
+
+impl LogFilter {
+    /// No restriction, the block range is "latest"
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set start block number
+    pub fn set_from_block(mut self, from_block: Option<u64>) -> Self {
+        self.from_block = from_block;
+        self
+    }
+
+    /// Set end block number
+    pub fn set_to_block(mut self, to_block: Option<u64>) -> Self {
+        self.to_block = to_block;
+        self
+    }
+
+    /// Set contract addresses that emit the logs
+    pub fn set_address(mut self, address: Option<Vec<Address>>) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Set topics by position
+    pub fn set_topics(mut self, topics: Option<Vec<Option<H256>>>) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    pub(crate) fn into_params(self) -> ParamsValue {
+        let block = |number: Option<u64>| {
+            ParamsValue::String(
+                number
+                    .map(|number| format!("{:#x}", number))
+                    .unwrap_or_else(|| "latest".to_string()),
+            )
+        };
+        let mut object = HashMap::new();
+        object.insert(String::from("fromBlock"), block(self.from_block));
+        object.insert(String::from("toBlock"), block(self.to_block));
+        object.insert(
+            String::from("topics"),
+            ParamsValue::List(
+                self.topics
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|topic| match topic {
+                        Some(topic) => ParamsValue::String(format!("0x{:x}", topic)),
+                        None => ParamsValue::Null,
+                    })
+                    .collect(),
+            ),
+        );
+        if let Some(address) = self.address {
+            object.insert(
+                String::from("address"),
+                ParamsValue::List(
+                    address
+                        .into_iter()
+                        .map(|address| ParamsValue::Address(AbiAddress::from(address.0)))
+                        .collect(),
+                ),
+            );
+        }
+        ParamsValue::Map(object)
+    }
+}
+
+/// Log emitted by contract
+#[derive(Clone, Debug)]
+pub struct Log {
+    /// Contract address
+    pub address: Address,
+    /// Indexed topics
+    pub topics: Vec<H256>,
+    /// ABI encoded non-indexed arguments
+    pub data: Vec<u8>,
+    /// Hash of the transaction
+    pub transaction_hash: H256,
+    /// Block number
+    pub block_number: u64,
+    /// Index in the block
+    pub log_index: u64,
+}
+
+impl TryFrom<&ParamsValue> for Log {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let map = value
+            .as_map()
+            .ok_or_else(|| ToolError::Customize(format!("{} is not a log", value)))?;
+        let field = |key: &str| {
+            map.get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Log has no {}", key)))
+        };
+        let hash = |value: &ParamsValue| {
+            value
+                .as_str()
+                .and_then(|hash| H256::from_str(remove_0x(hash)).ok())
+                .ok_or_else(|| ToolError::Customize(format!("{} is not a hash", value)))
+        };
+        let number = |key: &str| {
+            field(key)?
+                .as_u64()
+                .ok_or_else(|| ToolError::Customize(format!("Log {} is not a number", key)))
+        };
+
+        let address = field("address")?;
+        let topics = match field("topics")? {
+            ParamsValue::List(topics) => topics.iter().map(hash).collect::<Result<_, _>>()?,
+            other => return Err(ToolError::Customize(format!("{} is not topics", other))),
+        };
+        Ok(Log {
+            address: address
+                .as_address()
+                .ok_or_else(|| ToolError::Customize(format!("{} is not an address", address)))?,
+            topics,
+            data: decode(remove_0x(&field("data")?.clone().into_string()?))
+                .map_err(ToolError::Decode)?,
+            transaction_hash: hash(field("transactionHash")?)?,
+            block_number: number("blockNumber")?,
+            log_index: number("logIndex")?,
+        })
+    }
+}
+
+/// Receipt of `getTransactionReceipt`
+#[derive(Clone, Debug)]
+pub struct TransactionReceipt {
+    /// Hash of the transaction
+    pub transaction_hash: H256,
+    /// Index in the block
+    pub transaction_index: u64,
+    /// Hash of the block
+    pub block_hash: H256,
+    /// Block number
+    pub block_number: u64,
+    /// Quota used by the transactions of the block up to this one
+    pub cumulative_quota_used: U256,
+    /// Quota used by the transaction
+    pub quota_used: U256,
+    /// Address of the contract created by the transaction
+    pub contract_address: Option<Address>,
+    /// Logs emitted
+    pub logs: Vec<Log>,
+    /// Bloom filter of the log addresses and topics, only in CITA 2.0 and later
+    pub logs_bloom: Option<[u8; 256]>,
+    /// Error message of a failed transaction
+    pub error_message: Option<String>,
+}
+
+impl TryFrom<&ParamsValue> for TransactionReceipt {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let map = value
+            .as_map()
+            .ok_or_else(|| ToolError::Customize(format!("{} is not a receipt", value)))?;
+        let field = |key: &str| {
+            map.get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Receipt has no {}", key)))
+        };
+        let hash = |key: &str| {
+            let value = field(key)?;
+            value
+                .as_str()
+                .and_then(|hash| H256::from_str(remove_0x(hash)).ok())
+                .ok_or_else(|| ToolError::Customize(format!("{} is not a hash", value)))
+        };
+        let number = |key: &str| {
+            field(key)?
+                .as_u64()
+                .ok_or_else(|| ToolError::Customize(format!("Receipt {} is not a number", key)))
+        };
+        let present = |key: &str| match map.get(key) {
+            None | Some(ParamsValue::Null) => None,
+            Some(value) => Some(value),
+        };
+
+        let logs = match field("logs")? {
+            ParamsValue::List(logs) => logs.iter().map(Log::try_from).collect::<Result<_, _>>()?,
+            other => return Err(ToolError::Customize(format!("{} is not logs", other))),
+        };
+        Ok(TransactionReceipt {
+            transaction_hash: hash("transactionHash")?,
+            transaction_index: number("transactionIndex")?,
+            block_hash: hash("blockHash")?,
+            block_number: number("blockNumber")?,
+            cumulative_quota_used: field("cumulativeQuotaUsed")?.clone().into_u256()?,
+            quota_used: field("quotaUsed")?.clone().into_u256()?,
+            contract_address: match present("contractAddress") {
+                Some(address) => Some(address.as_address().ok_or_else(|| {
+                    ToolError::Customize(format!("{} is not an address", address))
+                })?),
+                None => None,
+            },
+            logs,
+            logs_bloom: present("logsBloom").map(parse_bloom).transpose()?,
+            error_message: present("errorMessage")
+                .map(|message| message.clone().into_string())
+                .transpose()?,
+        })
+    }
+}
+
+/// Header of a block, with the block hash
+#[derive(Clone, Debug)]
+pub struct BlockHeader {
+    /// Hash of the block
+    pub hash: H256,
+    /// Block number
+    pub number: u64,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    /// Hash of the previous block
+    pub prev_hash: H256,
+    /// Proposer of the block
+    pub proposer: Address,
+    /// State root after the block
+    pub state_root: H256,
+    /// Root of the transactions of the block
+    pub transactions_root: H256,
+    /// Root of the receipts of the block
+    pub receipts_root: H256,
+}
+
+/// Parsed from the block of `getBlockByNumber` or `getBlockByHash`
+impl TryFrom<&ParamsValue> for BlockHeader {
+    type Error = ToolError;
+
+    fn try_from(value: &ParamsValue) -> Result<Self, Self::Error> {
+        let block = value
+            .as_map()
+            .ok_or_else(|| ToolError::Customize(format!("{} is not a block", value)))?;
+        let header = block
+            .get("header")
+            .and_then(ParamsValue::as_map)
+            .ok_or_else(|| ToolError::Customize("Block has no header".to_string()))?;
+        let field = |key: &str| {
+            header
+                .get(key)
+                .ok_or_else(|| ToolError::Customize(format!("Block header has no {}", key)))
+        };
+        let hash = |value: &ParamsValue| {
+            value
+                .as_str()
+                .and_then(|hash| H256::from_str(remove_0x(hash)).ok())
+                .ok_or_else(|| ToolError::Customize(format!("{} is not a hash", value)))
+        };
+        let number = |key: &str| {
+            field(key)?.as_u64().ok_or_else(|| {
+                ToolError::Customize(format!("Block header {} is not a number", key))
+            })
+        };
+
+        let proposer = field("proposer")?;
+        Ok(BlockHeader {
+            hash: hash(
+                block
+                    .get("hash")
+                    .ok_or_else(|| ToolError::Customize("Block has no hash".to_string()))?,
+            )?,
+            number: number("number")?,
+            timestamp: number("timestamp")?,
+            prev_hash: hash(field("prevHash")?)?,
+            proposer: proposer
+                .as_address()
+                .ok_or_else(|| ToolError::Customize(format!("{} is not an address", proposer)))?,
+            state_root: hash(field("stateRoot")?)?,
+            transactions_root: hash(field("transactionsRoot")?)?,
+            receipts_root: hash(field("receiptsRoot")?)?,
+        })
+    }
+}
+
+/// Parse a hex encoded 2048 bits bloom filter
+pub(crate) fn parse_bloom(value: &ParamsValue) -> Result<[u8; 256], ToolError> {
+    let bytes = value
+        .as_str()
+        .and_then(|bloom| decode(remove_0x(bloom)).ok())
+        .filter(|bytes| bytes.len() == 256)
+        .ok_or_else(|| ToolError::Customize(format!("{} is not a logs bloom", value)))?;
+    let mut bloom = [0u8; 256];
+    bloom.copy_from_slice(&bytes);
+    Ok(bloom)
+}
+
+/// Whether the address may be in the logs of the bloom filter, `false` means it's surely not
+///
+/// The filter of the yellow paper: the low 11 bits of the first three pairs of bytes
+/// of the keccak-256 hash of the address are set, counting from the last bit.
+pub fn contains_address(bloom: &[u8; 256], address: &Address) -> bool {
+    let mut hash = [0u8; 32];
+    address.sha3_crypt_hash_into(&mut hash);
+    hash[..6].chunks(2).all(|pair| {
+        let bit = ((usize::from(pair[0]) << 8) | usize::from(pair[1])) & 2047;
+        bloom[255 - bit / 8] & (1 << (bit % 8)) != 0
+    })
+}
+
+/// Trace of `debug_traceTransaction`
+#[cfg(feature = "debug-api")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    /// Quota used by the transaction
+    #[serde(rename = "gas")]
+    pub gas_used: u64,
+    /// Return data, or the revert data of a failed transaction
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub return_value: Vec<u8>,
+    /// Executed opcodes in order
+    pub struct_logs: Vec<TraceLog>,
+}
+
+/// An executed opcode of the trace
+#[cfg(feature = "debug-api")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceLog {
+    /// Program counter
+    pub pc: u64,
+    /// Opcode name
+    pub op: String,
+    /// Quota left before the opcode
+    pub gas: u64,
+    /// Quota cost of the opcode
+    pub gas_cost: u64,
+    /// Call depth, from 1
+    pub depth: u64,
+    /// Stack before the opcode, hex words from the bottom
+    #[serde(default)]
+    pub stack: Vec<String>,
+    /// Memory before the opcode, hex words
+    #[serde(default)]
+    pub memory: Vec<String>,
+}
+
+#[cfg(feature = "debug-api")]
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+#[cfg(feature = "debug-api")]
+fn deserialize_bytes<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    let hex = <String as serde::Deserialize>::deserialize(deserializer)?;
+    decode(remove_0x(&hex)).map_err(serde::de::Error::custom)
+}
+
+/// Chain information of `getMetaData`
+#[derive(Clone, Debug, Default)]
+pub struct ChainMetadata {
+    /// Chain id of version 0 transaction
+    pub chain_id: u32,
+    /// Chain id of version 1+ transaction, zero before version 1
+    pub chain_id_v1: U256,
+    /// Chain name
+    pub chain_name: String,
+    /// Operator of the chain
+    pub operator: String,
+    /// Website of the operator
+    pub website: String,
+    /// Genesis block timestamp, in milliseconds
+    pub genesis_timestamp: u64,
+    /// Consensus nodes
+    pub validators: Vec<Address>,
+    /// Block interval, in milliseconds
+    pub block_interval: u64,
+    /// Token name
+    pub token_name: String,
+    /// Token symbol
+    pub token_symbol: String,
+    /// Token avatar url
+    pub token_avatar: String,
+    /// Protocol version
+    pub version: u32,
+    /// Economical model, 0 is quota, 1 is charge
+    pub economical_model: u64,
+}
+
+impl TryFrom<JsonRpcResponse> for ChainMetadata {
+    type Error = ToolError;
+
+    fn try_from(response: JsonRpcResponse) -> Result<Self, Self::Error> {
+        let mut map = match response.into_result()? {
+            ResponseValue::Map(map) => map,
+            result => {
+                return Err(ToolError::Customize(format!(
+                    "Unexpected metadata: {}",
+                    result
+                )))
+            }
+        };
+        let mut string = |key: &str| match map.remove(key) {
+            Some(ParamsValue::String(value)) => value,
+            _ => String::new(),
+        };
+        let (chain_name, operator, website) =
+            (string("chainName"), string("operator"), string("website"));
+        let (token_name, token_symbol, token_avatar) = (
+            string("tokenName"),
+            string("tokenSymbol"),
+            string("tokenAvatar"),
+        );
+        let number = |key: &str| {
+            map.get(key)
+                .and_then(ParamsValue::as_u64)
+                .ok_or_else(|| ToolError::Customize(format!("Metadata has no {}", key)))
+        };
+        let validators = match map.get("validators") {
+            Some(ParamsValue::List(validators)) => validators
+                .iter()
+                .map(|validator| {
+                    validator.as_address().ok_or_else(|| {
+                        ToolError::Customize(format!("{} is not an address", validator))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            _ => Vec::new(),
+        };
+
+        Ok(ChainMetadata {
+            chain_id: number("chainId")? as u32,
+            chain_id_v1: match map.get("chainIdV1") {
+                Some(value) => value.clone().into_u256()?,
+                None => U256::zero(),
+            },
+            chain_name,
+            operator,
+            website,
+            genesis_timestamp: number("genesisTimestamp")?,
+            validators,
+            block_interval: number("blockInterval")?,
+            token_name,
+            token_symbol,
+            token_avatar,
+            version: number("version").unwrap_or(0) as u32,
+            economical_model: number("economicalModel").unwrap_or(0),
+        })
+    }
+}
+
+/// Transaction version and chain id the chain expects, from `getMetaData`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChainVersion {
+    /// Protocol version, the version of the transactions
+    pub tx_version: u32,
+    /// Chain id of version 0 transaction
+    pub chain_id: u32,
+    /// Chain id of version 1+ transaction, `None` before version 1
+    pub chain_id_v1: Option<U256>,
+}
+
+impl<'a> From<&'a ChainMetadata> for ChainVersion {
+    fn from(metadata: &'a ChainMetadata) -> Self {
+        ChainVersion {
+            tx_version: metadata.version,
+            chain_id: metadata.chain_id,
+            chain_id_v1: if metadata.version == 0 {
+                None
+            } else {
+                Some(metadata.chain_id_v1)
+            },
+        }
+    }
+}
+
+/// Connected peer of `peersInfo`
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PeerInfo {
+    /// Node address
+    pub node_id: String,
+    /// Network address
+    pub address: String,
+}
+
+/// Chain status, from the chain and the system contracts
+#[derive(Clone, Debug)]
+pub struct ChainStatus {
+    /// Current height
+    pub block_number: u64,
+    /// Chain metadata
+    pub metadata: ChainMetadata,
+    /// Number of consensus nodes of the node manager
+    pub node_count: u64,
+    /// Block quota limit
+    pub block_quota_limit: u64,
+    /// Quota price
+    pub quota_price: U256,
+    /// Whether the emergency brake is on
+    pub emergency_brake_active: bool,
+}
+
+/// Statistics of a range of blocks
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BlockStats {
+    /// First block of the range
+    pub from: u64,
+    /// Last block of the range
+    pub to: u64,
+    /// Transactions of all blocks
+    pub total_transactions: u64,
+    /// Average quota used per block
+    pub average_quota_used: u64,
+    /// Most quota used by a block
+    pub peak_quota_used: u64,
+    /// Milliseconds between consecutive blocks of the range, `None` for one block
+    pub block_time: Option<Distribution>,
+    /// Transactions per block
+    pub transactions_per_block: Distribution,
+    /// Proposers with the count of blocks they proposed, by address
+    pub proposers: BTreeMap<String, u64>,
+}
+
+impl BlockStats {
+    /// Statistics of the blocks of `getBlockByNumber`, in order of height
+    pub fn from_blocks(blocks: &[ParamsValue]) -> Result<Self, ToolError> {
+        if blocks.is_empty() {
+            return Err(ToolError::Customize("No block".to_string()));
+        }
+        let mut numbers = Vec::with_capacity(blocks.len());
+        let mut timestamps = Vec::with_capacity(blocks.len());
+        let mut quota_used = Vec::with_capacity(blocks.len());
+        let mut transactions = Vec::with_capacity(blocks.len());
+        let mut proposers = BTreeMap::new();
+        for block in blocks {
+            let header = BlockHeader::try_from(block)?;
+            let field = |section: &str, key: &str| {
+                block
+                    .as_map()
+                    .and_then(|block| block.get(section))
+                    .and_then(ParamsValue::as_map)
+                    .and_then(|section| section.get(key))
+                    .ok_or_else(|| {
+                        ToolError::Customize(format!("Block {} has no {}", header.number, key))
+                    })
+            };
+            quota_used.push(field("header", "quotaUsed")?.as_u64().ok_or_else(|| {
+                ToolError::Customize(format!("Block {} quotaUsed is not a number", header.number))
+            })?);
+            transactions.push(match field("body", "transactions")? {
+                ParamsValue::List(transactions) => transactions.len() as u64,
+                _ => 0,
+            });
+            numbers.push(header.number);
+            timestamps.push(header.timestamp);
+            *proposers
+                .entry(header.proposer.completed_lower_hex_with_0x())
+                .or_insert(0) += 1;
+        }
+
+        let block_times = timestamps
+            .windows(2)
+            .map(|pair| pair[1].saturating_sub(pair[0]))
+            .collect::<Vec<u64>>();
+        Ok(BlockStats {
+            from: numbers[0],
+            to: numbers[numbers.len() - 1],
+            total_transactions: transactions.iter().sum(),
+            average_quota_used: quota_used.iter().sum::<u64>() / quota_used.len() as u64,
+            peak_quota_used: quota_used.iter().cloned().max().unwrap_or(0),
+            block_time: Distribution::from_samples(block_times),
+            transactions_per_block: Distribution::from_samples(transactions).unwrap_or_default(),
+            proposers,
+        })
+    }
+}
+
+/// Distribution of samples, percentiles are nearest-rank
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Distribution {
+    /// Smallest sample
+    pub min: u64,
+    /// 50th percentile
+    pub median: u64,
+    /// 95th percentile
+    pub p95: u64,
+    /// Largest sample
+    pub max: u64,
+}
+
+impl Distribution {
+    /// Distribution of the samples, `None` without sample
+    pub fn from_samples(mut samples: Vec<u64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let percentile = |percent: usize| {
+            let rank = (samples.len() * percent + 99) / 100;
+            samples[rank.max(1) - 1]
+        };
+        Some(Distribution {
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// Health of a JSON-RPC url, measured by `blockNumber`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlHealth {
+    /// JSON-RPC url
+    pub url: String,
+    /// Round-trip time, `None` on failure
+    pub latency: Option<Duration>,
+    /// Current height of the node, `None` on failure
+    pub block_number: Option<u64>,
+    /// Why the url failed, such as timeout
+    pub error: Option<String>,
+}
+
+/// Spread of the heights of the urls, see `Client::check_consensus_divergence`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConsensusHealth {
+    /// Height of the highest url
+    pub max_block: u64,
+    /// Height of the lowest url
+    pub min_block: u64,
+    /// Blocks between the highest and the lowest url
+    pub divergence: u64,
+    /// The urls more than the threshold behind the highest one
+    pub divergent_urls: Vec<String>,
+}
+
+impl ConsensusHealth {
+    /// Heights of the urls that answered, `None` if none did
+    pub(crate) fn from_url_health(healths: &[UrlHealth], threshold: u64) -> Option<Self> {
+        let heights = healths
+            .iter()
+            .filter_map(|health| health.block_number.map(|height| (&health.url, height)))
+            .collect::<Vec<(&String, u64)>>();
+        let max_block = heights.iter().map(|(_, height)| *height).max()?;
+        let min_block = heights.iter().map(|(_, height)| *height).min()?;
+        Some(ConsensusHealth {
+            max_block,
+            min_block,
+            divergence: max_block - min_block,
+            divergent_urls: heights
+                .iter()
+                .filter(|(_, height)| max_block - height > threshold)
+                .map(|(url, _)| url.to_string())
+                .collect(),
+        })
+    }
+}
+
+/// Node software version, `major.minor.patch`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// Major version
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+    /// Patch version
+    pub patch: u32,
+}
+
+impl FromStr for Version {
+    type Err = ToolError;
+
+    /// Parse `v0.20.2` or `0.20.2`, pre-release and build suffixes are ignored
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || ToolError::Customize(format!("{} is not major.minor.patch", version));
+        let numbers = version
+            .trim()
+            .trim_start_matches('v')
+            .split(|c| c == '-' || c == '+')
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(u32::from_str)
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| invalid())?;
+        match numbers.as_slice() {
+            [major, minor, patch] => Ok(Version {
+                major: *major,
+                minor: *minor,
+                patch: *patch,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 /// Error
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -169,6 +1247,30 @@ impl ErrorResponse {
     }
 }
 
+/// The error object of JSON-RPC 2.0, or the plain message of JSON-RPC 1.0 with code 0
+fn deserialize_error<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ErrorResponse>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Error {
+        Object(ErrorResponse),
+        Message(String),
+    }
+
+    Ok(
+        match <Option<Error> as serde::Deserialize>::deserialize(deserializer)? {
+            Some(Error::Object(error)) => Some(error),
+            Some(Error::Message(message)) => Some(ErrorResponse {
+                code: 0,
+                message,
+                data: None,
+            }),
+            None => None,
+        },
+    )
+}
+
 impl fmt::Debug for ErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{}", serde_json::to_string_pretty(self).unwrap())
@@ -180,3 +1282,532 @@ impl fmt::Display for ErrorResponse {
         write!(f, "{}", json!(self))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        contains_address, BlockHeader, BlockStats, ChainMetadata, ChainVersion, ConsensusHealth,
+        Distribution, JsonRpcParams, JsonRpcResponse, JsonRpcVersion, Log, LogFilter, ParamsValue,
+        TransactionReceipt, UrlHealth, Version,
+    };
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+    use std::convert::{TryFrom, TryInto};
+    use std::str::FromStr;
+    use types::{Address, H256, U256};
+
+    fn response(result: &str) -> JsonRpcResponse {
+        serde_json::from_str(&format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#,
+            result
+        ))
+        .unwrap()
+    }
+
+    /// Every variant, nested up to `depth` levels of `List` and `Map`
+    fn params_values(depth: usize) -> Vec<ParamsValue> {
+        let mut values = vec![
+            ParamsValue::String(String::new()),
+            ParamsValue::String("0x1f".to_string()),
+            ParamsValue::String("\"quoted\"\n\u{4e2d}".to_string()),
+            ParamsValue::Int(0),
+            ParamsValue::Int(u64::max_value()),
+            ParamsValue::Bool(true),
+            ParamsValue::Bool(false),
+            ParamsValue::Null,
+        ];
+        if depth > 0 {
+            let children = params_values(depth - 1);
+            values.push(ParamsValue::List(Vec::new()));
+            values.push(ParamsValue::List(children.clone()));
+            values.push(ParamsValue::Map(HashMap::new()));
+            values.push(ParamsValue::Map(
+                children
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| (format!("key{}", index), value))
+                    .collect(),
+            ));
+        }
+        values
+    }
+
+    #[test]
+    fn test_params_round_trip() {
+        for value in params_values(2) {
+            let params = JsonRpcParams::new()
+                .insert("method", ParamsValue::String("call".to_string()))
+                .insert("params", value);
+            let json = serde_json::to_string(&params).unwrap();
+            let decoded: JsonRpcParams = serde_json::from_str(&json).unwrap();
+            assert!(decoded == params, "{} does not round trip", json);
+        }
+
+        assert!(serde_json::from_str::<ParamsValue>("-1").is_err());
+        assert!(serde_json::from_str::<ParamsValue>("1.5").is_err());
+    }
+
+    fn params_value() -> impl Strategy<Value = ParamsValue> {
+        let leaf = prop_oneof![
+            any::<String>().prop_map(ParamsValue::String),
+            any::<u64>().prop_map(ParamsValue::Int),
+            any::<bool>().prop_map(ParamsValue::Bool),
+            Just(ParamsValue::Null),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(ParamsValue::List),
+                prop::collection::hash_map(any::<String>(), inner, 0..8).prop_map(ParamsValue::Map),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_params_round_trip_arbitrary(
+            params in prop::collection::hash_map(any::<String>(), params_value(), 0..8)
+        ) {
+            let params = params
+                .into_iter()
+                .fold(JsonRpcParams::new(), |params, (key, value)| params.insert(key, value));
+            let json = serde_json::to_string(&params).unwrap();
+            let decoded: JsonRpcParams = serde_json::from_str(&json).unwrap();
+            prop_assert!(decoded == params, "{} does not round trip", json);
+        }
+    }
+
+    #[test]
+    fn test_try_into_primitive() {
+        let word = "0x0000000000000000000000000000000000000000000000000000000000000001";
+        let value: bool = response(word).try_into().unwrap();
+        assert!(value);
+        let value: u64 = response("0x1f").try_into().unwrap();
+        assert_eq!(value, 31);
+        let value: U256 = response(word).try_into().unwrap();
+        assert_eq!(value, U256::one());
+        let value: Result<bool, _> = response("0x02").try_into();
+        assert!(value.is_err());
+
+        let addresses: Vec<Address> =
+            response(&format!("0x{:064x}{:064x}{:064x}{:064x}", 32, 2, 1, 2))
+                .try_into()
+                .unwrap();
+        let address = |n: u8| {
+            let mut bytes = [0u8; 20];
+            bytes[19] = n;
+            Address::from(bytes)
+        };
+        assert_eq!(addresses, vec![address(1), address(2)]);
+        for data in &[
+            format!("0x{:064x}{:064x}{:064x}", 32, 2, 1),
+            format!("0x{:064x}{:064x}", 32, u64::max_value()),
+            format!("0x{:0>64}{:064x}", "f".repeat(64), 1),
+            format!("0x{:064x}{:0>64}", 32, "f".repeat(64)),
+        ] {
+            let addresses: Result<Vec<Address>, _> = response(data).try_into();
+            assert!(addresses.is_err());
+        }
+    }
+
+    #[test]
+    fn test_try_into_metadata() {
+        let response: JsonRpcResponse = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"result":{
+                "chainId": 0,
+                "chainIdV1": "0x1",
+                "chainName": "test-chain",
+                "operator": "test-operator",
+                "genesisTimestamp": 1528700000000,
+                "validators": ["0xffffffffffffffffffffffffffffffffff020009"],
+                "blockInterval": 3000,
+                "tokenName": "CITA Test Token",
+                "tokenSymbol": "CTT",
+                "tokenAvatar": "",
+                "version": 1,
+                "economicalModel": 0
+            }}"#,
+        )
+        .unwrap();
+        let metadata = ChainMetadata::try_from(response).unwrap();
+        assert_eq!(metadata.chain_id_v1, U256::one());
+        assert_eq!(metadata.chain_name, "test-chain");
+        assert_eq!(metadata.validators.len(), 1);
+        assert_eq!(metadata.block_interval, 3000);
+        assert_eq!(metadata.version, 1);
+        assert_eq!(metadata.website, "");
+        assert_eq!(
+            ChainVersion::from(&metadata),
+            ChainVersion {
+                tx_version: 1,
+                chain_id: 0,
+                chain_id_v1: Some(U256::one()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_consensus_health() {
+        let health = |url: &str, block_number: Option<u64>| UrlHealth {
+            url: url.to_string(),
+            latency: None,
+            block_number,
+            error: None,
+        };
+        assert_eq!(
+            ConsensusHealth::from_url_health(&[health("http://a/", None)], 5),
+            None
+        );
+        let healths = [
+            health("http://a/", Some(100)),
+            health("http://b/", Some(98)),
+            health("http://c/", Some(90)),
+            health("http://d/", None),
+        ];
+        assert_eq!(
+            ConsensusHealth::from_url_health(&healths, 5),
+            Some(ConsensusHealth {
+                max_block: 100,
+                min_block: 90,
+                divergence: 10,
+                divergent_urls: vec!["http://c/".to_string()],
+            })
+        );
+        assert!(ConsensusHealth::from_url_health(&healths, 10)
+            .unwrap()
+            .divergent_urls
+            .is_empty());
+    }
+
+    #[test]
+    fn test_log_filter_and_log() {
+        let filter = LogFilter::new()
+            .set_from_block(Some(16))
+            .set_topics(Some(vec![None, Some(H256::from(1))]));
+        assert_eq!(
+            serde_json::to_value(filter.into_params()).unwrap(),
+            serde_json::json!({
+                "fromBlock": "0x10",
+                "toBlock": "latest",
+                "topics": [
+                    null,
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                ]
+            })
+        );
+
+        let value: ParamsValue = serde_json::from_str(
+            r#"{
+                "address": "0xffffffffffffffffffffffffffffffffff020009",
+                "topics": ["0x0000000000000000000000000000000000000000000000000000000000000002"],
+                "data": "0x0102",
+                "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                "blockNumber": "0x1f",
+                "logIndex": "0x0"
+            }"#,
+        )
+        .unwrap();
+        let log = Log::try_from(&value).unwrap();
+        assert_eq!(log.topics, vec![H256::from(2)]);
+        assert_eq!(log.data, vec![1, 2]);
+        assert_eq!(log.transaction_hash, H256::from(3));
+        assert_eq!(log.block_number, 31);
+        assert_eq!(log.log_index, 0);
+    }
+
+    #[test]
+    fn test_address_value() {
+        let address = ParamsValue::address("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF020009").unwrap();
+        assert_eq!(
+            serde_json::to_value(&address).unwrap(),
+            serde_json::json!("0xffffffffffffffffffffffffffffffffff020009")
+        );
+        assert_eq!(
+            address.as_address(),
+            Some(Address::from_str("ffffffffffffffffffffffffffffffffff020009").unwrap())
+        );
+        assert!(ParamsValue::address("0xffffffffffffffffffffffffffffffffff0200").is_err());
+        assert!(ParamsValue::address("0xffffffffffffffffffffffffffffffffff02000g").is_err());
+    }
+
+    #[test]
+    fn test_u256_value() {
+        let value = ParamsValue::from_u256(U256::from(0xabc));
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000abc")
+        );
+        assert_eq!(value.into_u256().unwrap(), U256::from(0xabc));
+        assert_eq!(
+            serde_json::to_value(&ParamsValue::from_u256(U256::max_value())).unwrap(),
+            serde_json::json!(format!("0x{}", "f".repeat(64)))
+        );
+    }
+
+    #[test]
+    fn test_receipt_and_logs_bloom() {
+        let receipt = |bloom: &str| {
+            let value: ParamsValue = serde_json::from_str(&format!(
+                r#"{{
+                    "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                    "transactionIndex": "0x0",
+                    "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000004",
+                    "blockNumber": "0x1f",
+                    "cumulativeQuotaUsed": "0x5208",
+                    "quotaUsed": "0x5208",
+                    "contractAddress": null,
+                    "logs": [],
+                    "root": null,
+                    "errorMessage": null{}
+                }}"#,
+                bloom
+            ))
+            .unwrap();
+            TransactionReceipt::try_from(&value).unwrap()
+        };
+
+        let old = receipt("");
+        assert_eq!(old.block_number, 31);
+        assert_eq!(old.quota_used, U256::from(21000));
+        assert!(old.contract_address.is_none());
+        assert!(old.logs_bloom.is_none());
+        assert!(old.error_message.is_none());
+
+        // Bits of 0xffffffffffffffffffffffffffffffffff020009 only
+        let mut bits = vec!["00"; 256];
+        bits[45] = "80";
+        bits[78] = "80";
+        bits[246] = "01";
+        let bloom = receipt(&format!(r#","logsBloom": "0x{}""#, bits.concat()))
+            .logs_bloom
+            .unwrap();
+        let address = |address: &str| Address::from_str(address).unwrap();
+        assert!(contains_address(
+            &bloom,
+            &address("ffffffffffffffffffffffffffffffffff020009")
+        ));
+        assert!(!contains_address(
+            &bloom,
+            &address("ffffffffffffffffffffffffffffffffff020004")
+        ));
+        assert!(!contains_address(
+            &[0; 256],
+            &address("ffffffffffffffffffffffffffffffffff020009")
+        ));
+    }
+
+    #[test]
+    fn test_as_u64() {
+        let value = |value: &str| ParamsValue::String(value.to_string());
+        assert_eq!(value("0x10").as_u64(), Some(16));
+        assert_eq!(value("0X1f").as_u64(), Some(31));
+        assert_eq!(value("10").as_u64(), Some(10));
+        assert_eq!(value("1f").as_u64(), None);
+        assert_eq!(value("0x").as_u64(), None);
+        assert_eq!(ParamsValue::Int(7).as_u64(), Some(7));
+        assert_eq!(response("0x1").result().unwrap().as_u64(), Some(1));
+    }
+
+    #[test]
+    fn test_into_result() {
+        assert!(response("0x1").into_result().is_ok());
+
+        let error: JsonRpcResponse = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Invalid params"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            error.into_result().unwrap_err().to_string(),
+            "JSON-RPC error -32602: Invalid params, check the params of the method"
+        );
+
+        let null: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":null}"#).unwrap();
+        assert!(null.into_result().is_err());
+    }
+
+    #[test]
+    fn test_parse_version() {
+        let version: Version = "v0.20.2".parse().unwrap();
+        assert_eq!(
+            version,
+            Version {
+                major: 0,
+                minor: 20,
+                patch: 2
+            }
+        );
+        assert_eq!(version.to_string(), "0.20.2");
+        assert_eq!("1.0.0-rc1".parse::<Version>().unwrap().major, 1);
+        assert!("0.20.2".parse::<Version>().unwrap() < "0.21.0".parse().unwrap());
+        assert!("0.20".parse::<Version>().is_err());
+        assert!("0.x.1".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_try_into_address() {
+        let address: Address =
+            response("0x000000000000000000000000ffffffffffffffffffffffffffffffffff020009")
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            format!("{:x}", address),
+            "ffffffffffffffffffffffffffffffffff020009"
+        );
+
+        let addresses: Vec<Address> = response(concat!(
+            "0x0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "000000000000000000000000ffffffffffffffffffffffffffffffffff020009",
+            "000000000000000000000000ffffffffffffffffffffffffffffffffff02000a",
+        ))
+        .try_into()
+        .unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(
+            format!("{:x}", addresses[1]),
+            "ffffffffffffffffffffffffffffffffff02000a"
+        );
+    }
+
+    #[cfg(feature = "debug-api")]
+    #[test]
+    fn test_transaction_trace() {
+        let trace = r#"{
+            "gas": 21432,
+            "returnValue": "0x08c379a0",
+            "structLogs": [
+                {"pc": 0, "op": "PUSH1", "gas": 78568, "gasCost": 3, "depth": 1, "stack": [], "memory": []},
+                {"pc": 2, "op": "REVERT", "gas": 78565, "gasCost": 0, "depth": 1,
+                 "stack": ["0000000000000000000000000000000000000000000000000000000000000080"]}
+            ]
+        }"#;
+        let trace: super::TransactionTrace = serde_json::from_str(trace).unwrap();
+        assert_eq!(trace.gas_used, 21432);
+        assert_eq!(trace.return_value, vec![0x08, 0xc3, 0x79, 0xa0]);
+        assert_eq!(trace.struct_logs[1].op, "REVERT");
+        assert_eq!(trace.struct_logs[1].stack.len(), 1);
+        assert!(trace.struct_logs[1].memory.is_empty());
+
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["returnValue"], "0x08c379a0");
+        assert_eq!(json["structLogs"][0]["gasCost"], 3);
+    }
+
+    #[test]
+    fn test_block_header() {
+        let block: ParamsValue = serde_json::from_str(
+            r#"{
+                "version": 1,
+                "hash": "0xfc4dfb4e3ab0fdbf6f9f86bba80ebe8a3f28b5c1a4e7f7a2f0a7d3d2c7b5a9e1",
+                "header": {
+                    "timestamp": 1563254433005,
+                    "prevHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    "number": "0x1f",
+                    "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                    "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                    "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000004",
+                    "quotaUsed": "0x0",
+                    "proof": null,
+                    "proposer": "0x5a2a1c3b6b0f0e6e3a4e2f7f4c0b1c6e0d1e5b9a"
+                },
+                "body": {"transactions": []}
+            }"#,
+        )
+        .unwrap();
+        let header = BlockHeader::try_from(&block).unwrap();
+        assert_eq!(header.number, 31);
+        assert_eq!(header.timestamp, 1_563_254_433_005);
+        assert_eq!(header.prev_hash, H256::from(1));
+        assert_eq!(header.receipts_root, H256::from(4));
+        assert_eq!(
+            format!("{:x}", header.proposer),
+            "5a2a1c3b6b0f0e6e3a4e2f7f4c0b1c6e0d1e5b9a"
+        );
+        assert!(BlockHeader::try_from(&ParamsValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_block_stats() {
+        let block = |number: u64, timestamp: u64, quota_used: u64, txs: usize, proposer: u8| {
+            let transactions = vec![format!("\"0x{:064x}\"", 0); txs].join(",");
+            serde_json::from_str::<ParamsValue>(&format!(
+                r#"{{
+                    "version": 1,
+                    "hash": "0x{:064x}",
+                    "header": {{
+                        "timestamp": {},
+                        "prevHash": "0x{:064x}",
+                        "number": "{:#x}",
+                        "stateRoot": "0x{:064x}",
+                        "transactionsRoot": "0x{:064x}",
+                        "receiptsRoot": "0x{:064x}",
+                        "quotaUsed": "{:#x}",
+                        "proof": null,
+                        "proposer": "0x{:040x}"
+                    }},
+                    "body": {{"transactions": [{}]}}
+                }}"#,
+                number, timestamp, 0, number, 0, 0, 0, quota_used, proposer, transactions
+            ))
+            .unwrap()
+        };
+        let blocks = vec![
+            block(10, 30_000, 100, 1, 1),
+            block(11, 33_000, 300, 3, 2),
+            block(12, 39_000, 0, 0, 1),
+        ];
+        let stats = BlockStats::from_blocks(&blocks).unwrap();
+        assert_eq!((stats.from, stats.to), (10, 12));
+        assert_eq!(stats.total_transactions, 4);
+        assert_eq!(stats.average_quota_used, 133);
+        assert_eq!(stats.peak_quota_used, 300);
+        assert_eq!(
+            stats.block_time,
+            Some(Distribution {
+                min: 3000,
+                median: 3000,
+                p95: 6000,
+                max: 6000,
+            })
+        );
+        assert_eq!(stats.transactions_per_block.median, 1);
+        assert_eq!(stats.proposers[&format!("0x{:040x}", 1)], 2);
+        assert_eq!(stats.proposers.len(), 2);
+
+        let stats = BlockStats::from_blocks(&blocks[..1]).unwrap();
+        assert_eq!(stats.block_time, None);
+        assert!(BlockStats::from_blocks(&[]).is_err());
+    }
+
+    #[test]
+    fn test_json_rpc_version() {
+        let params = JsonRpcParams::new().json_rpc_version(JsonRpcVersion::V1);
+        assert!(params.get("jsonrpc").is_none());
+        let params = params.json_rpc_version(JsonRpcVersion::V2);
+        assert_eq!(
+            params.get("jsonrpc"),
+            Some(&ParamsValue::String("2.0".to_string()))
+        );
+        assert_eq!("1.0".parse::<JsonRpcVersion>().unwrap(), JsonRpcVersion::V1);
+        assert!("3.0".parse::<JsonRpcVersion>().is_err());
+
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"id":1,"result":null,"error":"unknown method"}"#).unwrap();
+        let error = response.error().unwrap();
+        assert_eq!(
+            (error.code(), error.message()),
+            (0, "unknown method".to_string())
+        );
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"id":1,"result":"0x10","error":null}"#).unwrap();
+        assert!(response.error().is_none());
+        assert!(!serde_json::to_string(&response)
+            .unwrap()
+            .contains("jsonrpc"));
+        let response: JsonRpcResponse = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+        )
+        .unwrap();
+        assert_eq!(response.error().unwrap().code(), -32601);
+    }
+}
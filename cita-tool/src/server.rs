@@ -0,0 +1,627 @@
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures::{future, sync::oneshot, Future, Stream};
+use hmac::{Hmac, Mac};
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::service::service_fn;
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use log::{error, info};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::client::basic::Client;
+use crate::error::ToolError;
+use crate::rpctypes::JsonRpcParams;
+
+/// JSON-RPC error code of an unparsable request body
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code of a request without method
+const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code of a method not in the allowlist or in the denylist
+const METHOD_NOT_ALLOWED: i64 = -32601;
+/// JSON-RPC error code of a method requiring auth without a valid token
+const UNAUTHORIZED: i64 = -32001;
+/// JSON-RPC error code of a failed forwarding
+const INTERNAL_ERROR: i64 = -32603;
+/// JSON-RPC error code of a request over the rate limit
+const LIMIT_EXCEEDED: i64 = -32005;
+
+/// Largest request body read, a larger one is rejected without reading the rest
+const MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+/// Threads forwarding the requests, the client blocks until the backend responds
+const WORKERS: usize = 16;
+/// Requests waiting for a worker, the others are rejected as busy
+const QUEUE_SIZE: usize = 256;
+
+/// Requests allowed in a period, parsed from `<count>/<s|m|h>`, such as `100/s`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+}
+
+impl RateLimit {
+    /// Allow the requests in every period
+    pub fn new(requests: u32, period: Duration) -> Self {
+        RateLimit { requests, period }
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = ToolError;
+
+    fn from_str(limit: &str) -> Result<Self, Self::Err> {
+        let invalid = || ToolError::Customize(format!("Invalid rate limit {}", limit));
+        let mut parts = limit.splitn(2, '/');
+        let requests = parts
+            .next()
+            .and_then(|requests| requests.trim().parse::<u32>().ok())
+            .filter(|requests| *requests > 0)
+            .ok_or_else(invalid)?;
+        let period = match parts.next().map(str::trim) {
+            Some("s") => Duration::from_secs(1),
+            Some("m") => Duration::from_secs(60),
+            Some("h") => Duration::from_secs(3600),
+            _ => return Err(invalid()),
+        };
+        Ok(RateLimit::new(requests, period))
+    }
+}
+
+/// Access control of the methods, such as the `[server]` table of a TOML file:
+///
+/// ```toml
+/// [server]
+/// allow = ["blockNumber", "call", "sendRawTransaction"]
+/// deny = ["sendRawTransaction"]
+/// require-auth = ["call"]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServerConfig {
+    /// Only these methods are forwarded, `None` forwards all
+    pub allow: Option<Vec<String>>,
+    /// These methods are always rejected
+    pub deny: Option<Vec<String>>,
+    /// These methods also require a valid JWT in `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub require_auth: Vec<String>,
+}
+
+/// Options of the JSON-RPC proxy
+#[derive(Clone, Debug, Default)]
+pub struct ServerOptions {
+    allow_methods: Option<HashSet<String>>,
+    deny_methods: HashSet<String>,
+    auth_methods: HashSet<String>,
+    jwt_secret: Option<Vec<u8>>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl ServerOptions {
+    /// Default options, every method is forwarded without limit
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the methods to forward, the others are rejected, `None` forwards all
+    pub fn set_allow_methods(mut self, methods: Option<Vec<String>>) -> Self {
+        self.allow_methods = methods.map(|methods| methods.into_iter().collect());
+        self
+    }
+
+    /// Set the methods always rejected, even if they are allowed
+    pub fn set_deny_methods(mut self, methods: Option<Vec<String>>) -> Self {
+        self.deny_methods = methods.unwrap_or_default().into_iter().collect();
+        self
+    }
+
+    /// Set the methods only forwarded with a valid JWT
+    pub fn set_auth_methods(mut self, methods: Vec<String>) -> Self {
+        self.auth_methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Set the secret verifying the HS256 JWT of the methods requiring auth
+    pub fn set_jwt_secret(mut self, secret: Option<Vec<u8>>) -> Self {
+        self.jwt_secret = secret;
+        self
+    }
+
+    /// Set the allowed, denied and auth required methods of the config
+    pub fn set_config(self, config: ServerConfig) -> Self {
+        self.set_allow_methods(config.allow)
+            .set_deny_methods(config.deny)
+            .set_auth_methods(config.require_auth)
+    }
+
+    /// Set the limit of the requests of all the callers, a batch counts every request
+    pub fn set_rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+}
+
+/// Fixed window counter of the requests
+struct Limiter {
+    limit: RateLimit,
+    window: Mutex<(Instant, u32)>,
+}
+
+impl Limiter {
+    fn new(limit: RateLimit) -> Self {
+        Limiter {
+            limit,
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Count the request, `false` if the window is full
+    fn try_acquire(&self) -> bool {
+        let mut window = self.window.lock().unwrap();
+        if window.0.elapsed() >= self.limit.period {
+            *window = (Instant::now(), 0);
+        }
+        if window.1 >= self.limit.requests {
+            return false;
+        }
+        window.1 += 1;
+        true
+    }
+
+    /// Whether the window is full, without counting a request
+    fn is_full(&self) -> bool {
+        let mut window = self.window.lock().unwrap();
+        if window.0.elapsed() >= self.limit.period {
+            *window = (Instant::now(), 0);
+        }
+        window.1 >= self.limit.requests
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Fixed pool of threads running the jobs, with a bounded queue
+struct Workers {
+    sender: Mutex<SyncSender<Job>>,
+}
+
+impl Workers {
+    fn new(count: usize, queue_size: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..count {
+            let receiver = Arc::clone(&receiver);
+            thread::spawn(move || loop {
+                let job = match receiver.lock().unwrap().recv() {
+                    Ok(job) => job,
+                    Err(_) => return,
+                };
+                job();
+            });
+        }
+        Workers {
+            sender: Mutex::new(sender),
+        }
+    }
+
+    /// Queue the job, `false` if the queue is full
+    fn try_run(&self, job: Job) -> bool {
+        self.sender.lock().unwrap().try_send(job).is_ok()
+    }
+}
+
+/// Forwards the allowed requests to the backend node
+struct Proxy {
+    // Cloned for every request, the clones share the connection pool
+    backend: Mutex<Client>,
+    allow_methods: Option<HashSet<String>>,
+    deny_methods: HashSet<String>,
+    auth_methods: HashSet<String>,
+    jwt_secret: Option<Vec<u8>>,
+    limiter: Option<Limiter>,
+}
+
+impl Proxy {
+    fn new(backend: Client, options: ServerOptions) -> Self {
+        Proxy {
+            backend: Mutex::new(backend),
+            allow_methods: options.allow_methods,
+            deny_methods: options.deny_methods,
+            auth_methods: options.auth_methods,
+            jwt_secret: options.jwt_secret,
+            limiter: options.rate_limit.map(Limiter::new),
+        }
+    }
+
+    /// Whether the rate limit is reached, checked before the body is read
+    fn rate_limited(&self) -> bool {
+        self.limiter.as_ref().map_or(false, Limiter::is_full)
+    }
+
+    /// Answer the request body, a single request or a batch, with the bearer token
+    fn handle(&self, body: &[u8], token: Option<&str>) -> Value {
+        let authorized = match (token, self.jwt_secret.as_ref()) {
+            (Some(token), Some(secret)) => verify_jwt(token, secret),
+            _ => false,
+        };
+        match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Array(calls)) => Value::Array(
+                calls
+                    .into_iter()
+                    .map(|call| self.forward(call, authorized))
+                    .collect(),
+            ),
+            Ok(call) => self.forward(call, authorized),
+            Err(err) => error_response(Value::Null, PARSE_ERROR, &err.to_string()),
+        }
+    }
+
+    /// Forward the request with the id of the caller, or reject it
+    fn forward(&self, call: Value, authorized: bool) -> Value {
+        let id = call.get("id").cloned().unwrap_or(Value::Null);
+        let method = match call.get("method").and_then(Value::as_str) {
+            Some(method) => method.to_string(),
+            None => return error_response(id, INVALID_REQUEST, "Request has no method"),
+        };
+        let allowed = self
+            .allow_methods
+            .as_ref()
+            .map_or(true, |allow_methods| allow_methods.contains(&method));
+        if !allowed || self.deny_methods.contains(&method) {
+            return error_response(
+                id,
+                METHOD_NOT_ALLOWED,
+                &format!("Method {} is not allowed", method),
+            );
+        }
+        if !authorized && self.auth_methods.contains(&method) {
+            return error_response(
+                id,
+                UNAUTHORIZED,
+                &format!("Method {} requires a valid bearer token", method),
+            );
+        }
+        if let Some(ref limiter) = self.limiter {
+            if !limiter.try_acquire() {
+                return error_response(id, LIMIT_EXCEEDED, "Rate limit exceeded");
+            }
+        }
+
+        let params = match serde_json::from_value::<JsonRpcParams>(call) {
+            Ok(params) => params,
+            Err(err) => return error_response(id, INVALID_REQUEST, &err.to_string()),
+        };
+        let backend = self.backend.lock().unwrap().clone();
+        let response = backend
+            .send_request(vec![params].into_iter())
+            .and_then(|mut responses| {
+                serde_json::to_value(responses.pop().unwrap()).map_err(ToolError::SerdeJson)
+            });
+        match response {
+            Ok(mut response) => {
+                response["id"] = id;
+                response
+            }
+            Err(err) => error_response(id, INTERNAL_ERROR, &err.to_string()),
+        }
+    }
+}
+
+/// Whether the token is a HS256 JWT signed by the secret, not expired if it has `exp`
+fn verify_jwt(token: &str, secret: &[u8]) -> bool {
+    let parts = token.split('.').collect::<Vec<&str>>();
+    if parts.len() != 3 {
+        return false;
+    }
+    let decode = |part: &str| {
+        base64::decode_config(part, base64::URL_SAFE_NO_PAD)
+            .ok()
+            .and_then(|json| serde_json::from_slice::<Value>(&json).ok())
+    };
+    match decode(parts[0]) {
+        Some(ref header) if header["alg"] == "HS256" => {}
+        _ => return false,
+    }
+    let signature = match base64::decode_config(parts[2], base64::URL_SAFE_NO_PAD) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac = match Hmac::<Sha256>::new_varkey(secret) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.input(token[..parts[0].len() + 1 + parts[1].len()].as_bytes());
+    if mac.verify(&signature).is_err() {
+        return false;
+    }
+    match decode(parts[1]) {
+        Some(ref claims) if claims.is_object() => match claims.get("exp") {
+            Some(exp) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|now| now.as_secs())
+                    .unwrap_or(0);
+                exp.as_u64().map_or(false, |exp| exp > now)
+            }
+            None => true,
+        },
+        _ => false,
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// The request rejected before it's handled, with the HTTP status
+struct Rejected {
+    status: StatusCode,
+    code: i64,
+    message: String,
+}
+
+impl Rejected {
+    fn new(status: StatusCode, code: i64, message: &str) -> Self {
+        Rejected {
+            status,
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    fn into_response(self) -> Response<Body> {
+        json_response(
+            self.status,
+            &error_response(Value::Null, self.code, &self.message),
+        )
+    }
+}
+
+/// Serve JSON-RPC on the address, forwarding the allowed requests through the client,
+/// blocks until the server stops
+///
+/// The requests are forwarded by a fixed pool of threads, since the client blocks
+/// until the backend responds, the ones beyond the queue are rejected as busy.
+/// Over the rate limit, a request is rejected before its body is read, and a body
+/// larger than 4 MiB is rejected. Fail if some methods require auth without the
+/// JWT secret.
+pub fn serve(listen: SocketAddr, backend: Client, options: ServerOptions) -> Result<(), ToolError> {
+    if !options.auth_methods.is_empty() && options.jwt_secret.is_none() {
+        return Err(ToolError::Customize(
+            "The JWT secret is required by the methods requiring auth".to_string(),
+        ));
+    }
+    let proxy = Arc::new(Proxy::new(backend, options));
+    let workers = Arc::new(Workers::new(WORKERS, QUEUE_SIZE));
+    let server = Server::try_bind(&listen)
+        .map_err(|err| ToolError::Customize(format!("Can't listen on {}: {}", listen, err)))?
+        .serve(move || {
+            let proxy = Arc::clone(&proxy);
+            let workers = Arc::clone(&workers);
+            service_fn(move |req: Request<Body>| {
+                let proxy = Arc::clone(&proxy);
+                let workers = Arc::clone(&workers);
+                if req.method() != Method::POST {
+                    let mut response = Response::new(Body::from("Only POST is supported"));
+                    *response.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+                    return future::Either::A(future::ok::<_, hyper::Error>(response));
+                }
+                if proxy.rate_limited() {
+                    let rejected = Rejected::new(
+                        StatusCode::TOO_MANY_REQUESTS,
+                        LIMIT_EXCEEDED,
+                        "Rate limit exceeded",
+                    );
+                    return future::Either::A(future::ok(rejected.into_response()));
+                }
+                let too_large = || {
+                    Rejected::new(
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        INVALID_REQUEST,
+                        "Request body is too large",
+                    )
+                };
+                let content_length = req
+                    .headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<usize>().ok());
+                if content_length.map_or(false, |length| length > MAX_BODY_SIZE) {
+                    return future::Either::A(future::ok(too_large().into_response()));
+                }
+                let token = req
+                    .headers()
+                    .get(AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    .filter(|value| value.starts_with("Bearer "))
+                    .map(|value| value["Bearer ".len()..].trim().to_string());
+                let handled = req
+                    .into_body()
+                    .map_err(|err| {
+                        Rejected::new(StatusCode::BAD_REQUEST, PARSE_ERROR, &err.to_string())
+                    })
+                    .fold(Vec::new(), move |mut body, chunk| {
+                        if body.len() + chunk.len() > MAX_BODY_SIZE {
+                            return Err(too_large());
+                        }
+                        body.extend_from_slice(&chunk);
+                        Ok(body)
+                    })
+                    .and_then(move |body| {
+                        let (sender, receiver) = oneshot::channel();
+                        let job: Job = Box::new(move || {
+                            let token = token.as_ref().map(String::as_str);
+                            let _ = sender.send(proxy.handle(&body, token));
+                        });
+                        if !workers.try_run(job) {
+                            return future::Either::A(future::err(Rejected::new(
+                                StatusCode::SERVICE_UNAVAILABLE,
+                                INTERNAL_ERROR,
+                                "Server is busy",
+                            )));
+                        }
+                        future::Either::B(receiver.map_err(|_| {
+                            Rejected::new(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                INTERNAL_ERROR,
+                                "Request is dropped",
+                            )
+                        }))
+                    })
+                    .then(|result| {
+                        Ok::<_, hyper::Error>(match result {
+                            Ok(body) => json_response(StatusCode::OK, &body),
+                            Err(rejected) => rejected.into_response(),
+                        })
+                    });
+                future::Either::B(handled)
+            })
+        });
+    info!("Listening on {}", listen);
+    hyper::rt::run(server.map_err(|err| error!("Server error: {}", err)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        verify_jwt, Job, Limiter, Proxy, RateLimit, ServerConfig, ServerOptions, Workers,
+        LIMIT_EXCEEDED, METHOD_NOT_ALLOWED, UNAUTHORIZED,
+    };
+    use crate::client::basic::Client;
+    use crate::rpctypes::JsonRpcResponse;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(
+            "100/s".parse::<RateLimit>().unwrap(),
+            RateLimit::new(100, Duration::from_secs(1))
+        );
+        assert_eq!(
+            "5/m".parse::<RateLimit>().unwrap(),
+            RateLimit::new(5, Duration::from_secs(60))
+        );
+        assert!("0/s".parse::<RateLimit>().is_err());
+        assert!("100".parse::<RateLimit>().is_err());
+        assert!("100/d".parse::<RateLimit>().is_err());
+    }
+
+    #[test]
+    fn test_limiter_is_full() {
+        let limiter = Limiter::new(RateLimit::new(1, Duration::from_secs(3600)));
+        assert!(!limiter.is_full());
+        assert!(limiter.try_acquire());
+        assert!(limiter.is_full());
+        assert!(!limiter.try_acquire());
+    }
+
+    #[test]
+    fn test_workers() {
+        let workers = Workers::new(2, 4);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        for index in 0..4 {
+            let sender = sender.clone();
+            let job: Job = Box::new(move || sender.send(index).unwrap());
+            assert!(workers.try_run(job));
+        }
+        let mut done = receiver.iter().take(4).collect::<Vec<i32>>();
+        done.sort();
+        assert_eq!(done, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":7,"result":"0x10"}"#).unwrap();
+        let proxy = Proxy::new(
+            Client::with_responses(vec![response]),
+            ServerOptions::new()
+                .set_allow_methods(Some(vec!["blockNumber".to_string()]))
+                .set_rate_limit(Some(RateLimit::new(1, Duration::from_secs(3600)))),
+        );
+        let responses = proxy.handle(
+            br#"[
+                {"jsonrpc":"2.0","id":"a","method":"blockNumber","params":[]},
+                {"jsonrpc":"2.0","id":"b","method":"sendRawTransaction","params":["0x"]},
+                {"jsonrpc":"2.0","id":"c","method":"blockNumber","params":[]}
+            ]"#,
+            None,
+        );
+        assert_eq!(responses[0]["id"], "a");
+        assert_eq!(responses[0]["result"], "0x10");
+        assert_eq!(responses[1]["error"]["code"], METHOD_NOT_ALLOWED);
+        assert_eq!(responses[2]["error"]["code"], LIMIT_EXCEEDED);
+    }
+
+    /// HS256 JWT of the claims signed by the secret
+    fn jwt(claims: &str, secret: &[u8]) -> String {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let encode = |data: &[u8]| base64::encode_config(data, base64::URL_SAFE_NO_PAD);
+        let message = format!(
+            "{}.{}",
+            encode(br#"{"alg":"HS256","typ":"JWT"}"#),
+            encode(claims.as_bytes())
+        );
+        let mut mac = Hmac::<Sha256>::new_varkey(secret).unwrap();
+        mac.input(message.as_bytes());
+        format!("{}.{}", message, encode(&mac.result().code()))
+    }
+
+    #[test]
+    fn test_verify_jwt() {
+        let secret = b"secret";
+        assert!(verify_jwt(&jwt(r#"{"sub":"admin"}"#, secret), secret));
+        assert!(verify_jwt(&jwt(r#"{"exp":99999999999}"#, secret), secret));
+        assert!(!verify_jwt(&jwt(r#"{"exp":1}"#, secret), secret));
+        assert!(!verify_jwt(&jwt(r#"{"sub":"admin"}"#, b"other"), secret));
+        assert!(!verify_jwt("not.a.token", secret));
+    }
+
+    #[test]
+    fn test_access_control() {
+        let response: JsonRpcResponse =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":7,"result":"0x10"}"#).unwrap();
+        let config: ServerConfig =
+            serde_json::from_str(r#"{"deny":["sendRawTransaction"],"require-auth":["call"]}"#)
+                .unwrap();
+        let proxy = Proxy::new(
+            Client::with_responses(vec![response]),
+            ServerOptions::new()
+                .set_config(config)
+                .set_jwt_secret(Some(b"secret".to_vec())),
+        );
+        let body = br#"[
+            {"jsonrpc":"2.0","id":"a","method":"sendRawTransaction","params":["0x"]},
+            {"jsonrpc":"2.0","id":"b","method":"call","params":[]}
+        ]"#;
+        let responses = proxy.handle(body, None);
+        assert_eq!(responses[0]["error"]["code"], METHOD_NOT_ALLOWED);
+        assert_eq!(responses[1]["error"]["code"], UNAUTHORIZED);
+        let responses = proxy.handle(body, Some(&jwt("{}", b"secret")));
+        assert_eq!(responses[0]["error"]["code"], METHOD_NOT_ALLOWED);
+        assert_eq!(responses[1]["result"], "0x10");
+    }
+}
@@ -0,0 +1,86 @@
+use protobuf::Message as MessageTrait;
+
+use crate::crypto::{sign, Hashable, PrivateKey};
+use crate::error::ToolError;
+use crate::protos::Transaction;
+
+/// Signs transactions, wherever the private key is kept
+pub trait Signer {
+    /// Sign the transaction, return the signature
+    fn sign_transaction(&mut self, tx: &Transaction) -> Result<Vec<u8>, ToolError>;
+}
+
+/// Signer of a private key in memory
+pub struct InMemorySigner(pub PrivateKey);
+
+impl Signer for InMemorySigner {
+    fn sign_transaction(&mut self, tx: &Transaction) -> Result<Vec<u8>, ToolError> {
+        let bytes = tx.write_to_bytes().map_err(ToolError::Proto)?;
+        let hash = match self.0.encryption() {
+            Some(encryption) => bytes.crypt_hash(encryption),
+            None => Default::default(),
+        };
+        Ok(sign(&self.0, &hash).to_vec())
+    }
+}
+
+/// Device keeping the private key, such as a hardware wallet or an HSM
+pub trait HardwareWalletBackend {
+    /// Sign the protobuf encoded transaction on the device, return the signature
+    ///
+    /// The device hashes the transaction with the algorithm of its key.
+    fn sign_encoded_transaction(&mut self, tx: &[u8]) -> Result<Vec<u8>, ToolError>;
+}
+
+/// Signer of a private key that never leaves the device
+///
+/// The crate has no backend of a specific device yet, it is provided by the caller.
+pub struct HardwareSigner(pub Box<dyn HardwareWalletBackend>);
+
+impl Signer for HardwareSigner {
+    fn sign_transaction(&mut self, tx: &Transaction) -> Result<Vec<u8>, ToolError> {
+        let bytes = tx.write_to_bytes().map_err(ToolError::Proto)?;
+        self.0.sign_encoded_transaction(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HardwareSigner, HardwareWalletBackend, InMemorySigner, Signer};
+    use crate::crypto::{pubkey_to_address, Encryption, KeyPair};
+    use crate::error::ToolError;
+    use crate::protos::Transaction;
+
+    /// Device signing with the key in it
+    struct Device(InMemorySigner);
+
+    impl HardwareWalletBackend for Device {
+        fn sign_encoded_transaction(&mut self, tx: &[u8]) -> Result<Vec<u8>, ToolError> {
+            let tx = protobuf::parse_from_bytes::<Transaction>(tx).map_err(ToolError::Proto)?;
+            self.0.sign_transaction(&tx)
+        }
+    }
+
+    #[test]
+    fn test_build_with_signer() {
+        let key_pair = KeyPair::new(Encryption::Secp256k1);
+        let mut tx = Transaction::new();
+        tx.set_nonce("1".to_string());
+        tx.set_quota(21_000);
+
+        let unverified = tx
+            .build_with_signer(&mut InMemorySigner(key_pair.privkey()))
+            .unwrap();
+        assert_eq!(
+            pubkey_to_address(&unverified.public_key(Encryption::Secp256k1).unwrap()),
+            key_pair.address()
+        );
+
+        let mut signer = HardwareSigner(Box::new(Device(InMemorySigner(key_pair.privkey()))));
+        let signed_on_device = tx.build_with_signer(&mut signer).unwrap();
+        assert_eq!(
+            pubkey_to_address(&signed_on_device.public_key(Encryption::Secp256k1).unwrap()),
+            key_pair.address()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use types::H160;
+
+/// `0xffffffffffffffffffffffffffffffffff` followed by the three bytes
+macro_rules! system_address {
+    ($a:expr, $b:expr, $c:expr) => {
+        H160([
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, $a, $b, $c,
+        ])
+    };
+}
+
+/// SysConfig contract
+pub const SYS_CONFIG: H160 = system_address!(0x02, 0x00, 0x00);
+/// NodeManager contract
+pub const NODE_MANAGER: H160 = system_address!(0x02, 0x00, 0x01);
+/// ChainManager contract
+pub const CHAIN_MANAGER: H160 = system_address!(0x02, 0x00, 0x02);
+/// QuotaManager contract
+pub const QUOTA_MANAGER: H160 = system_address!(0x02, 0x00, 0x03);
+/// PermissionManagement contract
+pub const PERMISSION_MANAGEMENT: H160 = system_address!(0x02, 0x00, 0x04);
+/// PermissionCreator contract
+pub const PERMISSION_CREATOR: H160 = system_address!(0x02, 0x00, 0x05);
+/// Authorization contract
+pub const AUTHORIZATION: H160 = system_address!(0x02, 0x00, 0x06);
+/// RoleManagement contract
+pub const ROLE_MANAGEMENT: H160 = system_address!(0x02, 0x00, 0x07);
+/// RoleCreator contract
+pub const ROLE_CREATOR: H160 = system_address!(0x02, 0x00, 0x08);
+/// Root group
+pub const ROOT_GROUP: H160 = system_address!(0x02, 0x00, 0x09);
+/// GroupManagement contract
+pub const GROUP_MANAGEMENT: H160 = system_address!(0x02, 0x00, 0x0a);
+/// GroupCreator contract
+pub const GROUP_CREATOR: H160 = system_address!(0x02, 0x00, 0x0b);
+/// Admin contract
+pub const ADMIN: H160 = system_address!(0x02, 0x00, 0x0c);
+/// RoleAuth contract
+pub const ROLE_AUTH: H160 = system_address!(0x02, 0x00, 0x0d);
+/// BatchTx contract
+pub const BATCH_TX: H160 = system_address!(0x02, 0x00, 0x0e);
+/// EmergencyBrake contract
+pub const EMERGENCY_BRAKE: H160 = system_address!(0x02, 0x00, 0x0f);
+/// PriceManager contract
+pub const PRICE_MANAGER: H160 = system_address!(0x02, 0x00, 0x10);
+/// VersionManager contract
+pub const VERSION_MANAGER: H160 = system_address!(0x02, 0x00, 0x11);
+/// AllGroups contract
+pub const ALL_GROUPS: H160 = system_address!(0x02, 0x00, 0x12);
+/// AutoExec contract
+pub const AUTO_EXEC: H160 = system_address!(0x02, 0x00, 0x13);
+
+/// Permission to send transactions
+pub const SEND_TX_PERMISSION: H160 = system_address!(0x02, 0x10, 0x00);
+/// Permission to create contracts
+pub const CREATE_CONTRACT_PERMISSION: H160 = system_address!(0x02, 0x10, 0x01);
+/// Permission to create permissions
+pub const NEW_PERMISSION_PERMISSION: H160 = system_address!(0x02, 0x10, 0x10);
+/// Permission to delete permissions
+pub const DELETE_PERMISSION_PERMISSION: H160 = system_address!(0x02, 0x10, 0x11);
+/// Permission to update permissions
+pub const UPDATE_PERMISSION_PERMISSION: H160 = system_address!(0x02, 0x10, 0x12);
+/// Permission to set authorizations
+pub const SET_AUTH_PERMISSION: H160 = system_address!(0x02, 0x10, 0x13);
+/// Permission to cancel authorizations
+pub const CANCEL_AUTH_PERMISSION: H160 = system_address!(0x02, 0x10, 0x14);
+/// Permission to create roles
+pub const NEW_ROLE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x15);
+/// Permission to delete roles
+pub const DELETE_ROLE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x16);
+/// Permission to update roles
+pub const UPDATE_ROLE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x17);
+/// Permission to set roles
+pub const SET_ROLE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x18);
+/// Permission to cancel roles
+pub const CANCEL_ROLE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x19);
+/// Permission to create groups
+pub const NEW_GROUP_PERMISSION: H160 = system_address!(0x02, 0x10, 0x1a);
+/// Permission to delete groups
+pub const DELETE_GROUP_PERMISSION: H160 = system_address!(0x02, 0x10, 0x1b);
+/// Permission to update groups
+pub const UPDATE_GROUP_PERMISSION: H160 = system_address!(0x02, 0x10, 0x1c);
+/// Permission to add nodes
+pub const NEW_NODE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x20);
+/// Permission to delete nodes
+pub const DELETE_NODE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x21);
+/// Permission to update node stakes
+pub const UPDATE_NODE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x22);
+/// Permission to set account quota limits
+pub const ACCOUNT_QUOTA_PERMISSION: H160 = system_address!(0x02, 0x10, 0x23);
+/// Permission to set the block quota limit
+pub const BLOCK_QUOTA_PERMISSION: H160 = system_address!(0x02, 0x10, 0x24);
+/// Permission to send batch transactions
+pub const BATCH_TX_PERMISSION: H160 = system_address!(0x02, 0x10, 0x25);
+/// Permission to set the emergency brake
+pub const EMERGENCY_BRAKE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x26);
+/// Permission to set the quota price
+pub const QUOTA_PRICE_PERMISSION: H160 = system_address!(0x02, 0x10, 0x27);
+/// Permission to set the protocol version
+pub const VERSION_PERMISSION: H160 = system_address!(0x02, 0x10, 0x28);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LowerHex;
+
+    #[test]
+    fn test_system_address() {
+        assert_eq!(
+            SEND_TX_PERMISSION.completed_lower_hex_with_0x(),
+            "0xffffffffffffffffffffffffffffffffff021000"
+        );
+        assert_eq!(
+            GROUP_MANAGEMENT.completed_lower_hex_with_0x(),
+            "0xffffffffffffffffffffffffffffffffff02000a"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Integration tests against a running CITA chain, see `docker-compose.yml`.
+//!
+//! Only built with the `integration-tests` feature, the url is `$CITA_URL`,
+//! default is `http://127.0.0.1:1337`.
+#![cfg(feature = "integration-tests")]
+
+use std::env;
+use std::thread;
+use std::time::Duration;
+
+use cita_tool::client::basic::Client;
+use cita_tool::client::system_contract::{
+    AuthorizationClient, AuthorizationExt, ContractCall, GroupClient, GroupExt, GroupManageClient,
+    GroupManagementExt, NodeManageClient, NodeManagementExt, QuotaManageClient, QuotaManagementExt,
+};
+use cita_tool::system_addresses::SEND_TX_PERMISSION;
+use cita_tool::{
+    address_from_privkey, privkey_from_str, ContractClient, LowerHex, PrivateKey, Token,
+    TransactionOptions,
+};
+use ethabi::Contract;
+
+const SUPER_ADMIN_PRIVATE_KEY: &str =
+    "0x5f0258a4778057a8a7d97809bd209055b2fbafa654ce7d31ec7191066b9225e6";
+
+/// Deploys a contract whose every method returns 42
+const ANSWER_BYTECODE: &str = "0x600a600c600039600a6000f3602a60005260206000f3";
+const ANSWER_ABI: &str = r#"[{"constant":true,"inputs":[],"name":"get","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
+
+fn client() -> Client {
+    let url = env::var("CITA_URL").unwrap_or_else(|_| "http://127.0.0.1:1337".to_string());
+    let client = Client::new().set_uri(&url);
+    // The chain may still be starting
+    for _ in 0..60 {
+        if client
+            .get_current_height()
+            .map_or(false, |height| height > 0)
+        {
+            return client;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    panic!("No block produced by {}", url);
+}
+
+fn super_admin() -> PrivateKey {
+    privkey_from_str(SUPER_ADMIN_PRIVATE_KEY).unwrap()
+}
+
+#[test]
+fn test_deploy_and_call() {
+    let mut client = client();
+    let abi = Contract::load(ANSWER_ABI.as_bytes()).unwrap();
+    let address = client
+        .deploy_contract(ANSWER_BYTECODE, &abi, &[], &super_admin(), None)
+        .unwrap();
+
+    let contract = ContractClient::new(ANSWER_ABI, address, client).unwrap();
+    match contract.call("get", &[], None).unwrap().as_slice() {
+        [Token::Uint(answer)] => assert_eq!(answer.low_u64(), 42),
+        tokens => panic!("Unexpected output: {:?}", tokens),
+    }
+}
+
+#[test]
+fn test_system_contract_state() {
+    let client = client();
+    assert!(
+        QuotaManageClient::create(client.clone())
+            .get_bql_u64(None)
+            .unwrap()
+            > 0
+    );
+
+    let nodes = NodeManageClient::create(client)
+        .contract_call_decoded("listNode", &[], None, None)
+        .unwrap();
+    match nodes.as_slice() {
+        [Token::Array(nodes)] => assert_eq!(nodes.len(), 1),
+        tokens => panic!("Unexpected output: {:?}", tokens),
+    }
+}
+
+#[test]
+fn test_authorization() {
+    let authorization = AuthorizationClient::create(client());
+    let send_tx = SEND_TX_PERMISSION.completed_lower_hex_with_0x();
+    let check = |account: &str| {
+        authorization
+            .contract_call_decoded("checkPermission", &[account, send_tx.as_str()], None, None)
+            .unwrap()
+    };
+
+    let super_admin = address_from_privkey(&super_admin()).completed_lower_hex_with_0x();
+    match check(&super_admin).as_slice() {
+        [Token::Bool(authorized)] => assert!(*authorized),
+        tokens => panic!("Unexpected output: {:?}", tokens),
+    }
+    match check("0x0000000000000000000000000000000000000001").as_slice() {
+        [Token::Bool(authorized)] => assert!(!*authorized),
+        tokens => panic!("Unexpected output: {:?}", tokens),
+    }
+}
+
+#[test]
+fn test_group_membership_diff() {
+    const ROOT_GROUP: &str = "0xffffffffffffffffffffffffffffffffff020009";
+    let mut client = client();
+    client.set_private_key(&super_admin());
+    let before = client.get_current_height().unwrap();
+    // A new account on every run
+    let account = format!("0x{:040x}", before + 0x1000);
+
+    let response = GroupManageClient::create(client.clone())
+        .add_accounts(
+            ROOT_GROUP,
+            ROOT_GROUP,
+            &format!("[{}]", &account[2..]),
+            TransactionOptions::new(),
+        )
+        .unwrap();
+    let hash = response
+        .result()
+        .and_then(|result| result.as_map().cloned())
+        .and_then(|result| {
+            result
+                .get("hash")
+                .and_then(|hash| hash.as_str().map(String::from))
+        })
+        .expect("No transaction hash");
+    let mut receipt = None;
+    for _ in 0..30 {
+        receipt = client.get_receipt(&hash).unwrap();
+        if receipt.is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    let after = receipt.expect("Transaction not in a block").block_number;
+
+    // The accounts at `before` must not include the new one, if the height is forwarded
+    let diff = GroupClient::create(client)
+        .diff_membership(ROOT_GROUP, before, after)
+        .unwrap();
+    assert_eq!(
+        diff.added
+            .iter()
+            .map(|account| format!("0x{:x}", account))
+            .collect::<Vec<String>>(),
+        vec![account]
+    );
+    assert!(diff.removed.is_empty());
+    let super_admin = address_from_privkey(&super_admin()).completed_lower_hex_with_0x();
+    assert!(diff
+        .unchanged
+        .iter()
+        .any(|account| format!("0x{:x}", account) == super_admin));
+}
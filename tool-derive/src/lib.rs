@@ -14,6 +14,8 @@ pub fn contract(input: TokenStream) -> TokenStream {
     let mut trait_name = "".to_string();
     let mut address = "".to_string();
     let mut path = "".to_string();
+    let mut abi: Option<syn::LitStr> = None;
+    let mut abi_contract: Option<syn::Path> = None;
     for meta_items in input.attrs.iter().filter_map(get_contract_meta_items) {
         for meta_item in meta_items {
             match meta_item {
@@ -38,12 +40,29 @@ pub fn contract(input: TokenStream) -> TokenStream {
                         // println!("{}", lit.value());
                     }
                 }
+                // parse #[contract(abi = "[{...}]")]
+                syn::NestedMeta::Meta(syn::Meta::NameValue(ref m)) if m.path.is_ident("abi") => {
+                    if let syn::Lit::Str(ref lit) = m.lit {
+                        abi = Some(lit.clone());
+                    }
+                }
+                // parse #[contract(abi_contract = "crate::contract_abi::FOO_CONTRACT")]
+                syn::NestedMeta::Meta(syn::Meta::NameValue(ref m))
+                    if m.path.is_ident("abi_contract") =>
+                {
+                    if let syn::Lit::Str(ref lit) = m.lit {
+                        abi_contract = Some(lit.parse().expect("abi_contract must be a path"));
+                    }
+                }
                 _ => {}
             }
         }
     }
-    if path == "" {
-        panic!("path must set");
+    let abi_sources = [path != "", abi.is_some(), abi_contract.is_some()];
+    match abi_sources.iter().filter(|set| **set).count() {
+        0 => panic!("one of path/abi/abi_contract must set"),
+        1 => {}
+        _ => panic!("only one of path/abi/abi_contract can be set"),
     }
     if address == "" {
         panic!("contract address must set");
@@ -53,8 +72,28 @@ pub fn contract(input: TokenStream) -> TokenStream {
     }
     // struct name
     let name = input.ident;
-    // parse str to LitStr
-    let path = syn::LitStr::new(&path, proc_macro2::Span::call_site());
+    // The body of `create`, borrows the static contract or parses the ABI JSON
+    let create = if let Some(abi_contract) = abi_contract {
+        quote!(
+            #name {
+                client,
+                address: Address::from_str(remove_0x(ADDRESS)).unwrap_or_else(|_| Address::default()),
+                contract: ::std::borrow::Cow::Borrowed(&*#abi_contract),
+            }
+        )
+    } else {
+        let abi = if let Some(abi) = abi {
+            quote!(#abi)
+        } else {
+            // parse str to LitStr
+            let path = syn::LitStr::new(&path, proc_macro2::Span::call_site());
+            quote!(include_str!(#path))
+        };
+        quote!(
+            let abi: &str = #abi;
+            Self::new(client, ADDRESS, abi)
+        )
+    };
     // parse str to Ident
     let trait_name = syn::Ident::new(&trait_name, proc_macro2::Span::call_site());
     let address = syn::LitStr::new(&address, proc_macro2::Span::call_site());
@@ -91,9 +130,37 @@ pub fn contract(input: TokenStream) -> TokenStream {
                     #name {
                         client,
                         address,
-                        contract,
+                        contract: ::std::borrow::Cow::Owned(contract),
                     }
                 }
+
+                /// Create a Contract Client at the address, instead of the default one
+                pub fn create_at(client: T, address: Address) -> Self {
+                    let mut contract_client =
+                        <Self as #trait_name<T, JsonRpcResponse, ToolError>>::create(client);
+                    contract_client.address = address;
+                    contract_client
+                }
+            }
+            impl #name<Client> {
+                /// Create a Contract Client answering with the canned responses in FIFO order,
+                /// without any network call, for tests
+                pub fn create_with_responses(responses: Vec<JsonRpcResponse>) -> Self {
+                    <Self as #trait_name<Client, JsonRpcResponse, ToolError>>::create(
+                        Client::with_responses(responses),
+                    )
+                }
+
+                /// Compare the functions of the ABI with the one stored on chain at the address,
+                /// with a warning if some are only in the ABI, since calls of them fail
+                pub fn validate_abi(
+                    &self,
+                    height: Option<&str>,
+                ) -> Result<::cita_tool::AbiValidation, ToolError> {
+                    let address = format!("{:?}", self.address);
+                    let abi = self.client.get_abi_text(&address, height)?;
+                    ::cita_tool::validate_onchain_abi(&self.contract, &address, &abi)
+                }
             }
             impl<T> ContractCall<JsonRpcResponse, ToolError> for #name<T>
                 where T: ClientExt<JsonRpcResponse, ToolError>
@@ -116,14 +183,13 @@ pub fn contract(input: TokenStream) -> TokenStream {
                     &mut self,
                     name: &str,
                     values: &[&str],
-                    quota: Option<u64>,
+                    options: TransactionOptions,
                     to_addr: Option<Address>,
                 ) -> Result<JsonRpcResponse, ToolError> {
                     let (code, to_address) = self.prepare_call_args(name, values, to_addr)?;
-                    let tx_options = TransactionOptions::new()
+                    let tx_options = options
                         .set_code(code.as_str())
-                        .set_address(to_address.as_str())
-                        .set_quota(quota);
+                        .set_address(to_address.as_str());
                     self.client.send_raw_transaction(
                         tx_options,
                     )
@@ -144,14 +210,19 @@ pub fn contract(input: TokenStream) -> TokenStream {
                         height.unwrap_or_else(|| "latest"),
                     )
                 }
+
+                fn get_function(&self, name: &str) -> Result<&ethabi::Function, ToolError> {
+                    self.contract
+                        .function(name)
+                        .map_err(|err| ToolError::Abi(format!("{}: {}", name, err)))
+                }
             }
             impl<T> #trait_name<T, JsonRpcResponse, ToolError> for #name<T>
                  where T: ClientExt<JsonRpcResponse, ToolError>,
              {
                     fn create(client: T) -> Self {
-                        static ABI: &str = include_str!(#path);
                         static ADDRESS: &str = #address;
-                        Self::new(client, ADDRESS, ABI)
+                        #create
                     }
             }
         )